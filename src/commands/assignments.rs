@@ -4,21 +4,53 @@
 
 use anyhow::Result;
 use colored::Colorize;
+use serde::Serialize;
 use std::fs;
 use std::path::Path;
 
-use crate::config::get_config;
+use crate::config::{Config, ConflictPolicy, HealthThresholds, get_config};
+use crate::core::assignment_readme::AssignmentReadme;
+use crate::core::calendar_export::CalendarExporter;
+use crate::core::directory_scanner::DirectoryScanner;
 use crate::core::file_operations::FileOperations;
-use crate::core::template::{builder::TemplateBuilder, engine::TemplateReference};
+use crate::core::git_integration::GitIntegration;
+use crate::core::recommendations::{Recommendation, RecommendationEngine, RecommendationPriority};
+use crate::core::status_manager::HealthStatus;
+use crate::core::template::{
+    builder::TemplateBuilder,
+    context::{RubricCriterion, TemplateContext},
+    engine::TemplateReference,
+    imports::ImportFixer,
+};
 use crate::core::validation::Validator;
 use crate::ui::output::{OutputManager, Status};
+use crate::ui::width::TextWidth;
 
 /// Create a new assignment using the template system
-pub fn create_assignment(course_id: &str, title: &str) -> Result<()> {
-    let config = get_config()?;
+#[allow(clippy::too_many_arguments)]
+pub fn create_assignment(
+    course_id: &str,
+    title: &str,
+    due: Option<&str>,
+    dump_context: bool,
+    on_conflict: Option<ConflictPolicy>,
+    authors: Option<&str>,
+    rubric: Option<&str>,
+    editor: Option<&str>,
+) -> Result<()> {
+    let config = get_config()?.with_editor_override(editor);
+    let course_id = &config.resolve_course_id(course_id);
 
     // Validate course ID
     Validator::validate_course_id(course_id)?;
+    let title = Validator::validate_title(title)?;
+    let authors = authors.map(Validator::parse_authors).transpose()?;
+    let rubric = rubric.map(Validator::parse_rubric).transpose()?;
+
+    let due_date = match due {
+        Some(due) => Validator::parse_due_date(due, chrono::Local::now().date_naive())?,
+        None => TemplateContext::default_due_date(&config),
+    };
 
     let course_name = config.get_course_name(course_id);
     if course_name.is_empty() {
@@ -38,49 +70,82 @@ pub fn create_assignment(course_id: &str, title: &str) -> Result<()> {
     );
 
     // Create assignment directory if it doesn't exist
-    let assignments_dir = Path::new(&config.paths.notes_dir)
-        .join(course_id)
-        .join("assignments");
+    let assignments_dir = config.get_assignments_dir(course_id);
+    let new_directory = !assignments_dir.exists();
 
-    if let Err(e) = fs::create_dir_all(&assignments_dir) {
-        OutputManager::print_status(
-            Status::Error,
-            &format!("Failed to create assignments directory: {}", e),
-        );
+    if let Err(e) = FileOperations::prepare_note_directory(&assignments_dir, &config) {
+        OutputManager::print_status(Status::Error, &format!("{:#}", e));
         return Ok(());
     }
 
-    // Generate filename
-    let sanitized_title = Validator::sanitize_filename(title);
-    let filename = format!("{}.typ", sanitized_title);
-    let file_path = assignments_dir.join(&filename);
-
-    // Check if file already exists
-    if file_path.exists() {
-        if config.note_preferences.create_backups {
-            let backup_path = file_path.with_extension("typ.bak");
-            if let Err(e) = fs::copy(&file_path, backup_path) {
-                OutputManager::print_status(
-                    Status::Warning,
-                    &format!("Could not create backup: {}", e),
-                );
+    if new_directory {
+        match AssignmentReadme::create_if_enabled(&assignments_dir, course_id, &config) {
+            Ok(Some(readme)) => {
+                OutputManager::print_status(Status::Info, &format!("Created {}", readme.display()))
             }
-        } else {
-            OutputManager::print_status(
-                Status::Error,
-                &format!("Assignment file already exists: {}", file_path.display()),
-            );
-            return Ok(());
+            Ok(None) => {}
+            Err(e) => OutputManager::print_status(
+                Status::Warning,
+                &format!("Could not create the assignments README: {:#}", e),
+            ),
         }
     }
 
+    // Generate filename
+    let filename = FileOperations::fit_filename(
+        "",
+        &Validator::sanitize_filename(title),
+        config.typst.new_file_extension(),
+        config.note_preferences.max_filename_length,
+    );
+
+    // Titles that sanitize identically map to the same file
+    let policy = on_conflict.unwrap_or(config.note_preferences.on_conflict);
+    let Some(file_path) =
+        super::notes::resolve_file_conflict(&assignments_dir.join(&filename), policy, &config)?
+    else {
+        println!("Cancelled.");
+        return Ok(());
+    };
+
+    super::notes::auto_update_templates(&config);
+
     // Generate content using the template system
-    match TemplateBuilder::new(course_id, &config)?
+    let mut builder = TemplateBuilder::new(course_id, &config)?
         .with_reference(TemplateReference::assignment())
         .with_title(title)
-        .with_sections(config.note_preferences.assignment_sections.clone())
-        .build()
-    {
+        .with_due_date(due_date)
+        .with_sections(config.note_preferences.sections_for("assignment"))
+        .with_context_dump(dump_context)
+        .with_render_cache(true);
+
+    if let Some(authors) = authors {
+        builder = builder.with_authors(authors);
+    }
+
+    if let Some(rubric) = rubric {
+        let total = RubricCriterion::total(&rubric);
+        if total != 100 {
+            OutputManager::print_status(
+                Status::Warning,
+                &format!("The rubric's points sum to {}, not 100", total),
+            );
+        }
+        builder = builder.with_rubric(rubric);
+    }
+
+    // Regenerating an existing assignment keeps the template version it imports
+    if let Ok(existing) = fs::read_to_string(&file_path) {
+        let reconciled = ImportFixer::reconcile_template_config(&config, &existing)?;
+        for warning in reconciled.warnings() {
+            OutputManager::print_status(Status::Warning, &warning);
+        }
+        builder = builder.with_template_config(reconciled.config);
+    }
+
+    super::notes::print_context_warnings(&builder);
+
+    match builder.build() {
         Ok(content) => {
             // Write file
             if let Err(e) = fs::write(&file_path, content) {
@@ -94,11 +159,18 @@ pub fn create_assignment(course_id: &str, title: &str) -> Result<()> {
             OutputManager::print_status(
                 Status::Success,
                 &format!(
-                    "Assignment created: {}",
-                    file_path.to_string_lossy().bright_white()
+                    "Assignment created: {} (due {})",
+                    file_path.to_string_lossy().bright_white(),
+                    due_date.to_string().yellow()
                 ),
             );
 
+            super::notes::auto_commit_created_file(
+                &file_path,
+                &GitIntegration::commit_message("assignment", course_id),
+                &config,
+            );
+
             // Auto-open if configured
             if config.note_preferences.auto_open_file {
                 OutputManager::print_status(Status::Info, "Opening in editor...");
@@ -145,20 +217,20 @@ pub fn create_assignment(course_id: &str, title: &str) -> Result<()> {
 }
 
 /// List recent assignments for a course
-pub fn list_recent_assignments(course_id: &str, limit: usize) -> Result<()> {
+pub fn list_recent_assignments(course_id: &str, limit: usize, since: Option<&str>) -> Result<()> {
     let config = get_config()?;
+    let course_id = &config.resolve_course_id(course_id);
 
     // Validate course ID
     Validator::validate_course_id(course_id)?;
+    let cutoff = super::notes::since_cutoff(since)?;
 
     OutputManager::print_status(
         Status::Loading,
         &format!("Finding recent assignments for {}", course_id.yellow()),
     );
 
-    let assignments_dir = Path::new(&config.paths.notes_dir)
-        .join(course_id)
-        .join("assignments");
+    let assignments_dir = config.get_assignments_dir(course_id);
 
     if !assignments_dir.exists() {
         println!(
@@ -174,20 +246,12 @@ pub fn list_recent_assignments(course_id: &str, limit: usize) -> Result<()> {
     }
 
     // Collect assignment files with modification times
-    let mut files = Vec::new();
-    match fs::read_dir(&assignments_dir) {
-        Ok(entries) => {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.extension().is_some_and(|ext| ext == "typ") {
-                    if let Ok(metadata) = entry.metadata() {
-                        if let Ok(modified) = metadata.modified() {
-                            files.push((path.to_string_lossy().to_string(), modified));
-                        }
-                    }
-                }
-            }
-        }
+    let mut files = match DirectoryScanner::scan_course_assignments(
+        &config.get_course_dir(course_id),
+        &config.typst.source_extensions(),
+        config.paths.follow_symlinks,
+    ) {
+        Ok(files) => files,
         Err(e) => {
             OutputManager::print_status(
                 Status::Error,
@@ -195,41 +259,57 @@ pub fn list_recent_assignments(course_id: &str, limit: usize) -> Result<()> {
             );
             return Ok(());
         }
+    };
+    if let Some(cutoff) = cutoff {
+        files = DirectoryScanner::modified_since(files, cutoff);
     }
 
     // Sort by modification time (newest first)
-    files.sort_by(|a, b| b.1.cmp(&a.1));
+    files.sort_by_key(|b| std::cmp::Reverse(b.modified));
 
     let assignments: Vec<String> = files
         .into_iter()
         .take(limit)
-        .map(|(path, _)| path)
+        .map(|file| file.path.to_string_lossy().to_string())
         .collect();
 
     if assignments.is_empty() {
-        println!(
-            "{} No assignments found for course {}",
-            "📝".dimmed(),
-            course_id.yellow()
-        );
+        match since {
+            Some(since) => println!(
+                "{} No assignments for course {} modified in the last {}",
+                "📝".dimmed(),
+                course_id.yellow(),
+                since.trim()
+            ),
+            None => println!(
+                "{} No assignments found for course {}",
+                "📝".dimmed(),
+                course_id.yellow()
+            ),
+        }
         println!(
             "Create one: {}",
             format!("noter assignment {} \"Assignment Title\"", course_id).bright_white()
         );
     } else {
         println!();
-        println!(
-            "{} Recent assignments for {}:",
-            "📝".blue(),
-            course_id.yellow()
-        );
+        match since {
+            Some(since) => println!(
+                "{} Assignments for {} from the last {}:",
+                "📝".blue(),
+                course_id.yellow(),
+                since.trim()
+            ),
+            None => println!(
+                "{} Recent assignments for {}:",
+                "📝".blue(),
+                course_id.yellow()
+            ),
+        }
         println!();
 
         for (i, assignment_path) in assignments.iter().enumerate() {
-            let file_name = std::path::Path::new(assignment_path)
-                .file_stem()
-                .unwrap_or_default()
-                .to_string_lossy();
+            let file_name = FileOperations::note_display_name(Path::new(assignment_path));
 
             println!(
                 "  {}. {}",
@@ -255,6 +335,7 @@ pub fn list_recent_assignments(course_id: &str, limit: usize) -> Result<()> {
 /// Show assignment statistics for a course
 pub fn show_assignment_stats(course_id: &str) -> Result<()> {
     let config = get_config()?;
+    let course_id = &config.resolve_course_id(course_id);
 
     // Validate course ID
     Validator::validate_course_id(course_id)?;
@@ -264,14 +345,12 @@ pub fn show_assignment_stats(course_id: &str) -> Result<()> {
         &format!("Calculating assignment stats for {}", course_id.yellow()),
     );
 
-    let assignments_dir = Path::new(&config.paths.notes_dir)
-        .join(course_id)
-        .join("assignments");
+    let assignments_dir = config.get_assignments_dir(course_id);
 
     let (count, last_modified) = if !assignments_dir.exists() {
         (0, None)
     } else {
-        get_assignment_stats_for_directory(&assignments_dir)?
+        get_assignment_stats_for_directory(&assignments_dir, &config)?
     };
 
     println!();
@@ -332,11 +411,11 @@ pub fn list_all_assignments() -> Result<()> {
     let mut course_assignments = Vec::new();
 
     for (course_id, course_name) in config.list_courses() {
-        let assignments_dir = Path::new(&config.paths.notes_dir)
-            .join(&course_id)
-            .join("assignments");
+        let assignments_dir = config.get_assignments_dir(&course_id);
 
-        if let Ok((count, last_modified)) = get_assignment_stats_for_directory(&assignments_dir) {
+        if let Ok((count, last_modified)) =
+            get_assignment_stats_for_directory(&assignments_dir, &config)
+        {
             total_assignments += count;
             if count > 0 {
                 course_assignments.push((course_id, course_name, count, last_modified));
@@ -372,6 +451,7 @@ pub fn list_all_assignments() -> Result<()> {
     });
 
     println!("{} Assignments by Course:", "📚".green());
+    let mut rows = Vec::new();
     for (course_id, course_name, count, last_modified) in course_assignments {
         let activity_indicator = if let Some(last_modified) = last_modified {
             let now = std::time::SystemTime::now();
@@ -390,14 +470,14 @@ pub fn list_all_assignments() -> Result<()> {
             "⚫".to_string()
         };
 
-        println!(
-            "  {} {} - {} ({})",
+        rows.push(vec![
             activity_indicator,
-            course_id.bright_blue(),
-            course_name,
-            format!("{} assignments", count).dimmed()
-        );
+            course_id.bright_blue().to_string(),
+            TextWidth::truncate(&course_name, OutputManager::MAX_NAME_WIDTH),
+            count.to_string(),
+        ]);
     }
+    OutputManager::print_table(&["", "Course", "Name", "Assignments"], &rows);
 
     println!();
     OutputManager::print_command_examples(&[
@@ -412,20 +492,94 @@ pub fn list_all_assignments() -> Result<()> {
     Ok(())
 }
 
-/// Show assignment health and activity analysis
-pub fn show_assignment_health(course_id: Option<&str>) -> Result<()> {
+/// Export assignment due dates to an iCalendar file
+pub fn export_due_dates_ics(course_id: Option<&str>, output: Option<&str>) -> Result<()> {
     let config = get_config()?;
+    let course_id = course_id.map(|id| config.resolve_course_id(id));
+    let course_id = course_id.as_deref();
 
-    let message = if let Some(course_id) = course_id {
-        format!("Analyzing assignment health for {}", course_id.yellow())
-    } else {
-        "Analyzing assignment health for all courses".to_string()
+    if let Some(course_id) = course_id {
+        Validator::validate_course_id(course_id)?;
+    }
+
+    OutputManager::print_status(Status::Loading, "Collecting assignment due dates");
+
+    let notes_dir = Path::new(&config.paths.notes_dir);
+    let export = CalendarExporter::collect_due_dates(
+        notes_dir,
+        course_id,
+        &config.typst.source_extensions(),
+        config.paths.follow_symlinks,
+    )?;
+
+    for skipped in &export.skipped {
+        OutputManager::print_status(
+            Status::Warning,
+            &format!("Skipped (no parseable due date): {}", skipped.display()),
+        );
+    }
+
+    if export.events.is_empty() {
+        OutputManager::print_status(Status::Info, "No assignments with due dates found");
+        println!(
+            "Add a line like {} to an assignment to include it",
+            "*Due Date*: 2025-03-14".bright_white()
+        );
+        return Ok(());
+    }
+
+    let output_path = match output {
+        Some(path) => Path::new(path).to_path_buf(),
+        None => {
+            let file_name = match course_id {
+                Some(course_id) => format!("{}-assignments.ics", course_id),
+                None => "assignments.ics".to_string(),
+            };
+            notes_dir.join(file_name)
+        }
     };
 
-    OutputManager::print_status(Status::Loading, &message);
+    FileOperations::safe_write(&output_path, &CalendarExporter::to_ics(&export.events))?;
 
-    let mut health_data = Vec::new();
+    OutputManager::print_status(
+        Status::Success,
+        &format!(
+            "Exported {} due date(s) to {}",
+            export.events.len(),
+            output_path.display()
+        ),
+    );
+    if !export.skipped.is_empty() {
+        println!(
+            "  {} assignment(s) skipped without a due date",
+            export.skipped.len().to_string().yellow()
+        );
+    }
+
+    Ok(())
+}
 
+/// Assignment health of a single course
+#[derive(Debug, Clone, Serialize)]
+pub struct CourseHealth {
+    pub course_id: String,
+    pub name: String,
+    /// Number of assignment files
+    pub count: usize,
+    /// Days since the most recent assignment change, `None` without any activity
+    pub days_since_activity: Option<u64>,
+    /// Health status from 0 (excellent) to 3 (critical)
+    pub status: usize,
+    /// Human-readable label for `status`
+    pub label: String,
+}
+
+/// Compute assignment health for one course, or all configured courses, sorted
+/// from healthiest to most critical
+pub fn compute_assignment_health(
+    config: &Config,
+    course_id: Option<&str>,
+) -> Result<Vec<CourseHealth>> {
     let courses_to_check = if let Some(specific_course) = course_id {
         vec![(
             specific_course.to_string(),
@@ -439,32 +593,70 @@ pub fn show_assignment_health(course_id: Option<&str>) -> Result<()> {
         config.list_courses()
     };
 
+    let mut health_data = Vec::new();
+
     for (course_id, course_name) in courses_to_check {
-        let assignments_dir = Path::new(&config.paths.notes_dir)
-            .join(&course_id)
-            .join("assignments");
-
-        if let Ok((count, last_modified)) = get_assignment_stats_for_directory(&assignments_dir) {
-            let health_status = calculate_assignment_health_status(count, last_modified);
-            let days_since_activity = if let Some(last_modified) = last_modified {
-                let now = std::time::SystemTime::now();
-                now.duration_since(last_modified)
+        let assignments_dir = config.get_assignments_dir(&course_id);
+
+        if let Ok((count, last_modified)) =
+            get_assignment_stats_for_directory(&assignments_dir, config)
+        {
+            let days_since_activity = last_modified.and_then(|last_modified| {
+                std::time::SystemTime::now()
+                    .duration_since(last_modified)
+                    .ok()
                     .map(|d| d.as_secs() / (24 * 60 * 60))
-                    .unwrap_or(999)
-            } else {
-                999 // No activity
-            };
+            });
+            let status = calculate_assignment_health_status(
+                count,
+                days_since_activity,
+                &config.health_thresholds,
+            );
 
-            health_data.push((
+            health_data.push(CourseHealth {
                 course_id,
-                course_name,
+                name: course_name,
                 count,
                 days_since_activity,
-                health_status,
-            ));
+                status: status.level(),
+                label: status.label().to_string(),
+            });
         }
     }
 
+    // Sort by health (0 = best, 3 = worst), then by days since activity (less is better)
+    health_data.sort_by_key(|health| {
+        (
+            health_status_to_priority(health.status),
+            health.days_since_activity.unwrap_or(u64::MAX),
+        )
+    });
+
+    Ok(health_data)
+}
+
+/// Show assignment health and activity analysis
+pub fn show_assignment_health(course_id: Option<&str>, json: bool) -> Result<()> {
+    let config = get_config()?;
+    let course_id = course_id.map(|id| config.resolve_course_id(id));
+    let course_id = course_id.as_deref();
+
+    if json {
+        let health_data = compute_assignment_health(&config, course_id)?;
+        println!("{}", serde_json::to_string_pretty(&health_data)?);
+        return Ok(());
+    }
+
+    let message = if let Some(course_id) = course_id {
+        format!("Analyzing assignment health for {}", course_id.yellow())
+    } else {
+        "Analyzing assignment health for all courses".to_string()
+    };
+
+    OutputManager::print_status(Status::Loading, &message);
+
+    let health_data = compute_assignment_health(&config, course_id)?;
+
     if health_data.is_empty() {
         OutputManager::print_status(Status::Info, "No assignment data found.");
         return Ok(());
@@ -474,50 +666,28 @@ pub fn show_assignment_health(course_id: Option<&str>) -> Result<()> {
     println!("{} Assignment Health Analysis", "🏥".blue());
     println!();
 
-    // Sort by health status and activity
-    health_data.sort_by(|a, b| {
-        // Sort by health (0 = best, 3 = worst), then by days since activity
-        let health_cmp = health_status_to_priority(a.4).cmp(&health_status_to_priority(b.4));
-        if health_cmp == std::cmp::Ordering::Equal {
-            a.3.cmp(&b.3) // Less days is better
-        } else {
-            health_cmp
-        }
-    });
-
-    // Clone health_data for recommendations before consuming it
-    let critical_courses: Vec<_> = health_data
-        .iter()
-        .filter(|(_, _, _, _, health)| *health >= 3)
-        .map(|(course_id, _, count, _, health)| (course_id.clone(), *count, *health))
-        .collect();
-
-    for (course_id, course_name, count, days_since, health_status) in health_data {
-        let (icon, status_text, color_fn): (_, _, fn(&str) -> colored::ColoredString) =
-            match health_status {
-                0 => ("🟢", "Excellent", |s: &str| s.bright_green()),
-                1 => ("🟡", "Good", |s: &str| s.bright_yellow()),
-                2 => ("🟠", "Warning", |s: &str| s.yellow()),
-                _ => ("🔴", "Critical", |s: &str| s.bright_red()),
-            };
+    for health in &health_data {
+        let (icon, color_fn): (_, fn(&str) -> colored::ColoredString) = match health.status {
+            0 => ("🟢", |s: &str| s.bright_green()),
+            1 => ("🟡", |s: &str| s.bright_yellow()),
+            2 => ("🟠", |s: &str| s.yellow()),
+            _ => ("🔴", |s: &str| s.bright_red()),
+        };
 
-        let activity_text = if days_since >= 999 {
-            "no activity".dimmed()
-        } else if days_since == 0 {
-            "active today".bright_green()
-        } else if days_since == 1 {
-            "active yesterday".green()
-        } else {
-            format!("active {} days ago", days_since).dimmed()
+        let activity_text = match health.days_since_activity {
+            None => "no activity".dimmed(),
+            Some(0) => "active today".bright_green(),
+            Some(1) => "active yesterday".green(),
+            Some(days) => format!("active {} days ago", days).dimmed(),
         };
 
         println!(
             "  {} {} {} - {} ({} assignments, {})",
             icon,
-            color_fn(status_text),
-            course_id.bright_blue(),
-            course_name,
-            count,
+            color_fn(&health.label),
+            health.course_id.bright_blue(),
+            health.name,
+            health.count,
             activity_text
         );
     }
@@ -525,22 +695,11 @@ pub fn show_assignment_health(course_id: Option<&str>) -> Result<()> {
     println!();
 
     // Provide recommendations
-    if !critical_courses.is_empty() {
+    let recommendations = RecommendationEngine::generate(&config, course_id)?;
+    if !recommendations.is_empty() {
         println!("{} Recommendations:", "💡".yellow());
-        for (course_id, count, _) in &critical_courses {
-            if *count == 0 {
-                println!(
-                    "  • Create first assignment for {}: {}",
-                    course_id.bright_blue(),
-                    format!("noter assignment {} \"Assignment 1\"", course_id).bright_white()
-                );
-            } else {
-                println!(
-                    "  • Resume work on {}: {}",
-                    course_id.bright_blue(),
-                    format!("noter assignments recent {}", course_id).bright_white()
-                );
-            }
+        for recommendation in &recommendations {
+            print_recommendation(recommendation);
         }
         println!();
     }
@@ -562,66 +721,147 @@ pub fn show_assignment_health(course_id: Option<&str>) -> Result<()> {
 
 // Helper functions
 
-/// Get assignment statistics for a directory
+/// Print a recommendation with an icon for its priority
+pub(crate) fn print_recommendation(recommendation: &Recommendation) {
+    let (icon, priority) = match recommendation.priority {
+        RecommendationPriority::High => ("🔴", "high"),
+        RecommendationPriority::Medium => ("🟡", "medium"),
+        RecommendationPriority::Low => ("🔵", "low"),
+    };
+
+    OutputManager::print_record(
+        &format!(
+            "  {} {}: {}",
+            icon,
+            recommendation.reason,
+            recommendation.command.bright_white()
+        ),
+        &[
+            "suggestion",
+            priority,
+            &recommendation.reason,
+            &recommendation.command,
+        ],
+    );
+}
+
+/// Number of assignments under a directory and when the newest was modified,
+/// skipping what `.noterignore` matches
 fn get_assignment_stats_for_directory(
     assignments_dir: &Path,
+    config: &Config,
 ) -> Result<(usize, Option<std::time::SystemTime>)> {
     if !assignments_dir.exists() {
         return Ok((0, None));
     }
 
-    let mut count = 0;
-    let mut most_recent = None;
-
-    for entry in fs::read_dir(assignments_dir)? {
-        let entry = entry?;
-        if entry.path().extension().is_some_and(|ext| ext == "typ") {
-            count += 1;
-
-            if let Ok(metadata) = entry.metadata() {
-                if let Ok(modified) = metadata.modified() {
-                    match most_recent {
-                        None => most_recent = Some(modified),
-                        Some(prev_time) => {
-                            if modified > prev_time {
-                                most_recent = Some(modified);
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
+    let files = DirectoryScanner::scan_directory_for_files(
+        assignments_dir,
+        &config.typst.source_extensions(),
+        config.paths.follow_symlinks,
+    )?;
+    let most_recent = DirectoryScanner::find_most_recent(&files).map(|file| file.modified);
 
-    Ok((count, most_recent))
+    Ok((files.len(), most_recent))
 }
 
 fn calculate_assignment_health_status(
     count: usize,
-    last_modified: Option<std::time::SystemTime>,
-) -> usize {
+    days_since_activity: Option<u64>,
+    thresholds: &HealthThresholds,
+) -> HealthStatus {
     if count == 0 {
-        return 3; // Critical - no assignments
+        return HealthStatus::Critical; // No assignments
     }
 
-    if let Some(last_modified) = last_modified {
-        let now = std::time::SystemTime::now();
-        if let Ok(duration) = now.duration_since(last_modified) {
-            let days = duration.as_secs() / (24 * 60 * 60);
-            match days {
-                0..=3 => 0,  // Excellent
-                4..=7 => 1,  // Good
-                8..=14 => 2, // Warning
-                _ => 3,      // Critical
-            }
-        } else {
-            3 // Critical - time error
-        }
-    } else {
-        3 // Critical - no timestamp
-    }
+    HealthStatus::from_days_since_activity(days_since_activity, thresholds)
 }
 
 fn health_status_to_priority(health: usize) -> usize {
     health // 0 = best, 3 = worst
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_compute_assignment_health() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let mut config = Config::default();
+        config.paths.notes_dir = temp_dir.path().to_string_lossy().to_string();
+
+        let assignments_dir = temp_dir.path().join("02101").join("assignments");
+        fs::create_dir_all(&assignments_dir)?;
+        fs::write(assignments_dir.join("assignment-1.typ"), "= Assignment 1")?;
+
+        let health = compute_assignment_health(&config, None)?;
+        assert_eq!(health.len(), config.courses.len());
+
+        // The active course sorts first; courses without assignments are critical
+        assert_eq!(health[0].course_id, "02101");
+        assert_eq!(health[0].count, 1);
+        assert_eq!(health[0].days_since_activity, Some(0));
+        assert_eq!(
+            (health[0].status, health[0].label.as_str()),
+            (0, "Excellent")
+        );
+
+        let last = health.last().unwrap();
+        assert_eq!((last.status, last.label.as_str()), (3, "Critical"));
+        assert_eq!(last.days_since_activity, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_similar_titles_resolve_by_conflict_policy() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let config = Config::default();
+        let filename = |title| format!("{}.typ", Validator::sanitize_filename(title));
+
+        // Both titles sanitize to problem-set-1.typ
+        assert_eq!(filename("Problem Set 1"), filename("Problem-Set #1"));
+        let existing = temp_dir.path().join(filename("Problem Set 1"));
+        let resolve =
+            |policy| super::super::notes::resolve_file_conflict(&existing, policy, &config);
+
+        // A free path is used as is, whatever the policy
+        let free = temp_dir.path().join("lab-1.typ");
+        assert_eq!(
+            super::super::notes::resolve_file_conflict(&free, ConflictPolicy::Error, &config)?,
+            Some(free)
+        );
+
+        fs::write(&existing, "= Problem Set 1")?;
+        assert_eq!(
+            resolve(ConflictPolicy::Suffix)?,
+            Some(temp_dir.path().join("problem-set-1-1.typ"))
+        );
+        assert!(resolve(ConflictPolicy::Error).is_err());
+        assert_eq!(resolve(ConflictPolicy::Overwrite)?, Some(existing.clone()));
+        // The existing file is never touched while resolving
+        assert_eq!(fs::read_to_string(&existing)?, "= Problem Set 1");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_assignment_stats_skip_ignored_files() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let config = Config::default();
+        let assignments_dir = temp_dir.path().join("02101").join("assignments");
+        fs::create_dir_all(assignments_dir.join("week-1"))?;
+        fs::write(assignments_dir.join("assignment-1.typ"), "")?;
+        fs::write(assignments_dir.join("week-1").join("assignment-2.typ"), "")?;
+        fs::write(assignments_dir.join("draft.typ"), "")?;
+        fs::write(temp_dir.path().join(".noterignore"), "draft.*\n")?;
+
+        let (count, last_modified) = get_assignment_stats_for_directory(&assignments_dir, &config)?;
+        assert_eq!(count, 2);
+        assert!(last_modified.is_some());
+
+        Ok(())
+    }
+}
@@ -1,12 +1,18 @@
 use anyhow::Result;
 use colored::*;
 use serde_json::Value;
+use std::io::{self, IsTerminal};
+use std::path::Path;
 
-use crate::config::{Config, TemplateRepository, get_config, update_author, update_editor};
+use crate::config::{
+    Config, TemplateRepository, UNSETTABLE_FIELDS, get_config, update_author, update_editor,
+};
+use crate::core::git_integration::GitIntegration;
 use crate::ui::output::{OutputManager, Status};
+use crate::ui::prompts::PromptManager;
 
 pub fn show_config() -> Result<()> {
-    let config = get_config()?;
+    let config = Config::load_unresolved()?;
 
     println!("{} Current Configuration:", "⚙️".blue());
     println!();
@@ -72,20 +78,20 @@ fn display_value(value: &Value, indent: usize, key: &str) {
     }
 }
 
+/// Print a single configuration value, undecorated, for use in scripts
 pub fn get_config_value(key: &str) -> Result<()> {
-    let config = get_config()?;
+    let config = Config::load_unresolved()?;
     let json_value = serde_json::to_value(&config)?;
 
     // Navigate to the value using dot notation
-    let value = navigate_json_path(&json_value, key)
-        .ok_or_else(|| anyhow::anyhow!("Configuration key '{}' not found", key))?;
+    let value = navigate_json_path(&json_value, key)?;
 
-    println!("{}: {}", key.green(), format_value_inline(value));
+    println!("{}", format_value_plain(value)?);
     Ok(())
 }
 
 pub fn set_config_value(key: &str, value: &str) -> Result<()> {
-    let mut config = get_config()?;
+    let mut config = Config::load_unresolved()?;
     let mut json_value = serde_json::to_value(&config)?;
 
     // Update the value using dot notation
@@ -93,6 +99,7 @@ pub fn set_config_value(key: &str, value: &str) -> Result<()> {
 
     // Deserialize back to Config and save
     config = serde_json::from_value(json_value)?;
+    validate_changed_section(&config, key)?;
     config.save()?;
 
     println!(
@@ -104,22 +111,40 @@ pub fn set_config_value(key: &str, value: &str) -> Result<()> {
     Ok(())
 }
 
-pub fn edit_config() -> Result<()> {
+/// Validate the section `key` belongs to, leaving problems elsewhere in the
+/// config to `config check`
+fn validate_changed_section(config: &Config, key: &str) -> Result<()> {
+    match key.split('.').next() {
+        Some("health_thresholds") => config.health_thresholds.validate(),
+        Some("semester_boundaries") => config.semester_boundaries.validate(),
+        _ => Ok(()),
+    }
+}
+
+pub fn unset_config_value(field: &str) -> Result<()> {
+    let mut config = Config::load_unresolved()?;
+    let key = config.unset_field(field)?;
+    config.save()?;
+
+    let fallback = UNSETTABLE_FIELDS
+        .iter()
+        .find(|unsettable| unsettable.key == key)
+        .map(|unsettable| unsettable.fallback)
+        .unwrap_or_default();
+
+    println!(
+        "{} Configuration cleared: {} ({})",
+        "✅".green(),
+        key.cyan(),
+        fallback.dimmed()
+    );
+    Ok(())
+}
+
+pub fn edit_config(editor: Option<&str>) -> Result<()> {
     let config_path = Config::config_file_path()?;
-    let config = get_config()?;
-
-    // Get editor
-    let editor = config
-        .preferred_editor
-        .or_else(|| std::env::var("EDITOR").ok())
-        .or_else(|| std::env::var("VISUAL").ok())
-        .unwrap_or_else(|| {
-            if cfg!(windows) {
-                "notepad".to_string()
-            } else {
-                "nano".to_string()
-            }
-        });
+    let config = Config::load_unresolved()?.with_editor_override(editor);
+    let editor = config.terminal_editor(|name| std::env::var(name).ok());
 
     println!(
         "{} Opening config file in {}...",
@@ -156,7 +181,7 @@ pub fn edit_config() -> Result<()> {
 }
 
 pub fn list_config_keys() -> Result<()> {
-    let config = get_config()?;
+    let config = Config::load_unresolved()?;
     let json_value = serde_json::to_value(&config)?;
 
     println!("{} Available Configuration Keys:", "🔑".blue());
@@ -198,7 +223,7 @@ pub fn interactive_config() -> Result<()> {
     println!("Press Enter to keep the current value, or type a new value.");
     println!();
 
-    let mut config = get_config()?;
+    let mut config = Config::load_unresolved()?;
 
     // Author
     print!("Author name [{}]: ", config.author.green());
@@ -317,20 +342,53 @@ pub fn interactive_config() -> Result<()> {
 }
 
 /// Navigate JSON value using dot notation path
-fn navigate_json_path<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
-    let parts: Vec<&str> = path.split('.').collect();
+fn navigate_json_path<'a>(value: &'a Value, path: &str) -> Result<&'a Value> {
     let mut current = value;
+    let mut parent = String::new();
+
+    for part in path.split('.') {
+        let Value::Object(map) = current else {
+            anyhow::bail!(
+                "Configuration key '{}' not found: '{}' has no fields",
+                path,
+                parent
+            );
+        };
 
-    for part in parts {
-        match current {
-            Value::Object(map) => {
-                current = map.get(part)?;
-            }
-            _ => return None,
+        current = map.get(part).ok_or_else(|| {
+            let mut fields: Vec<&str> = map.keys().map(String::as_str).collect();
+            fields.sort();
+            let location = if parent.is_empty() {
+                String::new()
+            } else {
+                format!(" under '{}'", parent)
+            };
+            anyhow::anyhow!(
+                "Configuration key '{}' not found. Available fields{}: {}",
+                path,
+                location,
+                fields.join(", ")
+            )
+        })?;
+
+        if !parent.is_empty() {
+            parent.push('.');
         }
+        parent.push_str(part);
     }
 
-    Some(current)
+    Ok(current)
+}
+
+/// Value as printed by `config get`: strings without quotes, `null` as an
+/// empty line and nested values as JSON
+fn format_value_plain(value: &Value) -> Result<String> {
+    Ok(match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        Value::Bool(_) | Value::Number(_) => value.to_string(),
+        Value::Array(_) | Value::Object(_) => serde_json::to_string_pretty(value)?,
+    })
 }
 
 /// Update JSON value using dot notation path
@@ -440,38 +498,6 @@ fn collect_json_keys(value: &Value, prefix: &str) -> Vec<String> {
     keys
 }
 
-/// Format a JSON value for inline display
-fn format_value_inline(value: &Value) -> String {
-    match value {
-        Value::String(s) => s.yellow().to_string(),
-        Value::Number(n) => n.to_string().cyan().to_string(),
-        Value::Bool(b) => {
-            if *b {
-                "true".bright_green().to_string()
-            } else {
-                "false".bright_red().to_string()
-            }
-        }
-        Value::Null => "null".bright_black().to_string(),
-        Value::Array(arr) => {
-            if arr.is_empty() {
-                "[]".bright_black().to_string()
-            } else {
-                format!("[{} items]", arr.len()).bright_black().to_string()
-            }
-        }
-        Value::Object(map) => {
-            if map.is_empty() {
-                "{}".bright_black().to_string()
-            } else {
-                format!("{{...}} ({} fields)", map.len())
-                    .bright_black()
-                    .to_string()
-            }
-        }
-    }
-}
-
 pub fn set_author(name: &str) -> Result<()> {
     update_author(name.to_string())?;
     println!("{} Author updated to: {}", "✅".green(), name.green());
@@ -494,7 +520,7 @@ pub fn add_template_repository(
     version: Option<&str>,
     template_path: Option<&str>,
 ) -> Result<()> {
-    let mut config = get_config()?;
+    let mut config = Config::load_unresolved()?;
 
     // Check if repository already exists
     if config
@@ -512,6 +538,7 @@ pub fn add_template_repository(
     let template_repo = TemplateRepository {
         name: name.to_string(),
         repository: repository.to_string(),
+        host: None,
         version: version.map(|v| v.to_string()),
         branch: None,
         template_path: template_path.map(|p| p.to_string()),
@@ -531,7 +558,7 @@ pub fn add_template_repository(
 }
 
 pub fn remove_template_repository(name: &str) -> Result<()> {
-    let mut config = get_config()?;
+    let mut config = Config::load_unresolved()?;
 
     let initial_len = config.templates.custom_repositories.len();
     config
@@ -549,7 +576,7 @@ pub fn remove_template_repository(name: &str) -> Result<()> {
 }
 
 pub fn enable_template_repository(name: &str, enabled: bool) -> Result<()> {
-    let mut config = get_config()?;
+    let mut config = Config::load_unresolved()?;
 
     let repo = config
         .templates
@@ -568,7 +595,7 @@ pub fn enable_template_repository(name: &str, enabled: bool) -> Result<()> {
 }
 
 pub fn list_template_repositories() -> Result<()> {
-    let config = get_config()?;
+    let config = Config::load_unresolved()?;
 
     if config.templates.custom_repositories.is_empty() {
         println!("{} No custom template repositories configured", "📝".blue());
@@ -603,7 +630,7 @@ pub fn list_template_repositories() -> Result<()> {
 }
 
 pub fn set_template_auto_update(enabled: bool) -> Result<()> {
-    let mut config = get_config()?;
+    let mut config = Config::load_unresolved()?;
     config.templates.auto_update = enabled;
     config.save()?;
 
@@ -626,9 +653,76 @@ pub fn show_config_path() -> Result<()> {
     Ok(())
 }
 
-pub fn check_config() -> Result<()> {
-    let config = get_config()?;
-    let warnings = config.validate()?;
+/// Print every configured directory after path resolution, with the working
+/// directory relative paths are resolved against
+pub fn show_resolved_paths() -> Result<()> {
+    let config = Config::load_unresolved()?;
+    let current_dir = std::env::current_dir()?;
+
+    println!("{} Resolved paths:", "📁".blue());
+    println!(
+        "  {:<20} {}",
+        "working directory",
+        current_dir.display().to_string().bright_white()
+    );
+    println!(
+        "  {:<20} {}",
+        "config file",
+        Config::config_file_path()?.display()
+    );
+    println!();
+
+    for (field, path) in config.paths.resolved_dirs(&current_dir)? {
+        let status = if Path::new(&path).is_dir() {
+            "exists".green()
+        } else if Path::new(&path).exists() {
+            "not a directory".yellow()
+        } else {
+            "missing".red()
+        };
+        println!("  {:<20} {} ({})", field, path, status);
+    }
+
+    Ok(())
+}
+
+pub fn check_config(fix: bool) -> Result<()> {
+    let mut config = Config::load_unresolved()?;
+    // A path that can't be resolved is reported along with the other warnings
+    let resolve_error = config.resolve_paths().err();
+
+    if fix && resolve_error.is_some() {
+        // Fixes would act on the unresolved paths, e.g. create a literal `$VAR` directory
+        OutputManager::print_status(
+            Status::Warning,
+            "Not fixing anything until every path resolves",
+        );
+        println!();
+    } else if fix {
+        let fixes = config.fix(|| {
+            GitIntegration::user_name().or_else(|| {
+                io::stdin()
+                    .is_terminal()
+                    .then(|| PromptManager::input("Your name", None).ok())
+                    .flatten()
+            })
+        })?;
+
+        if fixes.is_empty() {
+            OutputManager::print_status(Status::Info, "Nothing to fix automatically");
+        } else {
+            config.save()?;
+            for fix in &fixes {
+                OutputManager::print_status(Status::Success, fix);
+            }
+        }
+        println!();
+    }
+
+    let mut warnings = config.validate()?;
+    if let Some(error) = resolve_error {
+        warnings.insert(0, format!("{:#}", error));
+    }
 
     if warnings.is_empty() {
         println!("{} Configuration is valid!", "✅".green());
@@ -671,7 +765,7 @@ pub fn migrate_config() -> Result<()> {
 
 pub fn cleanse_config(skip_confirmation: bool) -> Result<()> {
     if !skip_confirmation {
-        let config = get_config()?;
+        let config = Config::load_unresolved()?;
         let config_path = Config::config_file_path()?;
 
         OutputManager::print_status(
@@ -715,3 +809,44 @@ pub fn cleanse_config(skip_confirmation: bool) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_config_get_prints_plain_values() -> Result<()> {
+        let config = json!({
+            "author": "Jane Doe",
+            "preferred_editor": null,
+            "paths": { "notes_dir": "/home/jane/notes", "flat_layout": false },
+        });
+
+        let get = |path| navigate_json_path(&config, path).and_then(format_value_plain);
+        assert_eq!(get("author")?, "Jane Doe");
+        assert_eq!(get("paths.notes_dir")?, "/home/jane/notes");
+        assert_eq!(get("paths.flat_layout")?, "false");
+        assert_eq!(get("preferred_editor")?, "");
+
+        let error = get("paths.vault").unwrap_err().to_string();
+        assert!(error.contains("under 'paths': flat_layout, notes_dir"));
+        assert!(get("author.name").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_config_set_validates_only_the_changed_section() {
+        let mut config = Config::default();
+        config.semester_boundaries.fall_start_month = 13;
+
+        // A broken section doesn't block changing another one
+        assert!(validate_changed_section(&config, "health_thresholds.good_days").is_ok());
+        assert!(validate_changed_section(&config, "author").is_ok());
+        assert!(validate_changed_section(&config, "semester_boundaries.fall_start_month").is_err());
+
+        config.health_thresholds.good_days = config.health_thresholds.warning_days + 1;
+        assert!(validate_changed_section(&config, "health_thresholds.good_days").is_err());
+    }
+}
@@ -3,27 +3,38 @@
 //! Thin command layer that delegates to core business logic.
 
 use crate::config::get_config;
-use crate::core::course_management::{CourseManager, get_common_courses};
+use crate::core::course_catalog::CourseCatalog;
+use crate::core::course_management::{CourseManager, get_common_courses, search_course_catalog};
 use crate::core::validation::Validator;
 use crate::ui::formatters::Formatters;
 use crate::ui::output::{OutputManager, Status};
+use crate::ui::prompts::PromptManager;
 use anyhow::Result;
 use colored::Colorize;
+use std::io::{self, IsTerminal};
 
 pub fn list_courses() -> Result<()> {
     let config = get_config()?;
     let courses = config.list_courses();
 
     let formatted_output = Formatters::format_course_list(&courses);
-    println!("{}", formatted_output);
+    if !formatted_output.is_empty() {
+        println!("{}", formatted_output);
+    }
+
+    // Aliases only clutter the plain course list
+    let aliases = config.list_course_aliases();
+    if !aliases.is_empty() && !OutputManager::is_plain() {
+        println!("{}", Formatters::format_course_aliases(&aliases));
+    }
 
     if !courses.is_empty() {
         print_usage_examples();
     } else {
-        println!(
+        OutputManager::print_hint(&format!(
             "Add courses with: {}",
             "noter courses add 02101 \"Introduction to Programming\"".bright_white()
-        );
+        ));
     }
 
     Ok(())
@@ -33,6 +44,20 @@ pub fn add_course(course_id: &str, course_name: &str) -> Result<()> {
     Validator::validate_course_id(course_id)?;
 
     let mut config = get_config()?;
+
+    // The built-in list is far from complete, so only a catalog file is
+    // trusted to know every course
+    let catalog = CourseCatalog::load_or_builtin(&config);
+    if catalog.source().is_some() && !catalog.contains(course_id) {
+        OutputManager::print_status(
+            Status::Warning,
+            &format!(
+                "{} is not in the course catalog; check the course code",
+                course_id.yellow()
+            ),
+        );
+    }
+
     let mut manager = CourseManager::new(&mut config);
 
     match manager.add_course(course_id, course_name) {
@@ -60,9 +85,10 @@ pub fn add_course(course_id: &str, course_name: &str) -> Result<()> {
 }
 
 pub fn remove_course(course_id: &str) -> Result<()> {
+    let mut config = get_config()?;
+    let course_id = &config.resolve_course_id(course_id);
     Validator::validate_course_id(course_id)?;
 
-    let mut config = get_config()?;
     let mut manager = CourseManager::new(&mut config);
 
     match manager.remove_course(course_id) {
@@ -94,10 +120,121 @@ pub fn remove_course(course_id: &str) -> Result<()> {
     Ok(())
 }
 
+pub fn add_course_alias(alias: &str, course_id: &str) -> Result<()> {
+    Validator::validate_course_id(course_id)?;
+
+    let mut config = get_config()?;
+
+    if !config.courses.contains_key(course_id) {
+        OutputManager::print_status(
+            Status::Warning,
+            &format!(
+                "Course {} is not in your configuration yet. Add it with 'noter courses add'",
+                course_id.yellow()
+            ),
+        );
+    }
+
+    match config.add_course_alias(alias, course_id) {
+        Ok(()) => {
+            OutputManager::print_status(
+                Status::Success,
+                &format!(
+                    "Added alias: {} -> {}",
+                    alias.to_lowercase().green(),
+                    course_id.yellow()
+                ),
+            );
+            println!(
+                "You can now create notes with: {}",
+                format!("noter note {}", alias.to_lowercase()).bright_white()
+            );
+        }
+        Err(e) => OutputManager::print_status(Status::Error, &e.to_string()),
+    }
+
+    Ok(())
+}
+
+pub fn remove_course_alias(alias: &str) -> Result<()> {
+    let mut config = get_config()?;
+
+    match config.remove_course_alias(alias)? {
+        Some(course_id) => OutputManager::print_status(
+            Status::Success,
+            &format!(
+                "Removed alias: {} -> {}",
+                alias.to_lowercase().dimmed(),
+                course_id.yellow()
+            ),
+        ),
+        None => OutputManager::print_status(
+            Status::Error,
+            &format!("Alias {} not found in your configuration.", alias.yellow()),
+        ),
+    }
+
+    Ok(())
+}
+
+/// Search the course catalog, optionally adding a result to the configuration
+pub fn search_courses(query: &str, limit: usize, add: bool) -> Result<()> {
+    let config = get_config()?;
+    let catalog = CourseCatalog::load(&config)?;
+    let mut matches = search_course_catalog(&catalog, query);
+    matches.truncate(limit);
+
+    if matches.is_empty() {
+        OutputManager::print_status(Status::Info, &format!("No DTU courses match '{}'", query));
+        println!(
+            "Add a course by hand with: {}",
+            "noter courses add <code> \"<name>\"".bright_white()
+        );
+        return Ok(());
+    }
+
+    OutputManager::print_section(&format!("Courses matching '{}'", query), Some("🔍"));
+    for course in &matches {
+        if config.courses.contains_key(&course.course_id) {
+            println!(
+                "  {} - {} {}",
+                course.course_id.dimmed(),
+                course.course_name.dimmed(),
+                "✓".green()
+            );
+        } else {
+            println!("  {} - {}", course.course_id.yellow(), course.course_name);
+        }
+    }
+    println!();
+
+    if !add {
+        return Ok(());
+    }
+
+    let selected = if matches.len() == 1 {
+        &matches[0]
+    } else if io::stdin().is_terminal() {
+        let options: Vec<String> = matches
+            .iter()
+            .map(|course| format!("{} - {}", course.course_id, course.course_name))
+            .collect();
+        &matches[PromptManager::select("Course to add", &options)?]
+    } else {
+        anyhow::bail!(
+            "{} courses match '{}'. Narrow the query or add one with 'noter courses add'",
+            matches.len(),
+            query
+        );
+    };
+
+    add_course(&selected.course_id, &selected.course_name)
+}
+
 pub fn browse_common_courses() -> Result<()> {
     let config = get_config()?;
     let user_courses: std::collections::HashSet<String> = config.courses.keys().cloned().collect();
-    let dtu_courses = crate::data::get_common_dtu_courses();
+    let catalog = CourseCatalog::load_or_builtin(&config);
 
     OutputManager::print_section("DTU Course Database", Some("🎓"));
 
@@ -119,6 +256,8 @@ pub fn browse_common_courses() -> Result<()> {
     for (category, courses) in categories {
         println!("{}:", category.bright_cyan());
         for (course_id, course_name) in *courses {
+            // The catalog file knows the current names
+            let course_name = catalog.name(course_id).unwrap_or(course_name);
             if user_courses.contains(*course_id) {
                 // Already configured - show dimmed
                 println!(
@@ -141,26 +280,23 @@ pub fn browse_common_courses() -> Result<()> {
         .flat_map(|(_, courses)| courses.iter().map(|(id, _)| *id))
         .collect();
 
-    let additional_courses: Vec<_> = dtu_courses
+    // The catalog iterates in code order
+    let additional_courses: Vec<_> = catalog
         .iter()
-        .filter(|(id, _)| !category_courses.contains(*id) && !user_courses.contains(**id))
+        .filter(|(id, _)| !category_courses.contains(id) && !user_courses.contains(*id))
         .collect();
 
     if !additional_courses.is_empty() {
         println!("{} More DTU courses:", "💡".blue());
-        let mut sorted_additional: Vec<_> = additional_courses.into_iter().collect();
-        sorted_additional.sort_by_key(|(id, _)| *id);
 
-        for (course_id, course_name) in sorted_additional.into_iter().take(10) {
+        for (course_id, course_name) in additional_courses.iter().take(10) {
             println!("  {} - {}", course_id.yellow(), course_name);
         }
-        if dtu_courses.len() - category_courses.len() > 10 {
+        if additional_courses.len() > 10 {
             println!(
                 "  {} ... and {} more courses",
                 "".dimmed(),
-                (dtu_courses.len() - category_courses.len() - 10)
-                    .to_string()
-                    .dimmed()
+                (additional_courses.len() - 10).to_string().dimmed()
             );
         }
         println!();
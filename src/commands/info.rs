@@ -5,33 +5,117 @@
 use anyhow::Result;
 use colored::Colorize;
 
-use crate::config::get_config;
-use crate::core::status_manager::StatusManager;
-use crate::ui::output::{OutputManager, Status};
+use crate::commands::assignments::print_recommendation;
+use crate::config::{Config, get_config};
+use crate::core::directory_scanner::FileInfo;
+use crate::core::file_operations::FileOperations;
+use crate::core::github_template_fetcher::GitHubTemplateFetcher;
+use crate::core::recommendations::RecommendationEngine;
+use crate::core::status_manager::{HealthStatus, StatusManager};
+use crate::core::validation::Validator;
+use crate::core::weekly_summary::WeeklySummary;
+use crate::ui::output::{Alignment, OutputManager, Status, TableColumn};
+use crate::ui::width::TextWidth;
+
+/// Maximum number of recommendations shown on the status dashboard
+const QUICK_SUGGESTION_LIMIT: usize = 3;
+
+/// Show build and environment details, as JSON for scripts and bug reports
+pub fn show_info(json: bool) -> Result<()> {
+    let config = get_config()?;
+    let info = StatusManager::get_environment_info(&config)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&info)?);
+        return Ok(());
+    }
+
+    OutputManager::print_section("DTU Notes Info", Some("ℹ️"));
+    OutputManager::print_key_value_pairs(
+        &[
+            ("Version".to_string(), info.version.clone()),
+            (
+                "Platform".to_string(),
+                format!("{} ({})", info.os, info.arch),
+            ),
+            (
+                "Typst".to_string(),
+                info.typst_version
+                    .clone()
+                    .unwrap_or_else(|| "not found".red().to_string()),
+            ),
+            (
+                "Config file".to_string(),
+                info.config_path.display().to_string(),
+            ),
+            ("Notes".to_string(), info.paths.notes_dir.clone()),
+            (
+                "Obsidian vault".to_string(),
+                info.paths.obsidian_dir.clone(),
+            ),
+            ("Templates".to_string(), info.paths.templates_dir.clone()),
+            (
+                "Typst packages".to_string(),
+                info.paths.typst_packages_dir.clone(),
+            ),
+        ],
+        None,
+    );
+
+    println!();
+    println!("📦 Template packages:");
+    if info.template_packages.is_empty() {
+        println!(
+            "  {}",
+            "none installed (run 'noter template update')".yellow()
+        );
+    }
+    for package in &info.template_packages {
+        let shadowed = if package.shadowed {
+            " (shadowed)".dimmed().to_string()
+        } else {
+            String::new()
+        };
+        println!(
+            "  {} {} [{}] {}{}",
+            package.name.bright_white(),
+            package.version.green(),
+            package.location,
+            package.path.display().to_string().dimmed(),
+            shadowed
+        );
+    }
+
+    println!();
+    println!(
+        "Paste {} into bug reports",
+        "noter info --json".bright_white()
+    );
 
+    Ok(())
+}
+
+/// Show the status dashboard. `window_days` overrides the configured
+/// activity window for the most active course (0 = all time).
 #[allow(dead_code)]
-pub fn show_enhanced_status() -> Result<()> {
+pub fn show_enhanced_status(window_days: Option<u64>) -> Result<()> {
     let config = get_config()?;
+    let window_days = window_days.unwrap_or(config.activity_window_days);
 
     OutputManager::print_section("DTU Notes Status Dashboard", Some("📊"));
 
     // Get comprehensive status information
     let system_status = StatusManager::get_system_status(&config)?;
-    let activity_summary = StatusManager::get_activity_summary(&config)?;
+    let activity_summary = StatusManager::get_activity_summary(&config, window_days)?;
     let course_health = StatusManager::get_course_health(&config)?;
     let semester_info = StatusManager::get_semester_info(&config);
 
     // Display system status
     show_system_status_section(&system_status);
+    show_template_update_notice(&config);
 
     // Display configuration warnings
-    if !system_status.configuration_warnings.is_empty() {
-        println!();
-        println!("{} Configuration Warnings:", "⚠️".yellow());
-        for warning in &system_status.configuration_warnings {
-            println!("  • {}", warning.yellow());
-        }
-    }
+    show_configuration_warnings(&system_status.configuration_warnings);
 
     // Display activity summary
     show_activity_summary_section(&activity_summary);
@@ -39,18 +123,226 @@ pub fn show_enhanced_status() -> Result<()> {
     // Display course health
     if !course_health.is_empty() {
         show_course_health_section(&course_health);
+        show_stale_pdfs_section(&course_health);
     }
 
     // Show semester info
-    println!();
-    println!(
-        "📅 Current semester: {}",
-        semester_info.current_semester.bright_green()
+    OutputManager::print_hint("");
+    OutputManager::print_record(
+        &format!(
+            "📅 Current semester: {}",
+            semester_info.current_semester.bright_green()
+        ),
+        &["semester", &semester_info.current_semester],
     );
 
     // Quick suggestions
+    OutputManager::print_hint("");
+    show_quick_suggestions(&config, &activity_summary)?;
+
+    Ok(())
+}
+
+/// Show one course in detail: its notes and assignments, health, last
+/// activity, uncompiled files and disk usage
+pub fn show_course_status(course_id: &str) -> Result<()> {
+    let config = get_config()?;
+    let course_id = &config.resolve_course_id(course_id);
+    Validator::validate_course_id(course_id)?;
+
+    let detail = StatusManager::get_course_detail(&config, course_id)?;
+    if !config.courses.contains_key(course_id) && !detail.has_directory() {
+        OutputManager::print_status(
+            Status::Error,
+            &format!(
+                "Course {} is not in your configuration and has no directory. Add it with 'noter courses add'",
+                course_id
+            ),
+        );
+        return Ok(());
+    }
+
+    let title = if detail.course_name.is_empty() {
+        course_id.to_string()
+    } else {
+        format!("{} - {}", course_id, detail.course_name)
+    };
+    OutputManager::print_section(&title, Some("🎓"));
+
+    let Some(health) = &detail.health else {
+        OutputManager::print_status(
+            Status::Info,
+            &format!("No course directory yet ({})", detail.course_path.display()),
+        );
+        OutputManager::print_hint(&format!(
+            "Create the first note with: {}",
+            format!("noter note {}", course_id).bright_white()
+        ));
+        return Ok(());
+    };
+
+    let last_activity = match &detail.last_activity {
+        Some(file) => {
+            let datetime: chrono::DateTime<chrono::Local> = file.modified.into();
+            format!(
+                "{} ({})",
+                datetime.format("%Y-%m-%d %H:%M"),
+                FileOperations::note_display_name(&file.path)
+            )
+        }
+        None => "never".to_string(),
+    };
+    OutputManager::print_key_value_pairs(
+        &[
+            (
+                "Directory".to_string(),
+                detail.course_path.display().to_string(),
+            ),
+            (
+                "Health".to_string(),
+                format!(
+                    "{} {} ({})",
+                    OutputManager::symbol(health_indicator(&health.health_status), ""),
+                    health.health_status.label(),
+                    last_activity_label(health.days_since_last_activity)
+                ),
+            ),
+            ("Last activity".to_string(), last_activity),
+            (
+                "Uncompiled".to_string(),
+                detail.uncompiled.len().to_string(),
+            ),
+            (
+                "Outdated PDFs".to_string(),
+                health.stale_pdfs_count.to_string(),
+            ),
+            (
+                "Disk usage".to_string(),
+                FileOperations::format_file_size(detail.disk_usage),
+            ),
+        ],
+        None,
+    );
+
+    print_course_files("📚 Notes", "note", &detail.notes);
+    print_course_files("📝 Assignments", "assignment", &detail.assignments);
+
+    if !detail.uncompiled.is_empty() {
+        OutputManager::print_hint("");
+        OutputManager::print_hint("📄 Not compiled yet:");
+        for path in &detail.uncompiled {
+            let relative_path = path.strip_prefix(&detail.course_path).unwrap_or(path);
+            OutputManager::print_record(
+                &format!("  • {}", relative_path.display()),
+                &["uncompiled".to_string(), path.display().to_string()],
+            );
+        }
+        OutputManager::print_hint(&format!(
+            "  {} Run {} to compile them",
+            "💡".yellow(),
+            "noter compile <file>".bright_white()
+        ));
+    }
+
+    Ok(())
+}
+
+/// A titled list of course files with their modification times. Plain
+/// records start with `kind`.
+fn print_course_files(title: &str, kind: &str, files: &[FileInfo]) {
+    OutputManager::print_hint("");
+    OutputManager::print_hint(&format!("{} ({}):", title, files.len()));
+    if files.is_empty() {
+        OutputManager::print_hint(&format!("  {}", "none".dimmed()));
+    }
+    for file in files {
+        let datetime: chrono::DateTime<chrono::Local> = file.modified.into();
+        let modified = datetime.format("%Y-%m-%d").to_string();
+        let name = FileOperations::note_display_name(&file.path);
+        OutputManager::print_record(
+            &format!("  {} {}", modified.dimmed(), name),
+            &[kind, &modified, &name, &file.path.display().to_string()],
+        );
+    }
+}
+
+/// Show this week's notes and assignments per course, grouped by day
+pub fn show_week() -> Result<()> {
+    let config = get_config()?;
+    let today = chrono::Local::now().date_naive();
+    let summary = WeeklySummary::collect(&config, today)?;
+
+    OutputManager::print_section(
+        &format!(
+            "Week of {} - {}",
+            summary.start.format("%d.%m"),
+            summary.end.format("%d.%m.%Y")
+        ),
+        Some("🗓️"),
+    );
+
+    let columns = [
+        TableColumn {
+            header: "Day".to_string(),
+            width: 9,
+            align: Alignment::Left,
+        },
+        TableColumn {
+            header: "Course".to_string(),
+            width: 8,
+            align: Alignment::Left,
+        },
+        TableColumn {
+            header: "Notes".to_string(),
+            width: 5,
+            align: Alignment::Right,
+        },
+        TableColumn {
+            header: "Assignments".to_string(),
+            width: 11,
+            align: Alignment::Right,
+        },
+    ];
+
+    let mut rows = Vec::new();
+    for day in summary.days.iter().filter(|day| day.date <= today) {
+        let label = WeeklySummary::day_label(day.date);
+        if day.courses.is_empty() {
+            rows.push(vec![
+                label,
+                "-".to_string(),
+                "0".to_string(),
+                "0".to_string(),
+            ]);
+            continue;
+        }
+
+        for (i, (course_id, counts)) in day.courses.iter().enumerate() {
+            rows.push(vec![
+                if i == 0 { label.clone() } else { String::new() },
+                course_id.clone(),
+                counts.notes.to_string(),
+                counts.assignments.to_string(),
+            ]);
+        }
+    }
+
+    OutputManager::print_table_with_columns(&columns, &rows);
     println!();
-    show_quick_suggestions(&activity_summary)?;
+
+    let (notes, assignments) = (summary.total_notes(), summary.total_assignments());
+    if notes + assignments == 0 {
+        OutputManager::print_status(Status::Info, "Nothing worked on yet this week");
+        println!("Start with: {}", "noter note <course_id>".bright_white());
+    } else {
+        println!(
+            "{} {} notes and {} assignments across {} courses",
+            "✨".yellow(),
+            notes.to_string().bright_green(),
+            assignments.to_string().bright_green(),
+            summary.active_courses().to_string().bright_blue()
+        );
+    }
 
     Ok(())
 }
@@ -97,13 +389,7 @@ pub fn show_status() -> Result<()> {
     show_system_status_section(&system_status);
 
     // Show configuration warnings
-    if !system_status.configuration_warnings.is_empty() {
-        println!();
-        println!("{} Configuration Warnings:", "⚠️".yellow());
-        for warning in &system_status.configuration_warnings {
-            println!("  • {}", warning.yellow());
-        }
-    }
+    show_configuration_warnings(&system_status.configuration_warnings);
 
     // Show course count
     println!();
@@ -175,102 +461,285 @@ pub fn list_courses() -> Result<()> {
 
 // Private helper functions for displaying status sections
 
+/// Show a one-line notice when a newer template release is available.
+///
+/// Relies on the cached release info so the dashboard stays fast, and stays
+/// silent when auto-update will handle it anyway.
+fn show_template_update_notice(config: &Config) {
+    if config.templates.auto_update {
+        return;
+    }
+
+    let Ok(updates) = GitHubTemplateFetcher::check_for_updates(config) else {
+        return;
+    };
+
+    for update in updates {
+        OutputManager::print_record(
+            &format!(
+                "📦 Template update available: {} → {} (run {})",
+                update.installed_version.yellow(),
+                update.latest_version.bright_green(),
+                "noter template update".bright_white()
+            ),
+            &[
+                "template_update",
+                &update.installed_version,
+                &update.latest_version,
+            ],
+        );
+    }
+}
+
 fn show_system_status_section(system_status: &crate::core::status_manager::SystemStatus) {
-    println!("🏗️ System Status:");
+    OutputManager::print_hint("🏗️ System Status:");
     for (name, exists) in &system_status.directories {
         let status = if *exists { "✅".green() } else { "❌".red() };
-        println!("  {}: {}", name, status);
+        OutputManager::print_record(
+            &format!("  {}: {}", name, status),
+            &["directory", name, presence(*exists)],
+        );
     }
 
-    println!();
-    println!("📦 Templates:");
+    OutputManager::print_hint("");
+    OutputManager::print_hint("📦 Templates:");
     for (template_path, exists) in &system_status.templates {
         let status = if *exists { "✅".green() } else { "❌".red() };
         let filename = std::path::Path::new(template_path)
             .file_name()
             .and_then(|n| n.to_str())
             .unwrap_or(template_path);
-        println!("  {}: {}", status, filename.dimmed());
+        OutputManager::print_record(
+            &format!("  {}: {}", status, filename.dimmed()),
+            &["template", filename, presence(*exists)],
+        );
+    }
+}
+
+/// Plain-output word for whether a path exists
+fn presence(exists: bool) -> &'static str {
+    if exists { "ok" } else { "missing" }
+}
+
+fn show_configuration_warnings(warnings: &[String]) {
+    if warnings.is_empty() {
+        return;
+    }
+
+    OutputManager::print_hint("");
+    OutputManager::print_hint(&format!("{} Configuration Warnings:", "⚠️".yellow()));
+    for warning in warnings {
+        OutputManager::print_record(
+            &format!("  • {}", warning.yellow()),
+            &["config_warning", warning],
+        );
     }
 }
 
 #[allow(dead_code)]
 fn show_activity_summary_section(activity_summary: &crate::core::status_manager::ActivitySummary) {
-    println!();
-    println!("📈 Recent Activity:");
+    OutputManager::print_hint("");
+    OutputManager::print_hint("📈 Recent Activity:");
 
     if activity_summary.total_notes == 0 && activity_summary.total_assignments == 0 {
-        println!("  No activity (run setup first)");
+        OutputManager::print_hint("  No activity (run setup first)");
         return;
     }
 
-    println!(
-        "  Total files: {} notes, {} assignments",
-        activity_summary.total_notes.to_string().green(),
-        activity_summary.total_assignments.to_string().blue()
+    OutputManager::print_record(
+        &format!(
+            "  Total files: {} notes, {} assignments",
+            activity_summary.total_notes.to_string().green(),
+            activity_summary.total_assignments.to_string().blue()
+        ),
+        &[
+            "files".to_string(),
+            activity_summary.total_notes.to_string(),
+            activity_summary.total_assignments.to_string(),
+        ],
     );
 
     if let Some(ref recent) = activity_summary.most_recent_activity {
         let datetime: chrono::DateTime<chrono::Local> = recent.timestamp.into();
-        println!(
-            "  Last activity: {} ({} - {})",
-            datetime.format("%Y-%m-%d %H:%M").to_string().bright_white(),
-            recent.course_id.yellow(),
-            recent.course_name.dimmed()
+        let timestamp = datetime.format("%Y-%m-%d %H:%M").to_string();
+        OutputManager::print_record(
+            &format!(
+                "  Last activity: {} ({} - {})\n  File: {}",
+                timestamp.bright_white(),
+                recent.course_id.yellow(),
+                recent.course_name.dimmed(),
+                recent.file_name.dimmed()
+            ),
+            &[
+                "last_activity",
+                &timestamp,
+                &recent.course_id,
+                &recent.file_name,
+            ],
         );
-        println!("  File: {}", recent.file_name.dimmed());
     }
 
-    if let Some((course_id, count)) = &activity_summary.most_active_course {
-        println!(
-            "  Most active: {} ({} files)",
-            course_id.yellow(),
-            count.to_string().green()
-        );
+    let window = match activity_summary.activity_window_days {
+        0 => String::new(),
+        days => format!(" (last {} days)", days),
+    };
+    match &activity_summary.most_active_course {
+        Some((course_id, count)) => OutputManager::print_record(
+            &format!(
+                "  Most active{}: {} ({} files)",
+                window,
+                course_id.yellow(),
+                count.to_string().green()
+            ),
+            &["most_active", course_id, &count.to_string()],
+        ),
+        None if !window.is_empty() => OutputManager::print_hint(&format!(
+            "  Most active{}: {}",
+            window,
+            "no changes".dimmed()
+        )),
+        None => {}
     }
 }
 
 #[allow(dead_code)]
 fn show_course_health_section(course_health: &[crate::core::status_manager::CourseHealthInfo]) {
-    println!();
-    println!("🎓 Course Health:");
+    OutputManager::print_hint("");
+    OutputManager::print_hint("🎓 Course Health:");
 
+    let mut rows = Vec::new();
     for health_info in course_health {
-        let health_indicator = match health_info.health_status {
-            crate::core::status_manager::HealthStatus::Excellent => "✅",
-            crate::core::status_manager::HealthStatus::Good => "⚠️",
-            crate::core::status_manager::HealthStatus::Warning => "🔴",
-            crate::core::status_manager::HealthStatus::Critical => "❌",
-        };
+        let health_indicator = OutputManager::symbol(
+            health_indicator(&health_info.health_status),
+            health_info.health_status.label(),
+        );
+        let last_activity = last_activity_label(health_info.days_since_last_activity);
+
+        rows.push(vec![
+            health_indicator.to_string(),
+            health_info.course_id.yellow().to_string(),
+            TextWidth::truncate(&health_info.course_name, OutputManager::MAX_NAME_WIDTH)
+                .dimmed()
+                .to_string(),
+            health_info.notes_count.to_string(),
+            health_info.assignments_count.to_string(),
+            last_activity.to_string(),
+        ]);
+    }
 
-        let last_activity = match health_info.days_since_last_activity {
-            0 => "today".bright_green(),
-            1 => "1 day ago".green(),
-            2..=7 => format!("{} days ago", health_info.days_since_last_activity).yellow(),
-            8..=14 => format!("{} days ago", health_info.days_since_last_activity).red(),
-            999 => "never".red(),
-            _ => format!("{} days ago", health_info.days_since_last_activity).red(),
-        };
+    OutputManager::print_table(
+        &[
+            "",
+            "Course",
+            "Name",
+            "Notes",
+            "Assignments",
+            "Last activity",
+        ],
+        &rows,
+    );
+}
 
-        println!(
-            "  {} {} - {} ({} notes, {} assignments, last: {})",
-            health_indicator,
-            health_info.course_id.yellow(),
-            health_info.course_name.dimmed(),
-            health_info.notes_count,
-            health_info.assignments_count,
-            last_activity
+fn health_indicator(health_status: &HealthStatus) -> &'static str {
+    match health_status {
+        HealthStatus::Excellent => "✅",
+        HealthStatus::Good => "⚠️",
+        HealthStatus::Warning => "🔴",
+        HealthStatus::Critical => "❌",
+    }
+}
+
+/// Days since the last activity, colored by how long ago it was
+fn last_activity_label(days: u64) -> colored::ColoredString {
+    match days {
+        0 => "today".bright_green(),
+        1 => "1 day ago".green(),
+        2..=7 => format!("{} days ago", days).yellow(),
+        8..=14 => format!("{} days ago", days).red(),
+        999 => "never".red(),
+        _ => format!("{} days ago", days).red(),
+    }
+}
+
+#[allow(dead_code)]
+fn show_stale_pdfs_section(course_health: &[crate::core::status_manager::CourseHealthInfo]) {
+    let stale_courses: Vec<_> = course_health
+        .iter()
+        .filter(|info| info.stale_pdfs_count > 0)
+        .collect();
+
+    if stale_courses.is_empty() {
+        return;
+    }
+
+    OutputManager::print_hint("");
+    OutputManager::print_hint("📄 Compiled PDFs:");
+
+    for health_info in &stale_courses {
+        let noun = if health_info.stale_pdfs_count == 1 {
+            "note needs"
+        } else {
+            "notes need"
+        };
+        OutputManager::print_record(
+            &format!(
+                "  {} {} - {} {} recompiling",
+                "🔄".yellow(),
+                health_info.course_id.yellow(),
+                health_info.stale_pdfs_count.to_string().bright_white(),
+                noun
+            ),
+            &[
+                "stale_pdfs",
+                &health_info.course_id,
+                &health_info.stale_pdfs_count.to_string(),
+            ],
         );
     }
+
+    OutputManager::print_hint(&format!(
+        "  {} Run {} on outdated files to refresh their PDFs",
+        "💡".yellow(),
+        "noter compile <file>".bright_white()
+    ));
 }
 
 #[allow(dead_code)]
 fn show_quick_suggestions(
+    config: &Config,
     activity_summary: &crate::core::status_manager::ActivitySummary,
 ) -> Result<()> {
-    println!("💡 Quick Suggestions:");
+    OutputManager::print_hint("💡 Quick Suggestions:");
+
+    let recommendations = RecommendationEngine::generate(config, None)?;
+    if !recommendations.is_empty() {
+        for recommendation in recommendations.iter().take(QUICK_SUGGESTION_LIMIT) {
+            print_recommendation(recommendation);
+        }
+        if recommendations.len() > QUICK_SUGGESTION_LIMIT {
+            OutputManager::print_hint(&format!(
+                "  {}",
+                format!(
+                    "... and {} more (see noter assignments health)",
+                    recommendations.len() - QUICK_SUGGESTION_LIMIT
+                )
+                .dimmed()
+            ));
+        }
+        OutputManager::print_hint("");
+    }
 
-    if let Some((course_id, _)) = &activity_summary.most_active_course {
+    // Without changes in the window, suggest the course worked on last
+    let focus_course = activity_summary
+        .most_active_course
+        .as_ref()
+        .map(|(course_id, _)| course_id)
+        .or(activity_summary
+            .most_recent_activity
+            .as_ref()
+            .map(|recent| &recent.course_id));
+
+    if let Some(course_id) = focus_course {
         OutputManager::print_command_examples(&[
             (
                 &format!("noter note {}", course_id),
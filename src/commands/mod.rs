@@ -13,39 +13,117 @@ pub mod courses;
 pub mod dev_tools;
 pub mod info;
 pub mod notes;
+pub mod registry;
 pub mod search;
+pub mod semester;
 pub mod setup;
+pub mod sync;
 pub mod templates;
 pub mod typst;
 
-use crate::{AssignmentAction, Commands, ConfigAction, CourseAction, SetupAction, TemplateAction};
+use crate::{
+    AssignmentAction, Commands, ConfigAction, CourseAction, SemesterAction, SetupAction,
+    TemplateAction,
+};
 
 #[cfg(feature = "dev-tools")]
 use crate::DevAction;
 
-/// Execute a command with proper error context
-pub fn execute_command(command: &Commands) -> Result<()> {
+/// Execute a command with proper error context. Files are opened with
+/// `editor`, the `--editor` override, when given.
+pub fn execute_command(command: &Commands, editor: Option<&str>) -> Result<()> {
     match command {
+        Commands::Note {
+            course_id,
+            template_type,
+            variant,
+            sections,
+            no_open,
+            batch: Some(count),
+            ..
+        } => notes::create_batch_notes(
+            course_id,
+            *count,
+            template_type.as_deref(),
+            variant,
+            sections,
+            *no_open,
+            editor,
+        )
+        .with_context(|| format!("Failed to create notes for course {}", course_id)),
         Commands::Note {
             course_id,
             title,
+            template_type,
             variant,
             sections,
             no_open,
-        } => notes::create_note(course_id, title, variant, sections, no_open)
-            .with_context(|| format!("Failed to create note for course {}", course_id)),
-        Commands::Assignment { course_id, title } => {
-            assignments::create_assignment(course_id, title).with_context(|| {
+            dump_context,
+            from_clipboard,
+            from_file,
+            batch: None,
+        } => notes::create_note(
+            course_id,
+            title,
+            template_type.as_deref(),
+            variant,
+            sections,
+            no_open,
+            *dump_context,
+            notes::NoteBodySource::from_flags(*from_clipboard, from_file.as_deref()),
+            editor,
+        )
+        .with_context(|| format!("Failed to create note for course {}", course_id)),
+        Commands::Assignment {
+            course_id,
+            title,
+            due,
+            dump_context,
+            on_conflict,
+            authors,
+            rubric,
+        } => assignments::create_assignment(
+            course_id,
+            title,
+            due.as_deref(),
+            *dump_context,
+            *on_conflict,
+            authors.as_deref(),
+            rubric.as_deref(),
+            editor,
+        )
+        .with_context(|| {
+            format!(
+                "Failed to create assignment '{}' for course {}",
+                title, course_id
+            )
+        }),
+        Commands::Group {
+            course_id,
+            title,
+            members,
+            no_open,
+        } => notes::create_group_note(course_id, title, members, *no_open, editor).with_context(
+            || {
                 format!(
-                    "Failed to create assignment '{}' for course {}",
+                    "Failed to create group notes '{}' for course {}",
                     title, course_id
                 )
-            })
-        }
+            },
+        ),
         Commands::Compile {
             filepath,
             check_status,
+            stdin,
+            output,
         } => {
+            if *stdin {
+                let output = output.as_deref().unwrap_or_default();
+                return typst::compile_stdin(output)
+                    .with_context(|| format!("Failed to compile stdin to: {}", output));
+            }
+
+            let filepath = filepath.as_deref().unwrap_or_default();
             if *check_status {
                 typst::check_compilation_status(filepath)
                     .with_context(|| format!("Failed to check compilation status: {}", filepath))?;
@@ -53,17 +131,33 @@ pub fn execute_command(command: &Commands) -> Result<()> {
             typst::compile_file(filepath)
                 .with_context(|| format!("Failed to compile file: {}", filepath))
         }
-        Commands::Check { filepath, detailed } => {
-            if let Some(filepath) = filepath {
+        Commands::Check {
+            filepath,
+            detailed,
+            uncompiled,
+            course_names,
+        } => {
+            if *uncompiled {
+                typst::check_uncompiled().with_context(|| "Failed to check for uncompiled notes")
+            } else if *course_names {
+                typst::check_course_names().with_context(|| "Failed to check course names")
+            } else if let Some(filepath) = filepath {
                 typst::check_file_status(filepath, *detailed)
                     .with_context(|| format!("Failed to check file status: {}", filepath))
             } else {
                 typst::check_all_files(*detailed).with_context(|| "Failed to check all files")
             }
         }
-        Commands::Watch { filepath } => typst::watch_file(filepath)
+        Commands::Watch { filepath, open } => typst::watch_file(filepath, *open)
             .with_context(|| format!("Failed to watch file: {}", filepath)),
-        Commands::Recent { course_id } => notes::list_recent(course_id)
+        Commands::Validate { json } => {
+            templates::validate_templates(*json).with_context(|| "Template validation failed")
+        }
+        Commands::Recent {
+            course_id,
+            since,
+            open,
+        } => notes::list_recent(course_id, since.as_deref(), *open, editor)
             .with_context(|| format!("Failed to list recent notes for course {}", course_id)),
         Commands::Setup { action } => {
             if let Some(action) = action {
@@ -72,10 +166,44 @@ pub fn execute_command(command: &Commands) -> Result<()> {
                 setup::setup_repository().with_context(|| "Failed to setup repository")
             }
         }
-        Commands::Index { course_id } => notes::create_index(course_id)
-            .with_context(|| format!("Failed to create index for course {}", course_id)),
-        Commands::Search { query } => {
-            search::search_notes(query).with_context(|| format!("Failed to search for: {}", query))
+        Commands::Index { course_id, all } => match course_id {
+            Some(course_id) if !*all => notes::create_index(course_id)
+                .with_context(|| format!("Failed to create index for course {}", course_id)),
+            _ => notes::create_all_indices().with_context(|| "Failed to create course indices"),
+        },
+        Commands::Search {
+            query,
+            replace,
+            all,
+            reindex,
+            case_sensitive,
+            ignore_case,
+            any,
+            all_words,
+            regex,
+            highlight_group,
+            count,
+            per_file,
+        } => {
+            if *reindex {
+                search::update_search_index().with_context(|| "Failed to update search index")?;
+            }
+
+            let case_sensitive = search::case_override(*case_sensitive, *ignore_case);
+            let match_mode = search::match_mode(*any, *all_words, *regex, *highlight_group);
+            match (query, replace) {
+                (Some(query), None) if *count => {
+                    search::count_matches(query, case_sensitive, match_mode, *per_file)
+                        .with_context(|| format!("Failed to count matches for: {}", query))
+                }
+                (Some(query), Some(replacement)) => {
+                    search::replace_in_notes(query, replacement, *all, case_sensitive)
+                        .with_context(|| format!("Failed to replace: {}", query))
+                }
+                (Some(query), None) => search::search_notes(query, case_sensitive, match_mode)
+                    .with_context(|| format!("Failed to search for: {}", query)),
+                (None, _) => Ok(()),
+            }
         }
         Commands::RebuildIndex { force } => {
             search::rebuild_index(*force).with_context(|| "Failed to rebuild search index")
@@ -85,21 +213,49 @@ pub fn execute_command(command: &Commands) -> Result<()> {
         Commands::Courses { action } => {
             execute_course_action(action).with_context(|| "Failed to execute course command")
         }
-        Commands::Clean => typst::clean_files().with_context(|| "Failed to clean compiled files"),
-        Commands::Status => {
-            info::show_enhanced_status().with_context(|| "Failed to show status information")
+        Commands::Clean {
+            all,
+            backups,
+            dry_run,
+        } => typst::clean_files(*all, *backups, *dry_run)
+            .with_context(|| "Failed to clean compiled files"),
+        Commands::Info { json } => {
+            info::show_info(*json).with_context(|| "Failed to collect environment information")
         }
-        Commands::Open { course_id } => notes::open_recent(course_id)
-            .with_context(|| format!("Failed to open recent note for course {}", course_id)),
-        Commands::Semester => {
-            info::show_semester().with_context(|| "Failed to show semester information")
+        Commands::Status {
+            course: Some(course_id),
+            ..
+        } => info::show_course_status(course_id)
+            .with_context(|| format!("Failed to show status of course {}", course_id)),
+        Commands::Status {
+            window,
+            course: None,
+        } => {
+            info::show_enhanced_status(*window).with_context(|| "Failed to show status information")
         }
-        Commands::Config { action } => {
-            execute_config_action(action).with_context(|| "Failed to execute config command")
+        Commands::Open {
+            course_id: Some(course_id),
+            index,
+        } => notes::open_recent(course_id, *index, editor)
+            .with_context(|| format!("Failed to open recent note for course {}", course_id)),
+        Commands::Open {
+            course_id: None, ..
+        } => notes::open_most_recent(editor).with_context(|| "Failed to open most recent note"),
+        Commands::OpenDir { course_id } => {
+            notes::open_directory(course_id.as_deref()).with_context(|| "Failed to open directory")
         }
-        Commands::Template { action } => {
-            execute_template_action(action).with_context(|| "Failed to execute template command")
+        Commands::Sync => sync::sync_notes().with_context(|| "Failed to sync notes"),
+        Commands::Semester { action: None } => {
+            info::show_semester().with_context(|| "Failed to show semester information")
         }
+        Commands::Semester {
+            action: Some(SemesterAction::End { yes }),
+        } => semester::end_semester(*yes).with_context(|| "Failed to finish semester cleanup"),
+        Commands::Week => info::show_week().with_context(|| "Failed to show weekly summary"),
+        Commands::Config { action } => execute_config_action(action, editor)
+            .with_context(|| "Failed to execute config command"),
+        Commands::Template { action } => execute_template_action(action, editor)
+            .with_context(|| "Failed to execute template command"),
         #[cfg(feature = "dev-tools")]
         Commands::Dev { action } => {
             execute_dev_action(action).with_context(|| "Failed to execute dev command")
@@ -116,39 +272,67 @@ fn execute_setup_action(action: &SetupAction) -> Result<()> {
 
 fn execute_assignment_action(action: &AssignmentAction) -> Result<()> {
     match action {
-        AssignmentAction::Recent { course_id, limit } => {
-            assignments::list_recent_assignments(course_id, *limit)
-        }
+        AssignmentAction::Recent {
+            course_id,
+            limit,
+            since,
+        } => assignments::list_recent_assignments(course_id, *limit, since.as_deref()),
         AssignmentAction::Stats { course_id } => assignments::show_assignment_stats(course_id),
         AssignmentAction::List => assignments::list_all_assignments(),
-        AssignmentAction::Health { course_id } => {
-            assignments::show_assignment_health(course_id.as_deref())
+        AssignmentAction::Health { course_id, json } => {
+            assignments::show_assignment_health(course_id.as_deref(), *json)
+        }
+        AssignmentAction::ExportIcs { course_id, output } => {
+            assignments::export_due_dates_ics(course_id.as_deref(), output.as_deref())
         }
     }
 }
 
-fn execute_template_action(action: &TemplateAction) -> Result<()> {
+fn execute_template_action(action: &TemplateAction, editor: Option<&str>) -> Result<()> {
     match action {
         TemplateAction::Status => templates::template_status(),
-        TemplateAction::Update => templates::update_template(),
+        TemplateAction::Update { dry_run } => {
+            if *dry_run {
+                templates::preview_template_update()
+            } else {
+                templates::update_template()
+            }
+        }
         TemplateAction::Reinstall => templates::reinstall_template(),
+        TemplateAction::FixImports { course_id } => {
+            templates::fix_template_imports(course_id.as_deref())
+        }
+        TemplateAction::Resolve {
+            course_id,
+            template_type,
+        } => templates::resolve_template(course_id, template_type),
+        TemplateAction::ClearCache => templates::clear_render_cache(),
+        TemplateAction::Pin { package, version } => templates::pin_template(package, version),
+        TemplateAction::Unpin { package } => templates::unpin_template(package),
         TemplateAction::Create {
             course_id,
             title,
             template_type,
             sections,
-        } => {
-            templates::create_custom_template(course_id, title, template_type, sections.as_deref())
-        }
+            output,
+        } => templates::create_custom_template(
+            course_id,
+            title,
+            template_type,
+            sections.as_deref(),
+            output.as_deref(),
+            editor,
+        ),
     }
 }
 
-fn execute_config_action(action: &ConfigAction) -> Result<()> {
+fn execute_config_action(action: &ConfigAction, editor: Option<&str>) -> Result<()> {
     match action {
         ConfigAction::Show => config_cmd::show_config(),
         ConfigAction::Get { key } => config_cmd::get_config_value(key),
         ConfigAction::Set { key, value } => config_cmd::set_config_value(key, value),
-        ConfigAction::Edit => config_cmd::edit_config(),
+        ConfigAction::Unset { field } => config_cmd::unset_config_value(field),
+        ConfigAction::Edit => config_cmd::edit_config(editor),
         ConfigAction::ListKeys => config_cmd::list_config_keys(),
         ConfigAction::Interactive => config_cmd::interactive_config(),
         ConfigAction::SetAuthor { name } => config_cmd::set_author(name),
@@ -174,7 +358,8 @@ fn execute_config_action(action: &ConfigAction) -> Result<()> {
         }
         ConfigAction::Reset => config_cmd::reset_config(),
         ConfigAction::Path => config_cmd::show_config_path(),
-        ConfigAction::Check => config_cmd::check_config(),
+        ConfigAction::Paths => config_cmd::show_resolved_paths(),
+        ConfigAction::Check { fix } => config_cmd::check_config(*fix),
         ConfigAction::Cleanse { yes } => config_cmd::cleanse_config(*yes),
         ConfigAction::Migrate => config_cmd::migrate_config(),
     }
@@ -189,6 +374,9 @@ fn execute_course_action(action: &CourseAction) -> Result<()> {
         } => courses::add_course(course_id, course_name),
         CourseAction::Remove { course_id } => courses::remove_course(course_id),
         CourseAction::Browse => courses::browse_common_courses(),
+        CourseAction::Search { query, limit, add } => courses::search_courses(query, *limit, *add),
+        CourseAction::Alias { alias, course_id } => courses::add_course_alias(alias, course_id),
+        CourseAction::Unalias { alias } => courses::remove_course_alias(alias),
     }
 }
 
@@ -2,81 +2,525 @@
 //!
 //! Handles lecture note creation, opening, and listing using core business logic.
 
-use crate::config::get_config;
-use crate::core::directory_scanner::DirectoryScanner;
-use crate::core::file_operations::FileOperations;
+use crate::config::{Config, ConflictPolicy, get_config};
+use crate::core::directory_scanner::{DirectoryScanner, FileInfo};
+use crate::core::file_operations::{FileOperations, PlannedLecture};
+use crate::core::git_integration::{GitCommitOutcome, GitIntegration};
+use crate::core::github_template_fetcher::GitHubTemplateFetcher;
+use crate::core::outline_import::OutlineImporter;
 use crate::core::status_manager::StatusManager;
 use crate::core::template::{builder::TemplateBuilder, engine::TemplateReference};
 use crate::core::validation::Validator;
+use crate::core::vault_index::VaultIndex;
 use crate::ui::output::{OutputManager, Status};
+use crate::ui::prompts::PromptManager;
 use anyhow::Result;
 use colored::Colorize;
 use std::fs;
-use std::path::Path;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Where the body of a new lecture note comes from
+pub enum NoteBodySource<'a> {
+    /// The template's sections
+    Template,
+    /// An outline on the clipboard
+    Clipboard,
+    /// An outline in a file
+    File(&'a str),
+}
+
+impl<'a> NoteBodySource<'a> {
+    pub fn from_flags(from_clipboard: bool, from_file: Option<&'a str>) -> Self {
+        match (from_clipboard, from_file) {
+            (true, _) => NoteBodySource::Clipboard,
+            (false, Some(path)) => NoteBodySource::File(path),
+            (false, None) => NoteBodySource::Template,
+        }
+    }
+
+    /// The imported Typst body, `None` for template sections
+    fn read_body(&self) -> Result<Option<String>> {
+        let outline = match self {
+            NoteBodySource::Template => return Ok(None),
+            NoteBodySource::Clipboard => OutlineImporter::read_clipboard()?,
+            NoteBodySource::File(path) => OutlineImporter::read_file(Path::new(path))?,
+        };
+        OutlineImporter::to_typst_body(&outline).map(Some)
+    }
+}
 
+/// `reading` -> `Reading`, for default note titles
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn create_note(
     course_id: &str,
     title: &Option<String>,
+    template_type: Option<&str>,
     variant: &Option<String>,
     sections: &Option<String>,
     no_open: &bool,
+    dump_context: bool,
+    body_source: NoteBodySource,
+    editor: Option<&str>,
 ) -> Result<()> {
-    let config = get_config()?;
+    let config = get_config()?.with_editor_override(editor);
+    let course_id = &config.resolve_course_id(course_id);
+    let title = title
+        .as_deref()
+        .map(Validator::validate_title)
+        .transpose()?;
+    // Read the outline first so a bad source fails before anything is generated
+    let body = body_source.read_body()?;
+    let template_type = config
+        .note_preferences
+        .note_type_for(course_id, template_type);
+
+    OutputManager::print_status(
+        Status::Loading,
+        &format!("Creating {} note...", template_type),
+    );
 
-    OutputManager::print_status(Status::Loading, "Creating lecture note...");
+    auto_update_templates(&config);
 
     // Generate the title as an owned String to avoid borrowing issues
     let note_title = match title {
-        Some(title) => title.clone(),
-        None => format!("Lecture - {}", chrono::Local::now().format("%B %d, %Y")),
+        Some(title) => title.to_string(),
+        None => format!(
+            "{} - {}",
+            capitalize(template_type),
+            chrono::Local::now().format("%B %d, %Y")
+        ),
     };
 
     // Generate content using builder
-    let mut builder = TemplateBuilder::new(course_id, &config)?
+    let reference = TemplateReference::for_type(template_type);
+    let mut builder = validated_builder(course_id, &config, &reference)?
         .with_title(&note_title)
         .with_reference(match variant {
-            Some(variant) => TemplateReference::lecture().with_variant(variant),
-            None => TemplateReference::lecture(),
-        });
-
-    builder = match sections {
-        None => builder,
-        Some(sects) => {
-            let sections_to_use = sects
-                .split(",")
-                .map(|s| s.trim().to_string())
-                .filter(|s| !s.is_empty())
-                .collect();
+            Some(variant) => reference.with_variant(variant),
+            None => reference,
+        })
+        .with_context_dump(dump_context)
+        .with_render_cache(true);
+
+    if let Some(sections) = parse_sections(sections) {
+        builder = builder.with_sections(sections);
+    }
+    if let Some(body) = body {
+        builder = builder.with_body(body);
+    }
 
-            builder.with_sections(sections_to_use)
-        }
-    };
+    print_context_warnings(&builder);
 
     // Build the template content
     let content = builder.build()?;
 
     // Generate filename and save
-    let variant = variant.clone().unwrap_or_else(|| String::from("lecture"));
-    let filename = FileOperations::generate_filename(course_id, &variant, title.as_deref());
+    let lectures_dir = config.get_lectures_dir(course_id);
+    let filename = FileOperations::generate_lecture_filename(
+        &lectures_dir,
+        course_id,
+        template_type,
+        title,
+        variant.as_deref(),
+        &config,
+    )?;
 
     // File operations
-    let filepath = Path::new(&config.paths.notes_dir)
-        .join(course_id)
-        .join("lectures")
-        .join(filename);
+    let filepath = lectures_dir.join(filename);
 
     FileOperations::create_file_with_content_and_open(&filepath, &content, &config, !*no_open)?;
 
+    auto_commit_created_file(
+        &filepath,
+        &GitIntegration::commit_message(&format!("{} note", template_type), course_id),
+        &config,
+    );
+
+    Ok(())
+}
+
+/// Template builder for a note of `reference`'s type, which the installed
+/// template package has to define
+fn validated_builder(
+    course_id: &str,
+    config: &Config,
+    reference: &TemplateReference,
+) -> Result<TemplateBuilder> {
+    let builder = TemplateBuilder::new(course_id, config)?;
+    Validator::validate_template_type(&reference.name, &builder.template_names())?;
+    Ok(builder)
+}
+
+/// Largest `--batch`, a full year of weekly lectures
+const MAX_BATCH_NOTES: u32 = 52;
+
+/// Create `count` placeholder notes for upcoming lectures, following the
+/// lecture numbering scheme. Notes that already exist are skipped, and only the
+/// first new note is opened.
+pub fn create_batch_notes(
+    course_id: &str,
+    count: u32,
+    template_type: Option<&str>,
+    variant: &Option<String>,
+    sections: &Option<String>,
+    no_open: bool,
+    editor: Option<&str>,
+) -> Result<()> {
+    if !(1..=MAX_BATCH_NOTES).contains(&count) {
+        anyhow::bail!(
+            "--batch must be between 1 and {}, got {}",
+            MAX_BATCH_NOTES,
+            count
+        );
+    }
+
+    let config = get_config()?.with_editor_override(editor);
+    let course_id = &config.resolve_course_id(course_id);
+    let template_type = config
+        .note_preferences
+        .note_type_for(course_id, template_type);
+
+    OutputManager::print_status(
+        Status::Loading,
+        &format!("Creating {} {} notes...", count, template_type),
+    );
+
+    auto_update_templates(&config);
+
+    let reference = TemplateReference::for_type(template_type);
+    // Check the type once, before any note is written
+    validated_builder(course_id, &config, &reference)?;
+    let reference = match variant {
+        Some(variant) => reference.with_variant(variant),
+        None => reference,
+    };
+    let sections = parse_sections(sections);
+
+    let lectures_dir = config.get_lectures_dir(course_id);
+    let planned = FileOperations::plan_lecture_batch(
+        &lectures_dir,
+        course_id,
+        template_type,
+        variant.as_deref(),
+        count,
+        chrono::Local::now().date_naive(),
+        &config,
+    )?;
+
+    let mut created = Vec::new();
+    for note in &planned {
+        let filepath = lectures_dir.join(&note.filename);
+        if note.exists {
+            OutputManager::print_status(
+                Status::Info,
+                &format!("Skipped {}, it already exists", filepath.display()),
+            );
+            continue;
+        }
+
+        let mut note_builder = TemplateBuilder::new(course_id, &config)?
+            .with_title(&batch_note_title(template_type, note))
+            .with_reference(reference.clone())
+            .with_render_cache(true);
+        if let Some(date) = note.date {
+            note_builder = note_builder.with_date(date);
+        }
+        if let Some(sections) = &sections {
+            note_builder = note_builder.with_sections(sections.clone());
+        }
+        if created.is_empty() {
+            print_context_warnings(&note_builder);
+        }
+
+        let content = note_builder.build()?;
+        let open = !no_open && created.is_empty();
+        FileOperations::create_file_with_content_and_open(&filepath, &content, &config, open)?;
+
+        println!("  {} {}", "•".green(), filepath.display());
+        created.push(filepath);
+    }
+
+    OutputManager::print_status(
+        Status::Success,
+        &format!(
+            "Created {} of {} {} notes",
+            created.len(),
+            planned.len(),
+            template_type
+        ),
+    );
+
+    if !created.is_empty() {
+        auto_commit_created_files(
+            &created,
+            &GitIntegration::commit_message(
+                &format!("{} {} notes", created.len(), template_type),
+                course_id,
+            ),
+            &config,
+        );
+    }
+
     Ok(())
 }
 
-pub fn open_recent(course_id: &str) -> Result<()> {
+/// `Lecture 6` with sequential numbering, else `Lecture - October 21, 2026`
+fn batch_note_title(template_type: &str, note: &PlannedLecture) -> String {
+    match (note.number, note.date) {
+        (Some(number), _) => format!("{} {}", capitalize(template_type), number),
+        (None, Some(date)) => format!(
+            "{} - {}",
+            capitalize(template_type),
+            date.format("%B %d, %Y")
+        ),
+        (None, None) => capitalize(template_type),
+    }
+}
+
+/// Sections given as a comma-separated `--sections` list
+fn parse_sections(sections: &Option<String>) -> Option<Vec<String>> {
+    sections.as_ref().map(|sections| {
+        sections
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect()
+    })
+}
+
+/// Create notes for a group project, with the members passed to the template
+/// as a `collaborators` array
+pub fn create_group_note(
+    course_id: &str,
+    title: &str,
+    members: &str,
+    no_open: bool,
+    editor: Option<&str>,
+) -> Result<()> {
+    let config = get_config()?.with_editor_override(editor);
+    let course_id = &config.resolve_course_id(course_id);
     Validator::validate_course_id(course_id)?;
-    let config = get_config()?;
+    let title = Validator::validate_title(title)?;
+    let members = Validator::parse_members(members)?;
+
+    OutputManager::print_status(Status::Loading, "Creating group project notes...");
+
+    auto_update_templates(&config);
+
+    let builder = group_note_builder(course_id, &config, title, &members)?.with_render_cache(true);
+
+    print_context_warnings(&builder);
+
+    let content = builder.build()?;
+
+    let lectures_dir = config.get_lectures_dir(course_id);
+    let filename = FileOperations::generate_filename(
+        course_id,
+        "group",
+        Some(title),
+        None,
+        config.typst.new_file_extension(),
+        config.note_preferences.max_filename_length,
+    );
+    let filepath = lectures_dir.join(filename);
+
+    FileOperations::create_file_with_content_and_open(&filepath, &content, &config, !no_open)?;
+
+    OutputManager::print_status(
+        Status::Success,
+        &format!(
+            "Group notes created: {} ({})",
+            filepath.display().to_string().bright_white(),
+            members.join(", ")
+        ),
+    );
+
+    auto_commit_created_file(
+        &filepath,
+        &GitIntegration::commit_message("group notes", course_id),
+        &config,
+    );
+
+    Ok(())
+}
+
+/// Builder for a group note, using the package's `group` template when it has
+/// one. Otherwise the note falls back to the lecture template, which takes no
+/// `collaborators` field, so the members are left out of the header.
+fn group_note_builder(
+    course_id: &str,
+    config: &Config,
+    title: &str,
+    members: &[String],
+) -> Result<TemplateBuilder> {
+    let builder = TemplateBuilder::new(course_id, config)?.with_title(title);
+    let group = TemplateReference::group();
+
+    if builder.template_names().contains(&group.name) {
+        return Ok(builder
+            .with_reference(group)
+            .with_custom_field("collaborators", members.to_vec()));
+    }
+
+    OutputManager::print_status(
+        Status::Warning,
+        "Template package has no group template, using the lecture template without collaborators",
+    );
+    Ok(builder.with_reference(TemplateReference::lecture()))
+}
+
+/// Apply pending template updates when auto-update is enabled, degrading any
+/// failure to a warning so note creation is never blocked
+pub(crate) fn auto_update_templates(config: &Config) {
+    match GitHubTemplateFetcher::auto_update_if_due(config) {
+        Ok(results) => {
+            for result in results {
+                OutputManager::print_status(
+                    Status::Info,
+                    &format!("Templates auto-updated to {}", result.version.green()),
+                );
+            }
+        }
+        Err(e) => OutputManager::print_status(
+            Status::Warning,
+            &format!("Template auto-update skipped: {}", e),
+        ),
+    }
+}
+
+/// Print template context warnings (e.g. empty author) without blocking creation
+pub(crate) fn print_context_warnings(builder: &TemplateBuilder) {
+    if let Ok(warnings) = builder.context_warnings() {
+        for warning in warnings {
+            OutputManager::print_status(Status::Warning, &warning);
+        }
+    }
+}
+
+/// Decide where a new file goes when `filepath` may already exist, following
+/// `policy`. Returns `None` when the user cancels.
+pub(crate) fn resolve_file_conflict(
+    filepath: &Path,
+    policy: ConflictPolicy,
+    config: &Config,
+) -> Result<Option<PathBuf>> {
+    if !filepath.exists() {
+        return Ok(Some(filepath.to_path_buf()));
+    }
 
-    let course_dir = format!("{}/{}/lectures", config.paths.notes_dir, course_id);
+    let policy = match policy {
+        ConflictPolicy::Prompt if !io::stdin().is_terminal() => ConflictPolicy::Suffix,
+        policy => policy,
+    };
 
-    if !Path::new(&course_dir).exists() {
+    let suffixed = || -> Result<PathBuf> {
+        let dir = filepath.parent().unwrap_or(Path::new("."));
+        let filename = filepath.file_name().unwrap_or_default().to_string_lossy();
+        let unique = FileOperations::generate_unique_filename(&dir.to_string_lossy(), &filename)?;
+        Ok(dir.join(unique))
+    };
+
+    let resolved = match policy {
+        ConflictPolicy::Prompt => {
+            let suffixed = suffixed()?;
+            let options = vec![
+                format!("Keep both (write {})", suffixed.display()),
+                "Overwrite the existing file".to_string(),
+                "Cancel".to_string(),
+            ];
+            match PromptManager::select(
+                &format!("{} already exists", filepath.display()),
+                &options,
+            )? {
+                0 => Some(suffixed),
+                1 => Some(overwrite_existing(filepath, config)?),
+                _ => None,
+            }
+        }
+        ConflictPolicy::Suffix => Some(suffixed()?),
+        ConflictPolicy::Overwrite => Some(overwrite_existing(filepath, config)?),
+        ConflictPolicy::Error => anyhow::bail!(
+            "{} already exists. Use --on-conflict suffix to keep both or --on-conflict overwrite to replace it",
+            filepath.display()
+        ),
+    };
+
+    if let Some(resolved) = resolved.as_deref().filter(|resolved| *resolved != filepath) {
+        OutputManager::print_status(
+            Status::Info,
+            &format!(
+                "{} already exists, writing {} instead",
+                filepath.display(),
+                resolved.display()
+            ),
+        );
+    }
+
+    Ok(resolved)
+}
+
+fn overwrite_existing(filepath: &Path, config: &Config) -> Result<PathBuf> {
+    if config.note_preferences.create_backups {
+        FileOperations::create_backup(filepath)?;
+    }
+    Ok(filepath.to_path_buf())
+}
+
+/// Commit a freshly created file when git auto-commit is enabled, reporting but never
+/// propagating failures so note creation itself still succeeds
+pub(crate) fn auto_commit_created_file(filepath: &Path, message: &str, config: &Config) {
+    auto_commit_created_files(&[filepath.to_path_buf()], message, config);
+}
+
+/// Like [`auto_commit_created_file`], committing several new files of one directory together
+pub(crate) fn auto_commit_created_files(filepaths: &[PathBuf], message: &str, config: &Config) {
+    let filepaths: Vec<&Path> = filepaths.iter().map(PathBuf::as_path).collect();
+    match GitIntegration::auto_commit_all(&filepaths, message, config) {
+        Ok(GitCommitOutcome::Committed) => {
+            OutputManager::print_status(Status::Success, &format!("Committed to git: {}", message))
+        }
+        Ok(GitCommitOutcome::Disabled | GitCommitOutcome::NotARepository) => {}
+        Err(e) => OutputManager::print_status(
+            Status::Warning,
+            &format!("Could not auto-commit to git: {}", e),
+        ),
+    }
+}
+
+/// Earliest modification time a `--since` window such as `14d` lets through
+pub(crate) fn since_cutoff(since: Option<&str>) -> Result<Option<SystemTime>> {
+    let Some(since) = since else {
+        return Ok(None);
+    };
+
+    let window = Validator::parse_duration(since)?.to_std()?;
+    Ok(Some(
+        SystemTime::now()
+            .checked_sub(window)
+            .unwrap_or(SystemTime::UNIX_EPOCH),
+    ))
+}
+
+/// Open the `index`th most recent note of a course (1 = most recent, the
+/// default), in the order `noter recent` lists them
+pub fn open_recent(course_id: &str, index: Option<usize>, editor: Option<&str>) -> Result<()> {
+    let config = get_config()?.with_editor_override(editor);
+    let course_id = &config.resolve_course_id(course_id);
+    Validator::validate_course_id(course_id)?;
+
+    let lectures_dir = config.get_lectures_dir(course_id);
+
+    if !lectures_dir.exists() {
         OutputManager::print_status(
             Status::Error,
             &format!("No lectures directory found for course {}", course_id),
@@ -88,24 +532,9 @@ pub fn open_recent(course_id: &str) -> Result<()> {
         return Ok(());
     }
 
-    // Find most recent file using directory scanner
-    let files = DirectoryScanner::scan_directory_for_files(&course_dir, &["typ"])?;
+    let files = recent_notes(&config, course_id)?;
 
-    if let Some(most_recent) = DirectoryScanner::find_most_recent(&files) {
-        OutputManager::print_status(
-            Status::Info,
-            &format!(
-                "Opening most recent note: {}",
-                most_recent
-                    .path
-                    .file_name()
-                    .unwrap()
-                    .to_string_lossy()
-                    .yellow()
-            ),
-        );
-        FileOperations::open_file(&most_recent.path, &config)?;
-    } else {
+    if files.is_empty() {
         OutputManager::print_status(
             Status::Warning,
             &format!("No lecture notes found for course {}", course_id),
@@ -114,48 +543,220 @@ pub fn open_recent(course_id: &str) -> Result<()> {
             "Create your first note with: {}",
             format!("noter note {}", course_id).bright_white()
         );
+        return Ok(());
     }
 
+    let index = index.unwrap_or(1);
+    let Some(note) = index.checked_sub(1).and_then(|i| files.get(i)) else {
+        anyhow::bail!(
+            "No note #{} for course {}: it has {} note(s), pick 1 to {}",
+            index,
+            course_id,
+            files.len(),
+            files.len()
+        );
+    };
+
+    let label = if index == 1 {
+        "most recent note".to_string()
+    } else {
+        format!("note #{}", index)
+    };
+    OutputManager::print_status(
+        Status::Info,
+        &format!(
+            "Opening {}: {}",
+            label,
+            FileOperations::note_display_name(&note.path).yellow()
+        ),
+    );
+    FileOperations::open_file(&note.path, &config)?;
+
     Ok(())
 }
 
-pub fn list_recent(course_id: &str) -> Result<()> {
-    Validator::validate_course_id(course_id)?;
+/// Lecture notes of a course, most recently modified first
+fn recent_notes(config: &Config, course_id: &str) -> Result<Vec<FileInfo>> {
+    let mut files = DirectoryScanner::scan_course_notes(
+        &config.get_course_dir(course_id),
+        &config.typst.source_extensions(),
+        config.paths.flat_layout,
+        config.paths.follow_symlinks,
+    )?;
+    files.sort_by_key(|file| std::cmp::Reverse(file.modified));
+
+    Ok(files)
+}
+
+/// Open the most recently modified note across all courses
+pub fn open_most_recent(editor: Option<&str>) -> Result<()> {
+    let config = get_config()?.with_editor_override(editor);
+    let activity_summary = StatusManager::get_activity_summary(&config, 0)?;
+
+    match activity_summary.most_recent_activity {
+        Some(recent) => {
+            OutputManager::print_status(
+                Status::Info,
+                &format!(
+                    "Resuming {} ({})",
+                    recent.file_name.yellow(),
+                    recent.course_id.dimmed()
+                ),
+            );
+            FileOperations::open_file(&recent.file_path, &config)?;
+        }
+        None => {
+            OutputManager::print_status(Status::Warning, "No notes found in any course");
+            println!(
+                "Create your first note with: {}",
+                "noter note 02101".bright_white()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Open the notes directory, or a course directory, in the file manager
+pub fn open_directory(course_id: Option<&str>) -> Result<()> {
     let config = get_config()?;
-    let course_dir = format!("{}/{}/lectures", config.paths.notes_dir, course_id);
+    let notes_dir = Path::new(&config.paths.notes_dir);
+
+    let dir = match course_id {
+        Some(course_id) => {
+            let course_id = config.resolve_course_id(course_id);
+            Validator::validate_course_id(&course_id)?;
+
+            let course_dir = notes_dir.join(&course_id);
+            if !course_dir.exists() {
+                OutputManager::print_status(
+                    Status::Warning,
+                    &format!("Course directory not found: {}", course_dir.display()),
+                );
+                if !PromptManager::confirm(&format!("Create it for {}", course_id), Some(true))? {
+                    return Ok(());
+                }
+                FileOperations::ensure_course_structure(&config, &course_id)?;
+            }
+            course_dir
+        }
+        None => {
+            if !notes_dir.exists() {
+                OutputManager::print_status(
+                    Status::Error,
+                    &format!("Notes directory not found: {}", notes_dir.display()),
+                );
+                println!("Run {} to create it", "noter setup".bright_white());
+                return Ok(());
+            }
+            notes_dir.to_path_buf()
+        }
+    };
+
+    OutputManager::print_status(
+        Status::Info,
+        &format!("Opening {}", dir.display().to_string().bright_white()),
+    );
+    FileOperations::open_in_file_manager(&dir)?;
+
+    Ok(())
+}
+
+/// Notes `noter recent` lists
+const RECENT_NOTES_SHOWN: usize = 10;
 
-    if !Path::new(&course_dir).exists() {
+pub fn list_recent(
+    course_id: &str,
+    since: Option<&str>,
+    open: bool,
+    editor: Option<&str>,
+) -> Result<()> {
+    let config = get_config()?.with_editor_override(editor);
+    let course_id = &config.resolve_course_id(course_id);
+    Validator::validate_course_id(course_id)?;
+    let cutoff = since_cutoff(since)?;
+    let lectures_dir = config.get_lectures_dir(course_id);
+
+    if !lectures_dir.exists() {
         OutputManager::print_status(
             Status::Error,
-            &format!("Course directory not found: {}", course_dir),
+            &format!("Course directory not found: {}", lectures_dir.display()),
         );
         return Ok(());
     }
 
-    OutputManager::print_section(&format!("Recent notes for {}", course_id), Some("📚"));
-
-    let mut files = DirectoryScanner::scan_directory_for_files(&course_dir, &["typ"])?;
+    let title = match since {
+        Some(since) => format!("Notes for {} from the last {}", course_id, since.trim()),
+        None => format!("Recent notes for {}", course_id),
+    };
+    OutputManager::print_section(&title, Some("📚"));
 
-    // Sort by modification time (most recent first)
-    files.sort_by(|a, b| b.modified.cmp(&a.modified));
+    let mut files = recent_notes(&config, course_id)?;
+    if let Some(cutoff) = cutoff {
+        files = DirectoryScanner::modified_since(files, cutoff);
+    }
 
     if files.is_empty() {
-        println!("  No notes found");
+        OutputManager::print_hint("  No notes found");
     } else {
-        for file in files.iter().take(10) {
-            if let Some(name) = file.path.file_name().and_then(|n| n.to_str()) {
-                let datetime: chrono::DateTime<chrono::Local> = file.modified.into();
-                println!("  {} - {}", name, datetime.format("%Y-%m-%d %H:%M"));
-            }
+        if cutoff.is_some() {
+            OutputManager::print_hint(&format!("  {} note(s) modified\n", files.len()));
+        }
+        // Numbered for `noter open <course> <n>`
+        for (i, file) in files.iter().take(RECENT_NOTES_SHOWN).enumerate() {
+            let datetime: chrono::DateTime<chrono::Local> = file.modified.into();
+            let number = (i + 1).to_string();
+            let name = FileOperations::note_display_name(&file.path);
+            let modified = datetime.format("%Y-%m-%d %H:%M").to_string();
+            OutputManager::print_record(
+                &format!("  {}. {} - {}", number.dimmed(), name, modified),
+                &[number, modified, name, file.path.display().to_string()],
+            );
+        }
+
+        if open {
+            OutputManager::print_hint("");
+            let shown = files.len().min(RECENT_NOTES_SHOWN);
+            // Without a terminal to ask, open the most recent
+            let index = if io::stdin().is_terminal() {
+                prompt_note_number(shown)?
+            } else {
+                1
+            };
+
+            let note = &files[index - 1];
+            OutputManager::print_status(
+                Status::Info,
+                &format!(
+                    "Opening {}",
+                    FileOperations::note_display_name(&note.path).yellow()
+                ),
+            );
+            FileOperations::open_file(&note.path, &config)?;
         }
     }
 
     Ok(())
 }
 
+/// Ask for the number of a listed note, 1 when left empty
+fn prompt_note_number(shown: usize) -> Result<usize> {
+    loop {
+        let input = PromptManager::input(&format!("Note to open (1-{})", shown), Some("1"))?;
+        match input.parse::<usize>() {
+            Ok(number) if (1..=shown).contains(&number) => return Ok(number),
+            _ => OutputManager::print_status(
+                Status::Warning,
+                &format!("Please enter a number between 1 and {}", shown),
+            ),
+        }
+    }
+}
+
 pub fn create_index(course_id: &str) -> Result<()> {
-    Validator::validate_course_id(course_id)?;
     let config = get_config()?;
+    let course_id = &config.resolve_course_id(course_id);
+    Validator::validate_course_id(course_id)?;
 
     // Look up course name from config
     let course_name = config
@@ -163,31 +764,29 @@ pub fn create_index(course_id: &str) -> Result<()> {
         .get(course_id)
         .ok_or_else(|| anyhow::anyhow!("Course '{}' not found in config", course_id))?;
 
-    let courses_dir = format!("{}/courses", config.paths.obsidian_dir);
-    let index_file = format!(
-        "{}/courses/{}-{}.md",
-        config.paths.obsidian_dir, course_id, course_name
-    );
+    let courses_dir = VaultIndex::courses_dir(&config);
+    let index_file = VaultIndex::course_index_path(&config, course_id, course_name);
     let semester = StatusManager::get_current_semester(&config);
 
-    if Path::new(&index_file).exists() {
+    if index_file.exists() {
         OutputManager::print_status(
             Status::Warning,
-            &format!("Index already exists: {}", index_file),
+            &format!("Index already exists: {}", index_file.display()),
         );
     } else {
         OutputManager::print_status(
             Status::Success,
-            &format!("Creating course index: {}", index_file),
+            &format!("Creating course index: {}", index_file.display()),
         );
 
-        let content = generate_obsidian_index_content(course_id, course_name, &semester);
+        let content = VaultIndex::course_index_content(course_id, course_name, &semester);
         fs::create_dir_all(&courses_dir)?;
         fs::write(&index_file, content)?;
     }
 
     if config.note_preferences.auto_open_file {
-        let vault_name = Path::new(&config.paths.obsidian_dir)
+        let vault_dir = config.get_obsidian_dir_path();
+        let vault_name = vault_dir
             .file_name()
             .and_then(|name| name.to_str())
             .unwrap_or("vault");
@@ -197,39 +796,119 @@ pub fn create_index(course_id: &str) -> Result<()> {
         );
         opener::open(obsidian_uri)?;
     } else {
-        println!("File created at: {}", index_file);
+        println!("File created at: {}", index_file.display());
     }
 
     Ok(())
 }
 
-fn generate_obsidian_index_content(course_id: &str, course_name: &str, semester: &str) -> String {
-    format!(
-        r#"# {} - {}
+/// Create the missing index of every configured course and refresh the vault
+/// hub linking to all of them
+pub fn create_all_indices() -> Result<()> {
+    let config = get_config()?;
+    if config.courses.is_empty() {
+        OutputManager::print_status(
+            Status::Warning,
+            "No courses configured. Add one with 'noter courses add'",
+        );
+        return Ok(());
+    }
+
+    let refresh = VaultIndex::refresh_all(&config)?;
+
+    for created in &refresh.created {
+        OutputManager::print_status(
+            Status::Success,
+            &format!("Created course index: {}", created.display()),
+        );
+    }
+    OutputManager::print_status(
+        Status::Complete,
+        &format!(
+            "Linked {} course(s) from {}",
+            refresh.courses,
+            refresh.hub_path.display()
+        ),
+    );
 
-## Course Information
-- **Course Code**: {}
-- **Semester**: {}
-- **University**: Technical University of Denmark (DTU)
-- **Professor**:
-- **Credits**:
+    Ok(())
+}
 
-## Recent Lectures
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_note_without_template_package_uses_fallback() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let mut config = Config::default();
+        config.paths.typst_packages_dir = temp_dir.path().to_string_lossy().to_string();
+
+        let content = validated_builder("02101", &config, &TemplateReference::lecture())?
+            .with_title("Graphs")
+            .build()?;
+
+        // Built from the fallback package's note template
+        assert!(content.contains("dtu-template"));
+        assert!(content.contains("dtu-note"));
+        Ok(())
+    }
 
-## Key Topics
+    fn write_package(dir: &Path, templates: &[(&str, &str)]) -> Result<()> {
+        let package_dir = dir.join("dtu-template").join("0.2.0");
+        fs::create_dir_all(&package_dir)?;
+        let mut toml = String::from("[metadata]\nname = \"dtu-template\"\nversion = \"0.2.0\"\n");
+        for (name, function) in templates {
+            toml.push_str(&format!(
+                "\n[[templates]]\nname = \"{name}\"\ndisplay_name = \"{name}\"\ndescription = \"\"\nfile = \"{name}.typ\"\nfunction = \"{function}\"\nsupports_variants = false\ndefault_sections = []\n"
+            ));
+        }
+        fs::write(package_dir.join(".noter.config.toml"), toml)?;
+        Ok(())
+    }
 
-## Assignments
+    fn group_note(templates: &[(&str, &str)]) -> Result<String> {
+        let temp_dir = TempDir::new()?;
+        write_package(temp_dir.path(), templates)?;
+        let mut config = Config::default();
+        config.paths.typst_packages_dir = temp_dir.path().to_string_lossy().to_string();
+        config.paths.templates_dir = temp_dir.path().join("none").to_string_lossy().to_string();
 
-## Connections to Other Courses
+        let members = vec!["Alice".to_string(), "Bob".to_string()];
+        group_note_builder("02101", &config, "Compilers", &members)?.build()
+    }
 
-## Questions & Review Points
+    #[test]
+    fn test_group_note_uses_group_template() -> Result<()> {
+        let content = group_note(&[("note", "dtu-note"), ("group", "dtu-group")])?;
 
-## Resources
-- Textbook:
-- Course website:
-- Office hours:
+        assert!(content.contains("#show: dtu-group.with("));
+        assert!(content.contains("collaborators"));
+        assert!(content.contains("Alice"));
+        Ok(())
+    }
 
-"#,
-        course_id, course_name, course_id, semester
-    )
+    #[test]
+    fn test_group_note_without_group_template_uses_lecture() -> Result<()> {
+        let content = group_note(&[("note", "dtu-note")])?;
+
+        assert!(content.contains("#show: dtu-note.with("));
+        assert!(!content.contains("collaborators"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_group_note_without_template_package_uses_fallback() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let mut config = Config::default();
+        config.paths.typst_packages_dir = temp_dir.path().to_string_lossy().to_string();
+
+        let members = vec!["Alice".to_string()];
+        let content = group_note_builder("02101", &config, "Compilers", &members)?.build()?;
+
+        assert!(content.contains("dtu-note"));
+        assert!(!content.contains("collaborators"));
+        Ok(())
+    }
 }
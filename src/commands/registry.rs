@@ -0,0 +1,249 @@
+//! Command registration
+//!
+//! Every subcommand is routed through a [`CommandRegistry`]. A new registry
+//! holds the built-in commands as its default registrations, and library users
+//! can add their own subcommands next to them without forking. Each custom
+//! command brings its own [`clap::Command`], so its arguments are parsed and
+//! shown in `--help` like a built-in's, and an unknown name still gets clap's
+//! error with its "did you mean" suggestion.
+//!
+//! A handler is a [`CommandHandler`]: it gets the parsed [`ArgMatches`] of its
+//! own subcommand and returns `anyhow::Result<()>`.
+//!
+//! ```no_run
+//! use clap::{Arg, Command};
+//! use noter::commands::registry::CommandRegistry;
+//!
+//! fn main() -> anyhow::Result<()> {
+//!     let mut registry = CommandRegistry::new();
+//!     registry.register(
+//!         Command::new("hello")
+//!             .about("Say hello")
+//!             .arg(Arg::new("name").default_value("DTU")),
+//!         Box::new(|args| {
+//!             println!("Hello, {}!", args.get_one::<String>("name").unwrap());
+//!             Ok(())
+//!         }),
+//!     )?;
+//!
+//!     // `my-noter hello Alice` runs the custom handler, `my-noter note 02101` the built-in
+//!     let matches = registry.command(Command::new("my-noter")).get_matches();
+//!     registry.execute(&matches)
+//! }
+//! ```
+
+use anyhow::Result;
+use clap::{ArgMatches, FromArgMatches, Subcommand};
+
+use crate::Commands;
+
+/// Handler for a custom command, called with the parsed arguments of that
+/// subcommand
+pub type CommandHandler = Box<dyn Fn(&ArgMatches) -> Result<()>>;
+
+enum Handler {
+    /// Parsed into [`Commands`] and run by [`super::execute_command`]
+    Builtin,
+    Custom(CommandHandler),
+}
+
+struct Registration {
+    command: clap::Command,
+    handler: Handler,
+}
+
+/// Registry of the commands the CLI can run, built-in and custom
+pub struct CommandRegistry {
+    registrations: Vec<Registration>,
+}
+
+impl Default for CommandRegistry {
+    fn default() -> Self {
+        let builtins = Commands::augment_subcommands(clap::Command::new("noter"));
+        Self {
+            registrations: builtins
+                .get_subcommands()
+                .map(|command| Registration {
+                    command: command.clone(),
+                    handler: Handler::Builtin,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl CommandRegistry {
+    /// A registry holding the built-in commands
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a handler for a custom command, described by `command`.
+    ///
+    /// Fails if the name (or an alias) is already taken by a built-in command
+    /// or another registered handler.
+    pub fn register(&mut self, command: clap::Command, handler: CommandHandler) -> Result<()> {
+        let names: Vec<String> = std::iter::once(command.get_name())
+            .chain(command.get_all_aliases())
+            .map(str::to_string)
+            .collect();
+
+        for name in &names {
+            match self.find(name).map(|registration| &registration.handler) {
+                Some(Handler::Builtin) => {
+                    anyhow::bail!("'{}' is a built-in command and cannot be overridden", name)
+                }
+                Some(Handler::Custom(_)) => {
+                    anyhow::bail!("A handler for '{}' is already registered", name)
+                }
+                None => {}
+            }
+        }
+
+        self.registrations.push(Registration {
+            command,
+            handler: Handler::Custom(handler),
+        });
+        Ok(())
+    }
+
+    /// Check whether a custom handler is registered under `name`
+    pub fn contains(&self, name: &str) -> bool {
+        matches!(
+            self.find(name).map(|registration| &registration.handler),
+            Some(Handler::Custom(_))
+        )
+    }
+
+    /// Names of all registered custom commands, sorted
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self
+            .registrations
+            .iter()
+            .filter(|registration| matches!(registration.handler, Handler::Custom(_)))
+            .map(|registration| registration.command.get_name())
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// `cli` with every registered command as a subcommand, one of which is required
+    pub fn command(&self, cli: clap::Command) -> clap::Command {
+        cli.subcommands(
+            self.registrations
+                .iter()
+                .map(|registration| registration.command.clone()),
+        )
+        .subcommand_required(true)
+        .arg_required_else_help(true)
+    }
+
+    /// Run the subcommand in `matches`, parsed with [`command`](Self::command)
+    pub fn execute(&self, matches: &ArgMatches) -> Result<()> {
+        let (name, args) = matches
+            .subcommand()
+            .ok_or_else(|| anyhow::anyhow!("No command given"))?;
+
+        match self.find(name).map(|registration| &registration.handler) {
+            Some(Handler::Custom(handler)) => handler(args),
+            Some(Handler::Builtin) => {
+                // A global `--editor`, when the command line defines one
+                let editor = matches
+                    .try_get_one::<String>("editor")
+                    .ok()
+                    .flatten()
+                    .map(String::as_str);
+                super::execute_command(&Commands::from_arg_matches(matches)?, editor)
+            }
+            None => anyhow::bail!(
+                "Unknown command '{}'. Run 'noter --help' to see available commands",
+                name
+            ),
+        }
+    }
+
+    fn find(&self, name: &str) -> Option<&Registration> {
+        self.registrations.iter().find(|registration| {
+            registration.command.get_name() == name
+                || registration
+                    .command
+                    .get_all_aliases()
+                    .any(|alias| alias == name)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{Arg, Command, error::ErrorKind};
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_register_rejects_builtin_names() {
+        let mut registry = CommandRegistry::new();
+
+        assert!(
+            registry
+                .register(Command::new("note"), Box::new(|_| Ok(())))
+                .is_err()
+        );
+        assert!(
+            registry
+                .register(Command::new("notes").alias("n"), Box::new(|_| Ok(())))
+                .is_err()
+        );
+        assert!(
+            registry
+                .register(Command::new("export"), Box::new(|_| Ok(())))
+                .is_ok()
+        );
+        assert!(
+            registry
+                .register(Command::new("export"), Box::new(|_| Ok(())))
+                .is_err()
+        );
+        assert_eq!(registry.names(), vec!["export"]);
+        assert!(registry.contains("export"));
+        assert!(!registry.contains("note"));
+    }
+
+    #[test]
+    fn test_execute_dispatches_custom_command() {
+        let calls = Rc::new(Cell::new(0));
+        let seen = Rc::clone(&calls);
+
+        let mut registry = CommandRegistry::new();
+        registry
+            .register(
+                Command::new("hello").arg(Arg::new("name").required(true)),
+                Box::new(move |args| {
+                    assert_eq!(args.get_one::<String>("name").unwrap(), "world");
+                    seen.set(seen.get() + 1);
+                    Ok(())
+                }),
+            )
+            .unwrap();
+
+        let matches = registry
+            .command(Command::new("noter"))
+            .try_get_matches_from(["noter", "hello", "world"])
+            .unwrap();
+        registry.execute(&matches).unwrap();
+
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn test_unknown_command_keeps_clap_suggestions() {
+        let registry = CommandRegistry::new();
+        let error = registry
+            .command(Command::new("noter"))
+            .try_get_matches_from(["noter", "nte"])
+            .unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::InvalidSubcommand);
+        assert!(error.to_string().contains("note"));
+    }
+}
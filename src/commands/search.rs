@@ -3,16 +3,60 @@
 //! Thin command layer that uses core search engine and ui formatters.
 
 use anyhow::Result;
-use std::path::Path;
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 use crate::config::{Config, get_config};
-use crate::core::directory_scanner::DirectoryScanner;
-use crate::core::search_engine::{SearchEngine, SearchLocation, SearchMatch, SearchOptions};
+use crate::core::file_operations::FileOperations;
+use crate::core::search_engine::{
+    MatchMode, SearchEngine, SearchMatch, SearchOptions, SearchSummary,
+};
+use crate::core::search_index::SearchIndex;
 use crate::ui::formatters::Formatters;
 use crate::ui::output::{OutputManager, Status};
+use crate::ui::prompts::PromptManager;
+
+/// Per-query case sensitivity from the `--case-sensitive`/`--ignore-case`
+/// flags, or `None` to use the configured default
+pub fn case_override(case_sensitive: bool, ignore_case: bool) -> Option<bool> {
+    match (case_sensitive, ignore_case) {
+        (true, _) => Some(true),
+        (_, true) => Some(false),
+        _ => None,
+    }
+}
 
-pub fn search_notes(query: &str) -> Result<()> {
-    let config = get_config()?;
+/// Match mode from the `--any`/`--all-words`/`--regex` flags, defaulting to substring
+pub fn match_mode(
+    any: bool,
+    all_words: bool,
+    regex: bool,
+    highlight_group: Option<usize>,
+) -> MatchMode {
+    match (any, all_words, regex) {
+        (true, _, _) => MatchMode::AnyWord,
+        (_, true, _) => MatchMode::AllWords,
+        (_, _, true) => MatchMode::Regex { highlight_group },
+        _ => MatchMode::Substring,
+    }
+}
+
+/// Configuration with a per-query case override applied, so every search path
+/// builds its `SearchOptions` from the same setting
+fn with_case_override(mut config: Config, case_sensitive: Option<bool>) -> Config {
+    if let Some(case_sensitive) = case_sensitive {
+        config.search.case_sensitive = case_sensitive;
+    }
+    config
+}
+
+pub fn search_notes(
+    query: &str,
+    case_sensitive: Option<bool>,
+    match_mode: MatchMode,
+) -> Result<()> {
+    let config = with_case_override(get_config()?, case_sensitive);
 
     OutputManager::print_status(Status::Loading, &format!("Searching for '{}'", query));
 
@@ -25,85 +69,166 @@ pub fn search_notes(query: &str) -> Result<()> {
         return Ok(());
     }
 
-    // Get search results using your existing SearchEngine
-    let results = if should_use_index(notes_path)? {
-        search_with_index(notes_path, query, &config)?
-    } else {
-        search_without_index(query, &config)?
-    };
+    // Large collections, and any with an index from `--reindex`, search through the index
+    let results =
+        if SearchIndex::index_path(notes_path).exists() || should_use_index(notes_path, &config)? {
+            search_with_index(notes_path, query, &config, match_mode)?
+        } else {
+            search_without_index(query, &config, match_mode)?
+        };
 
     display_search_results(results, query, &config)?;
     Ok(())
 }
 
 /// Search using index - returns Vec<SearchMatch>
-fn search_with_index(notes_path: &Path, query: &str, config: &Config) -> Result<Vec<SearchMatch>> {
-    let index = SearchEngine::get_or_build_index(notes_path)?;
-    let locations = SearchEngine::search_with_index(&index, query);
-
-    // Convert SearchLocations to SearchMatch
-    let mut results = Vec::new();
-    for location in locations {
-        if let Ok(search_match) = build_search_match_from_location(location, query, config) {
-            results.push(search_match);
+///
+/// The index is brought up to date first, then only the files it names as
+/// candidates are scanned line by line.
+fn search_with_index(
+    notes_path: &Path,
+    query: &str,
+    config: &Config,
+    match_mode: MatchMode,
+) -> Result<Vec<SearchMatch>> {
+    // Tokens can't narrow a regex, every file has to be read
+    if matches!(match_mode, MatchMode::Regex { .. }) {
+        return search_without_index(query, config, match_mode);
+    }
+
+    let index = SearchEngine::get_or_build_index(
+        notes_path,
+        &config.search.file_extensions,
+        config.paths.follow_symlinks,
+    )?;
+
+    match index_candidates(&index, notes_path, query, match_mode) {
+        Some(candidates) => {
+            SearchEngine::search_in_files(&candidates, query, &search_options(config, match_mode))
         }
+        None => search_without_index(query, config, match_mode),
     }
+}
 
-    // Limit results
-    results.truncate(config.search.max_results);
-    Ok(results)
+/// Print only how often `query` occurs: the total, or `<path>:<count>` per
+/// matching file followed by the total with `per_file`. No other output, so
+/// the result can be used in scripts.
+pub fn count_matches(
+    query: &str,
+    case_sensitive: Option<bool>,
+    match_mode: MatchMode,
+    per_file: bool,
+) -> Result<()> {
+    let mut config = with_case_override(get_config()?, case_sensitive);
+    // Count every match, not just the ones a normal search would show
+    config.search.max_results = usize::MAX;
+
+    let results = if Path::new(&config.paths.notes_dir).exists() {
+        search_without_index(query, &config, match_mode)?
+    } else {
+        Vec::new()
+    };
+    let summary = SearchSummary::from_matches(&results);
+
+    if per_file {
+        let notes_path = Path::new(&config.paths.notes_dir);
+        for (path, count) in &summary.per_file {
+            println!(
+                "{}:{}",
+                path.strip_prefix(notes_path).unwrap_or(path).display(),
+                count
+            );
+        }
+    }
+    println!("{}", summary.total_matches);
+
+    Ok(())
 }
 
 /// Search without index - use your existing method
-fn search_without_index(query: &str, config: &Config) -> Result<Vec<SearchMatch>> {
-    let search_options = SearchOptions {
+fn search_without_index(
+    query: &str,
+    config: &Config,
+    match_mode: MatchMode,
+) -> Result<Vec<SearchMatch>> {
+    SearchEngine::search_in_directory(
+        &config.paths.notes_dir,
+        query,
+        &search_options(config, match_mode),
+    )
+}
+
+fn search_options(config: &Config, match_mode: MatchMode) -> SearchOptions {
+    SearchOptions {
         case_sensitive: config.search.case_sensitive,
         max_results: config.search.max_results,
         context_lines: config.search.context_lines,
         file_extensions: config.search.file_extensions.clone(),
-    };
-
-    SearchEngine::search_in_directory(&config.paths.notes_dir, query, &search_options)
+        match_mode,
+        follow_symlinks: config.paths.follow_symlinks,
+        result_order: config.search.result_order,
+    }
 }
 
-/// Convert SearchLocation to SearchMatch
-fn build_search_match_from_location(
-    location: SearchLocation,
+/// Candidate files for `query`, or `None` when the query has no tokens to
+/// narrow by and every file has to be scanned
+fn index_candidates(
+    index: &SearchIndex,
+    notes_path: &Path,
     query: &str,
-    config: &Config,
-) -> Result<SearchMatch> {
-    let content = std::fs::read_to_string(&location.file_path)?;
-    let lines: Vec<&str> = content.lines().collect();
+    match_mode: MatchMode,
+) -> Option<Vec<PathBuf>> {
+    if match_mode != MatchMode::AnyWord {
+        return index.candidates(notes_path, query);
+    }
 
-    if location.line_number == 0 || location.line_number > lines.len() {
-        return Err(anyhow::anyhow!("Invalid line number"));
+    // A line may match any single word, so take the union of each word's candidates
+    let mut candidates = Vec::new();
+    for word in query.split_whitespace() {
+        candidates.extend(index.candidates(notes_path, word)?);
     }
+    candidates.sort();
+    candidates.dedup();
+    Some(candidates)
+}
 
-    let line_content = lines[location.line_number - 1].to_string();
+/// Build the search index, or update it for files changed since the last run
+pub fn update_search_index() -> Result<()> {
+    let config = get_config()?;
+    let notes_path = Path::new(&config.paths.notes_dir);
 
-    // Find the query in the line content
-    let query_lower = query.to_lowercase();
-    let line_lower = line_content.to_lowercase();
+    if !notes_path.exists() {
+        OutputManager::print_status(
+            Status::Error,
+            &format!("Notes directory not found: {}", config.paths.notes_dir),
+        );
+        return Ok(());
+    }
 
-    let (match_start, match_end) = if config.search.case_sensitive {
-        if let Some(pos) = line_content.find(query) {
-            (pos, pos + query.len())
-        } else {
-            (0, 0)
-        }
-    } else if let Some(pos) = line_lower.find(&query_lower) {
-        (pos, pos + query_lower.len())
-    } else {
-        (0, 0)
-    };
+    OutputManager::print_status(Status::Loading, "Updating search index...");
+
+    let start_time = std::time::Instant::now();
+    let (index, update) = SearchIndex::update_or_build(
+        notes_path,
+        &config.search.file_extensions,
+        config.paths.follow_symlinks,
+    )?;
+    index.save(notes_path)?;
+
+    OutputManager::print_status(
+        Status::Success,
+        &format!(
+            "Search index updated in {:.2}s: {} added, {} updated, {} removed, {} unchanged ({} tokens)",
+            start_time.elapsed().as_secs_f64(),
+            update.added,
+            update.updated,
+            update.removed,
+            update.unchanged,
+            index.tokens.len()
+        ),
+    );
 
-    Ok(SearchMatch {
-        file_path: location.file_path,
-        line_number: location.line_number,
-        line_content,
-        match_start,
-        match_end,
-    })
+    Ok(())
 }
 
 /// Display results using your existing formatter
@@ -127,6 +252,137 @@ fn display_search_results(results: Vec<SearchMatch>, query: &str, config: &Confi
     Ok(())
 }
 
+/// Find and replace `query` across all notes, prompting per line unless `apply_all` is set
+pub fn replace_in_notes(
+    query: &str,
+    replacement: &str,
+    apply_all: bool,
+    case_sensitive: Option<bool>,
+) -> Result<()> {
+    let config = with_case_override(get_config()?, case_sensitive);
+
+    let notes_path = Path::new(&config.paths.notes_dir);
+    if !notes_path.exists() {
+        OutputManager::print_status(
+            Status::Warning,
+            &format!("No notes directory found at: {}", config.paths.notes_dir),
+        );
+        return Ok(());
+    }
+
+    OutputManager::print_status(
+        Status::Loading,
+        &format!(
+            "Searching for '{}' to replace with '{}'",
+            query, replacement
+        ),
+    );
+
+    // Replacement must see every match, so bypass the result limit and the word index
+    let search_options = SearchOptions {
+        max_results: usize::MAX,
+        ..search_options(&config, MatchMode::Substring)
+    };
+    let results = SearchEngine::search_in_directory(notes_path, query, &search_options)?;
+
+    if results.is_empty() {
+        OutputManager::print_status(Status::Info, "No results found");
+        return Ok(());
+    }
+
+    let mut lines_by_file: BTreeMap<PathBuf, Vec<usize>> = BTreeMap::new();
+    for result in results {
+        lines_by_file
+            .entry(result.file_path)
+            .or_default()
+            .push(result.line_number);
+    }
+
+    let mut files_changed = 0;
+    let mut lines_changed = 0;
+
+    for (file_path, line_numbers) in &lines_by_file {
+        let content = std::fs::read_to_string(file_path)?;
+        let mut new_content = String::with_capacity(content.len());
+        let mut file_lines_changed = 0;
+
+        for (index, raw_line) in content.split_inclusive('\n').enumerate() {
+            let line = raw_line.trim_end_matches(['\r', '\n']);
+            let ending = &raw_line[line.len()..];
+
+            let replaced = if line_numbers.contains(&(index + 1)) {
+                SearchEngine::replace_in_line(
+                    line,
+                    query,
+                    replacement,
+                    config.search.case_sensitive,
+                )
+            } else {
+                None
+            };
+
+            match replaced {
+                Some(replaced_line)
+                    if confirm_replacement(
+                        file_path,
+                        index + 1,
+                        line,
+                        &replaced_line,
+                        apply_all,
+                    )? =>
+                {
+                    new_content.push_str(&replaced_line);
+                    new_content.push_str(ending);
+                    file_lines_changed += 1;
+                }
+                _ => new_content.push_str(raw_line),
+            }
+        }
+
+        if file_lines_changed > 0 {
+            if config.note_preferences.create_backups {
+                FileOperations::create_backup(file_path)?;
+            }
+            FileOperations::safe_write(file_path, &new_content)?;
+            files_changed += 1;
+            lines_changed += file_lines_changed;
+        }
+    }
+
+    OutputManager::print_status(
+        Status::Success,
+        &format!(
+            "Replaced {} line(s) in {} file(s)",
+            lines_changed, files_changed
+        ),
+    );
+
+    Ok(())
+}
+
+/// Show a pending replacement and ask whether to apply it
+fn confirm_replacement(
+    file_path: &Path,
+    line_number: usize,
+    old_line: &str,
+    new_line: &str,
+    apply_all: bool,
+) -> Result<bool> {
+    println!(
+        "{}:{}",
+        file_path.display().to_string().bright_blue(),
+        line_number.to_string().dimmed()
+    );
+    println!("  {} {}", "-".red(), old_line.trim().red());
+    println!("  {} {}", "+".green(), new_line.trim().green());
+
+    if apply_all {
+        return Ok(true);
+    }
+
+    PromptManager::confirm("Apply this replacement?", Some(true))
+}
+
 pub fn rebuild_index(force: bool) -> Result<()> {
     let config = get_config()?;
     let notes_path = Path::new(&config.paths.notes_dir);
@@ -143,7 +399,11 @@ pub fn rebuild_index(force: bool) -> Result<()> {
     println!("Scanning directory: {}", notes_path.display());
 
     // Check if we have enough files to warrant an index
-    let files = DirectoryScanner::scan_directory_for_files(notes_path, &["typ", "md"])?;
+    let files = SearchEngine::indexed_files(
+        notes_path,
+        &config.search.file_extensions,
+        config.paths.follow_symlinks,
+    )?;
 
     // Debug: Print found files
     println!("Files found:");
@@ -182,7 +442,7 @@ pub fn rebuild_index(force: bool) -> Result<()> {
     );
 
     // Remove existing index file if it exists
-    let index_path = notes_path.join(".notes-search-index");
+    let index_path = SearchIndex::index_path(notes_path);
     if index_path.exists() {
         std::fs::remove_file(&index_path)?;
         OutputManager::print_status(Status::Info, "Removed existing index");
@@ -190,20 +450,23 @@ pub fn rebuild_index(force: bool) -> Result<()> {
 
     // Build new index
     let start_time = std::time::Instant::now();
-    let index = SearchEngine::build_index(notes_path)?;
+    let index = SearchEngine::build_index(
+        notes_path,
+        &config.search.file_extensions,
+        config.paths.follow_symlinks,
+    )?;
     let duration = start_time.elapsed();
 
     // Save the new index
-    let serialized = serde_json::to_string(&index)?;
-    std::fs::write(&index_path, serialized)?;
+    index.save(notes_path)?;
 
     OutputManager::print_status(
         Status::Success,
         &format!(
-            "Search index rebuilt successfully in {:.2}s! Indexed {} files with {} words.",
+            "Search index rebuilt successfully in {:.2}s! Indexed {} files with {} distinct words.",
             duration.as_secs_f64(),
             files.len(),
-            index.word_map.len()
+            index.tokens.len()
         ),
     );
 
@@ -211,8 +474,12 @@ pub fn rebuild_index(force: bool) -> Result<()> {
 }
 
 /// Decide whether to use index based on collection size
-fn should_use_index(notes_path: &Path) -> Result<bool> {
-    let files = DirectoryScanner::scan_directory_for_files(notes_path, &["typ", "md"])?;
+fn should_use_index(notes_path: &Path, config: &Config) -> Result<bool> {
+    let files = SearchEngine::indexed_files(
+        notes_path,
+        &config.search.file_extensions,
+        config.paths.follow_symlinks,
+    )?;
     Ok(files.len() > 50) // Use index for collections with 50+ files
 }
 
@@ -232,13 +499,43 @@ mod tests {
         for i in 0..10 {
             fs::write(temp_path.join(format!("file{}.typ", i)), "test content")?;
         }
-        assert!(!should_use_index(temp_path)?);
+        let config = Config::default();
+        assert!(!should_use_index(temp_path, &config)?);
 
         // Create many files - should use index
         for i in 10..60 {
             fs::write(temp_path.join(format!("file{}.typ", i)), "test content")?;
         }
-        assert!(should_use_index(temp_path)?);
+        assert!(should_use_index(temp_path, &config)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_case_override() -> Result<()> {
+        assert_eq!(case_override(false, false), None);
+        assert_eq!(case_override(true, false), Some(true));
+        assert_eq!(case_override(false, true), Some(false));
+
+        let temp_dir = TempDir::new()?;
+        fs::write(temp_dir.path().join("test.typ"), "fn parseConfig()")?;
+
+        let mut config = Config::default();
+        config.paths.notes_dir = temp_dir.path().to_string_lossy().to_string();
+        config.search.case_sensitive = false;
+
+        let exact = with_case_override(config.clone(), Some(true));
+        assert!(search_without_index("parseconfig", &exact, MatchMode::Substring)?.is_empty());
+        assert_eq!(
+            search_without_index("parseConfig", &exact, MatchMode::Substring)?.len(),
+            1
+        );
+
+        let default = with_case_override(config, None);
+        assert_eq!(
+            search_without_index("parseconfig", &default, MatchMode::Substring)?.len(),
+            1
+        );
 
         Ok(())
     }
@@ -262,10 +559,11 @@ mod tests {
         config.paths.notes_dir = temp_path.to_string_lossy().to_string();
 
         // Search with index
-        let indexed_results = search_with_index(temp_path, "algorithms", &config)?;
+        let indexed_results =
+            search_with_index(temp_path, "algorithms", &config, MatchMode::Substring)?;
 
         // Search without index
-        let direct_results = search_without_index("algorithms", &config)?;
+        let direct_results = search_without_index("algorithms", &config, MatchMode::Substring)?;
 
         // Results should be similar (may differ slightly in ordering/format)
         assert!(!indexed_results.is_empty());
@@ -286,30 +584,4 @@ mod tests {
 
         Ok(())
     }
-
-    #[test]
-    fn test_build_search_match_from_location() -> Result<()> {
-        let temp_dir = TempDir::new()?;
-        let temp_path = temp_dir.path();
-        let file_path = temp_path.join("test.typ");
-
-        fs::write(&file_path, "line one\nthis has algorithms\nline three")?;
-
-        let location = SearchLocation {
-            file_path: file_path.clone(),
-            line_number: 2,
-            column: 2,
-        };
-
-        let config = Config::default();
-        let search_match = build_search_match_from_location(location, "algorithms", &config)?;
-
-        assert_eq!(search_match.file_path, file_path);
-        assert_eq!(search_match.line_number, 2);
-        assert_eq!(search_match.line_content, "this has algorithms");
-        assert!(search_match.match_start > 0);
-        assert!(search_match.match_end > search_match.match_start);
-
-        Ok(())
-    }
 }
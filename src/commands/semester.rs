@@ -0,0 +1,111 @@
+//! Semester workflow commands
+//!
+//! Thin command layer over the semester-end cleanup in core.
+
+use anyhow::Result;
+use colored::Colorize;
+
+use crate::config::get_config;
+use crate::core::file_operations::FileOperations;
+use crate::core::semester_cleanup::{SemesterCleanup, SemesterEndPlan};
+use crate::ui::output::{OutputManager, Status};
+use crate::ui::prompts::PromptManager;
+
+/// Archive every active course, then clean compiled PDFs and prune backups
+pub fn end_semester(yes: bool) -> Result<()> {
+    let config = get_config()?;
+    let plan = SemesterCleanup::plan(&config)?;
+
+    if plan.courses.is_empty() {
+        OutputManager::print_status(
+            Status::Info,
+            "No active courses to archive. Add one with 'noter courses add'",
+        );
+        return Ok(());
+    }
+
+    print_plan(&plan);
+
+    if !yes
+        && !PromptManager::confirm(
+            &format!("Archive {} course(s) and clean up", plan.courses.len()),
+            Some(false),
+        )?
+    {
+        println!("Cancelled.");
+        return Ok(());
+    }
+
+    let report = SemesterCleanup::run(&config, &plan)?;
+
+    for course in &report.courses {
+        println!(
+            "  {} {} → {} ({} files, {})",
+            "📦".blue(),
+            course.archive.course_id.bright_blue(),
+            course.archive.path.display(),
+            course.archive.files,
+            FileOperations::format_file_size(course.archive.size)
+        );
+        println!(
+            "     removed {} PDF(s) and {} backup(s), reclaimed {}",
+            course.pdfs_removed,
+            course.backups_removed,
+            FileOperations::format_file_size(course.bytes_reclaimed)
+        );
+    }
+
+    println!();
+    OutputManager::print_status(
+        Status::Success,
+        &format!(
+            "Created {} archive(s) ({}) and reclaimed {}",
+            report.courses.len(),
+            FileOperations::format_file_size(report.archive_size()),
+            FileOperations::format_file_size(report.bytes_reclaimed())
+        ),
+    );
+    println!(
+        "Archives are in {}",
+        report.archive_dir.display().to_string().bright_white()
+    );
+
+    Ok(())
+}
+
+fn print_plan(plan: &SemesterEndPlan) {
+    OutputManager::print_section(&format!("End of {}", plan.semester), Some("🎓"));
+
+    println!(
+        "Archives will be written to {}",
+        plan.archive_dir.display().to_string().bright_white()
+    );
+    println!();
+
+    for course in &plan.courses {
+        let replaces = if course.replaces_archive {
+            " (replaces existing archive)".yellow().to_string()
+        } else {
+            String::new()
+        };
+
+        println!(
+            "  {} archive{}, delete {} PDF(s) and {} backup(s) ({})",
+            course.course_id.bright_blue(),
+            replaces,
+            course.pdfs.len(),
+            course.backups.len(),
+            FileOperations::format_file_size(course.reclaimable)
+        );
+    }
+
+    println!();
+    println!(
+        "Total: {} course(s), {} PDF(s), {} backup(s), {} to reclaim",
+        plan.courses.len(),
+        plan.total_pdfs(),
+        plan.total_backups(),
+        FileOperations::format_file_size(plan.reclaimable()).bright_green()
+    );
+    println!();
+}
@@ -0,0 +1,61 @@
+//! Sync command implementation
+//!
+//! Thin command layer over core git integration for pulling and pushing notes.
+
+use anyhow::Result;
+use colored::Colorize;
+use std::path::Path;
+
+use crate::config::get_config;
+use crate::core::git_integration::GitIntegration;
+use crate::ui::output::{OutputManager, Status};
+
+/// Pull (with rebase) and push the notes directory
+pub fn sync_notes() -> Result<()> {
+    let config = get_config()?;
+    let notes_dir = Path::new(&config.paths.notes_dir);
+
+    if !GitIntegration::has_git_dir(notes_dir) {
+        OutputManager::print_status(
+            Status::Warning,
+            &format!(
+                "Notes directory is not a git repository: {}",
+                notes_dir.display()
+            ),
+        );
+        println!("To enable syncing, initialize it and add a remote:");
+        println!(
+            "  {}",
+            format!("git -C {} init", notes_dir.display()).bright_white()
+        );
+        println!(
+            "  {}",
+            format!("git -C {} remote add origin <url>", notes_dir.display()).bright_white()
+        );
+        return Ok(());
+    }
+
+    if GitIntegration::has_uncommitted_changes(notes_dir)? {
+        OutputManager::print_status(
+            Status::Error,
+            "You have uncommitted changes in your notes directory",
+        );
+        println!(
+            "Commit or stash them first, e.g. {}",
+            format!("git -C {} commit -am \"Update notes\"", notes_dir.display()).bright_white()
+        );
+        return Ok(());
+    }
+
+    OutputManager::print_status(Status::Loading, "Pulling latest changes...");
+    let pull_output = GitIntegration::pull_rebase(notes_dir)?;
+    if !pull_output.is_empty() {
+        println!("  {}", pull_output.dimmed());
+    }
+
+    OutputManager::print_status(Status::Loading, "Pushing local commits...");
+    GitIntegration::push(notes_dir)?;
+
+    OutputManager::print_status(Status::Success, "Notes synced");
+    Ok(())
+}
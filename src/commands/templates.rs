@@ -5,13 +5,19 @@
 use anyhow::Result;
 use colored::Colorize;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use crate::config::{Config, get_config};
+use crate::config::{Config, UserTemplateConfig, get_config};
+use crate::core::file_operations::FileOperations;
 use crate::core::github_template_fetcher::GitHubTemplateFetcher;
 use crate::core::template::config::{TemplateConfig, TemplateVariant};
+use crate::core::template::validation::{TemplateValidator, ValidationSeverity};
 use crate::core::template::{
-    builder::TemplateBuilder, discovery::TemplateDiscovery, engine::TemplateReference,
+    builder::TemplateBuilder,
+    cache::RenderCache,
+    discovery::{CourseTypeMatch, DiscoveredPackage, TemplateDiscovery},
+    engine::TemplateReference,
+    imports::ImportFixer,
 };
 use crate::core::validation::Validator;
 use crate::ui::output::{OutputManager, Status};
@@ -29,15 +35,24 @@ pub fn template_status() -> Result<()> {
 }
 
 fn display_template_discovery_status(config: &Config) {
-    match TemplateDiscovery::load_template_configs(config) {
-        Ok(template_configs) => {
-            if template_configs.is_empty() {
+    match TemplateDiscovery::discover_packages(config) {
+        Ok(packages) => {
+            let (active, shadowed): (Vec<DiscoveredPackage>, Vec<DiscoveredPackage>) =
+                packages.into_iter().partition(|package| !package.shadowed);
+            if active.is_empty() {
                 OutputManager::print_status(Status::Warning, "No template configurations found");
                 return;
             }
+            let template_configs: Vec<TemplateConfig> = active
+                .iter()
+                .map(|package| package.config.clone())
+                .collect();
 
             display_template_system_header();
-            display_all_template_packages(&template_configs);
+            display_resolution_order(config);
+            display_pinned_versions(config, &template_configs);
+            display_all_template_packages(&active);
+            display_shadowed_packages(&shadowed);
             display_all_available_templates(&template_configs);
             display_all_template_variants(&template_configs);
             display_consolidated_course_mapping(&template_configs);
@@ -52,15 +67,59 @@ fn display_template_system_header() {
     println!();
 }
 
-fn display_all_template_packages(template_configs: &[TemplateConfig]) {
+fn display_resolution_order(config: &Config) {
+    println!("Resolution Order:");
+    for (index, location) in config.templates.template_locations().iter().enumerate() {
+        println!(
+            "  {}. {} {}",
+            index + 1,
+            location.label().bright_white(),
+            location.directory(config).display().to_string().dimmed()
+        );
+    }
+    println!();
+}
+
+fn display_pinned_versions(config: &Config, template_configs: &[TemplateConfig]) {
+    if config.templates.pinned_versions.is_empty() {
+        return;
+    }
+
+    let mut pins: Vec<(&String, &String)> = config.templates.pinned_versions.iter().collect();
+    pins.sort();
+
+    println!("Pinned Versions:");
+    for (package, version) in pins {
+        let note = if TemplateDiscovery::is_installed(template_configs, package, version) {
+            String::new()
+        } else {
+            format!(" {}", "(not installed)".yellow())
+        };
+        println!(
+            "  📌 {} {}{}",
+            package.bright_white(),
+            version.green(),
+            note
+        );
+    }
+    println!();
+}
+
+fn display_all_template_packages(packages: &[DiscoveredPackage]) {
     println!("Template Packages:");
-    for (index, config) in template_configs.iter().enumerate() {
+    for (index, package) in packages.iter().enumerate() {
+        let config = &package.config;
         if index > 0 {
             println!();
         }
         println!("  Package {}:", index + 1);
         println!("    Name: {}", config.metadata.name.bright_white());
         println!("    Version: {}", config.metadata.version.green());
+        println!(
+            "    Location: {} ({})",
+            package.location.label().cyan(),
+            package.dir.display()
+        );
         if let Some(description) = &config.metadata.description {
             println!("    Description: {}", description.dimmed());
         }
@@ -73,6 +132,25 @@ fn display_all_template_packages(template_configs: &[TemplateConfig]) {
     }
 }
 
+fn display_shadowed_packages(shadowed: &[DiscoveredPackage]) {
+    if shadowed.is_empty() {
+        return;
+    }
+
+    println!();
+    println!("Shadowed Packages:");
+    for package in shadowed {
+        println!(
+            "  {} {} v{} in {} ({}) - an earlier location provides this package",
+            "•".yellow(),
+            package.config.metadata.name.bright_white(),
+            package.config.metadata.version,
+            package.location.label().cyan(),
+            package.dir.display().to_string().dimmed()
+        );
+    }
+}
+
 fn display_all_available_templates(template_configs: &[TemplateConfig]) {
     let all_templates = TemplateDiscovery::get_all_templates(template_configs);
 
@@ -311,6 +389,42 @@ pub fn update_template() -> Result<()> {
     Ok(())
 }
 
+/// Report what `template update` would install, without changing anything
+pub fn preview_template_update() -> Result<()> {
+    let config = get_config()?;
+
+    OutputManager::print_status(Status::Loading, "Checking for template updates...");
+    let planned = GitHubTemplateFetcher::plan_updates(&config)?;
+
+    if planned.is_empty() {
+        OutputManager::print_status(
+            Status::Warning,
+            "No templates would be updated (no repositories configured?)",
+        );
+    }
+
+    for update in &planned {
+        let action = match &update.installed_version {
+            Some(installed) if update.changes_version() => format!(
+                "{} -> {}",
+                installed.yellow(),
+                update.latest_version.green()
+            ),
+            Some(installed) => format!("{} (up to date, would be reinstalled)", installed.green()),
+            None => format!("not installed -> {}", update.latest_version.green()),
+        };
+        println!("  {} {}", update.name.bright_white(), action);
+    }
+
+    println!();
+    OutputManager::print_status(
+        Status::Info,
+        "Dry run: nothing was downloaded or installed. Run 'noter template update' to apply",
+    );
+
+    Ok(())
+}
+
 /// Force reinstall all templates
 pub fn reinstall_template() -> Result<()> {
     let config = get_config()?;
@@ -357,17 +471,299 @@ pub fn reinstall_template() -> Result<()> {
     Ok(())
 }
 
+/// Rewrite `@local` template imports in notes to the installed template versions
+pub fn fix_template_imports(course_id: Option<&str>) -> Result<()> {
+    let config = get_config()?;
+    let course_id = course_id.map(|id| config.resolve_course_id(id));
+    if let Some(course_id) = &course_id {
+        Validator::validate_course_id(course_id)?;
+    }
+
+    let installed = TemplateDiscovery::installed_package_versions(&config)?;
+    if installed.is_empty() {
+        OutputManager::print_status(Status::Warning, "No installed templates found");
+        println!(
+            "Install templates with: {}",
+            "noter template update".bright_white()
+        );
+        return Ok(());
+    }
+
+    OutputManager::print_status(
+        Status::Loading,
+        &match &course_id {
+            Some(course_id) => format!("Checking template imports in {}", course_id.yellow()),
+            None => "Checking template imports in all courses".to_string(),
+        },
+    );
+
+    let report = ImportFixer::fix_imports(&config, course_id.as_deref())?;
+
+    if report.fixed.is_empty() {
+        OutputManager::print_status(
+            Status::Success,
+            &format!(
+                "All imports are up to date ({} files checked)",
+                report.files_scanned
+            ),
+        );
+        return Ok(());
+    }
+
+    println!();
+    for fix in &report.fixed {
+        println!("  {} {}", "✏️".blue(), fix.path.display());
+    }
+
+    println!();
+    for (change, count) in report.version_changes() {
+        println!(
+            "  {} {} → {} ({} files)",
+            change.package.bright_white(),
+            change.from.red(),
+            change.to.bright_green(),
+            count
+        );
+    }
+
+    println!();
+    OutputManager::print_status(
+        Status::Success,
+        &format!(
+            "Updated imports in {} of {} files",
+            report.fixed.len(),
+            report.files_scanned
+        ),
+    );
+
+    Ok(())
+}
+
+/// Remove every cached rendered note
+pub fn clear_render_cache() -> Result<()> {
+    let cache = RenderCache::open()?;
+    let removed = cache.clear()?;
+
+    if removed == 0 {
+        OutputManager::print_status(Status::Info, "Render cache is already empty");
+    } else {
+        OutputManager::print_status(
+            Status::Success,
+            &format!(
+                "Removed {} cached render(s) from {}",
+                removed,
+                cache.dir().display()
+            ),
+        );
+    }
+
+    Ok(())
+}
+
+/// Pin a template package version for new notes and stop auto-updating it
+pub fn pin_template(package: &str, version: &str) -> Result<()> {
+    let mut config = get_config()?;
+    config.pin_template_version(package, version)?;
+    let version = config
+        .templates
+        .pinned_version(package)
+        .unwrap_or(version)
+        .to_string();
+
+    OutputManager::print_status(
+        Status::Success,
+        &format!("Pinned {} to {}", package.bright_white(), version.green()),
+    );
+
+    let configs = TemplateDiscovery::load_template_configs(&config)?;
+    if !TemplateDiscovery::is_installed(&configs, package, &version) {
+        let installed: Vec<&str> = configs
+            .iter()
+            .filter(|template| UserTemplateConfig::same_package(&template.metadata.name, package))
+            .map(|template| template.metadata.version.as_str())
+            .collect();
+        OutputManager::print_status(
+            Status::Warning,
+            &format!(
+                "{} {} isn't installed ({}); new notes use the newest installed version until it is",
+                package,
+                version,
+                if installed.is_empty() {
+                    "no versions installed".to_string()
+                } else {
+                    format!("installed: {}", installed.join(", "))
+                }
+            ),
+        );
+    }
+
+    Ok(())
+}
+
+/// Remove a template package's pin
+pub fn unpin_template(package: &str) -> Result<()> {
+    let mut config = get_config()?;
+    match config.unpin_template_version(package)? {
+        Some(version) => OutputManager::print_status(
+            Status::Success,
+            &format!(
+                "Unpinned {} (was {}); new notes use the newest installed version",
+                package.bright_white(),
+                version
+            ),
+        ),
+        None => OutputManager::print_status(Status::Info, &format!("{} is not pinned", package)),
+    }
+
+    Ok(())
+}
+
+/// Validate the template system, printing a text report or JSON issues
+pub fn validate_templates(json: bool) -> Result<()> {
+    let config = get_config()?;
+    let issues = TemplateValidator::validate_system(&config)?;
+
+    if json {
+        println!(
+            "{}",
+            TemplateValidator::format_validation_report_json(&issues)?
+        );
+    } else {
+        OutputManager::print_status(Status::Loading, "Validating template system...");
+        println!();
+        print!("{}", TemplateValidator::format_validation_report(&issues));
+        println!();
+    }
+
+    let error_count = issues
+        .iter()
+        .filter(|issue| issue.severity == ValidationSeverity::Error)
+        .count();
+
+    if error_count > 0 {
+        anyhow::bail!("Template validation found {} error(s)", error_count);
+    }
+
+    if !json {
+        OutputManager::print_status(Status::Success, "Template validation passed");
+    }
+
+    Ok(())
+}
+
+/// Show the template, variant and course type a course resolves to
+pub fn resolve_template(course_id: &str, template_type: &str) -> Result<()> {
+    let config = get_config()?;
+    let course_id = &config.resolve_course_id(course_id);
+    Validator::validate_course_id(course_id)?;
+
+    let template_ref = TemplateReference::for_type(template_type);
+    let template_config = TemplateDiscovery::load_template_config(&config)?;
+    let Some(resolution) = TemplateDiscovery::resolve_template(
+        std::slice::from_ref(&template_config),
+        course_id,
+        &template_ref.name,
+    ) else {
+        anyhow::bail!(
+            "Template '{}' not found in {} v{}. Run 'noter template status' to list templates",
+            template_ref.name,
+            template_config.metadata.name,
+            template_config.metadata.version
+        );
+    };
+
+    let course_name = config.courses.get(course_id.as_str()).cloned();
+    OutputManager::print_section(
+        &match course_name {
+            Some(name) => format!("Template for {} ({})", course_id, name),
+            None => format!("Template for {}", course_id),
+        },
+        Some("🔍"),
+    );
+
+    let reason = match &resolution.course_type_match {
+        CourseTypeMatch::Exact { package } => {
+            format!("exact match for {} in {}", course_id, package)
+        }
+        CourseTypeMatch::Pattern { package, pattern } => {
+            format!("matches pattern {} in {}", pattern, package)
+        }
+        CourseTypeMatch::Fallback => "no course mapping applies, using fallback".to_string(),
+    };
+    println!(
+        "  Course type: {} {}",
+        resolution.course_type.green(),
+        format!("({})", reason).dimmed()
+    );
+    println!(
+        "  Package:     {} v{}",
+        resolution.package.bright_white(),
+        resolution.version
+    );
+    println!(
+        "  Template:    {} {}",
+        resolution.template.name.yellow(),
+        format!(
+            "({}, {} in {})",
+            resolution.template.display_name,
+            resolution.template.function,
+            resolution.template.file
+        )
+        .dimmed()
+    );
+
+    match &resolution.variant {
+        Some(variant) => {
+            let reason = if variant.course_types.contains(&resolution.course_type) {
+                format!("matches course type {}", resolution.course_type)
+            } else {
+                "applies to all course types".to_string()
+            };
+            println!(
+                "  Variant:     {} {}",
+                variant.name.yellow(),
+                format!(
+                    "({}, {} in {}; {})",
+                    variant.display_name,
+                    variant
+                        .function
+                        .as_deref()
+                        .unwrap_or(&resolution.template.function),
+                    variant.file,
+                    reason
+                )
+                .dimmed()
+            );
+        }
+        None => println!(
+            "  Variant:     {} {}",
+            "none".dimmed(),
+            format!(
+                "(no variant of {} for course type {}, using the base template)",
+                resolution.template.name, resolution.course_type
+            )
+            .dimmed()
+        ),
+    }
+
+    Ok(())
+}
+
 /// Create a custom template using the new TemplateBuilder
 pub fn create_custom_template(
     course_id: &str,
     title: &str,
     template_type: &str,
     sections: Option<&str>,
+    output: Option<&str>,
+    editor: Option<&str>,
 ) -> Result<()> {
-    let config = get_config()?;
+    let config = get_config()?.with_editor_override(editor);
+    let course_id = &config.resolve_course_id(course_id);
 
     // Validate course ID
     Validator::validate_course_id(course_id)?;
+    let title = Validator::validate_title(title)?;
 
     OutputManager::print_status(
         Status::Loading,
@@ -378,19 +774,13 @@ pub fn create_custom_template(
         ),
     );
 
-    // Parse template reference from template type
-    let template_ref = match template_type.to_lowercase().as_str() {
-        "lecture" | "l" | "note" => TemplateReference::lecture(),
-        "assignment" | "a" => TemplateReference::assignment(),
-        "lab" | "lab-report" => TemplateReference::lab_report(),
-        "thesis" | "project" => TemplateReference::thesis(),
-        custom => TemplateReference::new(custom),
-    };
+    let template_ref = TemplateReference::for_type(template_type);
 
     // Build template using new TemplateBuilder
     let mut builder = TemplateBuilder::new(course_id, &config)?
         .with_title(title)
-        .with_reference(template_ref);
+        .with_reference(template_ref)
+        .with_render_cache(true);
 
     // Parse custom sections if provided
     if let Some(sections_str) = sections {
@@ -403,33 +793,36 @@ pub fn create_custom_template(
         if !custom_sections.is_empty() {
             builder = builder.with_sections(custom_sections);
         }
-    } else {
-        // Use default sections based on type
-        let default_sections = match template_type.to_lowercase().as_str() {
-            "assignment" | "a" => config.note_preferences.assignment_sections.clone(),
-            _ => config.note_preferences.lecture_sections.clone(),
-        };
-        builder = builder.with_sections(default_sections);
     }
+    // Without explicit sections the builder falls back to the type's section preset
 
     // Generate template content using the builder
     let content = builder.build()?;
 
     // Generate filename
-    let filename = generate_custom_template_filename(course_id, template_type, title);
-
-    // Create output directory
-    let output_dir = Path::new(&config.paths.notes_dir)
-        .join(course_id)
-        .join("templates");
+    let filename = generate_custom_template_filename(
+        course_id,
+        template_type,
+        title,
+        config.typst.new_file_extension(),
+    );
 
-    if !output_dir.exists() {
-        fs::create_dir_all(&output_dir)?;
+    let output_dir = match output {
+        Some(dir) => PathBuf::from(dir),
+        None => config.get_custom_templates_dir(course_id),
+    };
+    if output_dir.is_file() {
+        anyhow::bail!(
+            "Output location {} is a file, not a directory",
+            output_dir.display()
+        );
     }
+    FileOperations::ensure_directory_exists(&output_dir)?;
 
     // Write template file
     let file_path = output_dir.join(&filename);
     fs::write(&file_path, &content)?;
+    let file_path = fs::canonicalize(&file_path).unwrap_or(file_path);
 
     OutputManager::print_status(
         Status::Success,
@@ -442,18 +835,8 @@ pub fn create_custom_template(
     // Auto-open if configured
     if config.note_preferences.auto_open_file {
         OutputManager::print_status(Status::Info, "Opening in editor...");
-
-        if let Some(editor) = &config.preferred_editor {
-            if std::process::Command::new(editor)
-                .arg(&file_path)
-                .spawn()
-                .is_err()
-            {
-                // Fallback to system default
-                let _ = opener::open(&file_path);
-            }
-        } else {
-            let _ = opener::open(&file_path);
+        if let Err(e) = FileOperations::open_file(&file_path, &config) {
+            OutputManager::print_status(Status::Warning, &format!("Could not open file: {}", e));
         }
     }
 
@@ -475,10 +858,10 @@ pub fn create_custom_template(
             .collect::<Vec<_>>()
             .join(", ")
     } else {
-        match template_type.to_lowercase().as_str() {
-            "assignment" => config.note_preferences.assignment_sections.join(", "),
-            _ => config.note_preferences.lecture_sections.join(", "),
-        }
+        config
+            .note_preferences
+            .sections_for(template_type)
+            .join(", ")
     };
 
     if !sections_used.is_empty() {
@@ -502,7 +885,12 @@ pub fn create_custom_template(
 }
 
 /// Generate filename for custom templates
-fn generate_custom_template_filename(course_id: &str, template_type: &str, title: &str) -> String {
+fn generate_custom_template_filename(
+    course_id: &str,
+    template_type: &str,
+    title: &str,
+    extension: &str,
+) -> String {
     use chrono::Local;
 
     let date = Local::now().format("%Y-%m-%d").to_string();
@@ -511,10 +899,10 @@ fn generate_custom_template_filename(course_id: &str, template_type: &str, title
     if !title.is_empty() {
         let title_part = title.replace(' ', "-").to_lowercase();
         format!(
-            "{}-{}-{}-{}.typ",
-            date, course_id, template_part, title_part
+            "{}-{}-{}-{}.{}",
+            date, course_id, template_part, title_part, extension
         )
     } else {
-        format!("{}-{}-{}.typ", date, course_id, template_part)
+        format!("{}-{}-{}.{}", date, course_id, template_part, extension)
     }
 }
@@ -4,13 +4,21 @@
 
 use anyhow::Result;
 use colored::Colorize;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 
-use crate::config::get_config;
+use crate::config::{Config, get_config};
+use crate::core::course_name_check::CourseNameChecker;
+use crate::core::file_operations::FileOperations;
+use crate::core::template::imports::ImportFixer;
 use crate::core::typst_compiler::{CompilationStatus, TypstCompiler};
 use crate::ui::output::{OutputManager, Status};
+use crate::ui::prompts::PromptManager;
 
 pub fn compile_file(filepath: &str) -> Result<()> {
     let config = get_config()?;
+    warn_missing_font_paths(&config);
+    warn_template_version_mismatch(filepath, &config);
 
     OutputManager::print_status(
         Status::Loading,
@@ -24,11 +32,7 @@ pub fn compile_file(filepath: &str) -> Result<()> {
                 &format!("Compiled successfully: {}", output_path.bright_green()),
             );
 
-            // Show file size if available
-            if let Ok(metadata) = std::fs::metadata(&output_path) {
-                let size_kb = metadata.len() / 1024;
-                println!("File size: {} KB", size_kb.to_string().dimmed());
-            }
+            print_output_stats(&output_path);
 
             // Auto-open the compiled PDF if configured to do so
             if config.note_preferences.auto_open_file {
@@ -64,8 +68,49 @@ pub fn compile_file(filepath: &str) -> Result<()> {
     Ok(())
 }
 
-pub fn watch_file(filepath: &str) -> Result<()> {
+/// Compile Typst content piped through stdin to the given output path
+pub fn compile_stdin(output: &str) -> Result<()> {
+    let config = get_config()?;
+    warn_missing_font_paths(&config);
+
+    let mut content = String::new();
+    std::io::stdin().read_to_string(&mut content)?;
+
+    if content.trim().is_empty() {
+        anyhow::bail!("No Typst content received on stdin");
+    }
+
+    let output_path = TypstCompiler::compile_content(&content, Path::new(output), &config)?;
+
+    OutputManager::print_status(
+        Status::Success,
+        &format!("Compiled successfully: {}", output_path.bright_green()),
+    );
+    print_output_stats(&output_path);
+
+    Ok(())
+}
+
+/// "Compiled: 4 pages, 128 KB." for a compiled PDF, just the size when the
+/// page count can't be read
+fn print_output_stats(output_path: &str) {
+    let Ok(metadata) = std::fs::metadata(output_path) else {
+        return;
+    };
+    let size = format!("{} KB", metadata.len() / 1024);
+
+    let stats = match TypstCompiler::pdf_page_count(Path::new(output_path)) {
+        Some(1) => format!("1 page, {}", size),
+        Some(pages) => format!("{} pages, {}", pages, size),
+        None => size,
+    };
+    println!("Compiled: {}.", stats.dimmed());
+}
+
+pub fn watch_file(filepath: &str, open: bool) -> Result<()> {
     let config = get_config()?;
+    warn_missing_font_paths(&config);
+    warn_template_version_mismatch(filepath, &config);
 
     OutputManager::print_status(
         Status::Info,
@@ -74,7 +119,11 @@ pub fn watch_file(filepath: &str) -> Result<()> {
 
     println!("Press {} to stop", "Ctrl+C".yellow());
 
-    match TypstCompiler::watch_file(filepath, &config) {
+    if open {
+        println!("The PDF opens after the first successful compile");
+    }
+
+    match TypstCompiler::watch_file(filepath, &config, open) {
         Ok(_) => {
             OutputManager::print_status(Status::Info, "Watch stopped");
         }
@@ -86,27 +135,237 @@ pub fn watch_file(filepath: &str) -> Result<()> {
     Ok(())
 }
 
-pub fn clean_files() -> Result<()> {
+/// Label of the Obsidian vault in `noter clean --all` reports
+const OBSIDIAN_LABEL: &str = "Obsidian vault";
+
+/// Clean compiled PDFs in the current directory, or in every course and the
+/// Obsidian vault with `all`. Only PDFs a Typst source compiles to are removed.
+pub fn clean_files(all: bool, backups: bool, dry_run: bool) -> Result<()> {
     let config = get_config()?;
 
-    OutputManager::print_status(Status::Loading, "Cleaning compiled files...");
+    if dry_run {
+        return preview_clean(all, backups, &config);
+    }
 
-    match TypstCompiler::clean_files(&config) {
-        Ok(cleaned_count) => {
-            if cleaned_count > 0 {
-                OutputManager::print_status(
-                    Status::Success,
-                    &format!("Cleaned {} PDF files", cleaned_count),
-                );
-            } else {
-                OutputManager::print_status(Status::Info, "No PDF files found to clean");
+    if !all {
+        let current_dir = std::env::current_dir()?;
+        // Refuse before anything is removed
+        let backup_dirs = if backups {
+            backup_dirs(&current_dir, &config)?
+        } else {
+            Vec::new()
+        };
+        OutputManager::print_status(
+            Status::Loading,
+            &format!(
+                "Cleaning compiled files in {}",
+                current_dir.display().to_string().bright_white()
+            ),
+        );
+
+        let cleaned_count = TypstCompiler::clean_current_directory(&current_dir, &config)?;
+        if cleaned_count > 0 {
+            OutputManager::print_status(
+                Status::Success,
+                &format!("Cleaned {} PDF files", cleaned_count),
+            );
+        } else {
+            OutputManager::print_status(Status::Info, "No PDF files found to clean");
+        }
+        if backups {
+            report_pruned_backups(prune_backups(&backup_dirs, &config)?);
+        }
+        println!(
+            "Use {} to clean every course in {}",
+            "noter clean --all".bright_white(),
+            config.paths.notes_dir.bright_white()
+        );
+
+        return Ok(());
+    }
+
+    OutputManager::print_status(Status::Loading, "Cleaning compiled files in all courses...");
+
+    let cleaned = TypstCompiler::clean_all_courses(&config)?;
+    let vault_cleaned = TypstCompiler::clean_obsidian_vault(&config)?;
+    let total: usize = cleaned.iter().map(|(_, count)| count).sum::<usize>() + vault_cleaned;
+
+    if total == 0 {
+        OutputManager::print_status(Status::Info, "No PDF files found to clean");
+    } else {
+        for (course_id, count) in cleaned.iter().filter(|(_, count)| *count > 0) {
+            println!("  {} {} PDF files", course_id.bright_blue(), count);
+        }
+        if vault_cleaned > 0 {
+            println!(
+                "  {} {} PDF files",
+                OBSIDIAN_LABEL.bright_blue(),
+                vault_cleaned
+            );
+        }
+        OutputManager::print_status(
+            Status::Success,
+            &format!(
+                "Cleaned {} PDF files across {} courses",
+                total,
+                cleaned.iter().filter(|(_, count)| *count > 0).count()
+            ),
+        );
+    }
+
+    if backups {
+        let notes_dir = Path::new(&config.paths.notes_dir);
+        let course_dirs: Vec<PathBuf> = cleaned
+            .iter()
+            .map(|(course_id, _)| notes_dir.join(course_id))
+            .collect();
+        report_pruned_backups(prune_backups(&course_dirs, &config)?);
+    }
+
+    Ok(())
+}
+
+/// Directories `noter clean --backups` prunes from `current_dir`: the part of
+/// the course it is in, or every course from the notes directory itself.
+/// Backups outside course directories are never removed.
+fn backup_dirs(current_dir: &Path, config: &Config) -> Result<Vec<PathBuf>> {
+    let notes_dir = Path::new(&config.paths.notes_dir);
+    let outside = || {
+        anyhow::anyhow!(
+            "{} is not inside a course directory of {}, so no backups were removed. Run it from a course, or use 'noter clean --all --backups'",
+            current_dir.display(),
+            notes_dir.display()
+        )
+    };
+
+    let canonical_notes_dir = notes_dir.canonicalize().map_err(|_| outside())?;
+    let canonical_current_dir = current_dir.canonicalize()?;
+    let relative = canonical_current_dir
+        .strip_prefix(&canonical_notes_dir)
+        .map_err(|_| outside())?;
+
+    let course_ids = TypstCompiler::course_ids(notes_dir, config)?;
+    match relative.components().next() {
+        None => Ok(course_ids
+            .iter()
+            .map(|course_id| notes_dir.join(course_id))
+            .collect()),
+        Some(course)
+            if course_ids
+                .iter()
+                .any(|id| course.as_os_str() == id.as_str()) =>
+        {
+            Ok(vec![current_dir.to_path_buf()])
+        }
+        Some(_) => Err(outside()),
+    }
+}
+
+/// Remove the backups under each of `dirs`, returning the files removed and bytes freed
+fn prune_backups(dirs: &[PathBuf], config: &Config) -> Result<(usize, u64)> {
+    let (mut removed, mut freed) = (0, 0);
+    for dir in dirs {
+        let (count, bytes) = FileOperations::prune_backups(dir, config.paths.follow_symlinks)?;
+        removed += count;
+        freed += bytes;
+    }
+    Ok((removed, freed))
+}
+
+fn report_pruned_backups((removed, freed): (usize, u64)) {
+    if removed == 0 {
+        OutputManager::print_status(Status::Info, "No backup files found to clean");
+    } else {
+        OutputManager::print_status(
+            Status::Success,
+            &format!(
+                "Removed {} backup files ({})",
+                removed,
+                FileOperations::format_file_size(freed)
+            ),
+        );
+    }
+}
+
+/// List the files `noter clean` would remove with their sizes and the total
+/// space reclaimed, without deleting anything
+fn preview_clean(all: bool, backups: bool, config: &Config) -> Result<()> {
+    let (base, groups) = if all {
+        let notes_dir = PathBuf::from(&config.paths.notes_dir);
+        let mut groups = TypstCompiler::all_compiled_outputs(config)?
+            .into_iter()
+            .map(|(course_id, mut files)| {
+                if backups {
+                    files.extend(FileOperations::find_backups(
+                        &notes_dir.join(&course_id),
+                        config.paths.follow_symlinks,
+                    )?);
+                }
+                Ok((course_id, files))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // A vault inside the notes directory shares files with the courses
+        let mut vault_files = TypstCompiler::obsidian_outputs(config)?;
+        vault_files.retain(|file| !groups.iter().any(|(_, files)| files.contains(file)));
+        groups.push((OBSIDIAN_LABEL.to_string(), vault_files));
+        (notes_dir, groups)
+    } else {
+        let current_dir = std::env::current_dir()?;
+        let mut files = TypstCompiler::directory_outputs(&current_dir, config)?;
+        if backups {
+            for dir in backup_dirs(&current_dir, config)? {
+                files.extend(FileOperations::find_backups(
+                    &dir,
+                    config.paths.follow_symlinks,
+                )?);
             }
         }
-        Err(_e) => {
-            // Silently ignore errors when cleaning PDFs
+        let label = current_dir.display().to_string();
+        (current_dir, vec![(label, files)])
+    };
+
+    let file_count: usize = groups.iter().map(|(_, files)| files.len()).sum();
+    if file_count == 0 {
+        OutputManager::print_status(Status::Info, "Nothing to clean. No files were removed");
+        return Ok(());
+    }
+
+    OutputManager::print_section("Files To Remove", Some("🔍"));
+    let mut reclaimable = 0;
+    for (label, files) in groups.iter().filter(|(_, files)| !files.is_empty()) {
+        let size = FileOperations::total_size(files);
+        reclaimable += size;
+        println!(
+            "{} ({} files, {})",
+            label.bright_blue(),
+            files.len(),
+            FileOperations::format_file_size(size)
+        );
+
+        for file in files {
+            let relative_path = file.strip_prefix(&base).unwrap_or(file).display();
+            println!(
+                "  • {} {}",
+                relative_path,
+                FileOperations::format_file_size(FileOperations::total_size(std::slice::from_ref(
+                    file
+                )))
+                .dimmed()
+            );
         }
+        println!();
     }
 
+    OutputManager::print_status(
+        Status::Info,
+        &format!(
+            "Dry run: {} files would reclaim {}. No files were removed",
+            file_count,
+            FileOperations::format_file_size(reclaimable).bright_white()
+        ),
+    );
+
     Ok(())
 }
 
@@ -364,3 +623,177 @@ pub fn check_all_files(detailed: bool) -> Result<()> {
 
     Ok(())
 }
+
+/// List notes and assignments without a PDF, grouped by course, and offer to
+/// compile them
+pub fn check_uncompiled() -> Result<()> {
+    let config = get_config()?;
+    let uncompiled = TypstCompiler::uncompiled_sources(&config)?;
+
+    if uncompiled.is_empty() {
+        OutputManager::print_status(Status::Success, "Every note has a PDF");
+        return Ok(());
+    }
+
+    OutputManager::print_section("Uncompiled Notes", Some("🔴"));
+    for (course_id, sources) in &uncompiled {
+        let course_name = config.courses.get(course_id).cloned().unwrap_or_default();
+        println!(
+            "{} {} ({})",
+            course_id.bright_blue(),
+            course_name.dimmed(),
+            sources.len()
+        );
+        for source in sources {
+            let relative_path = source
+                .strip_prefix(&config.paths.notes_dir)
+                .unwrap_or(source)
+                .display()
+                .to_string();
+            println!("  • {}", relative_path);
+        }
+        println!();
+    }
+
+    let total: usize = uncompiled.iter().map(|(_, sources)| sources.len()).sum();
+    if !PromptManager::confirm(&format!("Compile {} file(s) now", total), Some(false))? {
+        println!(
+            "Compile them later with {}",
+            "noter compile <file>".bright_white()
+        );
+        return Ok(());
+    }
+
+    warn_missing_font_paths(&config);
+    let mut failed = 0;
+    for source in uncompiled.iter().flat_map(|(_, sources)| sources) {
+        let filepath = source.to_string_lossy();
+        match TypstCompiler::compile_file(&filepath, &config) {
+            Ok(output_path) => OutputManager::print_status(
+                Status::Success,
+                &format!("Compiled {}", output_path.bright_green()),
+            ),
+            Err(e) => {
+                failed += 1;
+                OutputManager::print_status(
+                    Status::Error,
+                    &format!("Failed to compile {}: {}", filepath, e),
+                );
+            }
+        }
+    }
+
+    println!();
+    if failed == 0 {
+        OutputManager::print_status(Status::Complete, &format!("Compiled {} file(s)", total));
+    } else {
+        OutputManager::print_status(
+            Status::Warning,
+            &format!("Compiled {} of {} file(s)", total - failed, total),
+        );
+    }
+
+    Ok(())
+}
+
+/// List notes whose header course name differs from the configured one, and
+/// offer to update them
+pub fn check_course_names() -> Result<()> {
+    let config = get_config()?;
+    let mismatches = CourseNameChecker::find_mismatches(&config)?;
+
+    if mismatches.is_empty() {
+        OutputManager::print_status(
+            Status::Success,
+            "Every note header matches its configured course name",
+        );
+        return Ok(());
+    }
+
+    OutputManager::print_section("Outdated Course Names", Some("🔴"));
+    let mut current_course: Option<&str> = None;
+    for mismatch in &mismatches {
+        if current_course != Some(mismatch.course_id.as_str()) {
+            if current_course.is_some() {
+                println!();
+            }
+            current_course = Some(&mismatch.course_id);
+            println!(
+                "{} {}",
+                mismatch.course_id.bright_blue(),
+                mismatch.expected.dimmed()
+            );
+        }
+
+        let relative_path = mismatch
+            .path
+            .strip_prefix(&config.paths.notes_dir)
+            .unwrap_or(&mismatch.path)
+            .display()
+            .to_string();
+        println!(
+            "  • {}: \"{}\" → \"{}\"",
+            relative_path,
+            mismatch.found.yellow(),
+            mismatch.expected.green()
+        );
+    }
+    println!();
+
+    let total = mismatches.len();
+    if !PromptManager::confirm(&format!("Update {} note(s)", total), Some(false))? {
+        println!(
+            "Update them later with {}",
+            "noter check --course-names".bright_white()
+        );
+        return Ok(());
+    }
+
+    let mut updated = 0;
+    for mismatch in &mismatches {
+        match CourseNameChecker::fix(mismatch) {
+            Ok(true) => updated += 1,
+            Ok(false) => {}
+            Err(e) => OutputManager::print_status(
+                Status::Error,
+                &format!("Failed to update {}: {}", mismatch.path.display(), e),
+            ),
+        }
+    }
+
+    if updated == total {
+        OutputManager::print_status(Status::Complete, &format!("Updated {} note(s)", updated));
+    } else {
+        OutputManager::print_status(
+            Status::Warning,
+            &format!("Updated {} of {} note(s)", updated, total),
+        );
+    }
+
+    Ok(())
+}
+
+/// Warn when a note imports a template version that isn't installed
+fn warn_template_version_mismatch(filepath: &str, config: &Config) {
+    let Ok(content) = TypstCompiler::resolve_input_path(filepath, config)
+        .and_then(|path| Ok(std::fs::read_to_string(path)?))
+    else {
+        return;
+    };
+
+    if let Ok(reconciled) = ImportFixer::reconcile_template_config(config, &content) {
+        for warning in reconciled.warnings() {
+            OutputManager::print_status(Status::Warning, &warning);
+        }
+    }
+}
+
+/// Warn about configured font directories that don't exist
+fn warn_missing_font_paths(config: &Config) {
+    for dir in TypstCompiler::missing_font_paths(config) {
+        OutputManager::print_status(
+            Status::Warning,
+            &format!("Font directory not found, skipping: {}", dir),
+        );
+    }
+}
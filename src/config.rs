@@ -1,7 +1,10 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use crate::core::git_integration::GitIntegration;
+use crate::core::process::{DEFAULT_TIMEOUT_SECONDS, ProcessRunner};
 
 /// Current config version - increment when making breaking changes
 ///
@@ -10,7 +13,16 @@ use std::path::PathBuf;
 ///
 /// Version history:
 /// - `1.0.0`: Initial versioned config with automatic migration system
-const CONFIG_VERSION: &str = "1.0.0";
+const CONFIG_VERSION: &str = "1.1.0";
+
+/// Placeholder author of a fresh configuration
+pub const DEFAULT_AUTHOR: &str = "Your Name";
+
+/// Subdirectory of a course directory holding lecture notes
+pub const LECTURES_DIR: &str = "lectures";
+
+/// Subdirectory of a course directory holding assignments
+pub const ASSIGNMENTS_DIR: &str = "assignments";
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(default)]
@@ -27,6 +39,12 @@ pub struct Config {
     /// Semester format preference
     pub semester_format: SemesterFormat,
 
+    /// Months at which the spring and fall semesters begin
+    pub semester_boundaries: SemesterBoundaries,
+
+    /// First day of the week for weekly summaries
+    pub week_start: chrono::Weekday,
+
     /// Default note structure preferences
     pub note_preferences: NotePreferences,
 
@@ -42,14 +60,37 @@ pub struct Config {
     /// Search preferences
     pub search: SearchConfig,
 
+    /// Day thresholds used by course and assignment health analysis
+    pub health_thresholds: HealthThresholds,
+
+    /// Days of recent work counted for the most active course on the status
+    /// dashboard (0 = all time)
+    pub activity_window_days: u64,
+
+    /// Seconds Typst and git may run before they are stopped (0 = no timeout)
+    pub external_command_timeout_seconds: u64,
+
     /// User's DTU courses
     pub courses: std::collections::HashMap<String, String>,
 
+    /// Course shorthands mapping alias -> course ID (e.g. "introprog" -> "02101")
+    pub course_aliases: std::collections::HashMap<String, String>,
+
     /// Obsidian integration settings
     pub obsidian_integration: ObsidianIntegrationConfig,
 
     /// Metadata (Not used by user)
     pub metadata: Metadata,
+
+    /// `paths` as written in the config file and as resolved by
+    /// [`Config::resolve_paths`], so saving writes back the values from the file
+    #[serde(skip)]
+    pub(crate) unresolved_paths: Option<(PathConfig, PathConfig)>,
+
+    /// Editor given with `--editor` for this invocation, used instead of
+    /// `preferred_editor`. Never saved to the config file.
+    #[serde(skip)]
+    pub editor_override: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -84,14 +125,107 @@ pub struct NotePreferences {
     /// Include date in lecture note titles
     pub include_date_in_title: bool,
 
-    /// Default sections for lecture notes
-    pub lecture_sections: Vec<String>,
+    /// Default sections keyed by template type (e.g. "lecture", "assignment", "lab-report")
+    pub section_presets: std::collections::HashMap<String, Vec<String>>,
+
+    /// Typst placeholder content under a section heading, keyed by section name.
+    /// Takes precedence over the template package's scaffolding.
+    pub section_scaffolding: std::collections::HashMap<String, String>,
 
-    /// Default sections for assignments
-    pub assignment_sections: Vec<String>,
+    /// Template type `note` creates for a course ID when no `--type` is given,
+    /// e.g. "reading". Courses not listed get lecture notes.
+    pub course_note_types: std::collections::HashMap<String, String>,
 
     /// Whether to create backup of existing files
     pub create_backups: bool,
+
+    /// Commit newly created notes and assignments when the notes directory is a git repo
+    pub git_auto_commit: bool,
+
+    /// How lecture note files are named
+    pub lecture_numbering: LectureNumbering,
+
+    /// Days after creation an assignment is due when no due date is given
+    pub default_due_days: u32,
+
+    /// Longest generated note or assignment filename, extension included.
+    /// Longer titles are truncated and suffixed with a short hash; 0 disables the limit.
+    pub max_filename_length: usize,
+
+    /// What to do when a new assignment's file already exists
+    pub on_conflict: ConflictPolicy,
+
+    /// Write a README into a course's assignments directory when it's created
+    pub create_assignment_readme: bool,
+
+    /// Markdown file used for assignment directory READMEs, with `{{course_id}}`,
+    /// `{{course_name}}` and `{{semester}}` placeholders. Built-in text when unset.
+    pub assignment_readme_template: Option<String>,
+
+    /// Pre-1.1.0 lecture sections, folded into `section_presets` on migration
+    #[serde(rename = "lecture_sections", skip_serializing)]
+    pub(crate) legacy_lecture_sections: Option<Vec<String>>,
+
+    /// Pre-1.1.0 assignment sections, folded into `section_presets` on migration
+    #[serde(rename = "assignment_sections", skip_serializing)]
+    pub(crate) legacy_assignment_sections: Option<Vec<String>>,
+}
+
+impl NotePreferences {
+    /// Preset key used when a template type has no preset of its own
+    pub const FALLBACK_PRESET: &'static str = "lecture";
+
+    /// Template type `note` creates unless told otherwise
+    pub const DEFAULT_NOTE_TYPE: &'static str = "lecture";
+
+    /// Template type for new notes in a course: the explicit type if given,
+    /// then the course's `course_note_types` entry, then lecture
+    pub fn note_type_for<'a>(&'a self, course_id: &str, explicit: Option<&'a str>) -> &'a str {
+        explicit
+            .or_else(|| self.course_note_types.get(course_id).map(String::as_str))
+            .unwrap_or(Self::DEFAULT_NOTE_TYPE)
+    }
+
+    /// Get the default sections for a template type.
+    ///
+    /// Looks up the type name directly, then its canonical name (so `note` and
+    /// `l` resolve to `lecture`). Types with a built-in preset the config
+    /// doesn't have yet (e.g. `group` in older configs) use the built-in one;
+    /// anything else falls back to the lecture preset.
+    pub fn sections_for(&self, template_type: &str) -> Vec<String> {
+        let template_type = template_type.to_lowercase();
+        let canonical = Self::canonical_preset_name(&template_type);
+
+        self.section_presets
+            .get(&template_type)
+            .or_else(|| self.section_presets.get(canonical))
+            .cloned()
+            .or_else(|| Self::default().section_presets.remove(canonical))
+            .or_else(|| self.section_presets.get(Self::FALLBACK_PRESET).cloned())
+            .unwrap_or_default()
+    }
+
+    /// Map template type aliases onto their preset key
+    fn canonical_preset_name(template_type: &str) -> &str {
+        match template_type {
+            "note" | "l" => "lecture",
+            "a" => "assignment",
+            "lab" => "lab-report",
+            "project" => "thesis",
+            other => other,
+        }
+    }
+
+    /// Move pre-1.1.0 `lecture_sections`/`assignment_sections` into `section_presets`
+    fn migrate_legacy_sections(&mut self) {
+        if let Some(sections) = self.legacy_lecture_sections.take() {
+            self.section_presets.insert("lecture".to_string(), sections);
+        }
+        if let Some(sections) = self.legacy_assignment_sections.take() {
+            self.section_presets
+                .insert("assignment".to_string(), sections);
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -108,6 +242,23 @@ pub struct PathConfig {
 
     /// Typst packages directory
     pub typst_packages_dir: String,
+
+    /// Keep lecture notes directly in `<notes_dir>/<course_id>/` instead of a
+    /// `lectures/` subdirectory. Assignments stay in `assignments/`.
+    pub flat_layout: bool,
+
+    /// Scan symlinked course directories and subdirectories. Each directory
+    /// is scanned once, so link cycles are harmless.
+    pub follow_symlinks: bool,
+
+    /// Course catalog file (.json or .csv) supplementing the built-in DTU
+    /// course list; see [`CourseCatalog`](crate::core::course_catalog::CourseCatalog)
+    pub course_catalog: Option<String>,
+
+    /// Create a missing course directory when a note or assignment is
+    /// written into it. When off, creation fails instead, so a mistyped path
+    /// doesn't silently gain a new tree; `noter setup` still creates them.
+    pub create_missing_dirs: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -122,11 +273,98 @@ pub struct UserTemplateConfig {
     /// Cache templates locally for faster access
     pub enable_caching: bool,
 
-    /// Auto-update templates on startup
+    /// Auto-update templates before creating notes when a newer release is available
     pub auto_update: bool,
 
+    /// Never contact GitHub for update checks; only cached release info is used
+    pub offline: bool,
+
+    /// How long cached release info is trusted before checking GitHub again
+    pub update_check_interval_hours: u64,
+
     /// Template preference order (repository names)
     pub preference_order: Vec<String>,
+
+    /// Where template packages are looked up, first match wins. A package
+    /// found in an earlier location shadows every version of it in later ones.
+    pub resolution_order: Vec<TemplateLocation>,
+
+    /// Directory `template create` writes custom templates to: relative to the
+    /// course directory, or absolute
+    pub custom_template_dir: String,
+
+    /// How generated notes import the template package
+    pub import_style: ImportStyle,
+
+    /// Template package versions to use instead of the newest installed one,
+    /// keyed by package name. Pinned packages are never auto-updated.
+    pub pinned_versions: std::collections::HashMap<String, String>,
+}
+
+impl UserTemplateConfig {
+    /// Version `package` is pinned to. Repository names such as `dtu_template`
+    /// match the package they install, `dtu-template`.
+    pub fn pinned_version(&self, package: &str) -> Option<&str> {
+        self.pinned_entry(package)
+            .map(|(_, version)| version.as_str())
+    }
+
+    /// Whether two names refer to the same template package, ignoring case
+    /// and `_` versus `-`
+    pub fn same_package(a: &str, b: &str) -> bool {
+        let normalize = |name: &str| name.trim().to_lowercase().replace('_', "-");
+        normalize(a) == normalize(b)
+    }
+
+    /// The `pinned_versions` key and version `package` is pinned under
+    fn pinned_entry(&self, package: &str) -> Option<(&String, &String)> {
+        self.pinned_versions
+            .iter()
+            .find(|(pinned, _)| Self::same_package(pinned, package))
+    }
+}
+
+/// Form of the `#import` line at the top of generated notes
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportStyle {
+    /// `#import "@local/pkg:ver":*`, importing everything the package exports
+    #[default]
+    Glob,
+    /// `#import "@local/pkg:ver": lecture-template`, importing only the
+    /// template function the note calls and the functions its section
+    /// scaffolding calls
+    Explicit,
+}
+
+/// A directory template packages are discovered in
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TemplateLocation {
+    /// The Typst local packages directory (`paths.typst_packages_dir`)
+    Packages,
+    /// The project templates directory (`paths.templates_dir`)
+    Project,
+}
+
+impl TemplateLocation {
+    pub const DEFAULT_ORDER: [TemplateLocation; 2] =
+        [TemplateLocation::Packages, TemplateLocation::Project];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TemplateLocation::Packages => "packages",
+            TemplateLocation::Project => "project",
+        }
+    }
+
+    /// The directory this location refers to
+    pub fn directory(self, config: &Config) -> &Path {
+        match self {
+            TemplateLocation::Packages => Path::new(&config.paths.typst_packages_dir),
+            TemplateLocation::Project => Path::new(&config.paths.templates_dir),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -135,9 +373,14 @@ pub struct TemplateRepository {
     /// Display name for the repository
     pub name: String,
 
-    /// GitHub repository in format "owner/repo"
+    /// GitHub repository in format "owner/repo", or a full repository URL
+    /// (e.g. "https://gitlab.com/group/templates")
     pub repository: String,
 
+    /// Host to fetch from when `repository` is given as "owner/repo"
+    /// (e.g. "gitlab.gbar.dtu.dk"); defaults to GitHub
+    pub host: Option<String>,
+
     /// Specific version/tag to use (None for latest)
     pub version: Option<String>,
 
@@ -204,6 +447,7 @@ impl Default for TemplateRepository {
         Self {
             name: String::new(),
             repository: String::new(),
+            host: None,
             version: None,
             branch: None,
             template_path: None,
@@ -212,6 +456,26 @@ impl Default for TemplateRepository {
     }
 }
 
+impl UserTemplateConfig {
+    /// Locations to search in order, without repeats. An empty
+    /// `resolution_order` falls back to the default order.
+    pub fn template_locations(&self) -> Vec<TemplateLocation> {
+        let order: &[TemplateLocation] = if self.resolution_order.is_empty() {
+            &TemplateLocation::DEFAULT_ORDER
+        } else {
+            &self.resolution_order
+        };
+
+        let mut locations = Vec::new();
+        for location in order {
+            if !locations.contains(location) {
+                locations.push(*location);
+            }
+        }
+        locations
+    }
+}
+
 impl Default for UserTemplateConfig {
     fn default() -> Self {
         Self {
@@ -219,7 +483,13 @@ impl Default for UserTemplateConfig {
             use_official_fallback: true,
             enable_caching: true,
             auto_update: false,
+            offline: false,
+            update_check_interval_hours: 24,
             preference_order: vec!["official".to_string()],
+            resolution_order: TemplateLocation::DEFAULT_ORDER.to_vec(),
+            custom_template_dir: "templates".to_string(),
+            import_style: ImportStyle::Glob,
+            pinned_versions: std::collections::HashMap::new(),
         }
     }
 }
@@ -239,6 +509,10 @@ impl Default for PathConfig {
                 .join("typst/packages/local")
                 .to_string_lossy()
                 .to_string(),
+            flat_layout: false,
+            follow_symlinks: true,
+            course_catalog: None,
+            create_missing_dirs: true,
         }
     }
 }
@@ -251,11 +525,67 @@ impl PathConfig {
         self.notes_dir = Self::resolve_path(&self.notes_dir, &current_dir)?;
         self.obsidian_dir = Self::resolve_path(&self.obsidian_dir, &current_dir)?;
         self.templates_dir = Self::resolve_path(&self.templates_dir, &current_dir)?;
+        self.typst_packages_dir = Self::resolve_path(&self.typst_packages_dir, &current_dir)?;
+        if let Some(catalog) = &self.course_catalog {
+            self.course_catalog = Some(Self::resolve_path(catalog, &current_dir)?);
+        }
 
         Ok(())
     }
 
+    /// These paths with every field still at its `resolved` value replaced by
+    /// the `unresolved` one it was resolved from
+    fn restore_unresolved(&self, unresolved: &PathConfig, resolved: &PathConfig) -> PathConfig {
+        let restore = |current: &String, unresolved: &String, resolved: &String| {
+            if current == resolved {
+                unresolved.clone()
+            } else {
+                current.clone()
+            }
+        };
+
+        PathConfig {
+            notes_dir: restore(&self.notes_dir, &unresolved.notes_dir, &resolved.notes_dir),
+            obsidian_dir: restore(
+                &self.obsidian_dir,
+                &unresolved.obsidian_dir,
+                &resolved.obsidian_dir,
+            ),
+            templates_dir: restore(
+                &self.templates_dir,
+                &unresolved.templates_dir,
+                &resolved.templates_dir,
+            ),
+            typst_packages_dir: restore(
+                &self.typst_packages_dir,
+                &unresolved.typst_packages_dir,
+                &resolved.typst_packages_dir,
+            ),
+            course_catalog: if self.course_catalog == resolved.course_catalog {
+                unresolved.course_catalog.clone()
+            } else {
+                self.course_catalog.clone()
+            },
+            ..self.clone()
+        }
+    }
+
+    /// Every configured directory resolved against `base` the same way
+    /// [`resolve_paths`](Self::resolve_paths) does, keyed by field name
+    pub fn resolved_dirs(&self, base: &Path) -> Result<Vec<(&'static str, String)>> {
+        [
+            ("notes_dir", &self.notes_dir),
+            ("obsidian_dir", &self.obsidian_dir),
+            ("templates_dir", &self.templates_dir),
+            ("typst_packages_dir", &self.typst_packages_dir),
+        ]
+        .into_iter()
+        .map(|(field, path)| Ok((field, Self::resolve_path(path, base)?)))
+        .collect()
+    }
+
     fn resolve_path(path: &str, base: &std::path::Path) -> Result<String> {
+        let path = &Self::expand_env_vars(path, cfg!(windows), |name| std::env::var(name).ok())?;
         let path_buf = if std::path::Path::new(path).is_absolute() {
             std::path::PathBuf::from(path)
         } else {
@@ -289,22 +619,135 @@ impl PathConfig {
             .to_string_lossy()
             .to_string())
     }
+
+    /// Replace environment variables in `path` with their values: `%VAR%` on
+    /// Windows, `$VAR` and `${VAR}` elsewhere. A `$` or `%` that doesn't start
+    /// a variable is kept as is.
+    fn expand_env_vars(
+        path: &str,
+        windows: bool,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<String> {
+        let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+        let value = |name: &str| {
+            lookup(name).with_context(|| {
+                format!(
+                    "Environment variable {} used in path '{}' is not set",
+                    name, path
+                )
+            })
+        };
+
+        let mut expanded = String::with_capacity(path.len());
+        let mut rest = path;
+        let marker = if windows { '%' } else { '$' };
+        while let Some(start) = rest.find(marker) {
+            expanded.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+
+            let (name, consumed) = if windows {
+                match after.find('%') {
+                    Some(end) if end > 0 && after[..end].chars().all(is_name_char) => {
+                        (&after[..end], end + 1)
+                    }
+                    _ => ("", 0),
+                }
+            } else if let Some(braced) = after.strip_prefix('{') {
+                let end = braced
+                    .find('}')
+                    .with_context(|| format!("Unclosed '${{' in path '{}'", path))?;
+                (&braced[..end], end + 2)
+            } else {
+                let end = after.find(|c| !is_name_char(c)).unwrap_or(after.len());
+                (&after[..end], end)
+            };
+
+            if name.is_empty() {
+                expanded.push(marker);
+            } else {
+                expanded.push_str(&value(name)?);
+            }
+            rest = &after[consumed..];
+        }
+        expanded.push_str(rest);
+
+        Ok(expanded)
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(default)]
 pub struct TypstConfig {
     /// Additional compile arguments
     pub compile_args: Vec<String>,
 
+    /// Extra compile arguments per course ID, appended after `compile_args`
+    /// when the compiled file belongs to that course
+    pub course_compile_args: std::collections::HashMap<String, Vec<String>>,
+
     /// Watch mode arguments
     pub watch_args: Vec<String>,
 
+    /// Font directories passed as `--font-path` to every compile and watch
+    pub font_paths: Vec<String>,
+
     /// Whether to clean PDFs before compiling
     pub clean_before_compile: bool,
 
     /// Output directory for PDFs (relative to source)
     pub output_dir: Option<String>,
+
+    /// File extensions recognized as Typst sources when scanning and compiling.
+    /// New notes are created with the first extension, `.typ` by default.
+    pub source_extensions: Vec<String>,
+}
+
+impl Default for TypstConfig {
+    fn default() -> Self {
+        Self {
+            compile_args: Vec::new(),
+            course_compile_args: std::collections::HashMap::new(),
+            watch_args: Vec::new(),
+            font_paths: Vec::new(),
+            clean_before_compile: false,
+            output_dir: None,
+            source_extensions: vec!["typ".to_string(), "typst".to_string()],
+        }
+    }
+}
+
+impl TypstConfig {
+    /// Recognized source extensions without a leading dot, falling back to `typ`
+    pub fn source_extensions(&self) -> Vec<&str> {
+        let extensions: Vec<&str> = self
+            .source_extensions
+            .iter()
+            .map(|ext| ext.trim_start_matches('.'))
+            .filter(|ext| !ext.is_empty())
+            .collect();
+
+        if extensions.is_empty() {
+            vec!["typ"]
+        } else {
+            extensions
+        }
+    }
+
+    /// Extension new notes are created with: the first recognized one
+    pub fn new_file_extension(&self) -> &str {
+        self.source_extensions()[0]
+    }
+
+    /// Check whether a path has one of the recognized source extensions
+    pub fn is_source_file(&self, path: &std::path::Path) -> bool {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| {
+                self.source_extensions()
+                    .iter()
+                    .any(|source_ext| source_ext.eq_ignore_ascii_case(ext))
+            })
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -321,6 +764,139 @@ pub struct SearchConfig {
 
     /// File extensions to search in
     pub file_extensions: Vec<String>,
+
+    /// Order of the results, applied before `max_results` cuts them off
+    pub result_order: ResultOrder,
+}
+
+/// How search results are ordered
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResultOrder {
+    /// By file path, then line number
+    #[default]
+    FileThenLine,
+    /// Files with the most matches first
+    Relevance,
+    /// Most recently modified files first
+    Modified,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct HealthThresholds {
+    /// Maximum days since the last activity for a course to be "Excellent"
+    pub excellent_days: u64,
+
+    /// Maximum days since the last activity for a course to be "Good"
+    pub good_days: u64,
+
+    /// Maximum days since the last activity before a course becomes "Critical"
+    pub warning_days: u64,
+}
+
+impl Default for HealthThresholds {
+    fn default() -> Self {
+        Self {
+            excellent_days: 3,
+            good_days: 7,
+            warning_days: 14,
+        }
+    }
+}
+
+impl HealthThresholds {
+    /// Ensure the thresholds are strictly increasing
+    pub fn validate(&self) -> Result<()> {
+        if self.excellent_days >= self.good_days || self.good_days >= self.warning_days {
+            anyhow::bail!(
+                "Health thresholds must be increasing: excellent_days ({}) < good_days ({}) < warning_days ({})",
+                self.excellent_days,
+                self.good_days,
+                self.warning_days
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LectureNumbering {
+    /// "2025-03-12-02101-lecture.typ"
+    #[default]
+    Date,
+    /// "lecture_06.typ", numbered after the highest existing lecture
+    Sequential,
+}
+
+/// How to resolve a new file whose name is already taken
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictPolicy {
+    /// Ask whether to keep both, overwrite or cancel. Falls back to `suffix`
+    /// when not running interactively.
+    #[default]
+    Prompt,
+    /// Keep the existing file and write the new one as `<name>-1.typ`, `<name>-2.typ`, ...
+    Suffix,
+    /// Replace the existing file, backing it up first when backups are enabled
+    Overwrite,
+    /// Stop with an error
+    Error,
+}
+
+/// Academic calendar boundaries used to decide which semester a date falls in.
+/// Months from `spring_start_month` up to `fall_start_month` belong to that
+/// year's spring semester, later months to its fall semester, and months before
+/// `spring_start_month` to the previous year's fall semester (e.g. January
+/// exams with `spring_start_month: 2`).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct SemesterBoundaries {
+    /// First month (1-12) of the spring semester
+    pub spring_start_month: u32,
+
+    /// First month (1-12) of the fall semester
+    pub fall_start_month: u32,
+}
+
+impl Default for SemesterBoundaries {
+    fn default() -> Self {
+        Self {
+            spring_start_month: 1,
+            fall_start_month: 7,
+        }
+    }
+}
+
+impl SemesterBoundaries {
+    /// Ensure both months are valid and spring starts before fall
+    pub fn validate(&self) -> Result<()> {
+        let valid = |month: u32| (1..=12).contains(&month);
+        if !valid(self.spring_start_month)
+            || !valid(self.fall_start_month)
+            || self.spring_start_month >= self.fall_start_month
+        {
+            anyhow::bail!(
+                "Semester boundaries must be months 1-12 with spring_start_month ({}) < fall_start_month ({})",
+                self.spring_start_month,
+                self.fall_start_month
+            );
+        }
+        Ok(())
+    }
+
+    /// Academic year and season of `date`, as `(year, is_spring)`
+    pub fn season_for(&self, date: chrono::NaiveDate) -> (i32, bool) {
+        use chrono::Datelike;
+
+        let (year, month) = (date.year(), date.month());
+        if month < self.spring_start_month {
+            (year - 1, false)
+        } else {
+            (year, month < self.fall_start_month)
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -356,18 +932,26 @@ impl Default for Config {
         }
 
         Self {
-            author: "Your Name".to_string(),
+            author: DEFAULT_AUTHOR.to_string(),
             preferred_editor: None,
             template_version: env!("CARGO_PKG_VERSION").to_string(),
             semester_format: SemesterFormat::YearSeason,
+            semester_boundaries: SemesterBoundaries::default(),
+            week_start: chrono::Weekday::Mon,
             note_preferences: NotePreferences::default(),
             paths: PathConfig::default(),
             templates: UserTemplateConfig::default(),
             typst: TypstConfig::default(),
             search: SearchConfig::default(),
+            health_thresholds: HealthThresholds::default(),
+            activity_window_days: 30,
+            external_command_timeout_seconds: DEFAULT_TIMEOUT_SECONDS,
             courses: default_courses,
+            course_aliases: std::collections::HashMap::new(),
             obsidian_integration: ObsidianIntegrationConfig::default(),
             metadata: Metadata::default(),
+            unresolved_paths: None,
+            editor_override: None,
         }
     }
 }
@@ -378,21 +962,49 @@ impl Default for NotePreferences {
             auto_open_file: true,
             auto_open_dir: false,
             include_date_in_title: true,
-            lecture_sections: vec![
-                "Key Concepts".to_string(),
-                "Mathematical Framework".to_string(),
-                "Examples".to_string(),
-                "Important Points".to_string(),
-                "Questions & Follow-up".to_string(),
-                "Connections to Previous Material".to_string(),
-                "Next Class Preview".to_string(),
-            ],
-            assignment_sections: vec![
-                "Problem 1".to_string(),
-                "Problem 2".to_string(),
-                "Problem 3".to_string(),
-            ],
+            section_presets: std::collections::HashMap::from([
+                (
+                    "lecture".to_string(),
+                    vec![
+                        "Key Concepts".to_string(),
+                        "Mathematical Framework".to_string(),
+                        "Examples".to_string(),
+                        "Important Points".to_string(),
+                        "Questions & Follow-up".to_string(),
+                        "Connections to Previous Material".to_string(),
+                        "Next Class Preview".to_string(),
+                    ],
+                ),
+                (
+                    "assignment".to_string(),
+                    vec![
+                        "Problem 1".to_string(),
+                        "Problem 2".to_string(),
+                        "Problem 3".to_string(),
+                    ],
+                ),
+                (
+                    "group".to_string(),
+                    vec![
+                        "Project Overview".to_string(),
+                        "Roles & Responsibilities".to_string(),
+                        "Meeting Notes".to_string(),
+                        "Tasks & Deadlines".to_string(),
+                    ],
+                ),
+            ]),
+            section_scaffolding: std::collections::HashMap::new(),
+            course_note_types: std::collections::HashMap::new(),
             create_backups: false,
+            git_auto_commit: false,
+            lecture_numbering: LectureNumbering::default(),
+            default_due_days: 7,
+            max_filename_length: 100,
+            on_conflict: ConflictPolicy::default(),
+            create_assignment_readme: false,
+            assignment_readme_template: None,
+            legacy_lecture_sections: None,
+            legacy_assignment_sections: None,
         }
     }
 }
@@ -403,7 +1015,8 @@ impl Default for SearchConfig {
             max_results: 50,
             context_lines: 2,
             case_sensitive: false,
-            file_extensions: vec!["typ".to_string(), "md".to_string()],
+            file_extensions: vec!["typ".to_string(), "typst".to_string(), "md".to_string()],
+            result_order: ResultOrder::default(),
         }
     }
 }
@@ -425,13 +1038,24 @@ impl Config {
     /// # Examples
     ///
     /// ```no_run
-    /// use dtu_notes::config::Config;
+    /// use noter::config::Config;
     ///
     /// let config = Config::load()?;
     /// println!("Author: {}", config.author);
     /// # Ok::<(), anyhow::Error>(())
     /// ```
     pub fn load() -> Result<Self> {
+        let mut config = Self::load_unresolved()?;
+
+        // Resolve relative paths to absolute paths
+        config.resolve_paths()?;
+        Ok(config)
+    }
+
+    /// Load the configuration with `paths` exactly as written in the config
+    /// file, for commands that edit the file itself. Unlike [`Config::load`]
+    /// this doesn't fail when a path uses an unset environment variable.
+    pub fn load_unresolved() -> Result<Self> {
         let config_path = Self::config_file_path()?;
 
         let mut config = if config_path.exists() {
@@ -472,11 +1096,36 @@ impl Config {
             config
         };
 
-        // Resolve relative paths to absolute paths
-        config.paths.resolve_paths()?;
+        ProcessRunner::set_timeout_seconds(config.external_command_timeout_seconds);
+        config.fill_default_author(GitIntegration::user_name);
         Ok(config)
     }
 
+    /// Resolve `paths` to absolute paths with environment variables expanded.
+    /// [`Config::save`] still writes the paths as they were before, except
+    /// those changed since. On error the paths are left unresolved.
+    pub fn resolve_paths(&mut self) -> Result<()> {
+        let mut resolved = self.paths.clone();
+        resolved.resolve_paths()?;
+
+        let unresolved = std::mem::replace(&mut self.paths, resolved.clone());
+        self.unresolved_paths = Some((unresolved, resolved));
+        Ok(())
+    }
+
+    /// Replace the placeholder author with `git_user_name`, which is only
+    /// called while the author is still the placeholder. An explicit author is
+    /// never overridden.
+    fn fill_default_author(&mut self, git_user_name: impl FnOnce() -> Option<String>) {
+        if self.author != DEFAULT_AUTHOR {
+            return;
+        }
+
+        if let Some(name) = git_user_name() {
+            self.author = name;
+        }
+    }
+
     /// Check if config needs migration based on version
     ///
     /// Compares the config's version against the current version constant.
@@ -511,21 +1160,23 @@ impl Config {
     /// 3. Implement migration logic (field renames, type changes, etc.)
     /// 4. Update migration_notes with a description of changes
     fn migrate(mut config: Config) -> Result<Self> {
-        let old_version = config.metadata.config_version.as_str();
+        let old_version = config.metadata.config_version.clone();
 
         // Perform version-specific migrations
-        match old_version {
+        match old_version.as_str() {
             "" | "0.0.0" => {
                 // Migration from initial version (no version tracking)
+                config.note_preferences.migrate_legacy_sections();
+                config.metadata.migration_notes =
+                    format!("Migrated from initial version to {}", CONFIG_VERSION);
+            }
+            "1.0.0" => {
+                // Lecture/assignment section lists became keyed section presets
+                config.note_preferences.migrate_legacy_sections();
                 config.metadata.migration_notes =
-                    "Migrated from initial version to 1.0.0".to_string();
+                    "Migrated from 1.0.0 to 1.1.0: moved lecture_sections and assignment_sections into section_presets".to_string();
             }
             // Add more version-specific migrations as needed
-            // "1.0.0" => {
-            //     // Example: rename field, restructure data, etc.
-            //     config.metadata.migration_notes =
-            //         "Migrated from 1.0.0 to 1.1.0".to_string();
-            // }
             v => {
                 config.metadata.migration_notes =
                     format!("Migration from version {} to {}", v, CONFIG_VERSION);
@@ -596,6 +1247,7 @@ impl Config {
         if let Some(note_prefs) = old_value.get("note_preferences") {
             if let Ok(note_prefs) = serde_json::from_value(note_prefs.clone()) {
                 new_config.note_preferences = note_prefs;
+                new_config.note_preferences.migrate_legacy_sections();
             }
         }
 
@@ -653,12 +1305,22 @@ impl Config {
             fs::create_dir_all(parent)?;
         }
 
-        let content = serde_json::to_string_pretty(self)?;
-        fs::write(&config_path, content)?;
+        fs::write(&config_path, self.to_json()?)?;
 
         Ok(())
     }
 
+    /// The config file content, with paths as they were before resolving
+    fn to_json(&self) -> Result<String> {
+        Ok(match &self.unresolved_paths {
+            Some((unresolved, resolved)) => serde_json::to_string_pretty(&Config {
+                paths: self.paths.restore_unresolved(unresolved, resolved),
+                ..self.clone()
+            })?,
+            None => serde_json::to_string_pretty(self)?,
+        })
+    }
+
     /// Get the path to the config file
     pub fn config_file_path() -> Result<PathBuf> {
         let config_dir = dirs::home_dir()
@@ -687,6 +1349,32 @@ impl Config {
         self.save()
     }
 
+    /// Clear an optional setting so its default applies again. `field` is a
+    /// short name or dot-notation key from [`UNSETTABLE_FIELDS`]; the
+    /// dot-notation key of the cleared setting is returned.
+    pub fn unset_field(&mut self, field: &str) -> Result<&'static str> {
+        let unsettable = UNSETTABLE_FIELDS
+            .iter()
+            .find(|unsettable| unsettable.name == field || unsettable.key == field)
+            .ok_or_else(|| {
+                let names: Vec<&str> = UNSETTABLE_FIELDS.iter().map(|u| u.name).collect();
+                anyhow::anyhow!(
+                    "Unknown field '{}'. Fields that can be unset: {}",
+                    field,
+                    names.join(", ")
+                )
+            })?;
+
+        (unsettable.clear)(self);
+        Ok(unsettable.key)
+    }
+
+    /// Formatted semester that `date` falls in, by `semester_boundaries`
+    pub fn semester_for_date(&self, date: chrono::NaiveDate) -> String {
+        let (year, is_spring) = self.semester_boundaries.season_for(date);
+        self.format_semester(year, is_spring)
+    }
+
     /// Get formatted semester string
     pub fn format_semester(&self, year: i32, is_spring: bool) -> String {
         match &self.semester_format {
@@ -707,6 +1395,38 @@ impl Config {
         }
     }
 
+    /// Year and whether it is spring for a semester written by
+    /// [`Config::format_semester`]. Short forms are read as 20xx; custom
+    /// formats aren't parsed.
+    pub fn parse_semester(&self, semester: &str) -> Option<(i32, bool)> {
+        let season = |season: &str| match season {
+            "Spring" => Some(true),
+            "Fall" => Some(false),
+            _ => None,
+        };
+
+        match &self.semester_format {
+            SemesterFormat::YearSeason => {
+                let (year, is_spring) = semester.split_once(' ')?;
+                Some((year.parse().ok()?, season(is_spring)?))
+            }
+            SemesterFormat::SeasonYear => {
+                let (is_spring, year) = semester.split_once(' ')?;
+                Some((year.parse().ok()?, season(is_spring)?))
+            }
+            SemesterFormat::ShortForm => {
+                let is_spring = match semester.get(..1)? {
+                    "S" => true,
+                    "F" => false,
+                    _ => return None,
+                };
+                let year = semester.get(1..).filter(|year| year.len() == 2)?;
+                Some((2000 + year.parse::<i32>().ok()?, is_spring))
+            }
+            SemesterFormat::Custom(_) => None,
+        }
+    }
+
     /// Add a course
     pub fn add_course(&mut self, course_id: String, course_name: String) -> Result<()> {
         self.courses.insert(course_id, course_name);
@@ -720,11 +1440,125 @@ impl Config {
         Ok(removed)
     }
 
+    /// Resolve a course alias to its course ID, passing real course IDs through unchanged
+    pub fn resolve_course_id(&self, course_id_or_alias: &str) -> String {
+        if self.courses.contains_key(course_id_or_alias) {
+            return course_id_or_alias.to_string();
+        }
+
+        self.course_aliases
+            .get(&course_id_or_alias.to_lowercase())
+            .cloned()
+            .unwrap_or_else(|| course_id_or_alias.to_string())
+    }
+
+    /// Add or update a course alias
+    pub fn add_course_alias(&mut self, alias: &str, course_id: &str) -> Result<()> {
+        let alias = self.normalize_course_alias(alias)?;
+        self.course_aliases.insert(alias, course_id.to_string());
+        self.save()
+    }
+
+    /// Normalize an alias to lowercase, rejecting ones that collide with course IDs
+    pub fn normalize_course_alias(&self, alias: &str) -> Result<String> {
+        let alias = alias.trim().to_lowercase();
+
+        if alias.is_empty() {
+            anyhow::bail!("Alias cannot be empty");
+        }
+
+        if self.courses.contains_key(&alias)
+            || (alias.len() == 5 && alias.chars().all(|c| c.is_ascii_digit()))
+        {
+            anyhow::bail!("Alias '{}' collides with a course ID", alias);
+        }
+
+        Ok(alias)
+    }
+
+    /// Pin a template package to `version`, replacing any earlier pin
+    pub fn pin_template_version(&mut self, package: &str, version: &str) -> Result<()> {
+        let version = version.trim().trim_start_matches('v');
+        semver::Version::parse(version)
+            .map_err(|e| anyhow::anyhow!("Invalid version '{}': {}", version, e))?;
+
+        self.unpin_template_version_entry(package);
+        self.templates
+            .pinned_versions
+            .insert(package.trim().to_string(), version.to_string());
+        self.save()
+    }
+
+    /// Remove a template package's pin, returning the version it was pinned to
+    pub fn unpin_template_version(&mut self, package: &str) -> Result<Option<String>> {
+        let removed = self.unpin_template_version_entry(package);
+        self.save()?;
+        Ok(removed)
+    }
+
+    fn unpin_template_version_entry(&mut self, package: &str) -> Option<String> {
+        let key = self.templates.pinned_entry(package)?.0.clone();
+        self.templates.pinned_versions.remove(&key)
+    }
+
+    /// Remove a course alias, returning the course ID it pointed to
+    pub fn remove_course_alias(&mut self, alias: &str) -> Result<Option<String>> {
+        let removed = self.course_aliases.remove(&alias.trim().to_lowercase());
+        self.save()?;
+        Ok(removed)
+    }
+
+    /// List aliases as (alias, course ID) pairs sorted by alias
+    pub fn list_course_aliases(&self) -> Vec<(String, String)> {
+        let mut aliases: Vec<(String, String)> = self
+            .course_aliases
+            .iter()
+            .map(|(alias, id)| (alias.clone(), id.clone()))
+            .collect();
+        aliases.sort();
+        aliases
+    }
+
     /// Get course name
     pub fn get_course_name(&self, course_id: &str) -> String {
         self.courses.get(course_id).cloned().unwrap_or_default()
     }
 
+    /// Directory of a course inside the notes directory
+    pub fn get_course_dir(&self, course_id: &str) -> PathBuf {
+        PathBuf::from(&self.paths.notes_dir).join(course_id)
+    }
+
+    /// Directory lecture notes of a course are created in
+    /// (`<notes_dir>/<course_id>/lectures`, or the course directory itself
+    /// with `paths.flat_layout`)
+    pub fn get_lectures_dir(&self, course_id: &str) -> PathBuf {
+        if self.paths.flat_layout {
+            self.get_course_dir(course_id)
+        } else {
+            self.get_course_dir(course_id).join(LECTURES_DIR)
+        }
+    }
+
+    /// Directory assignments of a course are created in
+    /// (`<notes_dir>/<course_id>/assignments`)
+    pub fn get_assignments_dir(&self, course_id: &str) -> PathBuf {
+        self.get_course_dir(course_id).join(ASSIGNMENTS_DIR)
+    }
+
+    /// Directory custom templates of a course are created in
+    /// (`templates.custom_template_dir`, relative to the course directory)
+    pub fn get_custom_templates_dir(&self, course_id: &str) -> PathBuf {
+        // Joining an absolute path replaces the course directory
+        self.get_course_dir(course_id)
+            .join(&self.templates.custom_template_dir)
+    }
+
+    /// Root of the Obsidian vault
+    pub fn get_obsidian_dir_path(&self) -> PathBuf {
+        PathBuf::from(&self.paths.obsidian_dir)
+    }
+
     /// List all courses
     pub fn list_courses(&self) -> Vec<(String, String)> {
         let mut courses: Vec<(String, String)> = self
@@ -737,6 +1571,33 @@ impl Config {
     }
 
     /// Get list of preferred editors in order
+    /// Open files with `editor`, when given, instead of `preferred_editor` or
+    /// the OS defaults
+    pub fn with_editor_override(mut self, editor: Option<&str>) -> Self {
+        if let Some(editor) = editor {
+            self.editor_override = Some(editor.to_string());
+        }
+        self
+    }
+
+    /// Editor to run attached to the terminal: the `--editor` override, then
+    /// `preferred_editor`, `$EDITOR` and `$VISUAL` as looked up by `env`, and
+    /// finally the OS default
+    pub fn terminal_editor(&self, env: impl Fn(&str) -> Option<String>) -> String {
+        self.editor_override
+            .clone()
+            .or_else(|| self.preferred_editor.clone())
+            .or_else(|| env("EDITOR"))
+            .or_else(|| env("VISUAL"))
+            .unwrap_or_else(|| {
+                if cfg!(windows) {
+                    "notepad".to_string()
+                } else {
+                    "nano".to_string()
+                }
+            })
+    }
+
     pub fn get_editor_list(&self) -> Vec<String> {
         let mut editors = Vec::new();
 
@@ -771,7 +1632,7 @@ impl Config {
     pub fn validate(&self) -> Result<Vec<String>> {
         let mut warnings = Vec::new();
 
-        if self.author == "Your Name" {
+        if self.author == DEFAULT_AUTHOR {
             warnings.push("Author name is set to default value".to_string());
         }
 
@@ -779,6 +1640,14 @@ impl Config {
             warnings.push("Max search results is set to 0".to_string());
         }
 
+        if let Err(e) = self.health_thresholds.validate() {
+            warnings.push(e.to_string());
+        }
+
+        if let Err(e) = self.semester_boundaries.validate() {
+            warnings.push(e.to_string());
+        }
+
         // Check if template directory exists
         if !std::path::Path::new(&self.paths.templates_dir).exists() {
             warnings.push(format!(
@@ -789,20 +1658,106 @@ impl Config {
 
         Ok(warnings)
     }
+
+    /// Repair the [`validate`](Self::validate) warnings that have an obvious
+    /// fix: replace the placeholder author with `author_name()`, when it gives
+    /// one, reset `search.max_results` of 0 and create a missing templates
+    /// directory. Returns a description of each fix; saving is up to the caller.
+    pub fn fix(&mut self, author_name: impl FnOnce() -> Option<String>) -> Result<Vec<String>> {
+        let mut fixes = Vec::new();
+
+        if self.author == DEFAULT_AUTHOR {
+            let name = author_name()
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty() && name != DEFAULT_AUTHOR);
+            if let Some(name) = name {
+                fixes.push(format!("Set author to '{}'", name));
+                self.author = name;
+            }
+        }
+
+        if self.search.max_results == 0 {
+            self.search.max_results = SearchConfig::default().max_results;
+            fixes.push(format!(
+                "Reset search.max_results to {}",
+                self.search.max_results
+            ));
+        }
+
+        let templates_dir = Path::new(&self.paths.templates_dir);
+        if !templates_dir.exists() {
+            fs::create_dir_all(templates_dir).with_context(|| {
+                format!(
+                    "Could not create template directory {}",
+                    templates_dir.display()
+                )
+            })?;
+            fixes.push(format!(
+                "Created template directory '{}'",
+                self.paths.templates_dir
+            ));
+        }
+
+        Ok(fixes)
+    }
+}
+
+/// An optional setting `noter config unset` can clear
+pub struct UnsettableField {
+    /// Short name accepted on the command line
+    pub name: &'static str,
+    /// Dot-notation key, also accepted
+    pub key: &'static str,
+    /// What applies once the setting is cleared
+    pub fallback: &'static str,
+    clear: fn(&mut Config),
 }
 
+pub const UNSETTABLE_FIELDS: &[UnsettableField] = &[
+    UnsettableField {
+        name: "editor",
+        key: "preferred_editor",
+        fallback: "$EDITOR, then the platform's default editors",
+        clear: |config| config.preferred_editor = None,
+    },
+    UnsettableField {
+        name: "output-dir",
+        key: "typst.output_dir",
+        fallback: "PDFs are written next to their source",
+        clear: |config| config.typst.output_dir = None,
+    },
+    UnsettableField {
+        name: "vault-structure",
+        key: "obsidian_integration.vault_structure",
+        fallback: "no vault structure",
+        clear: |config| config.obsidian_integration.vault_structure = None,
+    },
+    UnsettableField {
+        name: "course-catalog",
+        key: "paths.course_catalog",
+        fallback: "only the built-in DTU course list",
+        clear: |config| config.paths.course_catalog = None,
+    },
+    UnsettableField {
+        name: "assignment-readme-template",
+        key: "note_preferences.assignment_readme_template",
+        fallback: "the built-in assignment README",
+        clear: |config| config.note_preferences.assignment_readme_template = None,
+    },
+];
+
 /// Helper functions for other modules to use
 pub fn get_config() -> Result<Config> {
     Config::load()
 }
 
 pub fn update_author(new_author: String) -> Result<()> {
-    let mut config = Config::load()?;
+    let mut config = Config::load_unresolved()?;
     config.set_author(new_author)
 }
 
 pub fn update_editor(new_editor: Option<String>) -> Result<()> {
-    let mut config = Config::load()?;
+    let mut config = Config::load_unresolved()?;
     config.set_editor(new_editor)
 }
 
@@ -810,6 +1765,71 @@ pub fn update_editor(new_editor: Option<String>) -> Result<()> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_unset_field() {
+        let mut config = Config {
+            preferred_editor: Some("nvim".to_string()),
+            ..Default::default()
+        };
+        config.typst.output_dir = Some("pdf".to_string());
+
+        assert_eq!(config.unset_field("editor").unwrap(), "preferred_editor");
+        assert_eq!(config.preferred_editor, None);
+
+        assert_eq!(
+            config.unset_field("typst.output_dir").unwrap(),
+            "typst.output_dir"
+        );
+        assert_eq!(config.typst.output_dir, None);
+
+        let error = config.unset_field("author").unwrap_err().to_string();
+        assert!(error.contains("editor, output-dir, vault-structure"));
+    }
+
+    #[test]
+    fn test_course_directory_helpers() {
+        let mut config = Config::default();
+        config.paths.notes_dir = "/home/student/notes".to_string();
+        config.paths.obsidian_dir = "/home/student/vault".to_string();
+
+        assert_eq!(
+            config.get_course_dir("02101"),
+            PathBuf::from("/home/student/notes/02101")
+        );
+        assert_eq!(
+            config.get_lectures_dir("02101"),
+            PathBuf::from("/home/student/notes/02101/lectures")
+        );
+        assert_eq!(
+            config.get_assignments_dir("02101"),
+            PathBuf::from("/home/student/notes/02101/assignments")
+        );
+        assert_eq!(
+            config.get_obsidian_dir_path(),
+            PathBuf::from("/home/student/vault")
+        );
+        assert_eq!(
+            config.get_custom_templates_dir("02101"),
+            PathBuf::from("/home/student/notes/02101/templates")
+        );
+
+        config.templates.custom_template_dir = "/home/student/shared-templates".to_string();
+        assert_eq!(
+            config.get_custom_templates_dir("02101"),
+            PathBuf::from("/home/student/shared-templates")
+        );
+
+        config.paths.flat_layout = true;
+        assert_eq!(
+            config.get_lectures_dir("02101"),
+            PathBuf::from("/home/student/notes/02101")
+        );
+        assert_eq!(
+            config.get_assignments_dir("02101"),
+            PathBuf::from("/home/student/notes/02101/assignments")
+        );
+    }
+
     #[test]
     fn test_default_config() {
         let config = Config::default();
@@ -817,22 +1837,321 @@ mod tests {
         assert!(config.note_preferences.auto_open_file);
     }
 
+    #[test]
+    fn test_fill_default_author_keeps_explicit_author() {
+        let mut config = Config::default();
+        config.fill_default_author(|| None);
+        assert_eq!(config.author, DEFAULT_AUTHOR);
+
+        config.fill_default_author(|| Some("Ada Lovelace".to_string()));
+        assert_eq!(config.author, "Ada Lovelace");
+
+        // Git is not consulted once an author is set
+        config.fill_default_author(|| panic!("git user.name looked up"));
+        assert_eq!(config.author, "Ada Lovelace");
+    }
+
+    #[test]
+    fn test_fix_repairs_fixable_warnings() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let templates_dir = temp_dir.path().join("templates");
+
+        let mut config = Config::default();
+        config.paths.templates_dir = templates_dir.to_string_lossy().into_owned();
+        config.search.max_results = 0;
+
+        // Without a name the placeholder author stays and is still reported
+        assert_eq!(config.fix(|| None)?.len(), 2);
+        assert!(templates_dir.is_dir());
+        assert_eq!(
+            config.search.max_results,
+            SearchConfig::default().max_results
+        );
+        assert_eq!(
+            config.validate()?,
+            vec!["Author name is set to default value".to_string()]
+        );
+
+        assert_eq!(
+            config.fix(|| Some(" Ada Lovelace ".to_string()))?,
+            vec!["Set author to 'Ada Lovelace'".to_string()]
+        );
+        assert!(config.validate()?.is_empty());
+        assert!(config.fix(|| panic!("author looked up"))?.is_empty());
+        Ok(())
+    }
+
     #[test]
     fn test_semester_formatting() {
         let config = Config::default();
         assert_eq!(config.format_semester(2024, true), "2024 Spring");
         assert_eq!(config.format_semester(2024, false), "2024 Fall");
+        assert_eq!(config.parse_semester("2024 Fall"), Some((2024, false)));
+        assert_eq!(config.parse_semester("Fall 2024"), None);
     }
 
     #[test]
-    fn test_editor_list() {
+    fn test_resolved_dirs_use_base() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let base = temp_dir.path().canonicalize()?;
+        fs::create_dir_all(base.join("notes"))?;
+
+        let paths = PathConfig {
+            notes_dir: "notes".to_string(),
+            obsidian_dir: "vault".to_string(),
+            templates_dir: base.join("templates").to_string_lossy().to_string(),
+            typst_packages_dir: "../packages".to_string(),
+            flat_layout: false,
+            follow_symlinks: true,
+            course_catalog: None,
+            create_missing_dirs: true,
+        };
+        let resolved: std::collections::HashMap<&str, String> =
+            paths.resolved_dirs(&base)?.into_iter().collect();
+
+        assert_eq!(resolved.len(), 4);
+        assert_eq!(resolved["notes_dir"], base.join("notes").to_string_lossy());
+        assert_eq!(
+            resolved["obsidian_dir"],
+            base.join("vault").to_string_lossy()
+        );
+        assert_eq!(
+            resolved["templates_dir"],
+            base.join("templates").to_string_lossy()
+        );
+        // Existing paths are canonicalized, missing ones only joined to the base
+        assert_eq!(
+            resolved["typst_packages_dir"],
+            base.join("../packages").to_string_lossy()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_saving_keeps_paths_as_written() -> Result<()> {
         let mut config = Config::default();
-        config.preferred_editor = Some("emacs".to_string());
+        config.paths.notes_dir = "notes".to_string();
+        config.paths.obsidian_dir = "vault".to_string();
+        config.paths.typst_packages_dir = "packages".to_string();
+        config.resolve_paths()?;
+        assert!(Path::new(&config.paths.notes_dir).is_absolute());
+        // Resolved like `noter config paths` shows it
+        let current_dir = std::env::current_dir()?;
+        let shown = config
+            .unresolved_paths
+            .as_ref()
+            .unwrap()
+            .0
+            .resolved_dirs(&current_dir)?;
+        assert!(shown.contains(&(
+            "typst_packages_dir",
+            config.paths.typst_packages_dir.clone()
+        )));
+
+        // A path changed after loading is saved as set, the others as written
+        config.paths.obsidian_dir = "/srv/vault".to_string();
+        let saved: Config = serde_json::from_str(&config.to_json()?)?;
+        assert_eq!(saved.paths.notes_dir, "notes");
+        assert_eq!(saved.paths.typst_packages_dir, "packages");
+        assert_eq!(saved.paths.obsidian_dir, "/srv/vault");
+
+        // An unset variable leaves the paths as they were
+        let mut config = Config::default();
+        let unset = if cfg!(windows) {
+            "%NOTER_TEST_UNSET%\\notes"
+        } else {
+            "$NOTER_TEST_UNSET/notes"
+        };
+        config.paths.notes_dir = unset.to_string();
+        assert!(config.resolve_paths().is_err());
+        assert_eq!(config.paths.notes_dir, unset);
+        assert!(config.unresolved_paths.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_expand_env_vars() {
+        let lookup = |name: &str| match name {
+            "HOME" => Some("/home/s123456".to_string()),
+            "USERPROFILE" => Some(r"C:\Users\s123456".to_string()),
+            _ => None,
+        };
+        let expand = |path, windows| PathConfig::expand_env_vars(path, windows, lookup);
+
+        assert_eq!(expand("$HOME/Notes", false).unwrap(), "/home/s123456/Notes");
+        assert_eq!(
+            expand("${HOME}_dtu/$HOME", false).unwrap(),
+            "/home/s123456_dtu//home/s123456"
+        );
+        assert_eq!(
+            expand(r"%USERPROFILE%\Notes", true).unwrap(),
+            r"C:\Users\s123456\Notes"
+        );
+        // Markers that don't start a variable stay
+        assert_eq!(expand("notes/$/100%", false).unwrap(), "notes/$/100%");
+        assert_eq!(expand("100% done", true).unwrap(), "100% done");
+
+        let missing = expand("$NOTES_ROOT/dtu", false).unwrap_err().to_string();
+        assert!(missing.contains("NOTES_ROOT"));
+        assert!(
+            expand("%NOTES_ROOT%", true)
+                .unwrap_err()
+                .to_string()
+                .contains("NOTES_ROOT")
+        );
+        assert!(expand("${HOME/Notes", false).is_err());
+    }
+
+    #[test]
+    fn test_semester_boundaries() {
+        use chrono::NaiveDate;
+
+        let date = |month| NaiveDate::from_ymd_opt(2024, month, 15).unwrap();
+        let mut config = Config::default();
+
+        // Default: January-June is spring, July onwards fall
+        assert_eq!(config.semester_for_date(date(1)), "2024 Spring");
+        assert_eq!(config.semester_for_date(date(7)), "2024 Fall");
+        assert_eq!(config.semester_for_date(date(9)), "2024 Fall");
+
+        // Every format agrees on the season
+        config.semester_format = SemesterFormat::ShortForm;
+        assert_eq!(config.semester_for_date(date(1)), "S24");
+        assert_eq!(config.semester_for_date(date(7)), "F24");
+
+        // DTU-style calendar: January exams belong to the previous fall, and
+        // the summer stays in spring until fall starts in August
+        config.semester_boundaries = SemesterBoundaries {
+            spring_start_month: 2,
+            fall_start_month: 8,
+        };
+        assert_eq!(config.semester_for_date(date(1)), "F23");
+        assert_eq!(config.semester_for_date(date(7)), "S24");
+        config.semester_format = SemesterFormat::YearSeason;
+        assert_eq!(config.semester_for_date(date(9)), "2024 Fall");
+
+        assert!(config.semester_boundaries.validate().is_ok());
+        config.semester_boundaries.fall_start_month = 2;
+        assert!(config.semester_boundaries.validate().is_err());
+        config.semester_boundaries.fall_start_month = 13;
+        assert!(config.semester_boundaries.validate().is_err());
+    }
+
+    #[test]
+    fn test_editor_list() {
+        let config = Config {
+            preferred_editor: Some("emacs".to_string()),
+            ..Config::default()
+        };
 
         let editors = config.get_editor_list();
         assert_eq!(editors[0], "emacs");
     }
 
+    #[test]
+    fn test_editor_override_beats_configured_editors() {
+        let env = |name: &str| (name == "EDITOR").then(|| "nano".to_string());
+        let config = Config {
+            preferred_editor: Some("emacs".to_string()),
+            ..Config::default()
+        };
+        assert_eq!(config.terminal_editor(env), "emacs");
+
+        let config = config.with_editor_override(Some("vim"));
+        assert_eq!(config.editor_override.as_deref(), Some("vim"));
+        assert_eq!(config.terminal_editor(env), "vim");
+
+        // Without --editor the configured editors apply
+        let config = Config::default().with_editor_override(None);
+        assert_eq!(config.editor_override, None);
+        assert_eq!(config.terminal_editor(env), "nano");
+    }
+
+    #[test]
+    fn test_sections_for_template_type() {
+        let mut prefs = NotePreferences::default();
+        prefs
+            .section_presets
+            .insert("lab-report".to_string(), vec!["Procedure".to_string()]);
+
+        assert_eq!(prefs.sections_for("lab"), vec!["Procedure".to_string()]);
+        assert_eq!(prefs.sections_for("note"), prefs.section_presets["lecture"]);
+        assert_eq!(prefs.sections_for("exam"), prefs.section_presets["lecture"]);
+
+        // Configs saved before a built-in preset existed still get it
+        let defaults = NotePreferences::default();
+        prefs.section_presets.remove("group");
+        assert_eq!(
+            prefs.sections_for("group"),
+            defaults.section_presets["group"]
+        );
+
+        // The note type falls back from the flag to the course default to lecture
+        prefs
+            .course_note_types
+            .insert("02101".to_string(), "reading".to_string());
+        assert_eq!(prefs.note_type_for("02101", None), "reading");
+        assert_eq!(prefs.note_type_for("02101", Some("summary")), "summary");
+        assert_eq!(prefs.note_type_for("02102", None), "lecture");
+    }
+
+    #[test]
+    fn test_migrate_legacy_sections() {
+        let json = r#"{
+            "metadata": {
+                "config_version": "1.0.0",
+                "created_at": "2025-01-01T00:00:00+00:00",
+                "last_updated": "2025-01-01T00:00:00+00:00",
+                "migration_notes": ""
+            },
+            "note_preferences": {
+                "lecture_sections": ["Summary"],
+                "assignment_sections": ["Task A"]
+            }
+        }"#;
+
+        let config: Config = serde_json::from_str(json).unwrap();
+        let config = Config::migrate(config).unwrap();
+
+        assert_eq!(config.metadata.config_version, CONFIG_VERSION);
+        assert_eq!(
+            config.note_preferences.sections_for("lecture"),
+            vec!["Summary".to_string()]
+        );
+        assert_eq!(
+            config.note_preferences.sections_for("assignment"),
+            vec!["Task A".to_string()]
+        );
+
+        let serialized = serde_json::to_string(&config).unwrap();
+        assert!(!serialized.contains("\"lecture_sections\":"));
+    }
+
+    #[test]
+    fn test_resolve_course_id() {
+        let mut config = Config::default();
+        config
+            .course_aliases
+            .insert("introprog".to_string(), "02101".to_string());
+
+        assert_eq!(config.resolve_course_id("introprog"), "02101");
+        assert_eq!(config.resolve_course_id("IntroProg"), "02101");
+        assert_eq!(config.resolve_course_id("02101"), "02101");
+        assert_eq!(config.resolve_course_id("unknown"), "unknown");
+    }
+
+    #[test]
+    fn test_course_alias_collision() {
+        let config = Config::default();
+
+        assert_eq!(config.normalize_course_alias(" Calc ").unwrap(), "calc");
+        assert!(config.normalize_course_alias("02101").is_err());
+        assert!(config.normalize_course_alias("12345").is_err());
+        assert!(config.normalize_course_alias("").is_err());
+    }
+
     #[test]
     fn test_config_file_path() {
         let config_path = Config::config_file_path().unwrap();
@@ -0,0 +1,126 @@
+//! README for new assignment directories
+//!
+//! With `note_preferences.create_assignment_readme` enabled, the first
+//! assignment of a course also writes a `README.md` into its new assignments
+//! directory, explaining the layout to collaborators of a shared repository.
+//! The text comes from `note_preferences.assignment_readme_template` when set,
+//! else a built-in template, with `{{course_id}}`, `{{course_name}}` and
+//! `{{semester}}` filled in. An existing README is never touched.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::core::status_manager::StatusManager;
+
+/// File name of the README in an assignments directory
+pub const README_FILE: &str = "README.md";
+
+const DEFAULT_TEMPLATE: &str = r#"# {{course_id}} {{course_name}} - Assignments
+
+Assignments for {{semester}}, one Typst file per assignment.
+
+## Conventions
+
+- File names are the assignment title in lowercase, e.g. `problem-set-1.typ`
+- Each file's header records its due date
+- Compile with `noter compile <file>`
+- Pull before editing and commit small, focused changes
+"#;
+
+pub struct AssignmentReadme;
+
+#[allow(dead_code)]
+impl AssignmentReadme {
+    /// Write the README into `assignments_dir` if enabled and it doesn't exist
+    /// yet. Returns the path when a README was written.
+    pub fn create_if_enabled(
+        assignments_dir: &Path,
+        course_id: &str,
+        config: &Config,
+    ) -> Result<Option<PathBuf>> {
+        let readme_path = assignments_dir.join(README_FILE);
+        if !config.note_preferences.create_assignment_readme || readme_path.exists() {
+            return Ok(None);
+        }
+
+        let content = Self::render(
+            &Self::template(config)?,
+            course_id,
+            &StatusManager::resolve_course_name(course_id, config),
+            &StatusManager::get_current_semester(config),
+        );
+        fs::write(&readme_path, content)?;
+
+        Ok(Some(readme_path))
+    }
+
+    /// The configured template file's content, or the built-in template
+    pub fn template(config: &Config) -> Result<String> {
+        match config
+            .note_preferences
+            .assignment_readme_template
+            .as_deref()
+        {
+            Some(path) => fs::read_to_string(path)
+                .with_context(|| format!("Could not read assignment README template {}", path)),
+            None => Ok(DEFAULT_TEMPLATE.to_string()),
+        }
+    }
+
+    /// `template` with its placeholders filled in
+    pub fn render(template: &str, course_id: &str, course_name: &str, semester: &str) -> String {
+        template
+            .replace("{{course_id}}", course_id)
+            .replace("{{course_name}}", course_name)
+            .replace("{{semester}}", semester)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_readme_is_written_once_from_the_template() {
+        let temp_dir = TempDir::new().unwrap();
+        let template = temp_dir.path().join("readme.md");
+        fs::write(&template, "# {{course_id}}: {{course_name}}\n").unwrap();
+
+        let mut config = Config::default();
+        assert_eq!(
+            AssignmentReadme::create_if_enabled(temp_dir.path(), "02101", &config).unwrap(),
+            None
+        );
+
+        config.note_preferences.create_assignment_readme = true;
+        config.note_preferences.assignment_readme_template =
+            Some(template.to_string_lossy().into_owned());
+        config.courses.insert(
+            "02101".to_string(),
+            "Introduction to Programming".to_string(),
+        );
+        let readme = AssignmentReadme::create_if_enabled(temp_dir.path(), "02101", &config)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(&readme).unwrap(),
+            "# 02101: Introduction to Programming\n"
+        );
+
+        // An existing README is left alone
+        fs::write(&readme, "Edited").unwrap();
+        assert_eq!(
+            AssignmentReadme::create_if_enabled(temp_dir.path(), "02101", &config).unwrap(),
+            None
+        );
+        assert_eq!(fs::read_to_string(&readme).unwrap(), "Edited");
+
+        assert!(
+            AssignmentReadme::render(DEFAULT_TEMPLATE, "02101", "Intro", "2026 Fall")
+                .starts_with("# 02101 Intro - Assignments\n\nAssignments for 2026 Fall")
+        );
+    }
+}
@@ -0,0 +1,322 @@
+//! Calendar export for assignment due dates
+//!
+//! Scans assignment files for due dates written in the front-matter convention
+//! (e.g. `*Due Date*: 2025-03-14 \`) and renders them as an iCalendar file.
+
+use anyhow::Result;
+use chrono::{Duration, NaiveDate, Utc};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::ASSIGNMENTS_DIR;
+use crate::core::directory_scanner::DirectoryScanner;
+
+/// Date formats accepted after a due-date marker
+const DUE_DATE_FORMATS: &[&str] = &["%Y-%m-%d", "%d/%m/%Y", "%d-%m-%Y", "%d.%m.%Y"];
+
+/// Line prefixes recognised as due-date markers (matched case-insensitively)
+const DUE_DATE_MARKERS: &[&str] = &["*due date*:", "due date:", "*due*:", "due:", "due-date:"];
+
+#[derive(Debug, Clone)]
+pub struct AssignmentDueDate {
+    pub course_id: String,
+    pub title: String,
+    pub due_date: NaiveDate,
+    pub file_path: PathBuf,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CalendarExport {
+    pub events: Vec<AssignmentDueDate>,
+    /// Assignment files without a parseable due date
+    pub skipped: Vec<PathBuf>,
+}
+
+pub struct CalendarExporter;
+
+#[allow(dead_code)]
+impl CalendarExporter {
+    /// Collect due dates from assignments, optionally limited to one course
+    pub fn collect_due_dates(
+        notes_dir: &Path,
+        course_id: Option<&str>,
+        extensions: &[&str],
+        follow_symlinks: bool,
+    ) -> Result<CalendarExport> {
+        let mut export = CalendarExport::default();
+
+        if !notes_dir.exists() {
+            return Ok(export);
+        }
+
+        let mut course_ids: Vec<String> = match course_id {
+            Some(id) => vec![id.to_string()],
+            // Only the course IDs are used, so the note layout doesn't matter
+            None => DirectoryScanner::scan_notes_directory(
+                notes_dir,
+                extensions,
+                false,
+                follow_symlinks,
+            )?
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect(),
+        };
+        course_ids.sort();
+
+        for course_id in course_ids {
+            let assignments_dir = notes_dir.join(&course_id).join(ASSIGNMENTS_DIR);
+            if !assignments_dir.exists() {
+                continue;
+            }
+
+            let mut files = DirectoryScanner::scan_directory_for_files(
+                &assignments_dir,
+                extensions,
+                follow_symlinks,
+            )?;
+            files.sort_by(|a, b| a.path.cmp(&b.path));
+
+            for file in files {
+                let content = fs::read_to_string(&file.path)?;
+                match Self::extract_due_date(&content) {
+                    Some(due_date) => export.events.push(AssignmentDueDate {
+                        course_id: course_id.clone(),
+                        title: Self::extract_title(&content, &file.path),
+                        due_date,
+                        file_path: file.path,
+                    }),
+                    None => export.skipped.push(file.path),
+                }
+            }
+        }
+
+        Ok(export)
+    }
+
+    /// Extract the first due date found in the document front matter
+    pub fn extract_due_date(content: &str) -> Option<NaiveDate> {
+        content.lines().find_map(|line| {
+            let trimmed = line.trim();
+            let lower = trimmed.to_lowercase();
+
+            DUE_DATE_MARKERS.iter().find_map(|marker| {
+                if !lower.starts_with(marker) {
+                    return None;
+                }
+                let value = trimmed
+                    .get(marker.len()..)?
+                    .trim()
+                    .trim_end_matches('\\')
+                    .trim_end_matches(',')
+                    .trim()
+                    .trim_matches('"');
+                Self::parse_date(value)
+            })
+        })
+    }
+
+    /// Extract the document title from the first heading, falling back to the file name
+    pub fn extract_title(content: &str, path: &Path) -> String {
+        content
+            .lines()
+            .map(str::trim)
+            .find_map(|line| {
+                line.strip_prefix("= ")
+                    .map(|title| title.trim().to_string())
+            })
+            .filter(|title| !title.is_empty())
+            .unwrap_or_else(|| {
+                path.file_stem()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .replace(['-', '_'], " ")
+            })
+    }
+
+    /// Render due dates as an RFC 5545 VCALENDAR with one all-day VEVENT each
+    pub fn to_ics(events: &[AssignmentDueDate]) -> String {
+        let dtstamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+        let mut lines = vec![
+            "BEGIN:VCALENDAR".to_string(),
+            "VERSION:2.0".to_string(),
+            "PRODID:-//DTU Notes//noter//EN".to_string(),
+            "CALSCALE:GREGORIAN".to_string(),
+            "METHOD:PUBLISH".to_string(),
+        ];
+
+        for event in events {
+            let summary = format!("{} - {}", event.course_id, event.title);
+            let uid = format!(
+                "{}-{}@noter",
+                event.course_id,
+                event
+                    .file_path
+                    .file_stem()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+            );
+
+            lines.push("BEGIN:VEVENT".to_string());
+            lines.push(format!("UID:{}", Self::escape_text(&uid)));
+            lines.push(format!("DTSTAMP:{}", dtstamp));
+            lines.push(format!(
+                "DTSTART;VALUE=DATE:{}",
+                event.due_date.format("%Y%m%d")
+            ));
+            lines.push(format!(
+                "DTEND;VALUE=DATE:{}",
+                (event.due_date + Duration::days(1)).format("%Y%m%d")
+            ));
+            lines.push(format!("SUMMARY:{}", Self::escape_text(&summary)));
+            lines.push(format!(
+                "DESCRIPTION:{}",
+                Self::escape_text(&event.file_path.display().to_string())
+            ));
+            lines.push("END:VEVENT".to_string());
+        }
+
+        lines.push("END:VCALENDAR".to_string());
+
+        lines
+            .iter()
+            .map(|line| Self::fold_line(line))
+            .collect::<Vec<_>>()
+            .join("\r\n")
+            + "\r\n"
+    }
+
+    // Private helper methods
+
+    /// Parse an absolute date in any of the accepted due-date formats
+    pub fn parse_date(value: &str) -> Option<NaiveDate> {
+        if let Some(date) = Self::parse_typst_datetime(value) {
+            return Some(date);
+        }
+
+        // Allow a trailing time component such as "2025-03-14 23:59"
+        let date_part = value.split_whitespace().next()?;
+        DUE_DATE_FORMATS
+            .iter()
+            .find_map(|format| NaiveDate::parse_from_str(date_part, format).ok())
+    }
+
+    /// Parse a Typst `datetime(year: 2025, month: 3, day: 14)` literal, as
+    /// written into generated assignment headers
+    fn parse_typst_datetime(value: &str) -> Option<NaiveDate> {
+        let args = value.strip_prefix("datetime(")?.split(')').next()?;
+
+        let (mut year, mut month, mut day) = (None, None, None);
+        for arg in args.split(',') {
+            let (key, number) = arg.split_once(':')?;
+            let number = number.trim();
+            match key.trim() {
+                "year" => year = number.parse().ok(),
+                "month" => month = number.parse().ok(),
+                "day" => day = number.parse().ok(),
+                _ => {}
+            }
+        }
+
+        NaiveDate::from_ymd_opt(year?, month?, day?)
+    }
+
+    fn escape_text(text: &str) -> String {
+        text.replace('\\', "\\\\")
+            .replace(';', "\\;")
+            .replace(',', "\\,")
+            .replace('\n', "\\n")
+    }
+
+    /// Fold content lines longer than 75 octets as required by RFC 5545
+    fn fold_line(line: &str) -> String {
+        let mut folded = String::with_capacity(line.len());
+        let mut line_len = 0;
+
+        for ch in line.chars() {
+            let ch_len = ch.len_utf8();
+            if line_len + ch_len > 75 {
+                folded.push_str("\r\n ");
+                line_len = 1;
+            }
+            folded.push(ch);
+            line_len += ch_len;
+        }
+
+        folded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_extract_due_date_formats() {
+        let content = "= Assignment 1\n*Course*: 02101 \\\n*Due Date*: 2025-03-14 \\\n";
+        assert_eq!(
+            CalendarExporter::extract_due_date(content),
+            NaiveDate::from_ymd_opt(2025, 3, 14)
+        );
+
+        assert_eq!(
+            CalendarExporter::extract_due_date("Due: 01/04/2025"),
+            NaiveDate::from_ymd_opt(2025, 4, 1)
+        );
+        assert_eq!(CalendarExporter::extract_due_date("Due: next week"), None);
+
+        // Header emitted for generated assignments
+        assert_eq!(
+            CalendarExporter::extract_due_date(
+                "#show: assignment.with(\n  due-date: datetime(year: 2025, month: 6, day: 1),\n)"
+            ),
+            NaiveDate::from_ymd_opt(2025, 6, 1)
+        );
+    }
+
+    #[test]
+    fn test_collect_due_dates_skips_unparseable() {
+        let temp_dir = TempDir::new().unwrap();
+        let assignments = temp_dir.path().join("02101").join("assignments");
+        fs::create_dir_all(&assignments).unwrap();
+        fs::write(
+            assignments.join("problem-set-1.typ"),
+            "= Problem Set 1\n*Due Date*: 2025-02-20 \\\n",
+        )
+        .unwrap();
+        fs::write(assignments.join("notes.typ"), "= Scratch\n").unwrap();
+
+        let export =
+            CalendarExporter::collect_due_dates(temp_dir.path(), None, &["typ"], true).unwrap();
+
+        assert_eq!(export.events.len(), 1);
+        assert_eq!(export.events[0].title, "Problem Set 1");
+        assert_eq!(export.skipped.len(), 1);
+    }
+
+    #[test]
+    fn test_to_ics_structure() {
+        let events = vec![AssignmentDueDate {
+            course_id: "02101".to_string(),
+            title: "Problem Set 1, Part A".to_string(),
+            due_date: NaiveDate::from_ymd_opt(2025, 2, 20).unwrap(),
+            file_path: PathBuf::from("problem-set-1.typ"),
+        }];
+
+        let ics = CalendarExporter::to_ics(&events);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.contains("DTSTART;VALUE=DATE:20250220\r\n"));
+        assert!(ics.contains("DTEND;VALUE=DATE:20250221\r\n"));
+        assert!(ics.contains("SUMMARY:02101 - Problem Set 1\\, Part A\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    }
+
+    #[test]
+    fn test_fold_line() {
+        let long_line = format!("SUMMARY:{}", "x".repeat(100));
+        let folded = CalendarExporter::fold_line(&long_line);
+        assert!(folded.split("\r\n").all(|part| part.len() <= 75));
+    }
+}
@@ -0,0 +1,102 @@
+//! Course archives
+//!
+//! Packs a course directory into a gzipped tarball under
+//! `<notes_dir>/archive/<semester>/`, so a finished semester can be kept out of
+//! the way without losing anything. The course directory itself is left in
+//! place.
+
+use anyhow::{Context, Result};
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+
+/// Directory inside the notes directory that holds archives
+pub const ARCHIVE_DIR: &str = "archive";
+
+/// An archive written for a course
+#[derive(Debug, Clone)]
+pub struct CourseArchive {
+    pub course_id: String,
+    pub path: PathBuf,
+    /// Files packed into the archive
+    pub files: usize,
+    /// Size of the archive on disk
+    pub size: u64,
+}
+
+pub struct CourseArchiver;
+
+#[allow(dead_code)]
+impl CourseArchiver {
+    /// Directory archives for `semester` (e.g. "2025 Fall") are written to
+    pub fn archive_dir(config: &Config, semester: &str) -> PathBuf {
+        let semester_dir = semester
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>()
+            .join("-");
+
+        Path::new(&config.paths.notes_dir)
+            .join(ARCHIVE_DIR)
+            .join(semester_dir)
+    }
+
+    /// Archive path for a course in `archive_dir`
+    pub fn archive_path(archive_dir: &Path, course_id: &str) -> PathBuf {
+        archive_dir.join(format!("{}.tar.gz", course_id))
+    }
+
+    /// Pack a course directory into `<archive_dir>/<course_id>.tar.gz`,
+    /// replacing an existing archive of the same course
+    pub fn archive_course(
+        config: &Config,
+        course_id: &str,
+        archive_dir: &Path,
+    ) -> Result<CourseArchive> {
+        let course_path = Path::new(&config.paths.notes_dir).join(course_id);
+        if !course_path.is_dir() {
+            anyhow::bail!("Course directory not found: {}", course_path.display());
+        }
+
+        fs::create_dir_all(archive_dir).with_context(|| {
+            format!(
+                "Failed to create archive directory {}",
+                archive_dir.display()
+            )
+        })?;
+
+        let path = Self::archive_path(archive_dir, course_id);
+        let file = File::create(&path)
+            .with_context(|| format!("Failed to create archive {}", path.display()))?;
+
+        let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+        builder
+            .append_dir_all(course_id, &course_path)
+            .with_context(|| format!("Failed to archive {}", course_path.display()))?;
+        builder.into_inner()?.finish()?;
+
+        Ok(CourseArchive {
+            course_id: course_id.to_string(),
+            files: Self::count_files(&course_path)?,
+            size: fs::metadata(&path)?.len(),
+            path,
+        })
+    }
+
+    fn count_files(dir: &Path) -> Result<usize> {
+        let mut count = 0;
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                count += Self::count_files(&path)?;
+            } else {
+                count += 1;
+            }
+        }
+
+        Ok(count)
+    }
+}
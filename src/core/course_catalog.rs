@@ -0,0 +1,295 @@
+//! Course catalog
+//!
+//! The built-in list of DTU courses in [`crate::data`] is compiled in. A
+//! catalog file at `paths.course_catalog` adds to it, and its names win over
+//! the built-in ones, so an up-to-date export can be dropped in without a new
+//! release. Two formats are read, chosen by file extension:
+//!
+//! - JSON: an object of codes to names, `{"02101": "Introduction to Programming"}`,
+//!   or an array of `{"code": "02101", "name": "Introduction to Programming"}`
+//! - CSV: `code,name` rows, with an optional header row. Names containing
+//!   commas are quoted, and further columns, such as ECTS points, are ignored.
+
+use anyhow::{Context, Result, anyhow};
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::config::Config;
+use crate::core::validation::Validator;
+
+/// An entry of a JSON catalog array
+#[derive(Deserialize)]
+struct CatalogEntry {
+    #[serde(alias = "course_id", alias = "id")]
+    code: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonCatalog {
+    Map(BTreeMap<String, String>),
+    List(Vec<CatalogEntry>),
+}
+
+/// The catalog loaded by [`CourseCatalog::cached`], with the file it was loaded for
+static CACHED: OnceLock<(Option<String>, CourseCatalog)> = OnceLock::new();
+
+#[derive(Debug, Clone)]
+pub struct CourseCatalog {
+    courses: BTreeMap<String, String>,
+    /// The catalog file merged into the built-in list, if any
+    source: Option<PathBuf>,
+}
+
+#[allow(dead_code)]
+impl CourseCatalog {
+    /// Only the compiled-in courses
+    pub fn builtin() -> Self {
+        Self {
+            courses: crate::data::get_common_dtu_courses()
+                .into_iter()
+                .map(|(code, name)| (code.to_string(), name.to_string()))
+                .collect(),
+            source: None,
+        }
+    }
+
+    /// The built-in courses merged with the configured catalog file. Fails
+    /// when the file is configured but missing or can't be parsed.
+    pub fn load(config: &Config) -> Result<Self> {
+        let mut catalog = Self::builtin();
+        let Some(path) = config.paths.course_catalog.as_deref() else {
+            return Ok(catalog);
+        };
+
+        let path = PathBuf::from(path);
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Could not read course catalog {}", path.display()))?;
+        catalog.courses.extend(
+            Self::parse(&content, &path)
+                .with_context(|| format!("Invalid course catalog {}", path.display()))?,
+        );
+        catalog.source = Some(path);
+
+        Ok(catalog)
+    }
+
+    /// Like [`CourseCatalog::load`], falling back to the built-in courses
+    /// with a warning when the catalog file can't be used
+    pub fn load_or_builtin(config: &Config) -> Self {
+        Self::load(config).unwrap_or_else(|e| {
+            eprintln!("⚠️  {:#}. Using the built-in course list", e);
+            Self::builtin()
+        })
+    }
+
+    /// Like [`CourseCatalog::load_or_builtin`], but the file is read, and a
+    /// problem with it reported, only once for the rest of this invocation
+    pub fn cached(config: &Config) -> Cow<'static, Self> {
+        let path = &config.paths.course_catalog;
+        let (cached_path, catalog) =
+            CACHED.get_or_init(|| (path.clone(), Self::load_or_builtin(config)));
+
+        if cached_path == path {
+            Cow::Borrowed(catalog)
+        } else {
+            Cow::Owned(Self::load_or_builtin(config))
+        }
+    }
+
+    /// Courses in a catalog file's content, in the format its extension names
+    pub fn parse(content: &str, path: &Path) -> Result<BTreeMap<String, String>> {
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_lowercase);
+
+        let courses = match extension.as_deref() {
+            Some("json") => Self::parse_json(content)?,
+            Some("csv") => Self::parse_csv(content)?,
+            _ => anyhow::bail!("Unsupported catalog format, use a .json or .csv file"),
+        };
+
+        if courses.is_empty() {
+            anyhow::bail!("The catalog contains no courses");
+        }
+        Ok(courses)
+    }
+
+    pub fn name(&self, course_id: &str) -> Option<&str> {
+        self.courses.get(course_id).map(String::as_str)
+    }
+
+    pub fn contains(&self, course_id: &str) -> bool {
+        self.courses.contains_key(course_id)
+    }
+
+    /// Every course as `(code, name)`, ordered by code
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.courses
+            .iter()
+            .map(|(code, name)| (code.as_str(), name.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.courses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.courses.is_empty()
+    }
+
+    /// The catalog file in use, `None` for the built-in list only
+    pub fn source(&self) -> Option<&Path> {
+        self.source.as_deref()
+    }
+
+    fn parse_json(content: &str) -> Result<BTreeMap<String, String>> {
+        let entries: Vec<(String, String)> = match serde_json::from_str(content)? {
+            JsonCatalog::Map(courses) => courses.into_iter().collect(),
+            JsonCatalog::List(entries) => entries
+                .into_iter()
+                .map(|entry| (entry.code, entry.name))
+                .collect(),
+        };
+
+        entries
+            .into_iter()
+            .map(|(code, name)| Self::entry(&code, &name).map_err(|e| anyhow!("{}: {}", code, e)))
+            .collect()
+    }
+
+    fn parse_csv(content: &str) -> Result<BTreeMap<String, String>> {
+        let mut courses = BTreeMap::new();
+
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim().trim_start_matches('\u{feff}');
+            if line.is_empty() {
+                continue;
+            }
+
+            let fields = Self::fields(line);
+            let [code, name, ..] = fields.as_slice() else {
+                anyhow::bail!("line {}: expected `code,name`", index + 1);
+            };
+
+            // A header row, e.g. `code,name`
+            if index == 0 && Validator::validate_course_id(code).is_err() {
+                continue;
+            }
+
+            let (code, name) =
+                Self::entry(code, name).map_err(|e| anyhow!("line {}: {}", index + 1, e))?;
+            courses.insert(code, name);
+        }
+
+        Ok(courses)
+    }
+
+    /// A validated `(code, name)` pair
+    fn entry(code: &str, name: &str) -> Result<(String, String)> {
+        let code = code.trim();
+        Validator::validate_course_id(code)?;
+
+        let name = name.trim();
+        if name.is_empty() {
+            anyhow::bail!("course {} has no name", code);
+        }
+        Ok((code.to_string(), name.to_string()))
+    }
+
+    /// The fields of a CSV row, split at each `,` or `;` outside quotes, with
+    /// the quotes removed and `""` unescaped
+    fn fields(line: &str) -> Vec<String> {
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut quoted = false;
+
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if quoted && chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => quoted = !quoted,
+                ',' | ';' if !quoted => fields.push(std::mem::take(&mut field).trim().to_string()),
+                _ => field.push(c),
+            }
+        }
+        fields.push(field.trim().to_string());
+
+        fields
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_catalog_file_overrides_builtin_courses() {
+        let temp_dir = TempDir::new().unwrap();
+        let csv = temp_dir.path().join("catalog.csv");
+        fs::write(
+            &csv,
+            "code,name\n02101,Introduction to Programming (Python)\n02450,\"Machine Learning, Data Mining\"\n\n99999;Brand New Course\n",
+        )
+        .unwrap();
+
+        let mut config = Config::default();
+        config.paths.course_catalog = Some(csv.to_string_lossy().into_owned());
+        let catalog = CourseCatalog::load(&config).unwrap();
+        assert_eq!(
+            catalog.name("02101"),
+            Some("Introduction to Programming (Python)")
+        );
+        assert_eq!(catalog.name("02450"), Some("Machine Learning, Data Mining"));
+        assert_eq!(catalog.name("99999"), Some("Brand New Course"));
+        // Built-in courses not in the file are kept
+        assert_eq!(
+            catalog.name("02102"),
+            Some("Algorithms and Data Structures")
+        );
+        assert_eq!(catalog.source(), Some(csv.as_path()));
+
+        let json = Path::new("catalog.json");
+        let map = CourseCatalog::parse(r#"{"02101": "Intro"}"#, json).unwrap();
+        let list = CourseCatalog::parse(r#"[{"code": "02101", "name": "Intro"}]"#, json).unwrap();
+        assert_eq!(map, list);
+
+        // Only the first two columns are read
+        let courses = CourseCatalog::parse(
+            "code,name,ects\n02101,Intro,5\n02450,\"Machine Learning, \"\"Data\"\" Mining\";5;Autumn\n",
+            &csv,
+        )
+        .unwrap();
+        assert_eq!(courses["02101"], "Intro");
+        assert_eq!(courses["02450"], "Machine Learning, \"Data\" Mining");
+
+        // Parse errors name the offending line or course
+        let error = CourseCatalog::parse("02101,Intro\n2101,Short code\n", &csv).unwrap_err();
+        assert!(error.to_string().starts_with("line 2:"), "{}", error);
+        assert!(CourseCatalog::parse(r#"{"02101": ""}"#, json).is_err());
+        assert!(CourseCatalog::parse("02101: Intro", Path::new("catalog.txt")).is_err());
+
+        config.paths.course_catalog = Some(
+            temp_dir
+                .path()
+                .join("missing.csv")
+                .to_string_lossy()
+                .into_owned(),
+        );
+        assert!(CourseCatalog::load(&config).is_err());
+        assert_eq!(
+            CourseCatalog::load_or_builtin(&config).len(),
+            CourseCatalog::builtin().len()
+        );
+    }
+}
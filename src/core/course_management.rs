@@ -6,6 +6,7 @@
 use anyhow::Result;
 
 use crate::config::Config;
+use crate::core::course_catalog::CourseCatalog;
 
 pub struct CourseManager<'a> {
     config: &'a mut Config,
@@ -46,6 +47,79 @@ impl<'a> CourseManager<'a> {
     }
 }
 
+/// A course catalog entry matching a search query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CourseMatch {
+    pub course_id: String,
+    pub course_name: String,
+    /// Higher is a better match
+    pub score: u32,
+}
+
+/// Search a course catalog by code or name, best matches first.
+///
+/// Exact codes rank highest, then code prefixes, exact names, names starting
+/// with the query, names containing it, names containing every query word, and
+/// finally names containing the query's letters in order. Matching ignores
+/// case.
+pub fn search_course_catalog(catalog: &CourseCatalog, query: &str) -> Vec<CourseMatch> {
+    let mut matches: Vec<CourseMatch> = catalog
+        .iter()
+        .filter_map(|(course_id, course_name)| {
+            Some(CourseMatch {
+                score: match_score(query, course_id, course_name)?,
+                course_id: course_id.to_string(),
+                course_name: course_name.to_string(),
+            })
+        })
+        .collect();
+
+    matches.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.course_id.cmp(&b.course_id))
+    });
+    matches
+}
+
+fn match_score(query: &str, course_id: &str, course_name: &str) -> Option<u32> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return None;
+    }
+    let name = course_name.to_lowercase();
+
+    if course_id == query {
+        return Some(100);
+    }
+    if course_id.starts_with(&query) {
+        return Some(90);
+    }
+    if name == query {
+        return Some(85);
+    }
+    if name.starts_with(&query) {
+        return Some(80);
+    }
+    if let Some(position) = name.find(&query) {
+        // Matches nearer the start of the name rank higher
+        return Some(70 - (position as u32).min(10));
+    }
+
+    let words: Vec<&str> = query.split_whitespace().collect();
+    if words.len() > 1 && words.iter().all(|word| name.contains(word)) {
+        return Some(50);
+    }
+
+    // Letters of the query in order, e.g. "adsa" in "Algorithms and Data Structures"
+    let mut letters = name.chars();
+    query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .all(|c| letters.any(|n| n == c))
+        .then_some(20)
+}
+
 /// Common DTU courses organized by category
 #[must_use]
 pub const fn get_common_courses()
@@ -71,3 +145,30 @@ pub const fn get_common_courses()
         ),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_course_catalog_ranks_matches() {
+        let ids = |query| -> Vec<String> {
+            search_course_catalog(&CourseCatalog::builtin(), query)
+                .into_iter()
+                .map(|course| course.course_id)
+                .collect()
+        };
+
+        assert_eq!(ids("02101"), vec!["02101"]);
+        assert_eq!(ids("0210")[..2], ["02101", "02102"]);
+
+        // Names starting with the query rank above names containing it
+        let matches = ids("algorithms");
+        assert_eq!(matches[..3], ["02102", "02105", "02110"]);
+
+        assert_eq!(ids("PROGRAMMING C++"), vec!["02393"]);
+        assert!(ids("fnprog").contains(&"02157".to_string()));
+        assert!(ids("quantum chromodynamics").is_empty());
+        assert!(ids("  ").is_empty());
+    }
+}
@@ -0,0 +1,150 @@
+//! Course names embedded in note headers
+//!
+//! New notes record the course name in the `course-name:` argument of their
+//! header. After a course is renamed in the configuration, older notes still
+//! carry the old name. [`CourseNameChecker`] finds those notes and rewrites
+//! that one argument, leaving the rest of each file alone.
+
+use anyhow::Result;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::core::directory_scanner::DirectoryScanner;
+use crate::core::file_operations::FileOperations;
+
+/// Header argument holding the course name
+pub const COURSE_NAME_ARGUMENT: &str = "course-name";
+
+/// A note whose header names its course differently from the configuration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CourseNameMismatch {
+    pub path: PathBuf,
+    pub course_id: String,
+    /// Name in the note's header
+    pub found: String,
+    /// Name in the configuration
+    pub expected: String,
+}
+
+pub struct CourseNameChecker;
+
+#[allow(dead_code)]
+impl CourseNameChecker {
+    /// Notes and assignments of every configured course whose header course
+    /// name differs from the configured one, ordered by path. Notes without
+    /// a `course-name:` argument are skipped.
+    pub fn find_mismatches(config: &Config) -> Result<Vec<CourseNameMismatch>> {
+        let extensions = config.typst.source_extensions();
+        let mut mismatches = Vec::new();
+
+        for (course_id, expected) in config.list_courses() {
+            let course_path = config.get_course_dir(&course_id);
+            if expected.is_empty() || !course_path.exists() {
+                continue;
+            }
+
+            let notes = DirectoryScanner::scan_course_notes(
+                &course_path,
+                &extensions,
+                config.paths.flat_layout,
+                config.paths.follow_symlinks,
+            )?;
+            let assignments = DirectoryScanner::scan_course_assignments(
+                &course_path,
+                &extensions,
+                config.paths.follow_symlinks,
+            )?;
+
+            for file in notes.into_iter().chain(assignments) {
+                let Some(found) =
+                    FileOperations::read_header_argument(&file.path, COURSE_NAME_ARGUMENT)
+                else {
+                    continue;
+                };
+                if found != expected {
+                    mismatches.push(CourseNameMismatch {
+                        path: file.path,
+                        course_id: course_id.clone(),
+                        found,
+                        expected: expected.clone(),
+                    });
+                }
+            }
+        }
+
+        mismatches.sort_by(|a, b| a.path.cmp(&b.path));
+        mismatches.dedup_by(|a, b| a.path == b.path);
+        Ok(mismatches)
+    }
+
+    /// Set the note's header course name to the configured one, backing the
+    /// file up first. Returns whether the file changed.
+    pub fn fix(mismatch: &CourseNameMismatch) -> Result<bool> {
+        let content = fs::read_to_string(&mismatch.path)?;
+        let Some(updated) = FileOperations::replace_header_argument(
+            &content,
+            COURSE_NAME_ARGUMENT,
+            &mismatch.expected,
+        ) else {
+            return Ok(false);
+        };
+
+        FileOperations::create_backup(&mismatch.path)?;
+        FileOperations::safe_write(&mismatch.path, &updated)?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_finds_and_fixes_renamed_course() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.paths.notes_dir = temp_dir.path().to_string_lossy().into_owned();
+        config
+            .courses
+            .insert("02101".to_string(), "Programming in Python".to_string());
+
+        let header = |name: &str| {
+            format!(
+                "#import \"@local/dtu-template:0.2.0\":*\n\n#show: dtu-note.with(\n  course: \"02101\",\n  course-name: \"{}\",\n)\n\n= Loops\ncourse-name: \"Intro\"\n",
+                name
+            )
+        };
+        let lectures = temp_dir.path().join("02101").join("lectures");
+        fs::create_dir_all(&lectures).unwrap();
+        let old = lectures.join("old.typ");
+        fs::write(&old, header("Introduction to Programming")).unwrap();
+        fs::write(lectures.join("new.typ"), header("Programming in Python")).unwrap();
+        fs::write(lectures.join("scratch.typ"), "= No header\n").unwrap();
+
+        let mismatches = CourseNameChecker::find_mismatches(&config).unwrap();
+        assert_eq!(
+            mismatches,
+            vec![CourseNameMismatch {
+                path: old.clone(),
+                course_id: "02101".to_string(),
+                found: "Introduction to Programming".to_string(),
+                expected: "Programming in Python".to_string(),
+            }]
+        );
+
+        assert!(CourseNameChecker::fix(&mismatches[0]).unwrap());
+        // Only the header argument changes
+        assert_eq!(
+            fs::read_to_string(&old).unwrap(),
+            header("Programming in Python")
+        );
+        assert!(
+            CourseNameChecker::find_mismatches(&config)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(!CourseNameChecker::fix(&mismatches[0]).unwrap());
+    }
+}
@@ -9,21 +9,30 @@ use chrono::{Duration, Utc};
 #[cfg(feature = "dev-tools")]
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
-use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
 use std::fs;
-use std::path::Path;
-use std::sync::{Mutex, OnceLock};
+use std::path::{Path, PathBuf};
 
 use crate::config::Config;
+use crate::core::file_operations::FileOperations;
 use crate::ui::output::{OutputManager, Status};
 
-// Global tracking of generated courses for cleanup
+/// Manifest of generated course codes, stored in the notes directory so cleanup
+/// knows exactly what to remove across runs
 #[cfg(feature = "dev-tools")]
-static GENERATED_COURSES: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+const DEV_DATA_MANIFEST: &str = ".dev-data-manifest.json";
 
+/// Course codes removed when no manifest exists (data generated by older versions)
 #[cfg(feature = "dev-tools")]
-fn get_generated_courses() -> &'static Mutex<HashSet<String>> {
-    GENERATED_COURSES.get_or_init(|| Mutex::new(HashSet::new()))
+const LEGACY_DEV_COURSES: &[&str] = &[
+    "02101", "02102", "02105", "02110", "02157", "02180", "02223", "02266", "02343", "02450",
+];
+
+#[cfg(feature = "dev-tools")]
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DevDataManifest {
+    course_codes: BTreeSet<String>,
 }
 
 /// Development data generator for creating realistic test content
@@ -74,17 +83,13 @@ impl DevDataGenerator {
 
         let mut stats = GenerationStats::new();
 
-        // Add courses to config and track them
+        // Add courses to config and record them in the manifest for cleanup
         for course in &courses {
             config
                 .courses
                 .insert(course.code.clone(), course.name.clone());
-
-            // Track generated course for cleanup
-            if let Ok(mut generated) = get_generated_courses().lock() {
-                generated.insert(course.code.clone());
-            }
         }
+        Self::record_generated_courses(notes_dir, &courses)?;
 
         // Save updated config
         config.save()?;
@@ -94,40 +99,32 @@ impl DevDataGenerator {
             fs::create_dir_all(&course_dir)?;
 
             // Generate course info file
-            self.generate_course_info(&course_dir, course)?;
-            stats.files_created += 1;
+            stats.record_file(self.generate_course_info(&course_dir, course)?);
 
             // Generate lecture notes (20-35 per course for high-yield)
             let note_count = self.rng.random_range(20..35);
             for i in 1..=note_count {
-                self.generate_lecture_note(&course_dir, course, i)?;
+                stats.record_file(self.generate_lecture_note(&course_dir, course, i)?);
                 stats.notes_created += 1;
-                stats.files_created += 1;
             }
 
             // Generate assignments (5-8 per course)
             let assignment_count = self.rng.random_range(5..9);
             for i in 1..=assignment_count {
-                self.generate_assignment(&course_dir, course, i)?;
+                stats.record_file(self.generate_assignment(&course_dir, course, i)?);
                 stats.assignments_created += 1;
-                stats.files_created += 1;
             }
 
-            // Generate study materials
-            self.generate_study_materials(&course_dir, course)?;
-            stats.files_created += 3; // Summary, cheat sheet, study guide
-            stats.courses_created += 1;
+            // Generate study materials (summary, cheat sheet, study guide)
+            for path in self.generate_study_materials(&course_dir, course)? {
+                stats.record_file(path);
+            }
+            stats.record_course(&course.code);
         }
 
         OutputManager::print_status(
             Status::Success,
-            &format!(
-                "High-yield simulation complete! Generated {} courses, {} notes, {} assignments, {} total files",
-                stats.courses_created,
-                stats.notes_created,
-                stats.assignments_created,
-                stats.files_created
-            ),
+            &format!("High-yield simulation complete! {}", stats.summary()),
         );
 
         Ok(stats)
@@ -141,8 +138,6 @@ impl DevDataGenerator {
         notes_per_course: usize,
         assignments_per_course: usize,
     ) -> Result<GenerationStats> {
-        let notes_dir = Path::new(&config.paths.notes_dir);
-
         OutputManager::print_status(
             Status::Loading,
             &format!(
@@ -151,52 +146,64 @@ impl DevDataGenerator {
             ),
         );
 
-        fs::create_dir_all(notes_dir)?;
-
         let courses: Vec<Course> = (0..course_count)
             .map(|i| self.generate_realistic_course(i))
             .collect();
 
-        let mut stats = GenerationStats::new();
-
-        // Add courses to config and track them
+        // Add courses to config and save it
         for course in &courses {
             config
                 .courses
                 .insert(course.code.clone(), course.name.clone());
-
-            // Track generated course for cleanup
-            if let Ok(mut generated) = get_generated_courses().lock() {
-                generated.insert(course.code.clone());
-            }
         }
-
-        // Save updated config
         config.save()?;
 
-        for course in &courses {
+        let stats = self.write_sample_courses(
+            Path::new(&config.paths.notes_dir),
+            &courses,
+            notes_per_course,
+            assignments_per_course,
+        )?;
+
+        OutputManager::print_status(
+            Status::Success,
+            &format!("Sample data generation complete! {}", stats.summary()),
+        );
+        Ok(stats)
+    }
+
+    /// Write the files of `courses` into `notes_dir` and record them in the
+    /// manifest for cleanup
+    fn write_sample_courses(
+        &mut self,
+        notes_dir: &Path,
+        courses: &[Course],
+        notes_per_course: usize,
+        assignments_per_course: usize,
+    ) -> Result<GenerationStats> {
+        fs::create_dir_all(notes_dir)?;
+        Self::record_generated_courses(notes_dir, courses)?;
+
+        let mut stats = GenerationStats::new();
+        for course in courses {
             let course_dir = notes_dir.join(&course.code);
             fs::create_dir_all(&course_dir)?;
 
-            self.generate_course_info(&course_dir, course)?;
-            stats.files_created += 1;
+            stats.record_file(self.generate_course_info(&course_dir, course)?);
 
             for i in 1..=notes_per_course {
-                self.generate_lecture_note(&course_dir, course, i)?;
+                stats.record_file(self.generate_lecture_note(&course_dir, course, i)?);
                 stats.notes_created += 1;
-                stats.files_created += 1;
             }
 
             for i in 1..=assignments_per_course {
-                self.generate_assignment(&course_dir, course, i)?;
+                stats.record_file(self.generate_assignment(&course_dir, course, i)?);
                 stats.assignments_created += 1;
-                stats.files_created += 1;
             }
 
-            stats.courses_created += 1;
+            stats.record_course(&course.code);
         }
 
-        OutputManager::print_status(Status::Success, "Sample data generation complete!");
         Ok(stats)
     }
 
@@ -211,35 +218,10 @@ impl DevDataGenerator {
 
         OutputManager::print_status(Status::Loading, "Cleaning dev data...");
 
-        let predefined_dev_courses = [
-            "02101", "02102", "02105", "02110", "02157", "02180", "02223", "02266", "02343",
-            "02450",
-        ];
-
-        // Get all courses to remove (predefined + dynamically generated)
-        let mut all_courses_to_remove = HashSet::new();
-
-        // Add predefined dev courses
-        for course_code in &predefined_dev_courses {
-            all_courses_to_remove.insert(course_code.to_string());
-        }
-
-        // Add dynamically generated courses (from tracking and pattern matching)
-        if let Ok(generated) = get_generated_courses().lock() {
-            for course_code in generated.iter() {
-                all_courses_to_remove.insert(course_code.clone());
-            }
-        }
-
-        // Also detect courses that match our generated pattern (02100xx format)
-        for (course_code, _) in &config.courses {
-            if course_code.starts_with("021") && course_code.len() == 7 {
-                // This looks like a dynamically generated course code
-                all_courses_to_remove.insert(course_code.clone());
-            }
-        }
-
-        let mut stats = CleanupStats::new();
+        let all_courses_to_remove: HashSet<String> = match Self::read_manifest(notes_dir) {
+            Some(manifest) => manifest.course_codes.into_iter().collect(),
+            None => Self::legacy_courses_to_remove(config),
+        };
 
         // Remove courses from config
         let mut courses_removed_from_config = 0;
@@ -261,41 +243,110 @@ impl DevDataGenerator {
             );
         }
 
-        for course_code in &all_courses_to_remove {
+        let stats = Self::remove_course_dirs(notes_dir, &all_courses_to_remove)?;
+
+        OutputManager::print_status(
+            Status::Success,
+            &format!(
+                "Dev data cleanup complete! Removed {} directories and {} files ({})",
+                stats.directories_removed,
+                stats.files_removed,
+                FileOperations::format_file_size(stats.bytes_removed)
+            ),
+        );
+
+        Ok(stats)
+    }
+
+    /// Remove the directories of `course_codes` from `notes_dir`, then the manifest
+    fn remove_course_dirs(
+        notes_dir: &Path,
+        course_codes: &HashSet<String>,
+    ) -> Result<CleanupStats> {
+        let mut stats = CleanupStats::new();
+
+        for course_code in course_codes {
             let course_dir = notes_dir.join(course_code);
             if course_dir.exists() {
                 // Count files before removal
-                if let Ok(entries) = fs::read_dir(&course_dir) {
-                    stats.files_removed += entries.count();
-                }
+                stats.files_removed += Self::count_files(&course_dir);
+                stats.bytes_removed += FileOperations::directory_size(&course_dir);
 
                 fs::remove_dir_all(&course_dir)?;
                 OutputManager::print_status(Status::Info, &format!("Removed {}", course_code));
                 stats.directories_removed += 1;
+                stats.removed_paths.push(course_dir);
             }
         }
 
-        // Clear the generated courses tracking
-        if let Ok(mut generated) = get_generated_courses().lock() {
-            generated.clear();
+        // Everything recorded has been removed, so drop the manifest too
+        let manifest_path = notes_dir.join(DEV_DATA_MANIFEST);
+        if manifest_path.exists() {
+            fs::remove_file(&manifest_path)?;
         }
 
-        OutputManager::print_status(
-            Status::Success,
-            &format!(
-                "Dev data cleanup complete! Removed {} directories and {} files",
-                stats.directories_removed, stats.files_removed
-            ),
+        Ok(stats)
+    }
+
+    /// Number of files below `dir`, without following symlinks
+    fn count_files(dir: &Path) -> usize {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return 0;
+        };
+
+        entries
+            .flatten()
+            .map(|entry| match fs::symlink_metadata(entry.path()) {
+                Ok(metadata) if metadata.is_dir() => Self::count_files(&entry.path()),
+                Ok(_) => 1,
+                Err(_) => 0,
+            })
+            .sum()
+    }
+
+    /// Add generated course codes to the manifest in the notes directory
+    fn record_generated_courses(notes_dir: &Path, courses: &[Course]) -> Result<()> {
+        let mut manifest = Self::read_manifest(notes_dir).unwrap_or_default();
+        manifest
+            .course_codes
+            .extend(courses.iter().map(|course| course.code.clone()));
+
+        fs::write(
+            notes_dir.join(DEV_DATA_MANIFEST),
+            serde_json::to_string_pretty(&manifest)?,
+        )?;
+        Ok(())
+    }
+
+    fn read_manifest(notes_dir: &Path) -> Option<DevDataManifest> {
+        let content = fs::read_to_string(notes_dir.join(DEV_DATA_MANIFEST)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Courses to remove for dev data generated before the manifest existed:
+    /// the predefined codes plus anything matching the generated 021xxxx pattern
+    fn legacy_courses_to_remove(config: &Config) -> HashSet<String> {
+        let mut courses: HashSet<String> = LEGACY_DEV_COURSES
+            .iter()
+            .map(|code| code.to_string())
+            .collect();
+
+        courses.extend(
+            config
+                .courses
+                .keys()
+                .filter(|code| code.starts_with("021") && code.len() == 7)
+                .cloned(),
         );
 
-        Ok(stats)
+        courses
     }
 
-    fn generate_course_info(&self, course_dir: &Path, course: &Course) -> Result<()> {
+    fn generate_course_info(&self, course_dir: &Path, course: &Course) -> Result<PathBuf> {
         let content = super::sample_content::CourseInfoTemplate::generate(course);
         let file_path = course_dir.join("course_info.typ");
-        fs::write(file_path, content)?;
-        Ok(())
+        fs::write(&file_path, content)?;
+        Ok(file_path)
     }
 
     fn generate_lecture_note(
@@ -303,7 +354,7 @@ impl DevDataGenerator {
         course_dir: &Path,
         course: &Course,
         lecture_num: usize,
-    ) -> Result<()> {
+    ) -> Result<PathBuf> {
         let topics = super::sample_content::get_lecture_topics(&course.code);
         let topic = &topics[lecture_num % topics.len()];
         let date = Utc::now() - Duration::days(self.rng.random_range(1..180));
@@ -316,8 +367,8 @@ impl DevDataGenerator {
         );
 
         let file_path = course_dir.join(format!("lecture_{:02}.typ", lecture_num));
-        fs::write(file_path, content)?;
-        Ok(())
+        fs::write(&file_path, content)?;
+        Ok(file_path)
     }
 
     fn generate_assignment(
@@ -325,7 +376,7 @@ impl DevDataGenerator {
         course_dir: &Path,
         course: &Course,
         assignment_num: usize,
-    ) -> Result<()> {
+    ) -> Result<PathBuf> {
         let assignment_types = [
             "Programming",
             "Theoretical",
@@ -349,11 +400,11 @@ impl DevDataGenerator {
         );
 
         let file_path = assignments_dir.join(format!("assignment_{:02}.typ", assignment_num));
-        fs::write(file_path, content)?;
-        Ok(())
+        fs::write(&file_path, content)?;
+        Ok(file_path)
     }
 
-    fn generate_study_materials(&self, course_dir: &Path, course: &Course) -> Result<()> {
+    fn generate_study_materials(&self, course_dir: &Path, course: &Course) -> Result<Vec<PathBuf>> {
         // Generate course summary
         let summary_content = super::sample_content::StudyMaterialsTemplate::generate(
             "Summary",
@@ -361,7 +412,7 @@ impl DevDataGenerator {
             "Course Overview",
         );
         let summary_path = course_dir.join("course_summary.typ");
-        fs::write(summary_path, summary_content)?;
+        fs::write(&summary_path, summary_content)?;
 
         // Generate cheat sheet
         let cheat_sheet_content = super::sample_content::StudyMaterialsTemplate::generate(
@@ -370,7 +421,7 @@ impl DevDataGenerator {
             "Quick Reference",
         );
         let cheat_sheet_path = course_dir.join("cheat_sheet.typ");
-        fs::write(cheat_sheet_path, cheat_sheet_content)?;
+        fs::write(&cheat_sheet_path, cheat_sheet_content)?;
 
         // Generate study guide
         let study_guide_content = super::sample_content::StudyMaterialsTemplate::generate(
@@ -379,9 +430,9 @@ impl DevDataGenerator {
             "Exam Preparation",
         );
         let study_guide_path = course_dir.join("study_guide.typ");
-        fs::write(study_guide_path, study_guide_content)?;
+        fs::write(&study_guide_path, study_guide_content)?;
 
-        Ok(())
+        Ok(vec![summary_path, cheat_sheet_path, study_guide_path])
     }
 
     fn get_predefined_courses(&self) -> Vec<Course> {
@@ -532,6 +583,12 @@ pub struct GenerationStats {
     pub notes_created: usize,
     pub assignments_created: usize,
     pub files_created: usize,
+    /// Total size of the written files
+    pub bytes_written: u64,
+    /// Codes of the generated courses, in generation order
+    pub course_codes: Vec<String>,
+    /// Every file written during generation
+    pub file_paths: Vec<PathBuf>,
 }
 
 #[cfg(feature = "dev-tools")]
@@ -539,6 +596,33 @@ impl GenerationStats {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Record a generated file
+    pub fn record_file(&mut self, path: PathBuf) {
+        self.files_created += 1;
+        self.bytes_written += fs::metadata(&path)
+            .map(|metadata| metadata.len())
+            .unwrap_or(0);
+        self.file_paths.push(path);
+    }
+
+    /// Record a fully generated course
+    pub fn record_course(&mut self, code: &str) {
+        self.courses_created += 1;
+        self.course_codes.push(code.to_string());
+    }
+
+    /// One-line summary used for the printed report
+    pub fn summary(&self) -> String {
+        format!(
+            "Generated {} courses, {} notes, {} assignments, {} total files ({})",
+            self.courses_created,
+            self.notes_created,
+            self.assignments_created,
+            self.files_created,
+            FileOperations::format_file_size(self.bytes_written)
+        )
+    }
 }
 
 /// Statistics for cleanup operations
@@ -547,6 +631,10 @@ impl GenerationStats {
 pub struct CleanupStats {
     pub directories_removed: usize,
     pub files_removed: usize,
+    /// Total size of the removed files
+    pub bytes_removed: u64,
+    /// Course directories that were removed
+    pub removed_paths: Vec<PathBuf>,
 }
 
 #[cfg(feature = "dev-tools")]
@@ -555,3 +643,58 @@ impl CleanupStats {
         Self::default()
     }
 }
+
+#[cfg(all(test, feature = "dev-tools"))]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_stats_match_written_and_removed_files() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let notes_dir = temp_dir.path();
+
+        let mut generator = DevDataGenerator::with_seed(7);
+        let courses: Vec<Course> = (0..2)
+            .map(|i| generator.generate_realistic_course(i))
+            .collect();
+        let stats = generator.write_sample_courses(notes_dir, &courses, 3, 2)?;
+
+        // Course info, notes and assignments of both courses
+        assert_eq!(stats.courses_created, 2);
+        assert_eq!(stats.notes_created, 6);
+        assert_eq!(stats.assignments_created, 4);
+        assert_eq!(stats.files_created, 12);
+        assert_eq!(stats.file_paths.len(), 12);
+        assert!(stats.file_paths.iter().all(|path| path.is_file()));
+        let course_dirs: Vec<PathBuf> = stats
+            .course_codes
+            .iter()
+            .map(|code| notes_dir.join(code))
+            .collect();
+        let written: u64 = course_dirs
+            .iter()
+            .map(|dir| FileOperations::directory_size(dir))
+            .sum();
+        assert_eq!(stats.bytes_written, written);
+
+        // Cleanup removes what the manifest recorded
+        let manifest = DevDataGenerator::read_manifest(notes_dir).unwrap();
+        let codes: HashSet<String> = manifest.course_codes.into_iter().collect();
+        assert_eq!(codes, stats.course_codes.iter().cloned().collect());
+
+        let cleanup = DevDataGenerator::remove_course_dirs(notes_dir, &codes)?;
+        assert_eq!(cleanup.directories_removed, 2);
+        assert_eq!(cleanup.files_removed, stats.files_created);
+        assert_eq!(cleanup.bytes_removed, stats.bytes_written);
+        let mut removed = cleanup.removed_paths.clone();
+        removed.sort();
+        let mut expected = course_dirs.clone();
+        expected.sort();
+        assert_eq!(removed, expected);
+        assert!(course_dirs.iter().all(|dir| !dir.exists()));
+        assert!(!notes_dir.join(DEV_DATA_MANIFEST).exists());
+
+        Ok(())
+    }
+}
@@ -1,13 +1,20 @@
 //! Directory scanning utilities
 //!
 //! Provides reusable directory scanning functionality used across
-//! multiple commands.
+//! multiple commands. Files and directories matched by the notes directory's
+//! `.noterignore` are skipped. Symlinked directories are followed unless
+//! `paths.follow_symlinks` is off, and never entered twice.
 
 use anyhow::Result;
+use rayon::prelude::*;
+use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
+use crate::config::{ASSIGNMENTS_DIR, LECTURES_DIR};
+use crate::core::ignore::IgnoreRules;
+
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub struct FileInfo {
@@ -23,120 +30,334 @@ pub struct CourseStats {
     pub assignments_count: usize,
     pub last_activity: Option<FileInfo>,
     pub total_files: usize,
+    /// Modification times of every note and assignment
+    pub modified_times: Vec<SystemTime>,
+}
+
+impl CourseStats {
+    /// Notes and assignments modified at or after `cutoff`
+    pub fn files_modified_since(&self, cutoff: SystemTime) -> usize {
+        self.modified_times
+            .iter()
+            .filter(|&&modified| modified >= cutoff)
+            .count()
+    }
 }
 
 pub struct DirectoryScanner;
 
+/// State shared across one recursive directory scan
+struct Walk<'a> {
+    extensions: &'a [&'a str],
+    ignore: &'a IgnoreRules,
+    follow_symlinks: bool,
+    /// Canonical paths of the directories entered so far
+    visited: HashSet<PathBuf>,
+}
+
 #[allow(dead_code)]
 impl DirectoryScanner {
-    pub fn scan_course_directory<P: AsRef<Path>>(course_path: P) -> Result<CourseStats> {
+    /// Count notes and assignments in a course, matching sources by `extensions`.
+    /// With `flat_layout`, notes live directly in the course directory instead
+    /// of its `lectures/` subdirectory. `follow_symlinks` controls whether
+    /// symlinked subdirectories are scanned.
+    pub fn scan_course_directory<P: AsRef<Path>>(
+        course_path: P,
+        extensions: &[&str],
+        flat_layout: bool,
+        follow_symlinks: bool,
+    ) -> Result<CourseStats> {
         let course_path = course_path.as_ref();
-        let mut stats = CourseStats {
-            notes_count: 0,
-            assignments_count: 0,
-            last_activity: None,
-            total_files: 0,
-        };
+        let lecture_files =
+            Self::scan_course_notes(course_path, extensions, flat_layout, follow_symlinks)?;
+        let assignment_files =
+            Self::scan_course_assignments(course_path, extensions, follow_symlinks)?;
 
-        // Scan lectures directory
-        let lectures_path = course_path.join("lectures");
-        if lectures_path.exists() {
-            let lecture_files = Self::scan_directory_for_files(&lectures_path, &["typ"])?;
-            stats.notes_count = lecture_files.len();
-            stats.total_files += lecture_files.len();
+        // The more recent of the newest note and the newest assignment
+        let last_activity = Self::find_most_recent(&lecture_files)
+            .into_iter()
+            .chain(Self::find_most_recent(&assignment_files))
+            .reduce(|note, assignment| {
+                if assignment.modified > note.modified {
+                    assignment
+                } else {
+                    note
+                }
+            });
+
+        Ok(CourseStats {
+            notes_count: lecture_files.len(),
+            assignments_count: assignment_files.len(),
+            last_activity,
+            total_files: lecture_files.len() + assignment_files.len(),
+            modified_times: lecture_files
+                .iter()
+                .chain(&assignment_files)
+                .map(|file| file.modified)
+                .collect(),
+        })
+    }
 
-            if let Some(most_recent) = Self::find_most_recent(&lecture_files) {
-                stats.last_activity = Some(most_recent);
+    /// Lecture notes of a course: everything under `lectures/`, or with
+    /// `flat_layout` the source files directly in the course directory
+    pub fn scan_course_notes(
+        course_path: &Path,
+        extensions: &[&str],
+        flat_layout: bool,
+        follow_symlinks: bool,
+    ) -> Result<Vec<FileInfo>> {
+        if flat_layout {
+            if !course_path.exists() {
+                return Ok(Vec::new());
             }
-        }
 
-        // Scan assignments directory
-        let assignments_path = course_path.join("assignments");
-        if assignments_path.exists() {
-            let assignment_files = Self::scan_directory_for_files(&assignments_path, &["typ"])?;
-            stats.assignments_count = assignment_files.len();
-            stats.total_files += assignment_files.len();
-
-            // Update last activity if assignment is more recent
-            if let Some(most_recent) = Self::find_most_recent(&assignment_files) {
-                match &stats.last_activity {
-                    None => stats.last_activity = Some(most_recent),
-                    Some(current) => {
-                        if most_recent.modified > current.modified {
-                            stats.last_activity = Some(most_recent);
-                        }
+            let ignore = IgnoreRules::find(course_path)?;
+            let mut files = Vec::new();
+            for entry in fs::read_dir(course_path)? {
+                let entry = entry?;
+                if let Some(file) = Self::source_file_info(&entry, extensions) {
+                    if !ignore.is_ignored(&file.path, false) {
+                        files.push(file);
                     }
                 }
             }
+            return Ok(files);
         }
 
-        Ok(stats)
+        let lectures_path = course_path.join(LECTURES_DIR);
+        if !lectures_path.exists() {
+            return Ok(Vec::new());
+        }
+        Self::scan_directory_for_files(&lectures_path, extensions, follow_symlinks)
     }
 
+    /// Assignments of a course, everything under `assignments/`
+    pub fn scan_course_assignments(
+        course_path: &Path,
+        extensions: &[&str],
+        follow_symlinks: bool,
+    ) -> Result<Vec<FileInfo>> {
+        let assignments_path = course_path.join(ASSIGNMENTS_DIR);
+        if !assignments_path.exists() {
+            return Ok(Vec::new());
+        }
+        Self::scan_directory_for_files(&assignments_path, extensions, follow_symlinks)
+    }
+
+    /// Source files anywhere under `dir_path`. Symlinked subdirectories are
+    /// only descended into with `follow_symlinks`, and each directory is
+    /// scanned at most once, so link cycles can't recurse forever.
     pub fn scan_directory_for_files<P: AsRef<Path>>(
         dir_path: P,
         extensions: &[&str],
+        follow_symlinks: bool,
     ) -> Result<Vec<FileInfo>> {
+        let dir_path = dir_path.as_ref();
+        let ignore = IgnoreRules::find(dir_path)?;
+        let mut visited = HashSet::new();
+        Self::should_descend(dir_path, true, &mut visited);
+
+        let mut walk = Walk {
+            extensions,
+            ignore: &ignore,
+            follow_symlinks,
+            visited,
+        };
         let mut files = Vec::new();
-        Self::scan_directory_recursive(dir_path.as_ref(), extensions, &mut files)?;
+        Self::scan_directory_recursive(dir_path, &mut walk, &mut files)?;
         Ok(files)
     }
 
+    /// Whether a directory walk should enter `dir`. Symlinked directories are
+    /// skipped unless `follow_symlinks` is set, as is any directory whose
+    /// canonical path is already in `visited`; entered directories are added.
+    pub fn should_descend(
+        dir: &Path,
+        follow_symlinks: bool,
+        visited: &mut HashSet<PathBuf>,
+    ) -> bool {
+        let is_symlink = fs::symlink_metadata(dir)
+            .map(|metadata| metadata.file_type().is_symlink())
+            .unwrap_or(false);
+        if is_symlink && !follow_symlinks {
+            return false;
+        }
+
+        // Broken links can't be canonicalized and have nothing to scan
+        match fs::canonicalize(dir) {
+            Ok(canonical) => visited.insert(canonical),
+            Err(_) => false,
+        }
+    }
+
     fn scan_directory_recursive(
         dir_path: &Path,
-        extensions: &[&str],
+        walk: &mut Walk,
         files: &mut Vec<FileInfo>,
     ) -> Result<()> {
         for entry in fs::read_dir(dir_path)? {
             let entry = entry?;
             let path = entry.path();
+            let is_dir = path.is_dir();
+            if walk.ignore.is_ignored(&path, is_dir) {
+                continue;
+            }
 
-            if path.is_file() {
-                if let Some(ext) = path.extension() {
-                    let ext_str = ext.to_string_lossy().to_lowercase();
-                    if extensions.contains(&ext_str.as_str()) {
-                        if let Ok(metadata) = entry.metadata() {
-                            if let Ok(modified) = metadata.modified() {
-                                files.push(FileInfo {
-                                    path,
-                                    modified,
-                                    size: metadata.len(),
-                                });
-                            }
-                        }
-                    }
-                }
-            } else if path.is_dir() {
+            if let Some(file) = Self::source_file_info(&entry, walk.extensions) {
+                files.push(file);
+            } else if is_dir && Self::should_descend(&path, walk.follow_symlinks, &mut walk.visited)
+            {
                 // Recursively scan subdirectories
-                Self::scan_directory_recursive(&path, extensions, files)?;
+                Self::scan_directory_recursive(&path, walk, files)?;
             }
         }
 
         Ok(())
     }
 
+    /// File info for a directory entry that is a file with one of `extensions`
+    fn source_file_info(entry: &fs::DirEntry, extensions: &[&str]) -> Option<FileInfo> {
+        let path = entry.path();
+        if !path.is_file() {
+            return None;
+        }
+
+        let ext = path.extension()?.to_string_lossy().to_lowercase();
+        if !extensions.contains(&ext.as_str()) {
+            return None;
+        }
+
+        let metadata = entry.metadata().ok()?;
+        Some(FileInfo {
+            modified: metadata.modified().ok()?,
+            size: metadata.len(),
+            path,
+        })
+    }
+
+    /// Files modified at or after `cutoff`
+    pub fn modified_since(files: Vec<FileInfo>, cutoff: SystemTime) -> Vec<FileInfo> {
+        files
+            .into_iter()
+            .filter(|file| file.modified >= cutoff)
+            .collect()
+    }
+
     pub fn find_most_recent(files: &[FileInfo]) -> Option<FileInfo> {
         files.iter().max_by_key(|file| file.modified).cloned()
     }
 
+    /// Stats for every course directory in the notes directory, sorted by
+    /// course ID. Courses are scanned in parallel. Symlinked course
+    /// directories are only included with `follow_symlinks`.
     pub fn scan_notes_directory<P: AsRef<Path>>(
         notes_dir: P,
+        extensions: &[&str],
+        flat_layout: bool,
+        follow_symlinks: bool,
     ) -> Result<Vec<(String, CourseStats)>> {
-        let mut course_stats = Vec::new();
+        let notes_dir = notes_dir.as_ref();
+        let ignore = IgnoreRules::find(notes_dir)?;
+        let mut course_dirs = Vec::new();
 
         for entry in fs::read_dir(notes_dir)? {
             let entry = entry?;
-            if entry.path().is_dir() {
+            let is_symlink = entry.file_type()?.is_symlink();
+            if entry.path().is_dir()
+                && (follow_symlinks || !is_symlink)
+                && !ignore.is_ignored(&entry.path(), true)
+            {
                 if let Some(course_id) = entry.file_name().to_str() {
                     // Check if it looks like a course code (5 digits)
                     if course_id.len() == 5 && course_id.chars().all(|c| c.is_ascii_digit()) {
-                        let stats = Self::scan_course_directory(entry.path())?;
-                        course_stats.push((course_id.to_string(), stats));
+                        course_dirs.push((course_id.to_string(), entry.path()));
                     }
                 }
             }
         }
 
+        let mut course_stats = course_dirs
+            .into_par_iter()
+            .map(|(course_id, course_path)| {
+                let stats = Self::scan_course_directory(
+                    course_path,
+                    extensions,
+                    flat_layout,
+                    follow_symlinks,
+                )?;
+                Ok((course_id, stats))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        course_stats.sort_by(|a, b| a.0.cmp(&b.0));
+
         Ok(course_stats)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_scan_course_directory_layouts() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let course = temp_dir.path().join("02101");
+        fs::create_dir_all(course.join(LECTURES_DIR))?;
+        fs::create_dir_all(course.join(ASSIGNMENTS_DIR))?;
+        fs::write(course.join(LECTURES_DIR).join("lecture-1.typ"), "")?;
+        fs::write(course.join("intro.typ"), "")?;
+        fs::write(course.join("overview.typ"), "")?;
+        fs::write(course.join(ASSIGNMENTS_DIR).join("assignment-1.typ"), "")?;
+
+        let nested = DirectoryScanner::scan_course_directory(&course, &["typ"], false, true)?;
+        assert_eq!((nested.notes_count, nested.assignments_count), (1, 1));
+
+        // Flat notes are the sources directly in the course directory, without
+        // descending into assignments
+        let flat = DirectoryScanner::scan_course_directory(&course, &["typ"], true, true)?;
+        assert_eq!((flat.notes_count, flat.assignments_count), (2, 1));
+        assert_eq!(flat.total_files, 3);
+        assert!(flat.last_activity.is_some());
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinked_directories_are_scanned_once() -> Result<()> {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = TempDir::new()?;
+        let notes_dir = temp_dir.path().join("notes");
+        let lectures = notes_dir.join("02101").join(LECTURES_DIR);
+        fs::create_dir_all(&lectures)?;
+        fs::write(lectures.join("lecture-1.typ"), "")?;
+        // A link back to its own parent would recurse forever without cycle checks
+        symlink(&lectures, lectures.join("loop"))?;
+
+        let shared = temp_dir.path().join("shared").join("02102");
+        fs::create_dir_all(shared.join(LECTURES_DIR))?;
+        fs::write(shared.join(LECTURES_DIR).join("lecture-1.typ"), "")?;
+        symlink(&shared, notes_dir.join("02102"))?;
+
+        let files = DirectoryScanner::scan_directory_for_files(&lectures, &["typ"], true)?;
+        assert_eq!(files.len(), 1);
+
+        let mut courses =
+            DirectoryScanner::scan_notes_directory(&notes_dir, &["typ"], false, true)?;
+        courses.sort_by(|a, b| a.0.cmp(&b.0));
+        let counts: Vec<(&str, usize)> = courses
+            .iter()
+            .map(|(id, stats)| (id.as_str(), stats.notes_count))
+            .collect();
+        assert_eq!(counts, vec![("02101", 1), ("02102", 1)]);
+
+        // Without following, the linked course is left out
+        let courses = DirectoryScanner::scan_notes_directory(&notes_dir, &["typ"], false, false)?;
+        assert_eq!(courses.len(), 1);
+        assert_eq!(courses[0].1.notes_count, 1);
+
+        Ok(())
+    }
+}
@@ -3,19 +3,45 @@
 //! Centralized file operations including opening files, creating directories,
 //! managing backups, and handling file system operations.
 
-use crate::config::Config;
+use crate::config::{Config, LectureNumbering};
+use crate::core::directory_scanner::DirectoryScanner;
+use crate::core::validation::Validator;
 use anyhow::{Context, Result, anyhow};
+use chrono::{Duration, NaiveDate};
 use colored::Colorize;
 use humansize::format_size;
+use std::collections::HashSet;
 use std::fs;
+use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 
+/// Lines of a note read when looking for its title
+const TITLE_HEADER_LINES: usize = 20;
+
+/// Days between the dated placeholders of a batch, one lecture a week
+const BATCH_DAYS_APART: i64 = 7;
+
+/// A placeholder lecture note of a batch, see [`FileOperations::plan_lecture_batch`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedLecture {
+    pub filename: String,
+    /// Date of the lecture; `number` is set instead with sequential numbering
+    pub date: Option<NaiveDate>,
+    pub number: Option<u32>,
+    /// A note with this filename already exists and is left alone
+    pub exists: bool,
+}
+
 pub struct FileOperations;
 
 #[allow(dead_code)]
 impl FileOperations {
     /// Open a file with the configured editor or system default
     pub fn open_file(filepath: &Path, config: &Config) -> Result<()> {
+        if let Some(editor) = &config.editor_override {
+            return Self::run_editor(editor, filepath);
+        }
+
         // Get preferred editor
         let editors = config.get_editor_list();
 
@@ -54,15 +80,36 @@ impl FileOperations {
         println!("{} Opened with {}", "✅".green(), editor);
         Ok(())
     }
+
+    /// Run the `--editor` override attached to the terminal, so terminal
+    /// editors like vim work, waiting for it to exit. There is no fallback.
+    fn run_editor(editor: &str, path: &Path) -> Result<()> {
+        let status = std::process::Command::new(editor)
+            .arg(path)
+            .status()
+            .with_context(|| format!("Failed to run editor: {}", editor))?;
+
+        if !status.success() {
+            anyhow::bail!("Editor {} exited with {}", editor, status);
+        }
+
+        println!("{} Opened with {}", "✅".green(), editor);
+        Ok(())
+    }
     //TOOD: Deduplicate code
 
     /// Opens a given filepath's parent directory
     fn open_file_directory(filepath: &Path, config: &Config) -> Result<()> {
-        let editors = config.get_editor_list();
         let dir = filepath
             .parent()
             .ok_or_else(|| anyhow!("Failed to get parent directory"))?;
 
+        if let Some(editor) = &config.editor_override {
+            return Self::run_editor(editor, dir);
+        }
+
+        let editors = config.get_editor_list();
+
         for editor in editors {
             println!("  Trying {}...", editor.dimmed());
 
@@ -85,19 +132,372 @@ impl FileOperations {
         Ok(())
     }
 
-    pub fn generate_filename(course_id: &str, type_: &str, title: Option<&str>) -> String {
-        let date = chrono::Local::now().format("%Y-%m-%d");
-        match title {
-            Some(t) => format!(
-                "{}-{}-{}.typ",
-                date,
-                course_id,
-                t.to_lowercase().replace(' ', "-")
+    /// Dated filename such as `2025-06-01-02450-ml.typ`, ending in `extension`.
+    /// A named template variant is appended so variant notes are
+    /// distinguishable on disk; the default variant (`None`) keeps the plain
+    /// name. The title is sanitized and, like the variant, shortened to keep
+    /// the filename within `max_length` (see [`FileOperations::fit_filename`]).
+    pub fn generate_filename(
+        course_id: &str,
+        type_: &str,
+        title: Option<&str>,
+        variant: Option<&str>,
+        extension: &str,
+        max_length: usize,
+    ) -> String {
+        Self::generate_filename_on(
+            chrono::Local::now().date_naive(),
+            course_id,
+            type_,
+            title,
+            variant,
+            extension,
+            max_length,
+        )
+    }
+
+    /// Like [`FileOperations::generate_filename`], dated `date` instead of today
+    pub fn generate_filename_on(
+        date: NaiveDate,
+        course_id: &str,
+        type_: &str,
+        title: Option<&str>,
+        variant: Option<&str>,
+        extension: &str,
+        max_length: usize,
+    ) -> String {
+        let date = date.format("%Y-%m-%d");
+        let title = title
+            .map(Validator::sanitize_filename)
+            .filter(|title| !title.is_empty());
+        let variant = Self::variant_suffix(variant);
+
+        let name = match (title, variant) {
+            (Some(title), Some(variant)) => format!("{}-{}", title, variant),
+            (Some(title), None) => title,
+            (None, Some(variant)) => variant,
+            (None, None) => type_.to_string(),
+        };
+
+        Self::fit_filename(
+            &format!("{}-{}-", date, course_id),
+            &name,
+            extension,
+            max_length,
+        )
+    }
+
+    /// `<prefix><name>.<extension>`, with `name` truncated to keep the whole
+    /// filename within `max_length` characters (0 for no limit). The prefix and
+    /// extension are always kept; see [`Validator::truncate_filename`].
+    pub fn fit_filename(prefix: &str, name: &str, extension: &str, max_length: usize) -> String {
+        let name = if max_length == 0 {
+            name.to_string()
+        } else {
+            let budget = max_length.saturating_sub(prefix.chars().count() + extension.len() + 1);
+            Validator::truncate_filename(name, budget.max(1))
+        };
+
+        format!("{}{}.{}", prefix, name, extension)
+    }
+
+    /// Filename for a new lecture note in `lectures_dir`, following the
+    /// configured `note_preferences.lecture_numbering` scheme
+    pub fn generate_lecture_filename(
+        lectures_dir: &Path,
+        course_id: &str,
+        type_: &str,
+        title: Option<&str>,
+        variant: Option<&str>,
+        config: &Config,
+    ) -> Result<String> {
+        let max_length = config.note_preferences.max_filename_length;
+        let extension = config.typst.new_file_extension();
+
+        match config.note_preferences.lecture_numbering {
+            LectureNumbering::Date => Ok(Self::generate_filename(
+                course_id, type_, title, variant, extension, max_length,
+            )),
+            LectureNumbering::Sequential => {
+                let (number, width) =
+                    Self::next_lecture_number(lectures_dir, &config.typst.source_extensions())?;
+                Ok(Self::sequential_filename(
+                    number, width, variant, extension, max_length,
+                ))
+            }
+        }
+    }
+
+    /// Filenames for `count` upcoming lecture notes. Sequential numbering
+    /// continues after the highest existing lecture; date numbering places the
+    /// notes a week apart from `start`, marking dates whose note already
+    /// exists so they can be skipped.
+    pub fn plan_lecture_batch(
+        lectures_dir: &Path,
+        course_id: &str,
+        type_: &str,
+        variant: Option<&str>,
+        count: u32,
+        start: NaiveDate,
+        config: &Config,
+    ) -> Result<Vec<PlannedLecture>> {
+        let max_length = config.note_preferences.max_filename_length;
+        let extension = config.typst.new_file_extension();
+
+        match config.note_preferences.lecture_numbering {
+            LectureNumbering::Date => {
+                let existing =
+                    Self::source_file_names(lectures_dir, &config.typst.source_extensions())?;
+                Ok((0..count)
+                    .map(|week| {
+                        let date = start + Duration::days(BATCH_DAYS_APART * i64::from(week));
+                        // Any note of the course on that day counts, whatever its title
+                        let prefix = format!("{}-{}-", date.format("%Y-%m-%d"), course_id);
+                        PlannedLecture {
+                            exists: existing.iter().any(|name| name.starts_with(&prefix)),
+                            filename: Self::generate_filename_on(
+                                date, course_id, type_, None, variant, extension, max_length,
+                            ),
+                            date: Some(date),
+                            number: None,
+                        }
+                    })
+                    .collect())
+            }
+            LectureNumbering::Sequential => {
+                let (first, width) =
+                    Self::next_lecture_number(lectures_dir, &config.typst.source_extensions())?;
+                Ok((first..first + count)
+                    .map(|number| {
+                        let filename = Self::sequential_filename(
+                            number, width, variant, extension, max_length,
+                        );
+                        PlannedLecture {
+                            exists: lectures_dir.join(&filename).exists(),
+                            filename,
+                            date: None,
+                            number: Some(number),
+                        }
+                    })
+                    .collect())
+            }
+        }
+    }
+
+    /// Names of the files in `dir` with one of `extensions`, none when it
+    /// doesn't exist
+    fn source_file_names(dir: &Path, extensions: &[&str]) -> Result<Vec<String>> {
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut names = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            let is_source = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| extensions.contains(&ext));
+            if let Some(name) = path.file_name().and_then(|name| name.to_str())
+                && is_source
+                && path.is_file()
+            {
+                names.push(name.to_string());
+            }
+        }
+        Ok(names)
+    }
+
+    /// `lecture_NN.typ`, or `lecture_NN-<variant>.typ` for a named variant,
+    /// with `extension`
+    fn sequential_filename(
+        number: u32,
+        width: usize,
+        variant: Option<&str>,
+        extension: &str,
+        max_length: usize,
+    ) -> String {
+        match Self::variant_suffix(variant) {
+            Some(variant) => Self::fit_filename(
+                &format!("lecture_{:0width$}-", number, width = width),
+                &variant,
+                extension,
+                max_length,
             ),
-            None => format!("{}-{}-{}.typ", date, course_id, type_),
+            None => format!("lecture_{:0width$}.{}", number, extension, width = width),
         }
     }
 
+    /// Sanitized variant name for filenames, `None` for the default variant
+    fn variant_suffix(variant: Option<&str>) -> Option<String> {
+        variant
+            .map(Validator::sanitize_filename)
+            .filter(|variant| !variant.is_empty())
+    }
+
+    /// Next lecture number after the highest `lecture_NN` file (or variant
+    /// `lecture_NN-<variant>` file) in a directory,
+    /// together with the zero-padding width to use. Gaps are not filled, and the
+    /// width never shrinks below the widest existing number (minimum two digits).
+    pub fn next_lecture_number(lectures_dir: &Path, extensions: &[&str]) -> Result<(u32, usize)> {
+        let mut highest = 0;
+        let mut width = 2;
+
+        if lectures_dir.exists() {
+            for entry in fs::read_dir(lectures_dir)? {
+                let path = entry?.path();
+                let is_source = path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| extensions.contains(&ext));
+                if !path.is_file() || !is_source {
+                    continue;
+                }
+
+                let Some(digits) = path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .and_then(|stem| stem.strip_prefix("lecture_"))
+                    .map(|rest| rest.split_once('-').map_or(rest, |(digits, _)| digits))
+                else {
+                    continue;
+                };
+                if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                    continue;
+                }
+
+                if let Ok(number) = digits.parse::<u32>() {
+                    highest = highest.max(number);
+                    width = width.max(digits.len());
+                }
+            }
+        }
+
+        Ok((highest + 1, width))
+    }
+
+    /// Title from the `title: "..."` argument of a note's `#show: ...with(...)`
+    /// header. Only the first [`TITLE_HEADER_LINES`] lines are read.
+    pub fn read_note_title(path: &Path) -> Option<String> {
+        Self::read_header_argument(path, "title")
+    }
+
+    /// Value of a string argument such as `author: "..."` in a note's
+    /// `#show: ...with(...)` header, read the same way as the title
+    pub fn read_header_argument(path: &Path, name: &str) -> Option<String> {
+        let file = fs::File::open(path).ok()?;
+        let mut in_show_rule = false;
+
+        for line in BufReader::new(file)
+            .lines()
+            .take(TITLE_HEADER_LINES)
+            .map_while(|line| line.ok())
+        {
+            let line = line.trim();
+            if line.starts_with("#show:") {
+                in_show_rule = true;
+            }
+            if !in_show_rule {
+                continue;
+            }
+
+            if let Some(value) = Self::string_argument(line, name) {
+                return Some(value);
+            }
+            if line.starts_with(')') {
+                in_show_rule = false;
+            }
+        }
+
+        None
+    }
+
+    /// `content` with the string argument `name` of its `#show: ...with(...)`
+    /// header set to `value`, found the same way as
+    /// [`FileOperations::read_header_argument`]. Only that argument changes;
+    /// `None` when the header has no such argument or it already has `value`.
+    pub fn replace_header_argument(content: &str, name: &str, value: &str) -> Option<String> {
+        let mut in_show_rule = false;
+        let mut offset = 0;
+
+        for line in content.split_inclusive('\n').take(TITLE_HEADER_LINES) {
+            let trimmed = line.trim();
+            if trimmed.starts_with("#show:") {
+                in_show_rule = true;
+            }
+
+            if in_show_rule {
+                if let Some((span, current)) = Self::string_argument_span(line, name) {
+                    if current == value {
+                        return None;
+                    }
+
+                    let quoted =
+                        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""));
+                    let start = offset + span.start;
+                    let end = offset + span.end;
+                    return Some(format!(
+                        "{}{}{}",
+                        &content[..start],
+                        quoted,
+                        &content[end..]
+                    ));
+                }
+                if trimmed.starts_with(')') {
+                    in_show_rule = false;
+                }
+            }
+            offset += line.len();
+        }
+
+        None
+    }
+
+    /// Note title for display, falling back to the file name
+    pub fn note_display_name(path: &Path) -> String {
+        Self::read_note_title(path).unwrap_or_else(|| {
+            path.file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string()
+        })
+    }
+
+    /// Quoted value of a `<name>:` argument within a line. `name` must start
+    /// an argument, so `title` doesn't match `subtitle:`.
+    fn string_argument(line: &str, name: &str) -> Option<String> {
+        let (_, value) = Self::string_argument_span(line, name)?;
+        let value = value.trim();
+        (!value.is_empty()).then(|| value.to_string())
+    }
+
+    /// Byte range of the quoted value of a `<name>:` argument within a line,
+    /// quotes included, with the unescaped value
+    fn string_argument_span(line: &str, name: &str) -> Option<(std::ops::Range<usize>, String)> {
+        let key = format!("{}:", name);
+        let (start, _) = line.match_indices(&key).find(|(start, _)| {
+            !line[..*start]
+                .chars()
+                .next_back()
+                .is_some_and(|c| c.is_alphanumeric() || c == '-' || c == '_')
+        })?;
+        let after_key = &line[start + key.len()..];
+        let quote = line.len() - after_key.trim_start().len();
+        let rest = line[quote..].strip_prefix('"')?;
+
+        let mut value = String::new();
+        let mut chars = rest.char_indices();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '\\' => value.extend(chars.next().map(|(_, c)| c)),
+                '"' => return Some((quote..quote + 1 + index + 1, value)),
+                c => value.push(c),
+            }
+        }
+
+        None
+    }
+
     /// Open a file via Obsidian URI
     pub fn open_obsidian_file(vault_path: &Path, relative_file_path: &str) -> Result<()> {
         let vault_name = vault_path
@@ -133,9 +533,8 @@ impl FileOperations {
 
     /// Create a file with content, handling backups and overwrites
     pub fn create_file_with_content(filepath: &Path, content: &str, config: &Config) -> Result<()> {
-        // Create parent directories if they don't exist
         if let Some(parent) = filepath.parent() {
-            fs::create_dir_all(parent)?;
+            Self::prepare_note_directory(parent, config)?;
         }
 
         // Handle existing file
@@ -152,6 +551,25 @@ impl FileOperations {
         Ok(())
     }
 
+    /// Write content atomically by writing a sibling temp file and renaming it over the target
+    pub fn safe_write(filepath: &Path, content: &str) -> Result<()> {
+        let file_name = filepath
+            .file_name()
+            .ok_or_else(|| anyhow!("Invalid file path: {}", filepath.display()))?;
+        let temp_path = filepath.with_file_name(format!(".{}.tmp", file_name.to_string_lossy()));
+
+        fs::write(&temp_path, content)
+            .with_context(|| format!("Failed to write temporary file: {}", temp_path.display()))?;
+
+        if let Err(e) = fs::rename(&temp_path, filepath) {
+            let _ = fs::remove_file(&temp_path);
+            return Err(e)
+                .with_context(|| format!("Failed to replace file: {}", filepath.display()));
+        }
+
+        Ok(())
+    }
+
     /// Create a backup of an existing file
     pub fn create_backup(file_path: &Path) -> Result<()> {
         if !file_path.exists() {
@@ -184,6 +602,80 @@ impl FileOperations {
         Ok(backup_path)
     }
 
+    /// Whether a file is a backup: a timestamped copy from [`Self::create_backup`]
+    /// (`<name>.bak.<timestamp>`) or a plain `<name>.bak`
+    pub fn is_backup_file(path: &Path) -> bool {
+        let Some(name) = path.file_name().map(|name| name.to_string_lossy()) else {
+            return false;
+        };
+
+        name.rsplit_once(".bak").is_some_and(|(stem, rest)| {
+            !stem.is_empty()
+                && (rest.is_empty()
+                    || rest.strip_prefix('.').is_some_and(|timestamp| {
+                        !timestamp.is_empty()
+                            && timestamp.chars().all(|c| c.is_ascii_digit() || c == '_')
+                    }))
+        })
+    }
+
+    /// Backup files under a directory, searched recursively. Symlinked
+    /// directories are only entered with `follow_symlinks`, each at most once.
+    pub fn find_backups(dir: &Path, follow_symlinks: bool) -> Result<Vec<PathBuf>> {
+        let mut backups = Vec::new();
+        if !dir.is_dir() {
+            return Ok(backups);
+        }
+
+        let mut visited: HashSet<PathBuf> = fs::canonicalize(dir).into_iter().collect();
+        Self::collect_backups(dir, follow_symlinks, &mut visited, &mut backups)?;
+
+        backups.sort();
+        Ok(backups)
+    }
+
+    fn collect_backups(
+        dir: &Path,
+        follow_symlinks: bool,
+        visited: &mut HashSet<PathBuf>,
+        backups: &mut Vec<PathBuf>,
+    ) -> Result<()> {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                if DirectoryScanner::should_descend(&path, follow_symlinks, visited) {
+                    Self::collect_backups(&path, follow_symlinks, visited, backups)?;
+                }
+            } else if Self::is_backup_file(&path) {
+                backups.push(path);
+            }
+        }
+        Ok(())
+    }
+
+    /// Delete every backup file under a directory, returning the number of
+    /// files removed and the bytes freed
+    pub fn prune_backups(dir: &Path, follow_symlinks: bool) -> Result<(usize, u64)> {
+        let backups = Self::find_backups(dir, follow_symlinks)?;
+        let mut freed = 0;
+
+        for backup in &backups {
+            freed += fs::metadata(backup)
+                .map(|metadata| metadata.len())
+                .unwrap_or(0);
+            fs::remove_file(backup)
+                .with_context(|| format!("Failed to remove backup {}", backup.display()))?;
+        }
+
+        Ok((backups.len(), freed))
+    }
+
+    /// Open a directory in the system file manager
+    pub fn open_in_file_manager(dir_path: &Path) -> Result<()> {
+        opener::open(dir_path)
+            .with_context(|| format!("Failed to open {} in file manager", dir_path.display()))
+    }
+
     /// Ensure directory exists, create if it doesn't
     pub fn ensure_directory_exists(dir_path: &Path) -> Result<()> {
         if !dir_path.exists() {
@@ -197,14 +689,29 @@ impl FileOperations {
         Ok(())
     }
 
+    /// Make sure the directory a note is written into exists, creating it
+    /// unless `paths.create_missing_dirs` is off. Then a missing directory is
+    /// an error that points at `noter setup`.
+    pub fn prepare_note_directory(dir: &Path, config: &Config) -> Result<()> {
+        if dir.is_dir() {
+            return Ok(());
+        }
+
+        if !config.paths.create_missing_dirs {
+            anyhow::bail!(
+                "Directory {} doesn't exist and paths.create_missing_dirs is off. Check the path, or run 'noter setup' to create the course directories",
+                dir.display()
+            );
+        }
+
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create directory {}", dir.display()))
+    }
+
     /// Ensure course directory structure exists (lectures and assignments)
-    pub fn ensure_course_structure(
-        base_path: &Path,
-        course_id: &str,
-    ) -> Result<(PathBuf, PathBuf)> {
-        let course_dir = base_path.join(course_id);
-        let lectures_dir = course_dir.join("lectures");
-        let assignments_dir = course_dir.join("assignments");
+    pub fn ensure_course_structure(config: &Config, course_id: &str) -> Result<(PathBuf, PathBuf)> {
+        let lectures_dir = config.get_lectures_dir(course_id);
+        let assignments_dir = config.get_assignments_dir(course_id);
 
         Self::ensure_directory_exists(&lectures_dir)?;
         Self::ensure_directory_exists(&assignments_dir)?;
@@ -235,6 +742,35 @@ impl FileOperations {
         Ok(Self::format_file_size(size))
     }
 
+    /// Combined size of `files` in bytes, counting files that can't be read as empty
+    pub fn total_size(files: &[PathBuf]) -> u64 {
+        files
+            .iter()
+            .filter_map(|file| fs::metadata(file).ok())
+            .map(|metadata| metadata.len())
+            .sum()
+    }
+
+    /// Size of every file below `dir` in bytes, without following symlinks.
+    /// A missing directory is empty.
+    pub fn directory_size(dir: &Path) -> u64 {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return 0;
+        };
+
+        entries
+            .flatten()
+            .filter_map(|entry| {
+                let metadata = fs::symlink_metadata(entry.path()).ok()?;
+                Some(if metadata.is_dir() {
+                    Self::directory_size(&entry.path())
+                } else {
+                    metadata.len()
+                })
+            })
+            .sum()
+    }
+
     /// Format file size as human readable string
     pub fn format_file_size(size: u64) -> String {
         format_size(size, humansize::DECIMAL)
@@ -421,6 +957,300 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_read_note_title_from_header() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let note = temp_dir.path().join("2025-03-12-02101-lecture.typ");
+        fs::write(
+            &note,
+            "#import \"@local/dtu-template:0.1.0\":*\n\n\
+             #show: dtu-note.with(\n  course: \"02101\",\n  \
+             title: \"Loops and \\\"Recursion\\\"\",\n  author: \"Student\"\n)\n\n\
+             = Key Concepts\n",
+        )
+        .unwrap();
+        assert_eq!(
+            FileOperations::note_display_name(&note),
+            "Loops and \"Recursion\""
+        );
+        assert_eq!(
+            FileOperations::read_header_argument(&note, "author").as_deref(),
+            Some("Student")
+        );
+
+        // Replacing an argument leaves the rest of the note alone
+        let content = fs::read_to_string(&note).unwrap();
+        let renamed =
+            FileOperations::replace_header_argument(&content, "title", "Loops \\ \"More\"")
+                .unwrap();
+        assert_eq!(
+            renamed,
+            content.replace(
+                r#"title: "Loops and \"Recursion\"""#,
+                r#"title: "Loops \\ \"More\"""#
+            )
+        );
+        fs::write(&note, &renamed).unwrap();
+        assert_eq!(
+            FileOperations::read_note_title(&note).as_deref(),
+            Some("Loops \\ \"More\"")
+        );
+        assert_eq!(
+            FileOperations::replace_header_argument(&renamed, "title", "Loops \\ \"More\""),
+            None
+        );
+        assert_eq!(
+            FileOperations::replace_header_argument(&renamed, "course-name", "Intro"),
+            None
+        );
+
+        // A title: outside the show rule is not the note title
+        let plain = temp_dir.path().join("scratch.typ");
+        fs::write(&plain, "= Scratch\ntitle: \"Not this\"\n").unwrap();
+        assert_eq!(FileOperations::read_note_title(&plain), None);
+        assert_eq!(FileOperations::note_display_name(&plain), "scratch.typ");
+    }
+
+    #[test]
+    fn test_next_lecture_number_skips_gaps_and_keeps_padding() {
+        let temp_dir = TempDir::new().unwrap();
+        let lectures = temp_dir.path();
+        let extensions = ["typ", "typst"];
+
+        assert_eq!(
+            FileOperations::next_lecture_number(lectures, &extensions).unwrap(),
+            (1, 2)
+        );
+
+        for name in ["lecture_01.typ", "lecture_02.typ", "lecture_05.typst"] {
+            fs::write(lectures.join(name), "").unwrap();
+        }
+        // Not lecture sources
+        fs::write(lectures.join("lecture_09.pdf"), "").unwrap();
+        fs::write(lectures.join("2025-03-12-02101-lecture.typ"), "").unwrap();
+
+        assert_eq!(
+            FileOperations::next_lecture_number(lectures, &extensions).unwrap(),
+            (6, 2)
+        );
+
+        fs::write(lectures.join("lecture_007.typ"), "").unwrap();
+        assert_eq!(
+            FileOperations::next_lecture_number(lectures, &extensions).unwrap(),
+            (8, 3)
+        );
+
+        let mut config = Config::default();
+        config.note_preferences.lecture_numbering = LectureNumbering::Sequential;
+        assert_eq!(
+            FileOperations::generate_lecture_filename(
+                lectures, "02101", "lecture", None, None, &config
+            )
+            .unwrap(),
+            "lecture_008.typ"
+        );
+    }
+
+    #[test]
+    fn test_plan_lecture_batch_follows_numbering_scheme() {
+        let temp_dir = TempDir::new().unwrap();
+        let lectures = temp_dir.path();
+        let start = NaiveDate::from_ymd_opt(2026, 9, 1).unwrap();
+        fs::write(lectures.join("2026-09-08-02101-lecture.typ"), "").unwrap();
+
+        let mut config = Config::default();
+        let planned = FileOperations::plan_lecture_batch(
+            lectures, "02101", "lecture", None, 3, start, &config,
+        )
+        .unwrap();
+        let filenames: Vec<&str> = planned.iter().map(|p| p.filename.as_str()).collect();
+        assert_eq!(
+            filenames,
+            [
+                "2026-09-01-02101-lecture.typ",
+                "2026-09-08-02101-lecture.typ",
+                "2026-09-15-02101-lecture.typ"
+            ]
+        );
+        assert_eq!(
+            planned.iter().map(|p| p.exists).collect::<Vec<_>>(),
+            [false, true, false]
+        );
+        assert_eq!(planned[2].date, NaiveDate::from_ymd_opt(2026, 9, 15));
+
+        fs::write(lectures.join("lecture_04.typ"), "").unwrap();
+        config.note_preferences.lecture_numbering = LectureNumbering::Sequential;
+        let planned = FileOperations::plan_lecture_batch(
+            lectures, "02101", "lecture", None, 2, start, &config,
+        )
+        .unwrap();
+        assert_eq!(planned[0].filename, "lecture_05.typ");
+        assert_eq!(planned[1].filename, "lecture_06.typ");
+        assert_eq!(planned[1].number, Some(6));
+    }
+
+    #[test]
+    fn test_plan_lecture_batch_matches_dated_notes_by_prefix() {
+        let temp_dir = TempDir::new().unwrap();
+        let lectures = temp_dir.path();
+        let start = NaiveDate::from_ymd_opt(2026, 9, 1).unwrap();
+        // A titled note and a note of another course on planned days, and a
+        // file that isn't a note
+        fs::write(lectures.join("2026-09-01-02101-graph-search.typ"), "").unwrap();
+        fs::write(lectures.join("2026-09-08-02102-lecture.typ"), "").unwrap();
+        fs::write(lectures.join("2026-09-15-02101-lecture.pdf"), "").unwrap();
+
+        let planned = FileOperations::plan_lecture_batch(
+            lectures,
+            "02101",
+            "lecture",
+            None,
+            3,
+            start,
+            &Config::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            planned.iter().map(|p| p.exists).collect::<Vec<_>>(),
+            [true, false, false]
+        );
+        assert_eq!(planned[0].filename, "2026-09-01-02101-lecture.typ");
+    }
+
+    #[test]
+    fn test_generate_filename_truncates_long_titles() {
+        let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+        let prefix = format!("{}-02101-", date);
+        let title = |last: char| format!("{}{}", "Dynamic Programming ".repeat(15), last);
+        assert_eq!(title('a').len(), 301);
+
+        let first = FileOperations::generate_filename(
+            "02101",
+            "assignment",
+            Some(&title('a')),
+            None,
+            "typ",
+            100,
+        );
+        let second = FileOperations::generate_filename(
+            "02101",
+            "assignment",
+            Some(&title('b')),
+            None,
+            "typ",
+            100,
+        );
+
+        for filename in [&first, &second] {
+            assert!(filename.len() <= 100, "{}", filename);
+            assert!(filename.starts_with(&prefix));
+            assert!(filename.ends_with(".typ"));
+        }
+        assert_ne!(first, second);
+
+        // A limit of 0 disables truncation
+        let unlimited = FileOperations::generate_filename(
+            "02101",
+            "assignment",
+            Some(&title('a')),
+            None,
+            "typ",
+            0,
+        );
+        assert!(unlimited.len() > 300);
+
+        // Assignment filenames have no prefix but keep the extension
+        let assignment = FileOperations::fit_filename(
+            "",
+            &Validator::sanitize_filename(&title('a')),
+            "typ",
+            100,
+        );
+        assert_eq!(assignment.len(), 100);
+        assert!(assignment.ends_with(".typ"));
+
+        // The extension counts towards the limit whatever its length
+        let longer = FileOperations::fit_filename("", &title('a'), "typst", 100);
+        assert_eq!(longer.len(), 100);
+        assert!(longer.ends_with(".typst"));
+    }
+
+    #[test]
+    fn test_generate_filename_includes_named_variant() {
+        let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+
+        // The default variant keeps the plain name
+        assert_eq!(
+            FileOperations::generate_filename("02450", "lecture", None, None, "typ", 100),
+            format!("{}-02450-lecture.typ", date)
+        );
+        assert_eq!(
+            FileOperations::generate_filename(
+                "02450",
+                "lecture",
+                Some("Neural Nets"),
+                None,
+                "typ",
+                100
+            ),
+            format!("{}-02450-neural-nets.typ", date)
+        );
+
+        assert_eq!(
+            FileOperations::generate_filename("02450", "lecture", None, Some("ml"), "typ", 100),
+            format!("{}-02450-ml.typ", date)
+        );
+        assert_eq!(
+            FileOperations::generate_filename(
+                "02450",
+                "lecture",
+                Some("Neural Nets"),
+                Some("Deep Learning/2"),
+                "typ",
+                100
+            ),
+            format!("{}-02450-neural-nets-deep-learning-2.typ", date)
+        );
+
+        // Sequential numbering keeps counting across variant files
+        let temp_dir = TempDir::new().unwrap();
+        let lectures = temp_dir.path();
+        fs::write(lectures.join("lecture_03-ml.typ"), "").unwrap();
+
+        let mut config = Config::default();
+        config.note_preferences.lecture_numbering = LectureNumbering::Sequential;
+        assert_eq!(
+            FileOperations::generate_lecture_filename(
+                lectures,
+                "02450",
+                "lecture",
+                None,
+                Some("ml"),
+                &config
+            )
+            .unwrap(),
+            "lecture_04-ml.typ"
+        );
+        assert_eq!(
+            FileOperations::generate_lecture_filename(
+                lectures, "02450", "lecture", None, None, &config
+            )
+            .unwrap(),
+            "lecture_04.typ"
+        );
+
+        // New files use the first configured source extension
+        config.typst.source_extensions = vec![".typst".to_string(), "typ".to_string()];
+        assert_eq!(
+            FileOperations::generate_lecture_filename(
+                lectures, "02450", "lecture", None, None, &config
+            )
+            .unwrap(),
+            "lecture_04.typst"
+        );
+    }
+
     #[test]
     #[ignore = "Currently incompatible with Humansize"]
     fn test_format_file_size() {
@@ -451,4 +1281,62 @@ mod tests {
         FileOperations::ensure_directory_exists(&test_path).unwrap();
         assert!(Path::new(&test_path).exists());
     }
+
+    #[test]
+    fn test_create_file_respects_create_missing_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        let note = temp_dir.path().join("notfs").join("02101").join("note.typ");
+        let mut config = Config::default();
+
+        config.paths.create_missing_dirs = false;
+        let error = FileOperations::create_file_with_content(&note, "= Note", &config).unwrap_err();
+        assert!(error.to_string().contains("noter setup"), "{}", error);
+        assert!(!temp_dir.path().join("notfs").exists());
+
+        config.paths.create_missing_dirs = true;
+        FileOperations::create_file_with_content(&note, "= Note", &config).unwrap();
+        assert_eq!(fs::read_to_string(&note).unwrap(), "= Note");
+    }
+
+    #[test]
+    fn test_safe_write_replaces_content() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("note.typ");
+        fs::write(&file_path, "old").unwrap();
+
+        FileOperations::safe_write(&file_path, "new").unwrap();
+
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "new");
+        assert!(!temp_dir.path().join(".note.typ.tmp").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_find_backups_survives_symlink_loops() -> Result<()> {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = TempDir::new()?;
+        let course = temp_dir.path().join("02101");
+        let lectures = course.join("lectures");
+        fs::create_dir_all(&lectures)?;
+        fs::write(lectures.join("lecture-1.typ.bak.20250101_120000"), "old")?;
+        fs::write(lectures.join("lecture-1.typ"), "= Current")?;
+        symlink(&course, lectures.join("loop"))?;
+
+        let elsewhere = temp_dir.path().join("elsewhere");
+        fs::create_dir_all(&elsewhere)?;
+        fs::write(elsewhere.join("keep.bak"), "not a course backup")?;
+        symlink(&elsewhere, course.join("linked"))?;
+
+        let expected = vec![lectures.join("lecture-1.typ.bak.20250101_120000")];
+        assert_eq!(FileOperations::find_backups(&course, false)?, expected);
+        // Following links, the loop is entered at most once
+        assert_eq!(FileOperations::find_backups(&course, true)?.len(), 2);
+
+        assert_eq!(FileOperations::prune_backups(&course, false)?, (1, 3));
+        assert!(elsewhere.join("keep.bak").exists());
+        assert!(lectures.join("lecture-1.typ").exists());
+
+        Ok(())
+    }
 }
@@ -0,0 +1,226 @@
+//! Git integration for git-backed notes directories
+//!
+//! Shells out to `git` to commit newly created notes when
+//! `note_preferences.git_auto_commit` is enabled, and to sync the notes
+//! directory with its remote. Every git process runs with the configured
+//! external command timeout.
+
+use anyhow::Result;
+use std::path::Path;
+use std::process::Command;
+
+use crate::config::Config;
+use crate::core::process::ProcessRunner;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GitCommitOutcome {
+    /// Auto-commit is turned off in the configuration
+    Disabled,
+    /// The file does not live inside a git work tree
+    NotARepository,
+    /// The file was committed
+    Committed,
+}
+
+pub struct GitIntegration;
+
+#[allow(dead_code)]
+impl GitIntegration {
+    /// Commit a newly created file if auto-commit is enabled and it lives in a git repo
+    pub fn auto_commit(
+        file_path: &Path,
+        message: &str,
+        config: &Config,
+    ) -> Result<GitCommitOutcome> {
+        Self::auto_commit_all(&[file_path], message, config)
+    }
+
+    /// Like [`GitIntegration::auto_commit`], committing files of one directory together
+    pub fn auto_commit_all(
+        file_paths: &[&Path],
+        message: &str,
+        config: &Config,
+    ) -> Result<GitCommitOutcome> {
+        if !config.note_preferences.git_auto_commit {
+            return Ok(GitCommitOutcome::Disabled);
+        }
+
+        let Some(first) = file_paths.first() else {
+            return Ok(GitCommitOutcome::Disabled);
+        };
+        let repo_dir = first.parent().unwrap_or_else(|| Path::new("."));
+        if !Self::is_git_repository(repo_dir) {
+            return Ok(GitCommitOutcome::NotARepository);
+        }
+
+        Self::commit_files(file_paths, message)?;
+        Ok(GitCommitOutcome::Committed)
+    }
+
+    /// Check whether a directory is inside a git work tree
+    pub fn is_git_repository(dir: &Path) -> bool {
+        ProcessRunner::output(
+            Command::new("git")
+                .arg("-C")
+                .arg(dir)
+                .args(["rev-parse", "--is-inside-work-tree"]),
+        )
+        .is_ok_and(|output| output.status.success())
+    }
+
+    /// Stage and commit a single file, leaving anything else in the index untouched
+    pub fn commit_file(file_path: &Path, message: &str) -> Result<()> {
+        Self::commit_files(&[file_path], message)
+    }
+
+    /// Stage and commit files that share a directory in a single commit
+    pub fn commit_files(file_paths: &[&Path], message: &str) -> Result<()> {
+        let Some(first) = file_paths.first() else {
+            return Ok(());
+        };
+        let repo_dir = first.parent().unwrap_or_else(|| Path::new("."));
+        let file_names = file_paths
+            .iter()
+            .map(|file_path| {
+                file_path
+                    .file_name()
+                    .ok_or_else(|| anyhow::anyhow!("Invalid file path: {}", file_path.display()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Self::run_git(repo_dir, &["add", "--"], &file_names)?;
+        Self::run_git(repo_dir, &["commit", "-m", message, "--"], &file_names)?;
+
+        Ok(())
+    }
+
+    /// Check whether a directory is the root of a git repository (has a `.git` entry)
+    pub fn has_git_dir(dir: &Path) -> bool {
+        dir.join(".git").exists()
+    }
+
+    /// Check for uncommitted changes (staged, unstaged or untracked) in the work tree
+    pub fn has_uncommitted_changes(repo_dir: &Path) -> Result<bool> {
+        let output = Self::git_output(repo_dir, &["status", "--porcelain"])?;
+        Ok(!output.trim().is_empty())
+    }
+
+    /// Pull with rebase, aborting the rebase and reporting conflicts if they occur
+    pub fn pull_rebase(repo_dir: &Path) -> Result<String> {
+        let output = ProcessRunner::output(
+            Command::new("git")
+                .arg("-C")
+                .arg(repo_dir)
+                .args(["pull", "--rebase"]),
+        )?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        if output.status.success() {
+            return Ok(stdout.trim().to_string());
+        }
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stdout.contains("CONFLICT") || stderr.contains("CONFLICT") {
+            let conflicted =
+                Self::git_output(repo_dir, &["diff", "--name-only", "--diff-filter=U"])
+                    .unwrap_or_default();
+            let _ = Self::git_output(repo_dir, &["rebase", "--abort"]);
+
+            anyhow::bail!(
+                "Pull produced merge conflicts in:\n{}\nThe rebase was aborted; resolve the conflicts manually with 'git pull --rebase'",
+                conflicted
+                    .lines()
+                    .map(|file| format!("  • {}", file))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            );
+        }
+
+        anyhow::bail!("git pull failed: {}", stderr.trim());
+    }
+
+    /// Push the current branch to its upstream
+    pub fn push(repo_dir: &Path) -> Result<()> {
+        Self::git_output(repo_dir, &["push"]).map(|_| ())
+    }
+
+    /// `user.name` from the git configuration, if git is installed and it's set
+    pub fn user_name() -> Option<String> {
+        let output =
+            ProcessRunner::output(Command::new("git").args(["config", "--get", "user.name"]))
+                .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (!name.is_empty()).then_some(name)
+    }
+
+    /// Generate a commit message such as "Add lecture note for 02101"
+    pub fn commit_message(kind: &str, course_id: &str) -> String {
+        format!("Add {} for {}", kind, course_id)
+    }
+
+    fn git_output(repo_dir: &Path, args: &[&str]) -> Result<String> {
+        let output = ProcessRunner::output(Command::new("git").arg("-C").arg(repo_dir).args(args))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("git {} failed: {}", args[0], stderr.trim());
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    fn run_git(repo_dir: &Path, args: &[&str], paths: &[&std::ffi::OsStr]) -> Result<()> {
+        let output = ProcessRunner::output(
+            Command::new("git")
+                .arg("-C")
+                .arg(repo_dir)
+                .args(args)
+                .args(paths),
+        )?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("git {} failed: {}", args[0], stderr.trim());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_auto_commit_disabled_by_default() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("note.typ");
+        std::fs::write(&file_path, "= Note").unwrap();
+
+        let outcome =
+            GitIntegration::auto_commit(&file_path, "Add note", &Config::default()).unwrap();
+        assert_eq!(outcome, GitCommitOutcome::Disabled);
+    }
+
+    #[test]
+    fn test_has_git_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(!GitIntegration::has_git_dir(temp_dir.path()));
+
+        std::fs::create_dir(temp_dir.path().join(".git")).unwrap();
+        assert!(GitIntegration::has_git_dir(temp_dir.path()));
+    }
+
+    #[test]
+    fn test_commit_message() {
+        assert_eq!(
+            GitIntegration::commit_message("lecture note", "02101"),
+            "Add lecture note for 02101"
+        );
+    }
+}
@@ -3,14 +3,32 @@
 //! Handles downloading templates from multiple GitHub repositories with fallback support
 
 use crate::config::{Config, Metadata, ObsidianIntegrationConfig, TemplateRepository};
+use crate::core::template_fetcher::{self, DEFAULT_HOST, TemplateFetcher};
+use crate::error::{NoterError, NoterResult};
 use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 const DEFAULT_TEMPLATE_REPO: &str = "HollowNumber/dtu-note-template";
 const GITHUB_API_BASE: &str = "https://api.github.com";
 
+/// Attempts at downloading a release archive before giving up
+const DOWNLOAD_ATTEMPTS: u32 = 3;
+/// Wait before a retry, multiplied by the number of failed attempts
+const RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Download an interrupted archive download belongs to, stored next to its
+/// partial file so a retry can continue it
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+struct PartialDownload {
+    url: String,
+    /// Size of the complete archive, when known
+    expected_size: Option<u64>,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct GitHubAsset {
     pub name: String,
@@ -38,37 +56,266 @@ pub struct TemplateDownloadResult {
     pub is_cached: bool,
 }
 
+/// Latest release info stored on disk so update checks don't hit GitHub every run
+#[derive(Debug, Deserialize, Serialize)]
+struct CachedRelease {
+    fetched_at: DateTime<Utc>,
+    release: GitHubRelease,
+}
+
+/// An installed template with a newer release available
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateUpdateInfo {
+    pub name: String,
+    pub installed_version: String,
+    pub latest_version: String,
+}
+
+/// What `noter template update` would do for one repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedUpdate {
+    pub name: String,
+    /// Installed version, `None` when the template isn't installed
+    pub installed_version: Option<String>,
+    pub latest_version: String,
+}
+
+impl PlannedUpdate {
+    /// Whether the update would install a different version than the one
+    /// installed. Updating reinstalls the latest release either way.
+    pub fn changes_version(&self) -> bool {
+        match &self.installed_version {
+            Some(installed) => {
+                installed.trim_start_matches('v') != self.latest_version.trim_start_matches('v')
+            }
+            None => true,
+        }
+    }
+}
+
 pub struct GitHubTemplateFetcher;
 
 #[allow(dead_code)]
 impl GitHubTemplateFetcher {
     /// Get the latest release information from a specific GitHub repository
-    pub fn get_latest_release(repo: &str) -> Result<GitHubRelease> {
+    pub fn get_latest_release(repo: &str) -> NoterResult<GitHubRelease> {
         let url = format!("{GITHUB_API_BASE}/repos/{repo}/releases/latest");
 
         let mut response = ureq::get(&url)
             .header("User-Agent", "dtu-notes-cli")
             .call()
-            .context("Failed to fetch latest release information")?;
+            .map_err(|e| {
+                NoterError::Network(format!("Failed to fetch latest release information: {e}"))
+            })?;
 
         if response.status() != 200 {
-            return Err(anyhow::anyhow!(
+            return Err(NoterError::Network(format!(
                 "GitHub API request failed with status: {}",
                 response.status()
-            ));
+            )));
         }
 
         let body_str = response
             .body_mut()
             .read_to_string()
-            .context("Failed to read response body")?;
+            .map_err(|e| NoterError::Network(format!("Failed to read response body: {e}")))?;
 
-        let release: GitHubRelease =
-            serde_json::from_str(&body_str).context("Failed to parse GitHub API response")?;
+        let release: GitHubRelease = serde_json::from_str(&body_str)?;
 
         Ok(release)
     }
 
+    /// Get the release information for a specific tag
+    pub fn get_release_by_tag(repo: &str, tag: &str) -> NoterResult<GitHubRelease> {
+        let url = format!("{GITHUB_API_BASE}/repos/{repo}/releases/tags/{tag}");
+
+        let mut response = ureq::get(&url)
+            .header("User-Agent", "dtu-notes-cli")
+            .call()
+            .map_err(|e| {
+                NoterError::Network(format!("Failed to fetch release {tag} information: {e}"))
+            })?;
+
+        let body_str = response
+            .body_mut()
+            .read_to_string()
+            .map_err(|e| NoterError::Network(format!("Failed to read response body: {e}")))?;
+
+        Ok(serde_json::from_str(&body_str)?)
+    }
+
+    /// Get the latest release, reusing cached release info younger than `max_age`.
+    ///
+    /// In offline mode only the cache is consulted (regardless of age) and `None`
+    /// is returned when nothing is cached.
+    pub fn get_latest_release_cached(
+        repo: &str,
+        max_age: Duration,
+        offline: bool,
+    ) -> Result<Option<GitHubRelease>> {
+        let cache_path = Self::get_release_cache_path(repo)?;
+        let max_age = if offline { Duration::MAX } else { max_age };
+
+        if let Some(release) = Self::read_cached_release(&cache_path, max_age) {
+            return Ok(Some(release));
+        }
+
+        if offline {
+            return Ok(None);
+        }
+
+        let release = Self::get_latest_release(repo)?;
+        Self::write_cached_release(&cache_path, &release)?;
+        Ok(Some(release))
+    }
+
+    /// Check installed templates against their latest (cached) releases
+    pub fn check_for_updates(config: &Config) -> Result<Vec<TemplateUpdateInfo>> {
+        let max_age = i64::try_from(config.templates.update_check_interval_hours)
+            .ok()
+            .and_then(Duration::try_hours)
+            .unwrap_or(Duration::MAX);
+        let mut updates = Vec::new();
+
+        for (name, installed_version) in Self::check_template_status(config)? {
+            let Some(installed_version) = installed_version else {
+                continue;
+            };
+
+            let repository = if name == "dtu_template" {
+                DEFAULT_TEMPLATE_REPO.to_string()
+            } else {
+                match config
+                    .templates
+                    .custom_repositories
+                    .iter()
+                    .find(|repo| repo.name == name)
+                {
+                    // Release caching is GitHub-specific; other hosts are checked on update
+                    Some(repo) if template_fetcher::repository_host(repo) == DEFAULT_HOST => {
+                        template_fetcher::repository_path(repo)
+                    }
+                    _ => continue,
+                }
+            };
+
+            let Some(release) =
+                Self::get_latest_release_cached(&repository, max_age, config.templates.offline)?
+            else {
+                continue;
+            };
+
+            let latest_version = release.tag_name.trim_start_matches('v').to_string();
+            if Self::is_newer_version(&installed_version, &latest_version) {
+                updates.push(TemplateUpdateInfo {
+                    name,
+                    installed_version,
+                    latest_version,
+                });
+            }
+        }
+
+        Ok(updates)
+    }
+
+    /// Update templates when `templates.auto_update` is enabled and a newer release
+    /// of a template that isn't pinned is available. Release info is cached, so
+    /// GitHub is contacted at most once per `update_check_interval_hours`.
+    pub fn auto_update_if_due(config: &Config) -> Result<Vec<TemplateDownloadResult>> {
+        if !config.templates.auto_update {
+            return Ok(Vec::new());
+        }
+
+        match Self::auto_update_config(config, &Self::check_for_updates(config)?) {
+            Some(unpinned) => Self::update_templates(&unpinned),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// The configuration an automatic update installs from: `config` with
+    /// pinned repositories disabled, and without the official fallback when
+    /// that is pinned. `None` when none of `updates` is for an unpinned template.
+    fn auto_update_config(config: &Config, updates: &[TemplateUpdateInfo]) -> Option<Config> {
+        let pinned = |name: &str| config.templates.pinned_version(name).is_some();
+        if updates.iter().all(|update| pinned(&update.name)) {
+            return None;
+        }
+
+        let mut unpinned = config.clone();
+        for repo in &mut unpinned.templates.custom_repositories {
+            repo.enabled &= !pinned(&repo.name);
+        }
+        unpinned.templates.use_official_fallback &= !pinned(&Self::official_repository().name);
+        Some(unpinned)
+    }
+
+    /// Compare two version strings (with or without a leading `v`) semantically
+    pub fn is_newer_version(installed: &str, latest: &str) -> bool {
+        let parse = |version: &str| semver::Version::parse(version.trim_start_matches('v')).ok();
+
+        match (parse(installed), parse(latest)) {
+            (Some(installed), Some(latest)) => latest > installed,
+            _ => false,
+        }
+    }
+
+    /// Resolve what [`Self::update_templates`] would install, without
+    /// downloading anything. Repositories are tried in the same order: the
+    /// first enabled custom repository whose latest release resolves, else the
+    /// official template when `use_official_fallback` is on.
+    pub fn plan_updates(config: &Config) -> Result<Vec<PlannedUpdate>> {
+        let installed: HashMap<String, Option<String>> =
+            Self::check_template_status(config)?.into_iter().collect();
+        let planned = |repo_config: &TemplateRepository, latest_version: String| PlannedUpdate {
+            name: repo_config.name.clone(),
+            installed_version: installed.get(&repo_config.name).cloned().flatten(),
+            latest_version,
+        };
+
+        for repo_config in config
+            .templates
+            .custom_repositories
+            .iter()
+            .filter(|r| r.enabled)
+        {
+            let latest = if template_fetcher::repository_host(repo_config) == DEFAULT_HOST {
+                Self::get_latest_release(&template_fetcher::repository_path(repo_config))
+                    .map(|release| release.tag_name)
+                    .map_err(anyhow::Error::from)
+            } else {
+                template_fetcher::fetcher_for(repo_config)
+                    .and_then(|fetcher| fetcher.latest_version(repo_config))
+            };
+
+            match latest {
+                Ok(latest_version) => return Ok(vec![planned(repo_config, latest_version)]),
+                Err(e) => eprintln!("Failed to check {}: {}", repo_config.name, e),
+            }
+        }
+
+        if !config.templates.use_official_fallback {
+            return Ok(Vec::new());
+        }
+
+        let official_repo = Self::official_repository();
+        let release = Self::get_latest_release(DEFAULT_TEMPLATE_REPO)
+            .map_err(|e| anyhow::anyhow!("Failed to check official repository: {}", e))?;
+        Ok(vec![planned(&official_repo, release.tag_name)])
+    }
+
+    /// The official DTU template repository, used as the fallback source
+    fn official_repository() -> TemplateRepository {
+        TemplateRepository {
+            name: "dtu_template".to_string(),
+            repository: DEFAULT_TEMPLATE_REPO.to_string(),
+            host: None,
+            version: None,
+            branch: None,
+            template_path: None,
+            enabled: true,
+        }
+    }
+
     /// Download and install templates from configured repositories with fallback
     pub fn download_and_install_templates(
         config: &Config,
@@ -83,7 +330,16 @@ impl GitHubTemplateFetcher {
                 continue;
             }
 
-            match Self::download_from_repository(config, repo_config, force_update) {
+            let result = template_fetcher::fetcher_for(repo_config).and_then(|fetcher| {
+                template_fetcher::install_from_repository(
+                    fetcher.as_ref(),
+                    config,
+                    repo_config,
+                    force_update,
+                )
+            });
+
+            match result {
                 Ok(result) => {
                     results.push(result);
                     success = true;
@@ -98,16 +354,14 @@ impl GitHubTemplateFetcher {
 
         // Fallback to official repository if no custom repos succeeded
         if !success && config.templates.use_official_fallback {
-            let official_repo = TemplateRepository {
-                name: "dtu_template".to_string(),
-                repository: DEFAULT_TEMPLATE_REPO.to_string(),
-                version: None,
-                branch: None,
-                template_path: None,
-                enabled: true,
-            };
-
-            match Self::download_from_repository(config, &official_repo, force_update) {
+            let official_repo = Self::official_repository();
+
+            match template_fetcher::install_from_repository(
+                &GitHubTemplateFetcher,
+                config,
+                &official_repo,
+                force_update,
+            ) {
                 Ok(result) => {
                     results.push(result);
                     success = true;
@@ -130,61 +384,8 @@ impl GitHubTemplateFetcher {
         Ok(results)
     }
 
-    /// Download from a specific repository configuration
-    fn download_from_repository(
-        config: &Config,
-        repo_config: &TemplateRepository,
-        force_update: bool,
-    ) -> Result<TemplateDownloadResult> {
-        let release = Self::get_latest_release(&repo_config.repository)?;
-
-        // Check if we already have this version cached
-        let cache_path = Self::get_cache_path(&repo_config.name, &release.tag_name)?;
-        let template_installed_marker = Path::new(&config.paths.templates_dir)
-            .join(&repo_config.name)
-            .join(".template_version");
-
-        let is_already_installed = if template_installed_marker.exists() {
-            if let Ok(installed_version) = fs::read_to_string(&template_installed_marker) {
-                installed_version.trim() == release.tag_name
-            } else {
-                false
-            }
-        } else {
-            false
-        };
-
-        if is_already_installed && !force_update {
-            return Ok(TemplateDownloadResult {
-                version: release.tag_name,
-                installed_path: PathBuf::from(&config.paths.templates_dir).join(&repo_config.name),
-                is_cached: true,
-            });
-        }
-
-        // Download if not cached or force update
-        if !cache_path.exists() || force_update {
-            Self::download_release(&release, &cache_path)?;
-        }
-
-        // Extract and install template
-        Self::extract_and_install(
-            &cache_path,
-            &config.paths.templates_dir,
-            &config.paths.typst_packages_dir,
-            &release.tag_name,
-            repo_config,
-        )?;
-
-        Ok(TemplateDownloadResult {
-            version: release.tag_name,
-            installed_path: PathBuf::from(&config.paths.templates_dir).join(&repo_config.name),
-            is_cached: cache_path.exists(),
-        })
-    }
-
     /// Get cache directory path for templates
-    fn get_cache_path(repo_name: &str, version: &str) -> Result<PathBuf> {
+    pub(crate) fn get_cache_path(repo_name: &str, version: &str) -> Result<PathBuf> {
         let cache_dir = dirs::cache_dir()
             .or_else(|| dirs::home_dir().map(|h| h.join(".cache")))
             .context("Could not determine cache directory")?
@@ -195,6 +396,39 @@ impl GitHubTemplateFetcher {
         Ok(cache_dir.join(format!("{}-{}.tar.gz", repo_name, version)))
     }
 
+    /// Get the path of the cached release info for a repository
+    fn get_release_cache_path(repo: &str) -> Result<PathBuf> {
+        let cache_dir = dirs::cache_dir()
+            .or_else(|| dirs::home_dir().map(|h| h.join(".cache")))
+            .context("Could not determine cache directory")?
+            .join("dtu-notes")
+            .join("releases");
+
+        Ok(cache_dir.join(format!("{}.json", repo.replace('/', "-"))))
+    }
+
+    /// Read cached release info if it exists and is younger than `max_age`
+    fn read_cached_release(cache_path: &Path, max_age: Duration) -> Option<GitHubRelease> {
+        let content = fs::read_to_string(cache_path).ok()?;
+        let cached: CachedRelease = serde_json::from_str(&content).ok()?;
+
+        let age = Utc::now().signed_duration_since(cached.fetched_at);
+        (age <= max_age).then_some(cached.release)
+    }
+
+    fn write_cached_release(cache_path: &Path, release: &GitHubRelease) -> Result<()> {
+        if let Some(parent) = cache_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let cached = CachedRelease {
+            fetched_at: Utc::now(),
+            release: release.clone(),
+        };
+        fs::write(cache_path, serde_json::to_string_pretty(&cached)?)?;
+        Ok(())
+    }
+
     /// Download the release asset (not tarball)
     fn download_release(release: &GitHubRelease, cache_path: &Path) -> Result<()> {
         // Look for a release asset that looks like a template (zip or tar.gz)
@@ -213,43 +447,177 @@ impl GitHubTemplateFetcher {
                 })
             });
 
-        let download_url = if let Some(asset) = template_asset {
-            &asset.browser_download_url
-        } else {
+        let (download_url, expected_size) = match template_asset {
+            Some(asset) => (
+                &asset.browser_download_url,
+                Some(asset.size).filter(|size| *size > 0),
+            ),
             // Fallback to tarball if no assets found
-            &release.tarball_url
+            None => (&release.tarball_url, None),
         };
 
-        let response = ureq::get(download_url)
-            .header("User-Agent", "dtu-notes-cli")
-            .call()
-            .context("Failed to download template release")?;
+        Self::download_resumable(download_url, expected_size, cache_path)
+    }
 
-        if response.status() != 200 {
-            return Err(anyhow::anyhow!(
-                "Failed to download template: HTTP {}",
-                response.status()
-            ));
+    /// Download `url` to `cache_path` through a partial file. A failed attempt
+    /// is retried from where it stopped using a range request; servers that
+    /// ignore ranges send the whole file again. The partial file is removed
+    /// once every attempt has failed.
+    fn download_resumable(url: &str, expected_size: Option<u64>, cache_path: &Path) -> Result<()> {
+        if let Some(parent) = cache_path.parent() {
+            fs::create_dir_all(parent)?;
         }
+        let part_path = Self::partial_path(cache_path, "part");
+        let state_path = Self::partial_path(cache_path, "part.json");
+
+        let mut attempt = 1;
+        loop {
+            match Self::download_attempt(url, expected_size, &part_path, &state_path) {
+                Ok(()) => {
+                    fs::rename(&part_path, cache_path)
+                        .context("Failed to write downloaded template to cache")?;
+                    let _ = fs::remove_file(&state_path);
+                    return Ok(());
+                }
+                Err(e) if attempt < DOWNLOAD_ATTEMPTS => {
+                    eprintln!(
+                        "Download attempt {} of {} failed: {:#}. Retrying...",
+                        attempt, DOWNLOAD_ATTEMPTS, e
+                    );
+                    std::thread::sleep(RETRY_DELAY * attempt);
+                    attempt += 1;
+                }
+                Err(e) => {
+                    let _ = fs::remove_file(&part_path);
+                    let _ = fs::remove_file(&state_path);
+                    return Err(e.context(format!(
+                        "Failed to download template after {} attempts",
+                        DOWNLOAD_ATTEMPTS
+                    )));
+                }
+            }
+        }
+    }
 
-        // Read response body using ureq's read_to_vec method
-        let bytes = response
-            .into_body()
-            .read_to_vec()
-            .context("Failed to read response body")?;
+    /// One download attempt into `part_path`, continuing the partial file when
+    /// it belongs to the same download
+    fn download_attempt(
+        url: &str,
+        expected_size: Option<u64>,
+        part_path: &Path,
+        state_path: &Path,
+    ) -> Result<()> {
+        let (offset, expected_size) =
+            Self::resume_offset(url, expected_size, part_path, state_path);
+        if offset > 0 && expected_size == Some(offset) {
+            return Ok(());
+        }
 
-        // Ensure parent directory exists
-        if let Some(parent) = cache_path.parent() {
-            fs::create_dir_all(parent)?;
+        let mut request = ureq::get(url).header("User-Agent", "dtu-notes-cli");
+        if offset > 0 {
+            request = request.header("Range", format!("bytes={}-", offset));
+        }
+        let response = match request.call() {
+            Ok(response) => response,
+            // The partial file doesn't match the archive anymore; start over
+            Err(ureq::Error::StatusCode(416)) => {
+                let _ = fs::remove_file(part_path);
+                anyhow::bail!("Server rejected resuming the download");
+            }
+            Err(e) => return Err(e).context("Failed to download template release"),
+        };
+
+        let resumed = offset > 0 && response.status() == 206;
+        if !resumed && response.status() != 200 {
+            anyhow::bail!("Failed to download template: HTTP {}", response.status());
         }
 
-        fs::write(cache_path, bytes).context("Failed to write downloaded template to cache")?;
+        let content_range = response
+            .headers()
+            .get("Content-Range")
+            .and_then(|value| value.to_str().ok())
+            .and_then(Self::content_range_total);
+        let expected_size = expected_size.or(if resumed {
+            content_range
+        } else {
+            response.body().content_length()
+        });
+        let mut file = if resumed {
+            fs::OpenOptions::new().append(true).open(part_path)?
+        } else {
+            // A full response replaces whatever was downloaded before
+            fs::File::create(part_path)?
+        };
+        fs::write(
+            state_path,
+            serde_json::to_string(&PartialDownload {
+                url: url.to_string(),
+                expected_size,
+            })?,
+        )?;
 
-        Ok(())
+        std::io::copy(&mut response.into_body().into_reader(), &mut file)
+            .context("Download interrupted")?;
+
+        let downloaded = fs::metadata(part_path)?.len();
+        match expected_size {
+            Some(size) if downloaded < size => {
+                anyhow::bail!("Download incomplete: {} of {} bytes", downloaded, size)
+            }
+            Some(size) if downloaded > size => {
+                let _ = fs::remove_file(part_path);
+                anyhow::bail!(
+                    "Downloaded {} bytes, more than the expected {}",
+                    downloaded,
+                    size
+                )
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Bytes already downloaded for `url` and the complete size, if known.
+    /// A partial file left by a different download is removed.
+    fn resume_offset(
+        url: &str,
+        expected_size: Option<u64>,
+        part_path: &Path,
+        state_path: &Path,
+    ) -> (u64, Option<u64>) {
+        let state: Option<PartialDownload> = fs::read_to_string(state_path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok());
+        let downloaded = fs::metadata(part_path).map(|metadata| metadata.len()).ok();
+
+        match (state, downloaded) {
+            (Some(state), Some(downloaded))
+                if state.url == url
+                    && (expected_size.is_none() || state.expected_size == expected_size) =>
+            {
+                (downloaded, expected_size.or(state.expected_size))
+            }
+            _ => {
+                let _ = fs::remove_file(part_path);
+                (0, expected_size)
+            }
+        }
+    }
+
+    /// Complete size from a `Content-Range` header (`bytes 100-199/200`)
+    fn content_range_total(value: &str) -> Option<u64> {
+        value.rsplit_once('/')?.1.trim().parse().ok()
+    }
+
+    /// `cache_path` with `suffix` appended to its file name
+    fn partial_path(cache_path: &Path, suffix: &str) -> PathBuf {
+        let mut name = cache_path.as_os_str().to_owned();
+        name.push(".");
+        name.push(suffix);
+        PathBuf::from(name)
     }
 
     /// Extract and install template files
-    fn extract_and_install(
+    pub(crate) fn extract_and_install(
         archive_path: &Path,
         _templates_dir: &str,
         typst_packages_dir: &str,
@@ -521,19 +889,30 @@ impl GitHubTemplateFetcher {
             preferred_editor: None,
             template_version: "0.1.0".to_string(),
             semester_format: crate::config::SemesterFormat::YearSeason,
+            semester_boundaries: crate::config::SemesterBoundaries::default(),
+            week_start: chrono::Weekday::Mon,
             note_preferences: crate::config::NotePreferences::default(),
             paths: crate::config::PathConfig {
                 notes_dir: "notes".to_string(),
                 obsidian_dir: "obsidian-vault".to_string(),
                 templates_dir: templates_dir.to_string(),
                 typst_packages_dir: typst_packages_dir.to_string(),
+                flat_layout: false,
+                follow_symlinks: true,
+                course_catalog: None,
+                create_missing_dirs: true,
             },
             templates: template_config,
             typst: crate::config::TypstConfig::default(),
             search: crate::config::SearchConfig::default(),
+            health_thresholds: crate::config::HealthThresholds::default(),
+            activity_window_days: 30,
+            external_command_timeout_seconds: 120,
             courses: std::collections::HashMap::new(),
+            course_aliases: std::collections::HashMap::new(),
             obsidian_integration: ObsidianIntegrationConfig::default(),
             metadata: Metadata::default(),
+            ..Config::default()
         };
 
         let results = Self::download_and_install_templates(&config, force_update)?;
@@ -550,6 +929,50 @@ impl GitHubTemplateFetcher {
     }
 }
 
+impl TemplateFetcher for GitHubTemplateFetcher {
+    fn latest_version(&self, repo_config: &TemplateRepository) -> Result<String> {
+        let repo = template_fetcher::repository_path(repo_config);
+        let release = Self::get_latest_release(&repo)?;
+
+        // Remember the release so `download` can reuse its asset list
+        Self::write_cached_release(&Self::get_release_cache_path(&repo)?, &release)?;
+
+        Ok(release.tag_name)
+    }
+
+    fn download(
+        &self,
+        repo_config: &TemplateRepository,
+        version: &str,
+        archive_path: &Path,
+    ) -> Result<()> {
+        let repo = template_fetcher::repository_path(repo_config);
+        let release =
+            match Self::read_cached_release(&Self::get_release_cache_path(&repo)?, Duration::MAX) {
+                Some(release) if release.tag_name == version => release,
+                _ => Self::get_release_by_tag(&repo, version)?,
+            };
+
+        Self::download_release(&release, archive_path)
+    }
+
+    fn install(
+        &self,
+        archive_path: &Path,
+        repo_config: &TemplateRepository,
+        version: &str,
+        config: &Config,
+    ) -> Result<()> {
+        Self::extract_and_install(
+            archive_path,
+            &config.paths.templates_dir,
+            &config.paths.typst_packages_dir,
+            version,
+            repo_config,
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -565,6 +988,56 @@ mod tests {
         assert!(!release.tarball_url.is_empty());
     }
 
+    #[test]
+    fn test_auto_update_leaves_pinned_repositories_alone() {
+        let repository = |name: &str| TemplateRepository {
+            name: name.to_string(),
+            repository: format!("owner/{}", name),
+            host: None,
+            version: None,
+            branch: None,
+            template_path: None,
+            enabled: true,
+        };
+        let update = |name: &str| TemplateUpdateInfo {
+            name: name.to_string(),
+            installed_version: "1.0.0".to_string(),
+            latest_version: "2.0.0".to_string(),
+        };
+
+        let mut config = Config::default();
+        config.templates.custom_repositories = vec![repository("pinned"), repository("free")];
+        config
+            .templates
+            .pinned_versions
+            .insert("pinned".to_string(), "1.0.0".to_string());
+
+        // Only pinned templates have updates, so nothing is installed
+        assert!(GitHubTemplateFetcher::auto_update_config(&config, &[update("pinned")]).is_none());
+
+        let unpinned =
+            GitHubTemplateFetcher::auto_update_config(&config, &[update("pinned"), update("free")])
+                .unwrap();
+        let enabled: Vec<&str> = unpinned
+            .templates
+            .custom_repositories
+            .iter()
+            .filter(|repo| repo.enabled)
+            .map(|repo| repo.name.as_str())
+            .collect();
+        assert_eq!(enabled, vec!["free"]);
+        assert!(unpinned.templates.use_official_fallback);
+
+        // Pinning the official template turns off the fallback
+        config
+            .templates
+            .pinned_versions
+            .insert("dtu-template".to_string(), "0.3.0".to_string());
+        let unpinned =
+            GitHubTemplateFetcher::auto_update_config(&config, &[update("free")]).unwrap();
+        assert!(!unpinned.templates.use_official_fallback);
+    }
+
     #[test]
     fn test_cache_path_generation() {
         let path = GitHubTemplateFetcher::get_cache_path("test-template", "v1.0.0").unwrap();
@@ -588,16 +1061,138 @@ mod tests {
                 obsidian_dir: "obsidian-vault".to_string(),
                 templates_dir: temp_dir.path().to_str().unwrap().to_string(),
                 typst_packages_dir: "packages".to_string(),
+                flat_layout: false,
+                follow_symlinks: true,
+                course_catalog: None,
+                create_missing_dirs: true,
             },
             templates: crate::config::UserTemplateConfig::default(),
             typst: crate::config::TypstConfig::default(),
             search: crate::config::SearchConfig::default(),
+            health_thresholds: crate::config::HealthThresholds::default(),
+            activity_window_days: 30,
+            external_command_timeout_seconds: 120,
             courses: std::collections::HashMap::new(),
-            obsidian_integration: todo!(),
-            metadata: todo!(),
+            ..Config::default()
         };
 
         let status = GitHubTemplateFetcher::check_template_status(&config).unwrap();
         assert_eq!(status, vec![("dtu_template".to_string(), None)]);
     }
+
+    #[test]
+    fn test_is_newer_version() {
+        assert!(GitHubTemplateFetcher::is_newer_version("0.2.0", "0.3.0"));
+        assert!(GitHubTemplateFetcher::is_newer_version("v0.2.0", "v0.2.1"));
+        assert!(!GitHubTemplateFetcher::is_newer_version("0.3.0", "0.3.0"));
+        assert!(!GitHubTemplateFetcher::is_newer_version("unknown", "0.3.0"));
+    }
+
+    #[test]
+    fn test_planned_update_changes_version() {
+        let planned = |installed: Option<&str>| PlannedUpdate {
+            name: "dtu_template".to_string(),
+            installed_version: installed.map(str::to_string),
+            latest_version: "v0.3.0".to_string(),
+        };
+
+        assert!(planned(None).changes_version());
+        assert!(planned(Some("0.2.0")).changes_version());
+        assert!(!planned(Some("0.3.0")).changes_version());
+        assert!(!planned(Some("v0.3.0")).changes_version());
+    }
+
+    #[test]
+    fn test_cached_release_respects_max_age() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_path = temp_dir.path().join("releases").join("owner-repo.json");
+        let release = GitHubRelease {
+            tag_name: "v0.3.0".to_string(),
+            name: "0.3.0".to_string(),
+            published_at: "2025-01-01T00:00:00Z".to_string(),
+            tarball_url: String::new(),
+            zipball_url: String::new(),
+            body: None,
+            prerelease: false,
+            assets: vec![],
+        };
+
+        GitHubTemplateFetcher::write_cached_release(&cache_path, &release).unwrap();
+
+        let cached = GitHubTemplateFetcher::read_cached_release(&cache_path, Duration::hours(1));
+        assert_eq!(cached.map(|r| r.tag_name), Some("v0.3.0".to_string()));
+        assert!(
+            GitHubTemplateFetcher::read_cached_release(&cache_path, Duration::seconds(-1))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_auto_update_disabled_does_nothing() {
+        let config = Config::default();
+        assert!(!config.templates.auto_update);
+
+        let results = GitHubTemplateFetcher::auto_update_if_due(&config).unwrap();
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_interrupted_download_resumes_with_range_request() {
+        use std::io::{BufRead, BufReader, Write};
+        use std::net::TcpListener;
+
+        let archive = b"0123456789";
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/template.tar.gz", listener.local_addr().unwrap());
+
+        // First response is cut off after four bytes, the second honors the range
+        let server = std::thread::spawn(move || {
+            let mut ranges = Vec::new();
+            for response in [
+                b"HTTP/1.1 200 OK\r\nContent-Length: 10\r\n\r\n0123".to_vec(),
+                b"HTTP/1.1 206 Partial Content\r\nContent-Length: 6\r\nContent-Range: bytes 4-9/10\r\n\r\n456789".to_vec(),
+            ] {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                let mut range = None;
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    if let Some(value) = line.to_lowercase().strip_prefix("range:") {
+                        range = Some(value.trim().to_string());
+                    }
+                    line.clear();
+                }
+                ranges.push(range);
+                stream.write_all(&response).unwrap();
+            }
+            ranges
+        });
+
+        let temp_dir = TempDir::new().unwrap();
+        let cache_path = temp_dir.path().join("template.tar.gz");
+        let part_path = GitHubTemplateFetcher::partial_path(&cache_path, "part");
+        let state_path = GitHubTemplateFetcher::partial_path(&cache_path, "part.json");
+
+        assert!(
+            GitHubTemplateFetcher::download_attempt(&url, None, &part_path, &state_path).is_err()
+        );
+        assert_eq!(fs::read(&part_path).unwrap(), b"0123");
+        GitHubTemplateFetcher::download_attempt(&url, None, &part_path, &state_path).unwrap();
+        assert_eq!(fs::read(&part_path).unwrap(), archive);
+        assert_eq!(
+            server.join().unwrap(),
+            vec![None, Some("bytes=4-".to_string())]
+        );
+
+        // A partial file from another download is discarded
+        assert_eq!(
+            GitHubTemplateFetcher::resume_offset("http://other", None, &part_path, &state_path),
+            (0, None)
+        );
+        assert!(!part_path.exists());
+        assert_eq!(
+            GitHubTemplateFetcher::content_range_total("bytes 4-9/10"),
+            Some(10)
+        );
+    }
 }
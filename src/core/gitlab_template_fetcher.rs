@@ -0,0 +1,196 @@
+//! GitLab template fetcher
+//!
+//! Fetches template releases from gitlab.com or self-hosted GitLab instances
+//! (such as a university GitLab) through the GitLab v4 REST API.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+use crate::config::{Config, TemplateRepository};
+use crate::core::github_template_fetcher::GitHubTemplateFetcher;
+use crate::core::template_fetcher::{self, TemplateFetcher};
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct GitLabRelease {
+    pub tag_name: String,
+    pub name: Option<String>,
+    #[serde(default)]
+    pub assets: GitLabAssets,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct GitLabAssets {
+    #[serde(default)]
+    pub links: Vec<GitLabAssetLink>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct GitLabAssetLink {
+    pub name: String,
+    pub url: String,
+    pub direct_asset_url: Option<String>,
+}
+
+pub struct GitLabTemplateFetcher;
+
+#[allow(dead_code)]
+impl GitLabTemplateFetcher {
+    /// Get the most recent release of a project
+    pub fn get_latest_release(host: &str, project: &str) -> Result<GitLabRelease> {
+        let url = format!(
+            "{}/releases?per_page=1&order_by=released_at&sort=desc",
+            Self::project_api_url(host, project)
+        );
+
+        let releases: Vec<GitLabRelease> = serde_json::from_str(&Self::get_text(&url)?)
+            .context("Failed to parse GitLab API response")?;
+
+        releases
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("No releases found for {} on {}", project, host))
+    }
+
+    /// Get the release for a specific tag
+    pub fn get_release_by_tag(host: &str, project: &str, tag: &str) -> Result<GitLabRelease> {
+        let url = format!("{}/releases/{}", Self::project_api_url(host, project), tag);
+        serde_json::from_str(&Self::get_text(&url)?).context("Failed to parse GitLab API response")
+    }
+
+    /// API base URL for a project, with the `group/project` path URL-encoded
+    pub fn project_api_url(host: &str, project: &str) -> String {
+        format!(
+            "https://{}/api/v4/projects/{}",
+            host,
+            project.replace('/', "%2F")
+        )
+    }
+
+    /// Pick the download URL: a template asset link if present, else the source archive
+    fn download_url(host: &str, project: &str, release: &GitLabRelease) -> String {
+        let template_link = release
+            .assets
+            .links
+            .iter()
+            .find(|link| link.name.to_lowercase().contains("template"))
+            .or_else(|| {
+                release.assets.links.iter().find(|link| {
+                    let name = link.name.to_lowercase();
+                    name.ends_with(".zip") || name.ends_with(".tar.gz")
+                })
+            });
+
+        match template_link {
+            Some(link) => link.direct_asset_url.clone().unwrap_or(link.url.clone()),
+            None => format!(
+                "{}/repository/archive.tar.gz?sha={}",
+                Self::project_api_url(host, project),
+                release.tag_name
+            ),
+        }
+    }
+
+    fn get_text(url: &str) -> Result<String> {
+        let mut response = ureq::get(url)
+            .header("User-Agent", "dtu-notes-cli")
+            .call()
+            .context("Failed to reach GitLab API")?;
+
+        response
+            .body_mut()
+            .read_to_string()
+            .context("Failed to read response body")
+    }
+}
+
+impl TemplateFetcher for GitLabTemplateFetcher {
+    fn latest_version(&self, repo_config: &TemplateRepository) -> Result<String> {
+        let host = template_fetcher::repository_host(repo_config);
+        let project = template_fetcher::repository_path(repo_config);
+
+        Ok(Self::get_latest_release(&host, &project)?.tag_name)
+    }
+
+    fn download(
+        &self,
+        repo_config: &TemplateRepository,
+        version: &str,
+        archive_path: &Path,
+    ) -> Result<()> {
+        let host = template_fetcher::repository_host(repo_config);
+        let project = template_fetcher::repository_path(repo_config);
+        let release = Self::get_release_by_tag(&host, &project, version)?;
+
+        let response = ureq::get(&Self::download_url(&host, &project, &release))
+            .header("User-Agent", "dtu-notes-cli")
+            .call()
+            .context("Failed to download template release")?;
+
+        let bytes = response
+            .into_body()
+            .read_to_vec()
+            .context("Failed to read response body")?;
+
+        if let Some(parent) = archive_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(archive_path, bytes).context("Failed to write downloaded template to cache")?;
+
+        Ok(())
+    }
+
+    fn install(
+        &self,
+        archive_path: &Path,
+        repo_config: &TemplateRepository,
+        version: &str,
+        config: &Config,
+    ) -> Result<()> {
+        GitHubTemplateFetcher::extract_and_install(
+            archive_path,
+            &config.paths.templates_dir,
+            &config.paths.typst_packages_dir,
+            version,
+            repo_config,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_api_url_encodes_path() {
+        assert_eq!(
+            GitLabTemplateFetcher::project_api_url("gitlab.com", "group/sub/templates"),
+            "https://gitlab.com/api/v4/projects/group%2Fsub%2Ftemplates"
+        );
+    }
+
+    #[test]
+    fn test_download_url_falls_back_to_archive() {
+        let release = GitLabRelease {
+            tag_name: "v1.0.0".to_string(),
+            name: None,
+            assets: GitLabAssets::default(),
+        };
+
+        assert_eq!(
+            GitLabTemplateFetcher::download_url("gitlab.com", "group/templates", &release),
+            "https://gitlab.com/api/v4/projects/group%2Ftemplates/repository/archive.tar.gz?sha=v1.0.0"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "network-tests")]
+    fn test_fetch_latest_gitlab_release() {
+        let release =
+            GitLabTemplateFetcher::get_latest_release("gitlab.com", "gitlab-org/gitlab-runner")
+                .unwrap();
+        assert!(!release.tag_name.is_empty());
+    }
+}
@@ -0,0 +1,294 @@
+//! `.noterignore` support
+//!
+//! A `.noterignore` file at the root of the notes directory lists glob
+//! patterns, one per line, for files and directories that scanning and
+//! searching should skip, in the spirit of `.gitignore`:
+//!
+//! ```text
+//! # Editor backups
+//! *.bak
+//! # Directories with these names, wherever they are
+//! archive/
+//! pdfs/
+//! # Anchored to the notes directory
+//! /02101/drafts
+//! ```
+//!
+//! `*` matches within one path component, `**` across components and `?` a
+//! single character. A trailing `/` only matches directories. Patterns with a
+//! leading or inner `/` are matched against the path relative to the notes
+//! directory, the others against each file or directory name. Everything
+//! under an ignored directory is ignored too.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Ignore file name, read from the notes directory
+pub const IGNORE_FILE: &str = ".noterignore";
+
+#[derive(Debug, Clone)]
+struct IgnorePattern {
+    regex: Regex,
+    /// Only matches directories (the pattern ended in `/`)
+    dir_only: bool,
+    /// Matched against the whole relative path instead of a single name
+    anchored: bool,
+}
+
+/// Patterns from a `.noterignore` file, relative to the directory holding it
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreRules {
+    root: PathBuf,
+    patterns: Vec<IgnorePattern>,
+}
+
+#[allow(dead_code)]
+impl IgnoreRules {
+    /// Parse ignore patterns relative to `root`. Blank lines and lines
+    /// starting with `#` are skipped.
+    pub fn parse(root: &Path, content: &str) -> Self {
+        let patterns = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(Self::parse_pattern)
+            .collect();
+
+        Self {
+            root: root.to_path_buf(),
+            patterns,
+        }
+    }
+
+    /// Rules from `<root>/.noterignore`, empty when the file doesn't exist
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join(IGNORE_FILE);
+        if !path.is_file() {
+            return Ok(Self {
+                root: root.to_path_buf(),
+                patterns: Vec::new(),
+            });
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Ok(Self::parse(root, &content))
+    }
+
+    /// Rules for a directory being scanned: the nearest `.noterignore` in
+    /// `dir` or one of its parents, so scanning a single course still honours
+    /// the file at the notes directory root. The search stops at the notes
+    /// directory, the parent of the course directory `dir` is in, or `dir`
+    /// itself outside of any course.
+    pub fn find(dir: &Path) -> Result<Self> {
+        let notes_dir = dir
+            .ancestors()
+            .find(|ancestor| Self::is_course_dir(ancestor))
+            .and_then(Path::parent)
+            .unwrap_or(dir);
+
+        for ancestor in dir.ancestors() {
+            if ancestor.join(IGNORE_FILE).is_file() {
+                return Self::load(ancestor);
+            }
+            if ancestor == notes_dir {
+                break;
+            }
+        }
+        Ok(Self::default())
+    }
+
+    /// Whether `dir` is named like a course directory, a five-digit course ID
+    fn is_course_dir(dir: &Path) -> bool {
+        dir.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.len() == 5 && name.bytes().all(|b| b.is_ascii_digit()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Whether `path`, inside the rules' root, is ignored. A path is also
+    /// ignored when one of its parent directories is.
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        if self.patterns.is_empty() {
+            return false;
+        }
+
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return false;
+        };
+        let components: Vec<String> = relative
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+                _ => None,
+            })
+            .collect();
+
+        (0..components.len()).any(|i| {
+            let prefix = components[..=i].join("/");
+            let component_is_dir = i + 1 < components.len() || is_dir;
+
+            self.patterns.iter().any(|pattern| {
+                if pattern.dir_only && !component_is_dir {
+                    return false;
+                }
+                let target = if pattern.anchored {
+                    &prefix
+                } else {
+                    &components[i]
+                };
+                pattern.regex.is_match(target)
+            })
+        })
+    }
+
+    fn parse_pattern(line: &str) -> Option<IgnorePattern> {
+        let dir_only = line.ends_with('/');
+        let pattern = line.trim_end_matches('/');
+        let anchored = pattern.contains('/');
+        let pattern = pattern.trim_start_matches('/');
+        if pattern.is_empty() {
+            return None;
+        }
+
+        let regex = Regex::new(&format!("^{}$", Self::glob_to_regex(pattern))).ok()?;
+        Some(IgnorePattern {
+            regex,
+            dir_only,
+            anchored,
+        })
+    }
+
+    fn glob_to_regex(glob: &str) -> String {
+        let mut regex = String::new();
+        let mut chars = glob.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '*' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    if chars.peek() == Some(&'/') {
+                        // `**/` also matches no directories at all
+                        chars.next();
+                        regex.push_str("(?:.*/)?");
+                    } else {
+                        regex.push_str(".*");
+                    }
+                }
+                '*' => regex.push_str("[^/]*"),
+                '?' => regex.push_str("[^/]"),
+                c => regex.push_str(&regex::escape(&c.to_string())),
+            }
+        }
+
+        regex
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::directory_scanner::DirectoryScanner;
+    use crate::core::search_engine::{MatchMode, SearchEngine, SearchOptions};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_ignore_patterns() {
+        let root = Path::new("/notes");
+        let rules = IgnoreRules::parse(
+            root,
+            "# comment\n\n*.bak\narchive/\n/02101/drafts\n**/scratch-?.typ\n",
+        );
+        let ignored = |path: &str, is_dir: bool| rules.is_ignored(&root.join(path), is_dir);
+
+        assert!(ignored("02101/lectures/notes.typ.bak", false));
+        assert!(!ignored("02101/lectures/bakery.typ", false));
+
+        // Directory patterns match directories and everything below them
+        assert!(ignored("archive", true));
+        assert!(ignored("02102/archive/old.typ", false));
+        assert!(!ignored("archive", false));
+
+        // Anchored patterns only match from the root
+        assert!(ignored("02101/drafts/idea.typ", false));
+        assert!(!ignored("02102/02101/drafts/idea.typ", false));
+
+        assert!(ignored("scratch-1.typ", false));
+        assert!(ignored("02101/lectures/scratch-2.typ", false));
+        assert!(!ignored("02101/lectures/scratch-10.typ", false));
+
+        // Paths outside the root are never ignored
+        assert!(!rules.is_ignored(Path::new("/elsewhere/notes.bak"), false));
+        assert!(IgnoreRules::parse(root, "# only comments\n/\n").is_empty());
+    }
+
+    #[test]
+    fn test_scanning_honours_ignore_file() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let notes_dir = temp_dir.path();
+        let lectures = notes_dir.join("02101").join("lectures");
+        fs::create_dir_all(&lectures)?;
+        fs::create_dir_all(notes_dir.join("pdfs"))?;
+        fs::write(lectures.join("lecture-1.typ"), "")?;
+        fs::write(lectures.join("draft.typ"), "")?;
+        fs::write(notes_dir.join("pdfs").join("export.typ"), "")?;
+
+        // Without the file nothing changes
+        let files = DirectoryScanner::scan_directory_for_files(notes_dir, &["typ"], true)?;
+        assert_eq!(files.len(), 3);
+
+        fs::write(notes_dir.join(IGNORE_FILE), "pdfs/\ndraft.*\n")?;
+        let files = DirectoryScanner::scan_directory_for_files(notes_dir, &["typ"], true)?;
+        assert_eq!(files.len(), 1);
+        assert!(files[0].path.ends_with("lecture-1.typ"));
+
+        // Scanning a subdirectory still finds the file at the root
+        let notes =
+            DirectoryScanner::scan_course_notes(&notes_dir.join("02101"), &["typ"], false, true)?;
+        assert_eq!(notes.len(), 1);
+
+        let options = SearchOptions {
+            case_sensitive: false,
+            max_results: 10,
+            context_lines: 0,
+            file_extensions: vec!["typ".to_string()],
+            match_mode: MatchMode::default(),
+            follow_symlinks: true,
+            result_order: Default::default(),
+        };
+        fs::write(lectures.join("draft.typ"), "graphs")?;
+        fs::write(lectures.join("lecture-1.typ"), "graphs")?;
+        let matches = SearchEngine::search_in_directory(notes_dir, "graphs", &options)?;
+        assert_eq!(matches.len(), 1);
+        assert!(matches[0].file_path.ends_with("lecture-1.typ"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ignore_file_above_notes_directory_is_not_used() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let notes_dir = temp_dir.path().join("notes");
+        let lectures = notes_dir.join("02101").join("lectures");
+        fs::create_dir_all(&lectures)?;
+        fs::write(lectures.join("lecture-1.typ"), "")?;
+        fs::write(temp_dir.path().join(IGNORE_FILE), "*.typ\n")?;
+
+        assert!(IgnoreRules::find(&notes_dir)?.is_empty());
+        assert!(IgnoreRules::find(&lectures)?.is_empty());
+        let notes =
+            DirectoryScanner::scan_course_notes(&notes_dir.join("02101"), &["typ"], false, true)?;
+        assert_eq!(notes.len(), 1);
+
+        // The notes directory's own file is still found from inside a course
+        fs::write(notes_dir.join(IGNORE_FILE), "lecture-*\n")?;
+        assert!(!IgnoreRules::find(&lectures)?.is_empty());
+
+        Ok(())
+    }
+}
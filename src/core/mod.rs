@@ -3,17 +3,33 @@
 //! This module contains the core domain logic separated from CLI commands
 //! and presentation concerns.
 
+pub mod assignment_readme;
+pub mod calendar_export;
+pub mod course_archive;
+pub mod course_catalog;
 pub mod course_management;
+pub mod course_name_check;
 #[cfg(feature = "dev-tools")]
 pub mod dev_data_generator;
 pub mod directory_scanner;
 pub mod file_operations;
+pub mod git_integration;
 pub mod github_template_fetcher;
+pub mod gitlab_template_fetcher;
+pub mod ignore;
+pub mod outline_import;
+pub mod process;
+pub mod recommendations;
 #[cfg(feature = "dev-tools")]
 pub mod sample_content;
 pub mod search_engine;
+pub mod search_index;
+pub mod semester_cleanup;
 pub mod setup_manager;
 pub mod status_manager;
 pub mod template;
+pub mod template_fetcher;
 pub mod typst_compiler;
 pub mod validation;
+pub mod vault_index;
+pub mod weekly_summary;
@@ -0,0 +1,203 @@
+//! Importing existing outlines as note bodies
+//!
+//! Text copied from slide decks or written elsewhere can be used as the body
+//! of a new note instead of the template's empty sections. Top-level bullet
+//! lines become `=` headings, nested bullets become Typst list items, and
+//! characters, line-leading markers and comments with a meaning in Typst
+//! markup are escaped so the text renders as written.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+/// Bullet markers recognised at the start of a line
+const BULLET_MARKERS: [char; 5] = ['-', '*', '•', '–', '‣'];
+
+/// Characters escaped so imported text isn't read as Typst markup
+const TYPST_MARKUP_CHARS: [char; 8] = ['\\', '#', '$', '*', '_', '@', '<', '`'];
+
+/// Markers that start a heading, list, enum or term item at the start of a line
+const TYPST_LINE_MARKERS: [char; 4] = ['=', '-', '+', '/'];
+
+pub struct OutlineImporter;
+
+#[allow(dead_code)]
+impl OutlineImporter {
+    /// Text currently on the system clipboard
+    pub fn read_clipboard() -> Result<String> {
+        arboard::Clipboard::new()
+            .and_then(|mut clipboard| clipboard.get_text())
+            .context("Could not read text from the clipboard")
+    }
+
+    /// Text of the file at `path`
+    pub fn read_file(path: &Path) -> Result<String> {
+        fs::read_to_string(path)
+            .with_context(|| format!("Could not read outline from {}", path.display()))
+    }
+
+    /// Typst body for an outline. Fails when there is nothing to import.
+    pub fn to_typst_body(text: &str) -> Result<String> {
+        let mut lines: Vec<String> = Vec::new();
+
+        for line in text.lines() {
+            let line = line.trim_end();
+            if line.is_empty() {
+                // Collapse runs of blank lines
+                if lines.last().is_some_and(|last| !last.is_empty()) {
+                    lines.push(String::new());
+                }
+                continue;
+            }
+
+            let indent = line.len() - line.trim_start().len();
+            match Self::bullet_text(line.trim_start()) {
+                Some(text) if indent == 0 => {
+                    if lines.last().is_some_and(|last| !last.is_empty()) {
+                        lines.push(String::new());
+                    }
+                    lines.push(format!("= {}", Self::escape(text)));
+                    lines.push(String::new());
+                }
+                Some(text) => lines.push(format!(
+                    "{}- {}",
+                    Self::list_indent(&line[..indent]),
+                    Self::escape(text)
+                )),
+                None => lines.push(Self::escape(line)),
+            }
+        }
+
+        while lines.last().is_some_and(|last| last.is_empty()) {
+            lines.pop();
+        }
+        if lines.is_empty() {
+            anyhow::bail!("The outline to import is empty");
+        }
+
+        Ok(format!("{}\n", lines.join("\n")))
+    }
+
+    /// The text after a bullet or `1.` / `1)` marker, if the line has one
+    fn bullet_text(line: &str) -> Option<&str> {
+        let mut chars = line.chars();
+        let first = chars.next()?;
+        let rest = if BULLET_MARKERS.contains(&first) {
+            chars.as_str()
+        } else {
+            let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            if digits == 0 {
+                return None;
+            }
+            line[digits..]
+                .strip_prefix('.')
+                .or_else(|| line[digits..].strip_prefix(')'))?
+        };
+
+        // A marker needs whitespace after it, so "-5" or "*bold*" aren't bullets
+        if !rest.starts_with(char::is_whitespace) {
+            return None;
+        }
+        let text = rest.trim();
+        (!text.is_empty()).then_some(text)
+    }
+
+    /// Typst list indentation for a source line's leading whitespace, counting
+    /// a tab as one nesting level
+    fn list_indent(whitespace: &str) -> String {
+        let width: usize = whitespace
+            .chars()
+            .map(|c| if c == '\t' { 2 } else { 1 })
+            .sum();
+        " ".repeat(width.max(2) / 2 * 2 - 2)
+    }
+
+    fn escape(text: &str) -> String {
+        let mut escaped = String::with_capacity(text.len());
+        if Self::starts_with_line_marker(text) {
+            escaped.push('\\');
+        }
+
+        let mut previous = None;
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            // Either slash of `//` would start a comment
+            let comment = c == '/' && (previous == Some('/') || chars.peek() == Some(&'/'));
+            if TYPST_MARKUP_CHARS.contains(&c) || comment {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+            previous = Some(c);
+        }
+        escaped
+    }
+
+    /// Whether `text` begins with a marker Typst reads as markup at the start of
+    /// a line: a run of `=`, or a `-`, `+` or `/`, followed by whitespace
+    fn starts_with_line_marker(text: &str) -> bool {
+        let Some(first) = text
+            .chars()
+            .next()
+            .filter(|c| TYPST_LINE_MARKERS.contains(c))
+        else {
+            return false;
+        };
+        let rest = if first == '=' {
+            text.trim_start_matches('=')
+        } else {
+            &text[first.len_utf8()..]
+        };
+        rest.is_empty() || rest.starts_with(char::is_whitespace)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outline_bullets_become_headings_and_lists() {
+        let outline = "• Sorting\r\n  – Insertion sort: O(n^2)\r\n\t* Merge sort\n\n\n- Graphs #2\n    1. BFS_queue\nSee $5 notes\n";
+        let body = OutlineImporter::to_typst_body(outline).unwrap();
+        assert_eq!(
+            body,
+            "= Sorting\n\
+             \n\
+             - Insertion sort: O(n^2)\n\
+             - Merge sort\n\
+             \n\
+             = Graphs \\#2\n\
+             \n\
+             \x20 - BFS\\_queue\n\
+             See \\$5 notes\n"
+        );
+
+        // Markers need a following space; plain lines pass through
+        assert_eq!(
+            OutlineImporter::to_typst_body("-5 degrees\n2024 recap").unwrap(),
+            "-5 degrees\n2024 recap\n"
+        );
+        assert!(OutlineImporter::to_typst_body(" \n\n").is_err());
+    }
+
+    #[test]
+    fn test_line_leading_markers_are_escaped() {
+        let body = |outline| OutlineImporter::to_typst_body(outline).unwrap();
+
+        assert_eq!(body("== Not a heading"), "\\== Not a heading\n");
+        assert_eq!(body("+ not an enum item"), "\\+ not an enum item\n");
+        assert_eq!(body("- - not a nested list"), "= \\- not a nested list\n");
+        assert_eq!(body("/ Term: not a term"), "\\/ Term: not a term\n");
+
+        // Markers without whitespace after them aren't markup
+        assert_eq!(body("==> +1 /path"), "==> +1 /path\n");
+    }
+
+    #[test]
+    fn test_comment_markers_are_escaped() {
+        assert_eq!(
+            OutlineImporter::to_typst_body("See https://typst.app // docs").unwrap(),
+            "See https:\\/\\/typst.app \\/\\/ docs\n"
+        );
+    }
+}
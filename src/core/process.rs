@@ -0,0 +1,173 @@
+//! External processes with a timeout
+//!
+//! Typst and git are run through [`ProcessRunner`], so a process that hangs,
+//! e.g. git waiting on a stalled remote, can't block `noter` forever. The
+//! timeout follows `external_command_timeout_seconds`, which is applied
+//! whenever the configuration is loaded; 0 disables it. Processes the user
+//! interacts with, such as editors and `typst watch`, run without a timeout.
+
+use std::io::{self, Read, Write};
+use std::process::{Child, Command, Output, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Timeout used until a configuration is loaded
+pub const DEFAULT_TIMEOUT_SECONDS: u64 = 120;
+
+static TIMEOUT_SECONDS: AtomicU64 = AtomicU64::new(DEFAULT_TIMEOUT_SECONDS);
+
+/// Longest pause between checks on a running process
+const MAX_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+pub struct ProcessRunner;
+
+#[allow(dead_code)]
+impl ProcessRunner {
+    /// Timeout for processes started from now on (0 = no timeout)
+    pub fn set_timeout_seconds(seconds: u64) {
+        TIMEOUT_SECONDS.store(seconds, Ordering::Relaxed);
+    }
+
+    /// The current timeout, `None` when it's disabled
+    pub fn timeout() -> Option<Duration> {
+        match TIMEOUT_SECONDS.load(Ordering::Relaxed) {
+            0 => None,
+            seconds => Some(Duration::from_secs(seconds)),
+        }
+    }
+
+    /// Run `command` to completion, capturing stdout and stderr, like
+    /// [`Command::output`] but killed after the configured timeout
+    pub fn output(command: &mut Command) -> io::Result<Output> {
+        Self::run(command, None, Self::timeout())
+    }
+
+    /// Like [`ProcessRunner::output`], writing `input` to the process's stdin
+    pub fn output_with_input(command: &mut Command, input: &[u8]) -> io::Result<Output> {
+        Self::run(command, Some(input), Self::timeout())
+    }
+
+    /// Run `command`, feeding it `input` and killing it once `timeout` passes.
+    /// A timed out process fails with [`io::ErrorKind::TimedOut`].
+    pub fn run(
+        command: &mut Command,
+        input: Option<&[u8]>,
+        timeout: Option<Duration>,
+    ) -> io::Result<Output> {
+        if input.is_some() {
+            command.stdin(Stdio::piped());
+        }
+        let mut child = command
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        // Write and read on threads so a full pipe can't deadlock the process
+        let writer = match (input, child.stdin.take()) {
+            (Some(input), Some(mut stdin)) => {
+                let input = input.to_vec();
+                // Dropping stdin after writing closes the pipe so the process sees EOF
+                Some(thread::spawn(move || stdin.write_all(&input)))
+            }
+            _ => None,
+        };
+        let stdout = Self::read_in_background(child.stdout.take());
+        let stderr = Self::read_in_background(child.stderr.take());
+
+        let status = match timeout {
+            Some(timeout) => match Self::wait_until(&mut child, Instant::now() + timeout)? {
+                Some(status) => status,
+                None => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(io::Error::new(
+                        io::ErrorKind::TimedOut,
+                        format!(
+                            "{} did not finish within {}s (see external_command_timeout_seconds)",
+                            command.get_program().to_string_lossy(),
+                            timeout.as_secs()
+                        ),
+                    ));
+                }
+            },
+            None => child.wait()?,
+        };
+
+        if let Some(writer) = writer {
+            // A process that exits without reading all its input closes the pipe early
+            match writer.join() {
+                Ok(Err(e)) if e.kind() != io::ErrorKind::BrokenPipe => return Err(e),
+                _ => {}
+            }
+        }
+
+        Ok(Output {
+            status,
+            stdout: stdout.join().unwrap_or_default(),
+            stderr: stderr.join().unwrap_or_default(),
+        })
+    }
+
+    /// Wait for `child` to exit, giving up at `deadline`
+    fn wait_until(
+        child: &mut Child,
+        deadline: Instant,
+    ) -> io::Result<Option<std::process::ExitStatus>> {
+        let mut interval = Duration::from_millis(1);
+        loop {
+            if let Some(status) = child.try_wait()? {
+                return Ok(Some(status));
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(None);
+            }
+            thread::sleep(interval.min(deadline - now));
+            interval = (interval * 2).min(MAX_POLL_INTERVAL);
+        }
+    }
+
+    fn read_in_background<R: Read + Send + 'static>(pipe: Option<R>) -> JoinHandle<Vec<u8>> {
+        thread::spawn(move || {
+            let mut buffer = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut buffer);
+            }
+            buffer
+        })
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_captures_output_and_times_out() {
+        let output = ProcessRunner::run(
+            &mut Command::new("cat"),
+            Some(b"= Notes\n"),
+            Some(Duration::from_secs(10)),
+        )
+        .unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"= Notes\n");
+
+        let started = Instant::now();
+        let error = ProcessRunner::run(
+            Command::new("sleep").arg("10"),
+            None,
+            Some(Duration::from_millis(100)),
+        )
+        .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        // No timeout waits for the process
+        let output =
+            ProcessRunner::run(Command::new("sh").args(["-c", "exit 3"]), None, None).unwrap();
+        assert_eq!(output.status.code(), Some(3));
+    }
+}
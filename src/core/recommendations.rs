@@ -0,0 +1,294 @@
+//! Recommendations engine
+//!
+//! Produces ranked, actionable suggestions from the notes directory: upcoming
+//! assignment due dates, courses whose activity is dropping, courses with notes
+//! but no assignments and courses that have gone quiet. Each recommendation
+//! carries the reason it was made and a command that addresses it.
+
+use anyhow::Result;
+use chrono::NaiveDate;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use crate::config::Config;
+use crate::core::calendar_export::CalendarExporter;
+use crate::core::directory_scanner::{CourseStats, DirectoryScanner};
+use crate::core::status_manager::HealthStatus;
+
+/// Assignments due within this many days are recommended
+const DUE_SOON_DAYS: i64 = 7;
+
+/// Assignments due within this many days are high priority
+const DUE_URGENT_DAYS: i64 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RecommendationPriority {
+    High,
+    Medium,
+    Low,
+}
+
+#[derive(Debug, Clone)]
+pub struct Recommendation {
+    pub priority: RecommendationPriority,
+    /// Course the recommendation is about, if any
+    pub course_id: Option<String>,
+    /// Why the recommendation was made
+    pub reason: String,
+    /// Suggested command to act on it
+    pub command: String,
+}
+
+pub struct RecommendationEngine;
+
+#[allow(dead_code)]
+impl RecommendationEngine {
+    /// Generate recommendations for one course, or all configured courses,
+    /// ranked from most to least urgent
+    pub fn generate(config: &Config, course_id: Option<&str>) -> Result<Vec<Recommendation>> {
+        let today = chrono::Local::now().date_naive();
+        let notes_dir = Path::new(&config.paths.notes_dir);
+
+        let mut recommendations = Self::due_date_recommendations(config, course_id, today)?;
+
+        let mut course_ids: Vec<String> = match course_id {
+            Some(id) => vec![id.to_string()],
+            None => config.courses.keys().cloned().collect(),
+        };
+        course_ids.sort();
+
+        for course_id in course_ids {
+            let course_path = notes_dir.join(&course_id);
+            let stats = if course_path.exists() {
+                DirectoryScanner::scan_course_directory(
+                    &course_path,
+                    &config.typst.source_extensions(),
+                    config.paths.flat_layout,
+                    config.paths.follow_symlinks,
+                )?
+            } else {
+                CourseStats {
+                    notes_count: 0,
+                    assignments_count: 0,
+                    last_activity: None,
+                    total_files: 0,
+                    modified_times: Vec::new(),
+                }
+            };
+
+            recommendations.extend(Self::course_recommendations(
+                config,
+                &course_id,
+                &course_path,
+                &stats,
+            )?);
+        }
+
+        // Stable sort keeps due dates in date order within a priority
+        recommendations.sort_by_key(|recommendation| recommendation.priority);
+
+        Ok(recommendations)
+    }
+
+    /// Assignments due in the next [`DUE_SOON_DAYS`] days
+    fn due_date_recommendations(
+        config: &Config,
+        course_id: Option<&str>,
+        today: NaiveDate,
+    ) -> Result<Vec<Recommendation>> {
+        let mut events = CalendarExporter::collect_due_dates(
+            Path::new(&config.paths.notes_dir),
+            course_id,
+            &config.typst.source_extensions(),
+            config.paths.follow_symlinks,
+        )?
+        .events;
+        events.sort_by_key(|event| event.due_date);
+
+        Ok(events
+            .into_iter()
+            .filter_map(|event| {
+                let days_left = (event.due_date - today).num_days();
+                if !(0..=DUE_SOON_DAYS).contains(&days_left) {
+                    return None;
+                }
+
+                let due = match days_left {
+                    0 => "today".to_string(),
+                    1 => "tomorrow".to_string(),
+                    days => format!("in {} days ({})", days, event.due_date),
+                };
+
+                Some(Recommendation {
+                    priority: if days_left <= DUE_URGENT_DAYS {
+                        RecommendationPriority::High
+                    } else {
+                        RecommendationPriority::Medium
+                    },
+                    course_id: Some(event.course_id),
+                    reason: format!("'{}' is due {}", event.title, due),
+                    command: format!("noter compile {}", event.file_path.display()),
+                })
+            })
+            .collect())
+    }
+
+    /// Activity-based recommendations for a single course
+    fn course_recommendations(
+        config: &Config,
+        course_id: &str,
+        course_path: &Path,
+        stats: &CourseStats,
+    ) -> Result<Vec<Recommendation>> {
+        let mut recommendations = Vec::new();
+        let recommend = |priority, reason: String, command: String| Recommendation {
+            priority,
+            course_id: Some(course_id.to_string()),
+            reason,
+            command,
+        };
+
+        if stats.total_files == 0 {
+            recommendations.push(recommend(
+                RecommendationPriority::Low,
+                format!("{} has no notes or assignments yet", course_id),
+                format!("noter note {}", course_id),
+            ));
+            return Ok(recommendations);
+        }
+
+        if stats.assignments_count == 0 {
+            recommendations.push(recommend(
+                RecommendationPriority::Medium,
+                format!(
+                    "{} has {} note(s) but no assignments",
+                    course_id, stats.notes_count
+                ),
+                format!("noter assignment {} \"Assignment 1\"", course_id),
+            ));
+        }
+
+        let days_since_activity = stats.last_activity.as_ref().and_then(|file| {
+            SystemTime::now()
+                .duration_since(file.modified)
+                .ok()
+                .map(|d| d.as_secs() / (24 * 60 * 60))
+        });
+        let health =
+            HealthStatus::from_days_since_activity(days_since_activity, &config.health_thresholds);
+
+        if health == HealthStatus::Critical {
+            let resume_command = if stats.assignments_count > 0 {
+                format!("noter assignments recent {}", course_id)
+            } else {
+                format!("noter open {}", course_id)
+            };
+
+            recommendations.push(recommend(
+                RecommendationPriority::Medium,
+                match days_since_activity {
+                    Some(days) => format!("No activity in {} for {} days", course_id, days),
+                    None => format!("No recorded activity in {}", course_id),
+                },
+                resume_command,
+            ));
+        } else if let Some((recent, previous)) = Self::activity_trend(course_path, config)? {
+            recommendations.push(recommend(
+                RecommendationPriority::Low,
+                format!(
+                    "Activity in {} is dropping: {} file(s) changed in the last {} days, {} the {} days before",
+                    course_id,
+                    recent,
+                    config.health_thresholds.good_days,
+                    previous,
+                    config.health_thresholds.good_days
+                ),
+                format!("noter note {}", course_id),
+            ));
+        }
+
+        Ok(recommendations)
+    }
+
+    /// Compare files changed in the last `good_days` with the window before it,
+    /// returning both counts when activity went down
+    fn activity_trend(course_path: &Path, config: &Config) -> Result<Option<(usize, usize)>> {
+        let window_days = config.health_thresholds.good_days.max(1);
+        let window = Duration::from_secs(window_days * 24 * 60 * 60);
+        let now = SystemTime::now();
+        let (Some(recent_start), Some(previous_start)) =
+            (now.checked_sub(window), now.checked_sub(window * 2))
+        else {
+            return Ok(None);
+        };
+
+        let files = DirectoryScanner::scan_directory_for_files(
+            course_path,
+            &config.typst.source_extensions(),
+            config.paths.follow_symlinks,
+        )?;
+        let recent = files
+            .iter()
+            .filter(|file| file.modified >= recent_start)
+            .count();
+        let previous = files
+            .iter()
+            .filter(|file| file.modified >= previous_start && file.modified < recent_start)
+            .count();
+
+        Ok((recent < previous).then_some((recent, previous)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_recommendations_are_ranked() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.paths.notes_dir = temp_dir.path().to_string_lossy().to_string();
+        config.courses = [
+            ("01005", "Advanced Engineering Mathematics 1"),
+            ("02101", "Introduction to Programming"),
+            ("02102", "Algorithms and Data Structures"),
+        ]
+        .iter()
+        .map(|(id, name)| (id.to_string(), name.to_string()))
+        .collect();
+
+        // 02101: an assignment due tomorrow
+        let assignments = temp_dir.path().join("02101").join("assignments");
+        fs::create_dir_all(&assignments).unwrap();
+        let due = chrono::Local::now().date_naive() + chrono::Duration::days(1);
+        fs::write(
+            assignments.join("assignment-1.typ"),
+            format!("= Lab 1\n*Due Date*: {} \\\n", due),
+        )
+        .unwrap();
+
+        // 02102: notes but no assignments
+        let lectures = temp_dir.path().join("02102").join("lectures");
+        fs::create_dir_all(&lectures).unwrap();
+        fs::write(lectures.join("lecture-1.typ"), "= Lecture 1").unwrap();
+
+        let recommendations = RecommendationEngine::generate(&config, None).unwrap();
+
+        assert_eq!(recommendations[0].priority, RecommendationPriority::High);
+        assert_eq!(recommendations[0].reason, "'Lab 1' is due tomorrow");
+        assert!(recommendations[0].command.starts_with("noter compile "));
+        assert!(recommendations[0].command.ends_with("assignment-1.typ"));
+
+        assert!(recommendations.iter().any(|r| {
+            r.course_id.as_deref() == Some("02102") && r.reason.contains("no assignments")
+        }));
+
+        // 01005 has no files at all, the least urgent recommendation
+        let last = recommendations.last().unwrap();
+        assert_eq!(last.priority, RecommendationPriority::Low);
+        assert_eq!(last.course_id.as_deref(), Some("01005"));
+    }
+}
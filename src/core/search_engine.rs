@@ -1,11 +1,18 @@
 //! Search engine for note content
 //!
 //! Handles searching through files with various options and filters.
-
-use crate::core::directory_scanner::DirectoryScanner;
+//! Directory searches skip whatever the notes directory's `.noterignore`
+//! matches, and only follow symlinked directories with
+//! [`SearchOptions::follow_symlinks`].
+
+use crate::config::ResultOrder;
+use crate::core::directory_scanner::{DirectoryScanner, FileInfo};
+use crate::core::ignore::IgnoreRules;
+use crate::core::search_index::SearchIndex;
 use anyhow::Result;
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use regex::{Regex, RegexBuilder};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
@@ -17,6 +24,52 @@ pub struct SearchMatch {
     pub line_content: String,
     pub match_start: usize,
     pub match_end: usize,
+    /// Byte spans of every match in `line_content`, in order; the first is
+    /// `match_start..match_end`
+    pub spans: Vec<(usize, usize)>,
+}
+
+/// Match counts for a set of search results
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchSummary {
+    /// Every occurrence, counting repeats on the same line
+    pub total_matches: usize,
+    /// Lines with at least one match
+    pub matching_lines: usize,
+    /// Occurrences per file, ordered by path
+    pub per_file: BTreeMap<PathBuf, usize>,
+}
+
+impl SearchSummary {
+    pub fn from_matches(matches: &[SearchMatch]) -> Self {
+        let mut summary = Self::default();
+        for search_match in matches {
+            let occurrences = search_match.spans.len().max(1);
+            summary.total_matches += occurrences;
+            summary.matching_lines += 1;
+            *summary
+                .per_file
+                .entry(search_match.file_path.clone())
+                .or_default() += occurrences;
+        }
+        summary
+    }
+}
+
+/// How a query is matched against a line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatchMode {
+    /// The whole query as one substring
+    #[default]
+    Substring,
+    /// Any word of the query
+    AnyWord,
+    /// Every word of the query, in any order
+    AllWords,
+    /// The query as a regular expression. With `highlight_group`, only that
+    /// capture group's span is highlighted, or the whole match when the group
+    /// doesn't take part in it.
+    Regex { highlight_group: Option<usize> },
 }
 
 #[derive(Debug, Clone)]
@@ -26,51 +79,102 @@ pub struct SearchOptions {
     pub max_results: usize,
     pub context_lines: usize,
     pub file_extensions: Vec<String>,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct SearchIndex {
-    pub word_map: HashMap<String, Vec<SearchLocation>>,
-    pub last_updated: SystemTime,
-}
-
-#[derive(Debug, Clone, Deserialize, Serialize)]
-pub struct SearchLocation {
-    pub file_path: PathBuf,
-    pub line_number: usize,
-    pub column: usize,
+    pub match_mode: MatchMode,
+    /// Descend into symlinked directories, each at most once
+    pub follow_symlinks: bool,
+    /// Order of the results before `max_results` truncates them
+    pub result_order: ResultOrder,
 }
 
 pub struct SearchEngine;
 
 impl SearchEngine {
-    const INDEX_FILE: &'static str = ".notes-search-index";
-
     pub fn search_in_directory<P: AsRef<Path>>(
         dir: P,
         query: &str,
         options: &SearchOptions,
     ) -> Result<Vec<SearchMatch>> {
+        Self::validate_query(query, options)?;
+        let dir = dir.as_ref();
+        let ignore = IgnoreRules::find(dir)?;
+        let mut visited = HashSet::new();
+        DirectoryScanner::should_descend(dir, true, &mut visited);
+
         let mut results = Vec::new();
-        Self::search_recursive(dir.as_ref(), query, options, &mut results)?;
+        Self::search_recursive(dir, query, options, &ignore, &mut visited, &mut results)?;
 
-        // Limit results
+        // Order before limiting, so the limit keeps the first results of that order
+        Self::order_results(&mut results, options.result_order);
         results.truncate(options.max_results);
         Ok(results)
     }
 
+    /// Search only the given files, e.g. candidates narrowed by the token index
+    pub fn search_in_files(
+        files: &[PathBuf],
+        query: &str,
+        options: &SearchOptions,
+    ) -> Result<Vec<SearchMatch>> {
+        Self::validate_query(query, options)?;
+        let mut results = Vec::new();
+        for path in files {
+            if Self::should_search_file(path, options) {
+                Self::search_in_file(path, query, options, &mut results)?;
+            }
+        }
+
+        Self::order_results(&mut results, options.result_order);
+        results.truncate(options.max_results);
+        Ok(results)
+    }
+
+    /// Sort `results` by `order`. Matches of the same rank stay in file then
+    /// line order, so truncating afterwards is deterministic.
+    pub fn order_results(results: &mut [SearchMatch], order: ResultOrder) {
+        results.sort_by(|a, b| (&a.file_path, a.line_number).cmp(&(&b.file_path, b.line_number)));
+
+        match order {
+            ResultOrder::FileThenLine => {}
+            ResultOrder::Relevance => {
+                let counts = SearchSummary::from_matches(results).per_file;
+                results.sort_by_key(|search_match| Reverse(counts[&search_match.file_path]));
+            }
+            ResultOrder::Modified => {
+                let mut modified = HashMap::new();
+                for search_match in results.iter() {
+                    modified
+                        .entry(search_match.file_path.clone())
+                        .or_insert_with(|| {
+                            fs::metadata(&search_match.file_path)
+                                .and_then(|metadata| metadata.modified())
+                                .unwrap_or(SystemTime::UNIX_EPOCH)
+                        });
+                }
+                results.sort_by_key(|search_match| Reverse(modified[&search_match.file_path]));
+            }
+        }
+    }
+
     fn search_recursive(
         dir: &Path,
         query: &str,
         options: &SearchOptions,
+        ignore: &IgnoreRules,
+        visited: &mut HashSet<PathBuf>,
         results: &mut Vec<SearchMatch>,
     ) -> Result<()> {
         for entry in fs::read_dir(dir)? {
             let entry = entry?;
             let path = entry.path();
+            let is_dir = path.is_dir();
+            if ignore.is_ignored(&path, is_dir) {
+                continue;
+            }
 
-            if path.is_dir() {
-                Self::search_recursive(&path, query, options, results)?;
+            if is_dir {
+                if DirectoryScanner::should_descend(&path, options.follow_symlinks, visited) {
+                    Self::search_recursive(&path, query, options, ignore, visited, results)?;
+                }
             } else if Self::should_search_file(&path, options) {
                 Self::search_in_file(&path, query, options, results)?;
             }
@@ -94,15 +198,30 @@ impl SearchEngine {
         results: &mut Vec<SearchMatch>,
     ) -> Result<()> {
         let content = fs::read_to_string(path)?;
+        let regex = match options.match_mode {
+            MatchMode::Regex { .. } => Some(Self::compile_regex(query, options.case_sensitive)?),
+            _ => None,
+        };
 
         for (line_num, line) in content.lines().enumerate() {
-            if let Some(match_pos) = Self::find_match(line, query, options.case_sensitive) {
+            // Spans are taken on the trimmed line so they line up with `line_content`
+            let line = line.trim();
+            let spans = Self::find_matches(
+                line,
+                query,
+                regex.as_ref(),
+                options.case_sensitive,
+                options.match_mode,
+            );
+
+            if let Some(&(match_start, match_end)) = spans.first() {
                 results.push(SearchMatch {
                     file_path: path.to_path_buf(),
                     line_number: line_num + 1,
-                    line_content: line.trim().to_string(),
-                    match_start: match_pos,
-                    match_end: match_pos + query.len(),
+                    line_content: line.to_string(),
+                    match_start,
+                    match_end,
+                    spans,
                 });
             }
         }
@@ -110,113 +229,224 @@ impl SearchEngine {
         Ok(())
     }
 
-    fn find_match(line: &str, query: &str, case_sensitive: bool) -> Option<usize> {
-        if case_sensitive {
-            line.find(query)
-        } else {
-            line.to_lowercase().find(&query.to_lowercase())
+    /// Fail early on a query the match mode can't use: an invalid regex, or a
+    /// highlight group the regex doesn't have
+    fn validate_query(query: &str, options: &SearchOptions) -> Result<()> {
+        let MatchMode::Regex { highlight_group } = options.match_mode else {
+            return Ok(());
+        };
+
+        let groups = Self::compile_regex(query, options.case_sensitive)?.captures_len() - 1;
+        if let Some(group) = highlight_group.filter(|group| *group > groups) {
+            anyhow::bail!(
+                "--highlight-group {} is out of range, the regex has {} capture group{}",
+                group,
+                groups,
+                if groups == 1 { "" } else { "s" }
+            );
         }
+        Ok(())
+    }
+
+    pub fn compile_regex(query: &str, case_sensitive: bool) -> Result<Regex> {
+        RegexBuilder::new(query)
+            .case_insensitive(!case_sensitive)
+            .build()
+            .map_err(|e| anyhow::anyhow!("Invalid regex '{}': {}", query, e))
     }
 
-    pub fn build_index(notes_dir: &Path) -> Result<SearchIndex> {
-        let mut word_map = HashMap::new();
-        let files = DirectoryScanner::scan_directory_for_files(notes_dir, &["typ", "md"])?;
-
-        for file_info in files {
-            if let Ok(content) = fs::read_to_string(&file_info.path) {
-                for (line_num, line) in content.lines().enumerate() {
-                    for (col, word) in line.split_whitespace().enumerate() {
-                        let word_clean = word.to_lowercase();
-                        let location = SearchLocation {
-                            file_path: file_info.path.clone(),
-                            line_number: line_num + 1,
-                            column: col,
-                        };
-
-                        word_map
-                            .entry(word_clean)
-                            .or_insert_with(Vec::new)
-                            .push(location);
-                    }
+    /// Spans of every non-empty match of `regex` in `line`: capture group
+    /// `group`'s span when given and it took part, else the whole match
+    pub fn find_regex_spans(
+        line: &str,
+        regex: &Regex,
+        group: Option<usize>,
+    ) -> Vec<(usize, usize)> {
+        regex
+            .captures_iter(line)
+            .filter_map(|captures| {
+                let whole = captures.get(0)?;
+                if whole.is_empty() {
+                    return None;
                 }
-            }
+                let span = group.and_then(|group| captures.get(group)).unwrap_or(whole);
+                Some((span.start(), span.end()))
+            })
+            .collect()
+    }
+
+    /// Find the byte spans of every non-overlapping occurrence of `query` in `line`
+    pub fn find_match_spans(line: &str, query: &str, case_sensitive: bool) -> Vec<(usize, usize)> {
+        if query.is_empty() {
+            return Vec::new();
         }
 
-        Ok(SearchIndex {
-            word_map,
-            last_updated: SystemTime::now(),
-        })
-    }
+        let (haystack, needle) = if case_sensitive {
+            (line.to_string(), query.to_string())
+        } else {
+            (line.to_lowercase(), query.to_lowercase())
+        };
 
-    pub fn search_indexed(&self, index: &SearchIndex, query: &str) -> Vec<SearchLocation> {
-        let query_lower = query.to_lowercase();
-        index
-            .word_map
-            .get(&query_lower)
-            .cloned()
-            .unwrap_or_default()
+        // Lowercasing can change byte lengths for some characters; spans are
+        // only meaningful when the folded line lines up with the original
+        if haystack.len() != line.len() {
+            return Vec::new();
+        }
+
+        haystack
+            .match_indices(&needle)
+            .map(|(start, matched)| (start, start + matched.len()))
+            .filter(|(start, end)| line.is_char_boundary(*start) && line.is_char_boundary(*end))
+            .collect()
     }
 
-    /// Get or create search index with automatic freshness checking
-    pub fn get_or_build_index(notes_dir: &Path) -> Result<SearchIndex> {
-        let index_path = notes_dir.join(Self::INDEX_FILE);
+    /// Replace every occurrence of `query` in `line`, returning `None` if nothing matched
+    pub fn replace_in_line(
+        line: &str,
+        query: &str,
+        replacement: &str,
+        case_sensitive: bool,
+    ) -> Option<String> {
+        let spans = Self::find_match_spans(line, query, case_sensitive);
+        if spans.is_empty() {
+            return None;
+        }
 
-        // Try to load existing index
-        if let Ok(index) = Self::load_index(&index_path) {
-            // Check if index is still fresh
-            if Self::is_index_fresh(&index, notes_dir)? {
-                return Ok(index);
-            }
+        let mut replaced = String::with_capacity(line.len());
+        let mut last_end = 0;
+        for (start, end) in spans {
+            replaced.push_str(&line[last_end..start]);
+            replaced.push_str(replacement);
+            last_end = end;
         }
+        replaced.push_str(&line[last_end..]);
 
-        // Build new index
-        let index = Self::build_index(notes_dir)?;
-        Self::save_index(&index, &index_path)?;
-        Ok(index)
+        Some(replaced)
     }
 
-    /// Check if index is newer than all files
-    fn is_index_fresh(index: &SearchIndex, notes_dir: &Path) -> Result<bool> {
-        let files = DirectoryScanner::scan_directory_for_files(notes_dir, &["typ", "md"])?;
+    /// Spans matched in `line` under `mode`, empty when the line does not match.
+    /// Word modes split the query on whitespace; overlapping spans are merged.
+    /// Regex mode uses `regex`, the query compiled once by the caller.
+    pub fn find_matches(
+        line: &str,
+        query: &str,
+        regex: Option<&Regex>,
+        case_sensitive: bool,
+        mode: MatchMode,
+    ) -> Vec<(usize, usize)> {
+        let words: Vec<&str> = match mode {
+            MatchMode::Substring => {
+                let spans = Self::find_match_spans(line, query, case_sensitive);
+                if !spans.is_empty() {
+                    return spans;
+                }
+                // Case folding changed byte lengths, so only the position is known
+                return Self::find_match(line, query, case_sensitive)
+                    .map(|pos| vec![(pos, pos + query.len())])
+                    .unwrap_or_default();
+            }
+            MatchMode::AnyWord | MatchMode::AllWords => query.split_whitespace().collect(),
+            MatchMode::Regex { highlight_group } => {
+                return regex
+                    .map(|regex| Self::find_regex_spans(line, regex, highlight_group))
+                    .unwrap_or_default();
+            }
+        };
+
+        let mut spans = Vec::new();
+        let mut matched_words = 0;
+        for word in &words {
+            let word_spans = Self::find_match_spans(line, word, case_sensitive);
+            if !word_spans.is_empty() {
+                matched_words += 1;
+                spans.extend(word_spans);
+            }
+        }
 
-        for file_info in files {
-            if file_info.modified > index.last_updated {
-                return Ok(false); // Found newer file
+        if matched_words == 0 || (mode == MatchMode::AllWords && matched_words < words.len()) {
+            return Vec::new();
+        }
+
+        spans.sort_unstable();
+        let mut merged: Vec<(usize, usize)> = Vec::with_capacity(spans.len());
+        for (start, end) in spans {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
             }
         }
-        Ok(true)
+        merged
     }
 
-    /// Save index to disk
-    fn save_index(index: &SearchIndex, path: &Path) -> Result<()> {
-        let serialized = serde_json::to_string(index)?;
-        fs::write(path, serialized)?;
-        Ok(())
+    fn find_match(line: &str, query: &str, case_sensitive: bool) -> Option<usize> {
+        if case_sensitive {
+            line.find(query)
+        } else {
+            line.to_lowercase().find(&query.to_lowercase())
+        }
     }
 
-    /// Load index from disk
-    fn load_index(path: &Path) -> Result<SearchIndex> {
-        let content = fs::read_to_string(path)?;
-        let index = serde_json::from_str(&content)?;
+    /// Index the files with one of `extensions` below `notes_dir`
+    pub fn build_index(
+        notes_dir: &Path,
+        extensions: &[String],
+        follow_symlinks: bool,
+    ) -> Result<SearchIndex> {
+        SearchIndex::build(notes_dir, extensions, follow_symlinks)
+    }
+
+    /// Files with one of `extensions` below `notes_dir`, the files an index covers
+    pub fn indexed_files(
+        notes_dir: &Path,
+        extensions: &[String],
+        follow_symlinks: bool,
+    ) -> Result<Vec<FileInfo>> {
+        let extensions: Vec<&str> = extensions.iter().map(String::as_str).collect();
+        DirectoryScanner::scan_directory_for_files(notes_dir, &extensions, follow_symlinks)
+    }
+
+    /// Load the search index and bring it up to date, saving it when anything changed
+    pub fn get_or_build_index(
+        notes_dir: &Path,
+        extensions: &[String],
+        follow_symlinks: bool,
+    ) -> Result<SearchIndex> {
+        let (index, update) = SearchIndex::update_or_build(notes_dir, extensions, follow_symlinks)?;
+        if update.changed() || !SearchIndex::index_path(notes_dir).exists() {
+            index.save(notes_dir)?;
+        }
         Ok(index)
     }
 
-    /// Fast indexed search
-    pub fn search_with_index(index: &SearchIndex, query: &str) -> Vec<SearchLocation> {
-        index
-            .word_map
-            .get(&query.to_lowercase())
-            .cloned()
-            .unwrap_or_default()
+    /// Check if index covers `extensions` and every file at its current state
+    #[cfg(test)]
+    fn is_index_fresh(
+        index: &SearchIndex,
+        notes_dir: &Path,
+        extensions: &[String],
+        follow_symlinks: bool,
+    ) -> Result<bool> {
+        index.is_fresh(notes_dir, extensions, follow_symlinks)
+    }
+
+    /// Load index from disk
+    #[cfg(test)]
+    fn load_index(path: &Path) -> Result<SearchIndex> {
+        SearchIndex::load_from(path)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::SearchConfig;
     use std::fs;
     use tempfile::TempDir;
 
+    fn extensions() -> Vec<String> {
+        SearchConfig::default().file_extensions
+    }
+
     fn create_test_files(dir: &Path, files: &[(&str, &str)]) -> Result<()> {
         for (filename, content) in files {
             let file_path = dir.join(filename);
@@ -228,6 +458,69 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_find_matches_word_modes() {
+        let line = "A binary search tree is a tree";
+
+        assert_eq!(
+            SearchEngine::find_matches(line, "binary tree", None, false, MatchMode::Substring),
+            vec![]
+        );
+        assert_eq!(
+            SearchEngine::find_matches(line, "Binary  tree", None, false, MatchMode::AllWords),
+            vec![(2, 8), (16, 20), (26, 30)]
+        );
+        assert_eq!(
+            SearchEngine::find_matches(line, "binary heap", None, false, MatchMode::AllWords),
+            vec![]
+        );
+        assert_eq!(
+            SearchEngine::find_matches(line, "binary heap", None, false, MatchMode::AnyWord),
+            vec![(2, 8)]
+        );
+
+        // Overlapping word matches are merged into one span
+        assert_eq!(
+            SearchEngine::find_matches(line, "search arch", None, false, MatchMode::AnyWord),
+            vec![(9, 15)]
+        );
+    }
+
+    #[test]
+    fn test_regex_highlight_group() {
+        let line = "Deadline: 2026-10-21, review 2026-11-04";
+        let regex = SearchEngine::compile_regex(r"(\d{4})-(\d{2})?-\d{2}", true).unwrap();
+
+        assert_eq!(
+            SearchEngine::find_regex_spans(line, &regex, None),
+            vec![(10, 20), (29, 39)]
+        );
+        assert_eq!(
+            SearchEngine::find_regex_spans(line, &regex, Some(2)),
+            vec![(15, 17), (34, 36)]
+        );
+
+        // A group that doesn't take part falls back to the whole match
+        let regex = SearchEngine::compile_regex(r"review (x)?\S+", true).unwrap();
+        assert_eq!(
+            SearchEngine::find_regex_spans(line, &regex, Some(1)),
+            vec![(22, 39)]
+        );
+
+        let options = |highlight_group| SearchOptions {
+            case_sensitive: false,
+            max_results: 10,
+            context_lines: 0,
+            file_extensions: vec!["typ".to_string()],
+            match_mode: MatchMode::Regex { highlight_group },
+            follow_symlinks: false,
+            result_order: ResultOrder::default(),
+        };
+        assert!(SearchEngine::validate_query(r"(\d+)", &options(Some(1))).is_ok());
+        assert!(SearchEngine::validate_query(r"(\d+)", &options(Some(2))).is_err());
+        assert!(SearchEngine::validate_query(r"(unclosed", &options(None)).is_err());
+    }
+
     #[test]
     fn test_build_index() -> Result<()> {
         let temp_dir = TempDir::new()?;
@@ -243,90 +536,36 @@ mod tests {
             ],
         )?;
 
-        let index = SearchEngine::build_index(temp_path)?;
+        let index = SearchEngine::build_index(temp_path, &extensions(), true)?;
 
         // Debug: Print what words were actually indexed
         println!(
-            "Indexed words: {:?}",
-            index.word_map.keys().collect::<Vec<_>>()
+            "Indexed tokens: {:?}",
+            index.tokens.keys().collect::<Vec<_>>()
         );
 
         // Check that words are indexed
-        assert!(index.word_map.contains_key("hello"));
-        assert!(index.word_map.contains_key("algorithms"));
-        assert!(index.word_map.contains_key("machine"));
+        assert!(index.tokens.contains_key("hello"));
+        assert!(index.tokens.contains_key("algorithms"));
+        assert!(index.tokens.contains_key("machine"));
 
         // Check if the subdirectory file was processed
         assert!(
-            index.word_map.contains_key("programming"),
+            index.tokens.contains_key("programming"),
             "Word 'programming' should be indexed"
         );
         assert!(
-            index.word_map.contains_key("systems"),
+            index.tokens.contains_key("systems"),
             "Word 'systems' should be indexed"
         );
         assert!(
-            index.word_map.contains_key("rust"),
+            index.tokens.contains_key("rust"),
             "Word 'rust' should be indexed"
         );
 
-        // Check location information
-        let hello_locations = index.word_map.get("hello").unwrap();
-        assert_eq!(hello_locations.len(), 1);
-        assert_eq!(hello_locations[0].line_number, 1);
-        assert_eq!(hello_locations[0].column, 0);
-
-        Ok(())
-    }
-
-    #[test]
-    fn test_search_with_index() -> Result<()> {
-        let temp_dir = TempDir::new()?;
-        let temp_path = temp_dir.path();
-
-        create_test_files(
-            temp_path,
-            &[
-                (
-                    "test1.typ",
-                    "algorithms and data structures\nsorting algorithms",
-                ),
-                ("test2.md", "machine learning algorithms\nneural networks"),
-            ],
-        )?;
-
-        let index = SearchEngine::build_index(temp_path)?;
-        let results = SearchEngine::search_with_index(&index, "algorithms");
-
-        assert_eq!(results.len(), 3); // Should find 3 occurrences
-
-        // Check that results contain expected files
-        let file_paths: Vec<_> = results
-            .iter()
-            .map(|r| r.file_path.file_name().unwrap().to_str().unwrap())
-            .collect();
-
-        assert!(file_paths.contains(&"test1.typ"));
-        assert!(file_paths.contains(&"test2.md"));
-
-        Ok(())
-    }
-
-    #[test]
-    fn test_case_insensitive_search() -> Result<()> {
-        let temp_dir = TempDir::new()?;
-        let temp_path = temp_dir.path();
-
-        create_test_files(temp_path, &[("test.typ", "ALGORITHMS and Data STRUCTURES")])?;
-
-        let index = SearchEngine::build_index(temp_path)?;
-
-        // Search with lowercase should find uppercase words
-        let results = SearchEngine::search_with_index(&index, "algorithms");
-        assert_eq!(results.len(), 1);
-
-        let results = SearchEngine::search_with_index(&index, "data");
-        assert_eq!(results.len(), 1);
+        // Check the indexed files
+        assert_eq!(index.files.len(), 3);
+        assert!(index.files.contains_key(Path::new("subdir/file3.typ")));
 
         Ok(())
     }
@@ -340,16 +579,16 @@ mod tests {
         create_test_files(temp_path, &[("test.typ", "persistent indexing test")])?;
 
         // Build and save index
-        let index = SearchEngine::build_index(temp_path)?;
+        let index = SearchEngine::build_index(temp_path, &extensions(), true)?;
         let serialized = serde_json::to_string(&index)?;
         fs::write(&index_path, serialized)?;
 
         // Load index from disk
         let loaded_index = SearchEngine::load_index(&index_path)?;
 
-        assert_eq!(index.word_map.len(), loaded_index.word_map.len());
-        assert!(loaded_index.word_map.contains_key("persistent"));
-        assert!(loaded_index.word_map.contains_key("indexing"));
+        assert_eq!(index.tokens.len(), loaded_index.tokens.len());
+        assert!(loaded_index.tokens.contains_key("persistent"));
+        assert!(loaded_index.tokens.contains_key("indexing"));
 
         Ok(())
     }
@@ -361,17 +600,27 @@ mod tests {
 
         create_test_files(temp_path, &[("test.typ", "initial content")])?;
 
-        let index = SearchEngine::build_index(temp_path)?;
+        let index = SearchEngine::build_index(temp_path, &extensions(), true)?;
 
         // Index should be fresh immediately after creation
-        assert!(SearchEngine::is_index_fresh(&index, temp_path)?);
+        assert!(SearchEngine::is_index_fresh(
+            &index,
+            temp_path,
+            &extensions(),
+            true
+        )?);
 
         // Wait a bit and modify a file
         std::thread::sleep(std::time::Duration::from_millis(10));
         fs::write(temp_path.join("test.typ"), "modified content")?;
 
         // Index should now be stale
-        assert!(!SearchEngine::is_index_fresh(&index, temp_path)?);
+        assert!(!SearchEngine::is_index_fresh(
+            &index,
+            temp_path,
+            &extensions(),
+            true
+        )?);
 
         Ok(())
     }
@@ -384,16 +633,23 @@ mod tests {
         create_test_files(temp_path, &[("test.typ", "test content for indexing")])?;
 
         // First call should build new index
-        let index1 = SearchEngine::get_or_build_index(temp_path)?;
-        assert!(index1.word_map.contains_key("test"));
+        let index1 = SearchEngine::get_or_build_index(temp_path, &extensions(), true)?;
+        assert!(index1.tokens.contains_key("test"));
 
         // Check that index file was created
         let index_path = temp_path.join(".notes-search-index");
         assert!(index_path.exists());
 
         // Second call should use existing index (if fresh)
-        let index2 = SearchEngine::get_or_build_index(temp_path)?;
-        assert_eq!(index1.word_map.len(), index2.word_map.len());
+        let index2 = SearchEngine::get_or_build_index(temp_path, &extensions(), true)?;
+        assert_eq!(index1.tokens.len(), index2.tokens.len());
+
+        // A configured extension is indexed once it is searched
+        create_test_files(temp_path, &[("notes.tex", "latex content")])?;
+        let mut with_tex = extensions();
+        with_tex.push("tex".to_string());
+        let index3 = SearchEngine::get_or_build_index(temp_path, &with_tex, true)?;
+        assert!(index3.tokens.contains_key("latex"));
 
         Ok(())
     }
@@ -403,49 +659,133 @@ mod tests {
         let temp_dir = TempDir::new()?;
         let temp_path = temp_dir.path();
 
-        let index = SearchEngine::build_index(temp_path)?;
-        assert!(index.word_map.is_empty());
+        let index = SearchEngine::build_index(temp_path, &extensions(), true)?;
+        assert!(index.tokens.is_empty());
 
         Ok(())
     }
 
     #[test]
-    fn test_file_extensions_filter() -> Result<()> {
+    fn test_search_summary_counts_every_occurrence() -> Result<()> {
         let temp_dir = TempDir::new()?;
-        let temp_path = temp_dir.path();
-
         create_test_files(
-            temp_path,
+            temp_dir.path(),
             &[
-                ("test.typ", "typst file content"),
-                ("test.md", "markdown file content"),
-                ("test.txt", "text file content"), // Should be ignored
-                ("test.rs", "rust file content"),  // Should be ignored
+                ("a.typ", "tree and tree\nno match\nTree"),
+                ("b.typ", "a tree"),
+                ("c.typ", "nothing here"),
             ],
         )?;
 
-        let index = SearchEngine::build_index(temp_path)?;
+        let options = SearchOptions {
+            case_sensitive: false,
+            max_results: usize::MAX,
+            context_lines: 0,
+            file_extensions: vec!["typ".to_string()],
+            match_mode: MatchMode::Substring,
+            follow_symlinks: true,
+            result_order: ResultOrder::default(),
+        };
+        let matches = SearchEngine::search_in_directory(temp_dir.path(), "tree", &options)?;
+        let summary = SearchSummary::from_matches(&matches);
+
+        assert_eq!(summary.total_matches, 4);
+        assert_eq!(summary.matching_lines, 3);
+        assert_eq!(
+            summary.per_file.into_iter().collect::<Vec<_>>(),
+            vec![
+                (temp_dir.path().join("a.typ"), 3),
+                (temp_dir.path().join("b.typ"), 1)
+            ]
+        );
 
-        // Should only index .typ and .md files
-        assert!(index.word_map.contains_key("typst"));
-        assert!(index.word_map.contains_key("markdown"));
-        assert!(!index.word_map.contains_key("rust"));
-        assert!(!index.word_map.contains_key("text"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_result_order_applies_before_truncation() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        create_test_files(
+            temp_dir.path(),
+            &[
+                ("a.md", "tree"),
+                ("b.typ", "tree\ntree tree\nno match"),
+                ("c.typ", "tree"),
+            ],
+        )?;
+        let now = SystemTime::now();
+        for (name, age) in [("a.md", 30), ("b.typ", 20), ("c.typ", 10)] {
+            fs::File::options()
+                .write(true)
+                .open(temp_dir.path().join(name))?
+                .set_modified(now - std::time::Duration::from_secs(age))?;
+        }
+
+        let search = |result_order, max_results| -> Result<Vec<(String, usize)>> {
+            let options = SearchOptions {
+                case_sensitive: false,
+                max_results,
+                context_lines: 0,
+                file_extensions: vec!["typ".to_string(), "md".to_string()],
+                match_mode: MatchMode::Substring,
+                follow_symlinks: true,
+                result_order,
+            };
+            Ok(
+                SearchEngine::search_in_directory(temp_dir.path(), "tree", &options)?
+                    .into_iter()
+                    .map(|m| {
+                        let name = m.file_path.file_name().unwrap().to_string_lossy();
+                        (name.into_owned(), m.line_number)
+                    })
+                    .collect(),
+            )
+        };
+        let entry = |name: &str, line| (name.to_string(), line);
+
+        assert_eq!(
+            search(ResultOrder::FileThenLine, usize::MAX)?,
+            vec![
+                entry("a.md", 1),
+                entry("b.typ", 1),
+                entry("b.typ", 2),
+                entry("c.typ", 1)
+            ]
+        );
+        assert_eq!(
+            search(ResultOrder::Relevance, 3)?,
+            vec![entry("b.typ", 1), entry("b.typ", 2), entry("a.md", 1)]
+        );
+        assert_eq!(
+            search(ResultOrder::Modified, 2)?,
+            vec![entry("c.typ", 1), entry("b.typ", 1)]
+        );
 
         Ok(())
     }
 
     #[test]
-    fn test_search_no_results() -> Result<()> {
+    fn test_file_extensions_filter() -> Result<()> {
         let temp_dir = TempDir::new()?;
         let temp_path = temp_dir.path();
 
-        create_test_files(temp_path, &[("test.typ", "algorithms and data structures")])?;
+        create_test_files(
+            temp_path,
+            &[
+                ("test.typ", "typst file content"),
+                ("test.md", "markdown file content"),
+                ("test.txt", "text file content"), // Should be ignored
+                ("test.rs", "rust file content"),  // Should be ignored
+            ],
+        )?;
 
-        let index = SearchEngine::build_index(temp_path)?;
-        let results = SearchEngine::search_with_index(&index, "nonexistent");
+        let index = SearchEngine::build_index(temp_path, &extensions(), true)?;
 
-        assert!(results.is_empty());
+        // Should only index .typ and .md files
+        assert!(index.tokens.contains_key("typst"));
+        assert!(index.tokens.contains_key("markdown"));
+        assert!(!index.tokens.contains_key("rust"));
+        assert!(!index.tokens.contains_key("text"));
 
         Ok(())
     }
@@ -472,16 +812,15 @@ mod tests {
         create_test_files(temp_path, &files_ref)?;
 
         let start = std::time::Instant::now();
-        let index = SearchEngine::build_index(temp_path)?;
+        let index = SearchEngine::build_index(temp_path, &extensions(), true)?;
         let build_time = start.elapsed();
 
         println!("Built index for {} files in {:?}", files.len(), build_time);
 
-        assert!(index.word_map.contains_key("algorithms"));
+        assert!(index.tokens.contains_key("algorithms"));
 
-        // Should have 100 occurrences of "algorithms"
-        let algorithm_locations = index.word_map.get("algorithms").unwrap();
-        assert_eq!(algorithm_locations.len(), 100);
+        // Every file contains "algorithms"
+        assert_eq!(index.tokens["algorithms"].len(), 100);
 
         Ok(())
     }
@@ -502,20 +841,36 @@ mod tests {
             ],
         )?;
 
-        let index = SearchEngine::build_index(temp_path)?;
+        let index = SearchEngine::build_index(temp_path, &extensions(), true)?;
 
         // Debug: Print all indexed words
         println!(
             "All indexed words: {:?}",
-            index.word_map.keys().collect::<Vec<_>>()
+            index.tokens.keys().collect::<Vec<_>>()
         );
 
         // Check that files from all levels are indexed
-        assert!(index.word_map.contains_key("root"));
-        assert!(index.word_map.contains_key("nested"));
-        assert!(index.word_map.contains_key("rust"));
-        assert!(index.word_map.contains_key("deep"));
+        assert!(index.tokens.contains_key("root"));
+        assert!(index.tokens.contains_key("nested"));
+        assert!(index.tokens.contains_key("rust"));
+        assert!(index.tokens.contains_key("deep"));
 
         Ok(())
     }
+
+    #[test]
+    fn test_replace_in_line() {
+        assert_eq!(
+            SearchEngine::replace_in_line("Eigen value and eigen value", "eigen", "Eigen", false),
+            Some("Eigen value and Eigen value".to_string())
+        );
+        assert_eq!(
+            SearchEngine::replace_in_line("Eigen value", "eigen", "x", true),
+            None
+        );
+        assert_eq!(
+            SearchEngine::replace_in_line("  indented term", "term", "word", true),
+            Some("  indented word".to_string())
+        );
+    }
 }
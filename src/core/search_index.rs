@@ -0,0 +1,340 @@
+//! On-disk search index for `noter search`
+//!
+//! [`SearchIndex`] keeps, for every indexed file, its modification time and
+//! the lowercased tokens it contains. It is built
+//! on the first search of a large workspace or by `noter search --reindex`,
+//! and updated incrementally: only files whose modification time changed are
+//! re-read. Searches use the tokens to narrow the files handed to the line
+//! scanner, and scan every file when a workspace has no index.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::core::directory_scanner::DirectoryScanner;
+
+/// Index file name, stored in the notes directory
+pub const INDEX_FILE: &str = ".notes-search-index";
+
+/// Bumped when the on-disk format changes; older indexes are rebuilt
+const INDEX_FORMAT_VERSION: u32 = 4;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SearchIndex {
+    /// On-disk format; indexes written before versioning read as 0
+    #[serde(default)]
+    pub version: u32,
+    pub last_updated: SystemTime,
+    /// Extensions the index was built for; another set is rebuilt
+    #[serde(default)]
+    pub extensions: Vec<String>,
+    /// Whether symlinked directories were indexed
+    #[serde(default)]
+    pub follow_symlinks: bool,
+    /// Indexed files, relative to the notes directory, with their modification time
+    #[serde(default)]
+    pub files: BTreeMap<PathBuf, SystemTime>,
+    /// Lowercased token to the files containing it
+    #[serde(default)]
+    pub tokens: BTreeMap<String, BTreeSet<PathBuf>>,
+}
+
+/// What an incremental update changed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexUpdate {
+    pub added: usize,
+    pub updated: usize,
+    pub removed: usize,
+    pub unchanged: usize,
+}
+
+impl IndexUpdate {
+    /// Whether any file was added, re-read or dropped
+    pub fn changed(&self) -> bool {
+        self.added + self.updated + self.removed > 0
+    }
+}
+
+#[allow(dead_code)]
+impl SearchIndex {
+    /// An index without any files
+    pub fn empty(extensions: &[String], follow_symlinks: bool) -> Self {
+        Self {
+            version: INDEX_FORMAT_VERSION,
+            last_updated: SystemTime::now(),
+            extensions: extensions.to_vec(),
+            follow_symlinks,
+            files: BTreeMap::new(),
+            tokens: BTreeMap::new(),
+        }
+    }
+
+    pub fn index_path(notes_dir: &Path) -> PathBuf {
+        notes_dir.join(INDEX_FILE)
+    }
+
+    /// Index the files with one of `extensions` below `notes_dir`
+    pub fn build(notes_dir: &Path, extensions: &[String], follow_symlinks: bool) -> Result<Self> {
+        let mut index = Self::empty(extensions, follow_symlinks);
+        index.update(notes_dir)?;
+        Ok(index)
+    }
+
+    /// Load the index, or `None` when it is absent, unreadable or from an
+    /// older format
+    pub fn load(notes_dir: &Path) -> Option<Self> {
+        Self::load_from(&Self::index_path(notes_dir)).ok()
+    }
+
+    /// Load the index stored at `path`, failing for an older format
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        let index: Self = serde_json::from_str(&content)?;
+        if index.version != INDEX_FORMAT_VERSION {
+            anyhow::bail!(
+                "Search index {} has an old format and needs rebuilding",
+                path.display()
+            );
+        }
+        Ok(index)
+    }
+
+    pub fn save(&self, notes_dir: &Path) -> Result<()> {
+        let path = Self::index_path(notes_dir);
+        fs::write(&path, serde_json::to_string(self)?)
+            .with_context(|| format!("Failed to write search index {}", path.display()))
+    }
+
+    /// Load the existing index and bring it up to date, re-reading only files
+    /// whose modification time changed. An index for other extensions or
+    /// symlink handling is rebuilt.
+    pub fn update_or_build(
+        notes_dir: &Path,
+        extensions: &[String],
+        follow_symlinks: bool,
+    ) -> Result<(Self, IndexUpdate)> {
+        let mut index = Self::load(notes_dir)
+            .filter(|index| {
+                index.extensions == extensions && index.follow_symlinks == follow_symlinks
+            })
+            .unwrap_or_else(|| Self::empty(extensions, follow_symlinks));
+
+        let update = index.update(notes_dir)?;
+        Ok((index, update))
+    }
+
+    /// Re-index new and modified files and drop deleted ones
+    pub fn update(&mut self, notes_dir: &Path) -> Result<IndexUpdate> {
+        let mut update = IndexUpdate::default();
+        let current = self.scan(notes_dir)?;
+
+        let removed: Vec<PathBuf> = self
+            .files
+            .keys()
+            .filter(|path| !current.contains_key(*path))
+            .cloned()
+            .collect();
+        for path in removed {
+            self.remove_file(&path);
+            update.removed += 1;
+        }
+
+        for (path, modified) in current {
+            match self.files.get(&path) {
+                Some(indexed) if *indexed == modified => {
+                    update.unchanged += 1;
+                    continue;
+                }
+                Some(_) => {
+                    self.remove_file(&path);
+                    update.updated += 1;
+                }
+                None => update.added += 1,
+            }
+
+            // Unreadable files are indexed without words and never match
+            let content = fs::read_to_string(notes_dir.join(&path)).unwrap_or_default();
+            for token in Self::tokenize(&content) {
+                self.tokens.entry(token).or_default().insert(path.clone());
+            }
+            self.files.insert(path, modified);
+        }
+
+        self.last_updated = SystemTime::now();
+        Ok(update)
+    }
+
+    /// Whether the index covers `extensions` and every searchable file is
+    /// indexed at its current modification time, with no deleted files left
+    pub fn is_fresh(
+        &self,
+        notes_dir: &Path,
+        extensions: &[String],
+        follow_symlinks: bool,
+    ) -> Result<bool> {
+        if self.extensions != extensions || self.follow_symlinks != follow_symlinks {
+            return Ok(false);
+        }
+
+        Ok(self.scan(notes_dir)? == self.files)
+    }
+
+    /// Files that may contain `query`. Every token of the query must occur
+    /// inside some token of the file, which holds for any substring match, so
+    /// no real match is excluded. Returns `None` when the query has no tokens
+    /// to narrow by.
+    pub fn candidates(&self, notes_dir: &Path, query: &str) -> Option<Vec<PathBuf>> {
+        let query_tokens = Self::tokenize(query);
+        if query_tokens.is_empty() {
+            return None;
+        }
+
+        let mut candidates: Option<BTreeSet<&PathBuf>> = None;
+        for query_token in &query_tokens {
+            let files: BTreeSet<&PathBuf> = self
+                .tokens
+                .iter()
+                .filter(|(token, _)| token.contains(query_token.as_str()))
+                .flat_map(|(_, files)| files)
+                .collect();
+
+            candidates = Some(match candidates {
+                Some(previous) => previous.intersection(&files).copied().collect(),
+                None => files,
+            });
+        }
+
+        Some(
+            candidates
+                .unwrap_or_default()
+                .into_iter()
+                .map(|path| notes_dir.join(path))
+                .collect(),
+        )
+    }
+
+    /// Lowercased alphanumeric runs of `text`
+    pub fn tokenize(text: &str) -> BTreeSet<String> {
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|token| !token.is_empty())
+            .map(str::to_lowercase)
+            .collect()
+    }
+
+    /// Searchable files keyed by their path relative to the notes directory
+    fn scan(&self, notes_dir: &Path) -> Result<BTreeMap<PathBuf, SystemTime>> {
+        let extensions: Vec<&str> = self.extensions.iter().map(String::as_str).collect();
+
+        Ok(DirectoryScanner::scan_directory_for_files(
+            notes_dir,
+            &extensions,
+            self.follow_symlinks,
+        )?
+        .into_iter()
+        .filter_map(|file| {
+            let relative = file.path.strip_prefix(notes_dir).ok()?.to_path_buf();
+            Some((relative, file.modified))
+        })
+        .collect())
+    }
+
+    fn remove_file(&mut self, path: &Path) {
+        self.files.remove(path);
+        self.tokens.retain(|_, files| {
+            files.remove(path);
+            !files.is_empty()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn extensions() -> Vec<String> {
+        vec!["typ".to_string(), "md".to_string()]
+    }
+
+    #[test]
+    fn test_candidates_narrow_to_matching_files() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let notes_dir = temp_dir.path();
+        fs::create_dir_all(notes_dir.join("02101"))?;
+        fs::write(
+            notes_dir.join("02101/sorting.typ"),
+            "Merge-sort and quicksort",
+        )?;
+        fs::write(notes_dir.join("graphs.md"), "Dijkstra's algorithm")?;
+        fs::write(notes_dir.join("ignored.txt"), "quicksort")?;
+
+        let (index, update) = SearchIndex::update_or_build(notes_dir, &extensions(), true)?;
+        assert_eq!(update.added, 2);
+
+        // Partial tokens still narrow, punctuation in the query is ignored
+        assert_eq!(
+            index.candidates(notes_dir, "quick"),
+            Some(vec![notes_dir.join("02101/sorting.typ")])
+        );
+        assert_eq!(
+            index.candidates(notes_dir, "merge-SORT"),
+            Some(vec![notes_dir.join("02101/sorting.typ")])
+        );
+        assert_eq!(index.candidates(notes_dir, "dijkstra sort"), Some(vec![]));
+        assert_eq!(index.candidates(notes_dir, "--"), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_update_is_incremental() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let notes_dir = temp_dir.path();
+        fs::write(notes_dir.join("a.typ"), "alpha")?;
+        fs::write(notes_dir.join("b.typ"), "beta")?;
+
+        let (index, _) = SearchIndex::update_or_build(notes_dir, &extensions(), true)?;
+        index.save(notes_dir)?;
+        assert!(index.is_fresh(notes_dir, &extensions(), true)?);
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        fs::write(notes_dir.join("a.typ"), "gamma")?;
+        fs::remove_file(notes_dir.join("b.typ"))?;
+        fs::write(notes_dir.join("c.typ"), "delta")?;
+        assert!(!index.is_fresh(notes_dir, &extensions(), true)?);
+
+        let (index, update) = SearchIndex::update_or_build(notes_dir, &extensions(), true)?;
+        assert_eq!(
+            update,
+            IndexUpdate {
+                added: 1,
+                updated: 1,
+                removed: 1,
+                unchanged: 0
+            }
+        );
+        assert!(!index.tokens.contains_key("alpha"));
+        assert!(!index.tokens.contains_key("beta"));
+        assert!(index.tokens.contains_key("gamma"));
+        assert_eq!(
+            index.tokens["gamma"],
+            BTreeSet::from([PathBuf::from("a.typ")])
+        );
+        assert!(index.is_fresh(notes_dir, &extensions(), true)?);
+
+        // A different extension set is treated as stale
+        assert!(!index.is_fresh(notes_dir, &["typ".to_string()], true)?);
+
+        // An index from before versioning is rebuilt rather than updated
+        fs::write(
+            SearchIndex::index_path(notes_dir),
+            r#"{"last_updated":{"secs_since_epoch":0,"nanos_since_epoch":0}}"#,
+        )?;
+        assert!(SearchIndex::load(notes_dir).is_none());
+
+        Ok(())
+    }
+}
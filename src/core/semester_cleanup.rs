@@ -0,0 +1,223 @@
+//! Semester-end cleanup
+//!
+//! Runs the archive, clean and backup-prune steps for every active course in
+//! one go. A [`SemesterEndPlan`] is built first so the work can be shown and
+//! confirmed before anything is written or deleted; [`SemesterCleanup::run`]
+//! then executes it through [`CourseArchiver`], [`TypstCompiler::clean_course`]
+//! and [`FileOperations::prune_backups`].
+
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::core::course_archive::{CourseArchive, CourseArchiver};
+use crate::core::file_operations::FileOperations;
+use crate::core::status_manager::StatusManager;
+use crate::core::typst_compiler::TypstCompiler;
+
+/// What the cleanup will do to one course
+#[derive(Debug, Clone)]
+pub struct CoursePlan {
+    pub course_id: String,
+    pub archive_path: PathBuf,
+    /// Whether an archive of this course already exists and will be replaced
+    pub replaces_archive: bool,
+    pub pdfs: Vec<PathBuf>,
+    pub backups: Vec<PathBuf>,
+    /// Combined size of the PDFs and backups to delete
+    pub reclaimable: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SemesterEndPlan {
+    pub semester: String,
+    pub archive_dir: PathBuf,
+    pub courses: Vec<CoursePlan>,
+}
+
+impl SemesterEndPlan {
+    pub fn total_pdfs(&self) -> usize {
+        self.courses.iter().map(|course| course.pdfs.len()).sum()
+    }
+
+    pub fn total_backups(&self) -> usize {
+        self.courses.iter().map(|course| course.backups.len()).sum()
+    }
+
+    pub fn reclaimable(&self) -> u64 {
+        self.courses.iter().map(|course| course.reclaimable).sum()
+    }
+}
+
+/// Outcome of the cleanup for one course
+#[derive(Debug, Clone)]
+pub struct CourseCleanup {
+    pub archive: CourseArchive,
+    pub pdfs_removed: usize,
+    pub backups_removed: usize,
+    pub bytes_reclaimed: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SemesterEndReport {
+    pub archive_dir: PathBuf,
+    pub courses: Vec<CourseCleanup>,
+}
+
+impl SemesterEndReport {
+    pub fn bytes_reclaimed(&self) -> u64 {
+        self.courses
+            .iter()
+            .map(|course| course.bytes_reclaimed)
+            .sum()
+    }
+
+    pub fn archive_size(&self) -> u64 {
+        self.courses.iter().map(|course| course.archive.size).sum()
+    }
+}
+
+pub struct SemesterCleanup;
+
+#[allow(dead_code)]
+impl SemesterCleanup {
+    /// Configured courses that have a directory in the notes directory
+    pub fn active_courses(config: &Config) -> Vec<String> {
+        let notes_dir = Path::new(&config.paths.notes_dir);
+        let mut course_ids: Vec<String> = config
+            .courses
+            .keys()
+            .filter(|course_id| notes_dir.join(course_id).is_dir())
+            .cloned()
+            .collect();
+        course_ids.sort();
+        course_ids
+    }
+
+    /// Work the cleanup would do for the current semester, without touching
+    /// any files
+    pub fn plan(config: &Config) -> Result<SemesterEndPlan> {
+        let semester = StatusManager::get_current_semester(config);
+        let archive_dir = CourseArchiver::archive_dir(config, &semester);
+        let notes_dir = Path::new(&config.paths.notes_dir);
+
+        let courses = Self::active_courses(config)
+            .into_iter()
+            .map(|course_id| {
+                let course_path = notes_dir.join(&course_id);
+                let pdfs = TypstCompiler::compiled_outputs(&course_path, config)?;
+                let backups =
+                    FileOperations::find_backups(&course_path, config.paths.follow_symlinks)?;
+                let archive_path = CourseArchiver::archive_path(&archive_dir, &course_id);
+
+                Ok(CoursePlan {
+                    replaces_archive: archive_path.exists(),
+                    reclaimable: FileOperations::total_size(&pdfs)
+                        + FileOperations::total_size(&backups),
+                    course_id,
+                    archive_path,
+                    pdfs,
+                    backups,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(SemesterEndPlan {
+            semester,
+            archive_dir,
+            courses,
+        })
+    }
+
+    /// Archive each planned course, then clean its PDFs and prune its backups.
+    /// A course is only cleaned once its archive has been written.
+    pub fn run(config: &Config, plan: &SemesterEndPlan) -> Result<SemesterEndReport> {
+        let notes_dir = Path::new(&config.paths.notes_dir);
+        let mut courses = Vec::new();
+
+        for course in &plan.courses {
+            let course_path = notes_dir.join(&course.course_id);
+            let archive =
+                CourseArchiver::archive_course(config, &course.course_id, &plan.archive_dir)?;
+
+            let pdf_bytes =
+                FileOperations::total_size(&TypstCompiler::compiled_outputs(&course_path, config)?);
+            let pdfs_removed = TypstCompiler::clean_course(&course_path, config)?;
+            let (backups_removed, backup_bytes) =
+                FileOperations::prune_backups(&course_path, config.paths.follow_symlinks)?;
+
+            courses.push(CourseCleanup {
+                archive,
+                pdfs_removed,
+                backups_removed,
+                bytes_reclaimed: pdf_bytes + backup_bytes,
+            });
+        }
+
+        Ok(SemesterEndReport {
+            archive_dir: plan.archive_dir.clone(),
+            courses,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_semester_end_archives_then_cleans() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let mut config = Config::default();
+        config.paths.notes_dir = temp_dir.path().to_string_lossy().to_string();
+        config.courses = [
+            ("02101", "Introduction to Programming"),
+            ("02102", "Algorithms"),
+        ]
+        .iter()
+        .map(|(id, name)| (id.to_string(), name.to_string()))
+        .collect();
+
+        // 02102 has no directory and is skipped
+        let lectures = temp_dir.path().join("02101").join("lectures");
+        fs::create_dir_all(&lectures)?;
+        fs::write(lectures.join("lecture_01.typ"), "= Lecture 1")?;
+        fs::write(lectures.join("lecture_01.pdf"), "%PDF compiled")?;
+        fs::write(lectures.join("lecture_01.typ.bak.20250101_120000"), "= Old")?;
+        fs::write(lectures.join("notes.bak"), "old")?;
+        fs::write(lectures.join("bakery.typ"), "= Not a backup")?;
+
+        let plan = SemesterCleanup::plan(&config)?;
+        assert_eq!(plan.courses.len(), 1);
+        assert_eq!(plan.total_pdfs(), 1);
+        assert_eq!(plan.total_backups(), 2);
+        assert_eq!(plan.reclaimable(), 13 + 5 + 3);
+        // Planning doesn't touch anything
+        assert!(lectures.join("lecture_01.pdf").exists());
+        assert!(!plan.archive_dir.exists());
+
+        let report = SemesterCleanup::run(&config, &plan)?;
+        assert_eq!(report.bytes_reclaimed(), plan.reclaimable());
+
+        let course = &report.courses[0];
+        assert_eq!((course.pdfs_removed, course.backups_removed), (1, 2));
+        // The archive is taken before cleaning, so it holds every file
+        assert_eq!(course.archive.files, 5);
+        assert!(
+            course
+                .archive
+                .path
+                .starts_with(temp_dir.path().join("archive"))
+        );
+        assert!(course.archive.size > 0);
+
+        assert!(lectures.join("lecture_01.typ").exists());
+        assert!(lectures.join("bakery.typ").exists());
+        assert!(!lectures.join("lecture_01.pdf").exists());
+        assert!(!lectures.join("notes.bak").exists());
+
+        Ok(())
+    }
+}
@@ -7,7 +7,7 @@ use anyhow::Result;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::config::Config;
+use crate::config::{Config, DEFAULT_AUTHOR};
 use crate::core::file_operations::FileOperations;
 use crate::core::github_template_fetcher::GitHubTemplateFetcher;
 
@@ -144,7 +144,7 @@ impl SetupManager {
         }
 
         // Check configuration
-        status.author_configured = config.author != "Your Name";
+        status.author_configured = config.author != DEFAULT_AUTHOR;
 
         Ok(status)
     }
@@ -289,8 +289,8 @@ impl SetupManager {
             let course_dir = Path::new(&config.paths.notes_dir).join(course_id);
 
             if !course_dir.exists() {
-                fs::create_dir_all(course_dir.join("lectures"))?;
-                fs::create_dir_all(course_dir.join("assignments"))?;
+                fs::create_dir_all(config.get_lectures_dir(course_id))?;
+                fs::create_dir_all(config.get_assignments_dir(course_id))?;
                 result.sample_courses.push(course_id.to_string());
             }
         }
@@ -405,7 +405,7 @@ Templates are located in `{}/dtu-template/`.
 
 - Use `noter recent 02101` to see recent notes for a course
 - Use `noter courses` to see all available DTU course codes
-- Use `noter clean` to remove compiled PDF files
+- Use `noter clean --all` to remove compiled PDF files from every course
 - Use `noter status` to check your setup
 
 Happy note-taking! 📚
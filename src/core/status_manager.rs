@@ -1,13 +1,20 @@
 //! Status and information management
 //!
 //! Handles status checking, activity summaries, and course health monitoring.
-
-use crate::config::Config;
-use crate::core::directory_scanner::{CourseStats, DirectoryScanner};
+//! Course directories are scanned in parallel and sorted afterwards, so the
+//! output order doesn't depend on scheduling.
+
+use crate::config::{Config, HealthThresholds, PathConfig};
+use crate::core::course_catalog::CourseCatalog;
+use crate::core::directory_scanner::{CourseStats, DirectoryScanner, FileInfo};
+use crate::core::file_operations::FileOperations;
+use crate::core::template::discovery::TemplateDiscovery;
+use crate::core::typst_compiler::TypstCompiler;
 use anyhow::Result;
-use chrono::Datelike;
+use rayon::prelude::*;
+use serde::Serialize;
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone)]
 pub struct SystemStatus {
@@ -16,19 +23,49 @@ pub struct SystemStatus {
     pub configuration_warnings: Vec<String>,
 }
 
+/// Build and environment details for bug reports, see `noter info`
+#[derive(Debug, Clone, Serialize)]
+pub struct EnvironmentInfo {
+    pub version: String,
+    pub os: String,
+    pub arch: String,
+    /// Output of `typst --version`, `None` when Typst isn't installed
+    pub typst_version: Option<String>,
+    pub config_path: PathBuf,
+    /// Paths as resolved when the configuration was loaded
+    pub paths: PathConfig,
+    pub template_packages: Vec<TemplatePackageInfo>,
+}
+
+/// An installed template package
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplatePackageInfo {
+    pub name: String,
+    pub version: String,
+    /// Resolution location label, "packages" or "project"
+    pub location: String,
+    pub path: PathBuf,
+    /// Whether a package of the same name in an earlier location wins
+    pub shadowed: bool,
+}
+
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub struct ActivitySummary {
     pub total_notes: usize,
     pub total_assignments: usize,
     pub most_recent_activity: Option<RecentActivity>,
+    /// Course with the most files modified in the activity window, and that count
     pub most_active_course: Option<(String, usize)>,
+    /// Days counted for the most active course (0 = all time)
+    pub activity_window_days: u64,
 }
 
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub struct RecentActivity {
     pub file_name: String,
+    pub file_path: std::path::PathBuf,
     pub course_id: String,
     pub course_name: String,
     pub timestamp: std::time::SystemTime,
@@ -43,6 +80,34 @@ pub struct CourseHealthInfo {
     pub assignments_count: usize,
     pub days_since_last_activity: u64,
     pub health_status: HealthStatus,
+    /// Notes and assignments whose PDF is missing or older than the source
+    pub stale_pdfs_count: usize,
+}
+
+/// One course in depth, for `status --course`
+#[derive(Debug, Clone)]
+pub struct CourseDetail {
+    pub course_id: String,
+    pub course_name: String,
+    pub course_path: PathBuf,
+    /// Notes, most recently modified first
+    pub notes: Vec<FileInfo>,
+    /// Assignments, most recently modified first
+    pub assignments: Vec<FileInfo>,
+    /// `None` while the course has no directory
+    pub health: Option<CourseHealthInfo>,
+    pub last_activity: Option<FileInfo>,
+    /// Notes and assignments without a PDF
+    pub uncompiled: Vec<PathBuf>,
+    /// Size of everything in the course directory, in bytes
+    pub disk_usage: u64,
+}
+
+impl CourseDetail {
+    /// Whether the course directory exists
+    pub fn has_directory(&self) -> bool {
+        self.health.is_some()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -54,6 +119,43 @@ pub enum HealthStatus {
     Critical,  // No files or very old activity
 }
 
+impl HealthStatus {
+    /// Health based only on days since the last activity (`None` = no activity)
+    ///
+    /// This is the shared calculation behind course and assignment health.
+    pub fn from_days_since_activity(
+        days_since_activity: Option<u64>,
+        thresholds: &HealthThresholds,
+    ) -> Self {
+        match days_since_activity {
+            Some(days) if days <= thresholds.excellent_days => HealthStatus::Excellent,
+            Some(days) if days <= thresholds.good_days => HealthStatus::Good,
+            Some(days) if days <= thresholds.warning_days => HealthStatus::Warning,
+            _ => HealthStatus::Critical,
+        }
+    }
+
+    /// Numeric level from 0 (excellent) to 3 (critical)
+    pub fn level(&self) -> usize {
+        match self {
+            HealthStatus::Excellent => 0,
+            HealthStatus::Good => 1,
+            HealthStatus::Warning => 2,
+            HealthStatus::Critical => 3,
+        }
+    }
+
+    /// Human-readable label
+    pub fn label(&self) -> &'static str {
+        match self {
+            HealthStatus::Excellent => "Excellent",
+            HealthStatus::Good => "Good",
+            HealthStatus::Warning => "Warning",
+            HealthStatus::Critical => "Critical",
+        }
+    }
+}
+
 pub struct StatusManager;
 
 #[allow(dead_code)]
@@ -100,30 +202,48 @@ impl StatusManager {
         })
     }
 
-    /// Get activity summary across all courses
-    pub fn get_activity_summary(config: &Config) -> Result<ActivitySummary> {
+    /// Get activity summary across all courses. The most active course is
+    /// the one with the most files modified in the last `window_days` days,
+    /// or over all time when `window_days` is 0; the totals are always all-time.
+    pub fn get_activity_summary(config: &Config, window_days: u64) -> Result<ActivitySummary> {
         if !Path::new(&config.paths.notes_dir).exists() {
             return Ok(ActivitySummary {
                 total_notes: 0,
                 total_assignments: 0,
                 most_recent_activity: None,
                 most_active_course: None,
+                activity_window_days: window_days,
             });
         }
 
-        let course_stats = DirectoryScanner::scan_notes_directory(&config.paths.notes_dir)?;
+        let course_stats = DirectoryScanner::scan_notes_directory(
+            &config.paths.notes_dir,
+            &config.typst.source_extensions(),
+            config.paths.flat_layout,
+            config.paths.follow_symlinks,
+        )?;
 
         let mut total_notes = 0;
         let mut total_assignments = 0;
         let mut most_recent_activity: Option<RecentActivity> = None;
         let mut course_activity: HashMap<String, usize> = HashMap::new();
+        // A window of 0, or one reaching back further than the clock can, covers all time
+        let cutoff = window_days
+            .checked_mul(24 * 60 * 60)
+            .filter(|_| window_days > 0)
+            .and_then(|secs| {
+                std::time::SystemTime::now().checked_sub(std::time::Duration::from_secs(secs))
+            });
 
         for (course_id, stats) in &course_stats {
             total_notes += stats.notes_count;
             total_assignments += stats.assignments_count;
 
-            let total_activity = stats.notes_count + stats.assignments_count;
-            course_activity.insert(course_id.clone(), total_activity);
+            let activity = match cutoff {
+                Some(cutoff) => stats.files_modified_since(cutoff),
+                None => stats.notes_count + stats.assignments_count,
+            };
+            course_activity.insert(course_id.clone(), activity);
 
             // Check for most recent activity
             if let Some(ref last_activity) = stats.last_activity {
@@ -140,6 +260,7 @@ impl StatusManager {
                         .unwrap_or_default()
                         .to_string_lossy()
                         .to_string(),
+                    file_path: last_activity.path.clone(),
                     course_id: course_id.clone(),
                     course_name,
                     timestamp: last_activity.modified,
@@ -156,14 +277,18 @@ impl StatusManager {
             }
         }
 
-        // Find most active course
-        let most_active_course = course_activity.into_iter().max_by_key(|&(_, count)| count);
+        // Find most active course, preferring the lower course ID on ties
+        let most_active_course = course_activity
+            .into_iter()
+            .filter(|&(_, count)| count > 0)
+            .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)));
 
         Ok(ActivitySummary {
             total_notes,
             total_assignments,
             most_recent_activity,
             most_active_course,
+            activity_window_days: window_days,
         })
     }
 
@@ -173,39 +298,150 @@ impl StatusManager {
             return Ok(Vec::new());
         }
 
-        let mut course_health = Vec::new();
+        let courses: Vec<(&String, &String, PathBuf)> = config
+            .courses
+            .iter()
+            .map(|(course_id, course_name)| {
+                let course_path = Path::new(&config.paths.notes_dir).join(course_id);
+                (course_id, course_name, course_path)
+            })
+            .filter(|(_, _, course_path)| course_path.exists())
+            .collect();
+
+        // Courses are scanned in parallel, which matters for large workspaces
+        let mut course_health = courses
+            .into_par_iter()
+            .map(|(course_id, course_name, course_path)| {
+                Self::course_health_info(config, course_id, course_name, &course_path)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Sort by activity, with the course ID breaking ties so the order is stable
+        course_health.sort_by(|a, b| {
+            a.days_since_last_activity
+                .cmp(&b.days_since_last_activity)
+                .then_with(|| a.course_id.cmp(&b.course_id))
+        });
+
+        Ok(course_health)
+    }
 
-        for (course_id, course_name) in &config.courses {
-            let course_path = Path::new(&config.paths.notes_dir).join(course_id);
+    /// Health of one course from a scan of its directory
+    fn course_health_info(
+        config: &Config,
+        course_id: &str,
+        course_name: &str,
+        course_path: &Path,
+    ) -> Result<CourseHealthInfo> {
+        let stats = DirectoryScanner::scan_course_directory(
+            course_path,
+            &config.typst.source_extensions(),
+            config.paths.flat_layout,
+            config.paths.follow_symlinks,
+        )?;
+        let days_since_last = Self::calculate_days_since_last_activity(&stats);
+        let health_status =
+            Self::determine_health_status(&stats, days_since_last, &config.health_thresholds);
+        let stale_pdfs_count = TypstCompiler::count_stale_outputs(course_path, config)?;
+
+        Ok(CourseHealthInfo {
+            course_id: course_id.to_string(),
+            course_name: course_name.to_string(),
+            notes_count: stats.notes_count,
+            assignments_count: stats.assignments_count,
+            days_since_last_activity: days_since_last,
+            health_status,
+            stale_pdfs_count,
+        })
+    }
 
-            if course_path.exists() {
-                let stats = DirectoryScanner::scan_course_directory(&course_path)?;
-                let days_since_last = Self::calculate_days_since_last_activity(&stats);
-                let health_status = Self::determine_health_status(&stats, days_since_last);
+    /// Notes, assignments, health, uncompiled files and disk usage of one
+    /// course. A course without a directory yet comes back empty.
+    pub fn get_course_detail(config: &Config, course_id: &str) -> Result<CourseDetail> {
+        let course_name = Self::resolve_course_name(course_id, config);
+        let course_path = config.get_course_dir(course_id);
+        let mut detail = CourseDetail {
+            course_id: course_id.to_string(),
+            course_name,
+            course_path,
+            notes: Vec::new(),
+            assignments: Vec::new(),
+            health: None,
+            last_activity: None,
+            uncompiled: Vec::new(),
+            disk_usage: 0,
+        };
+        if !detail.course_path.is_dir() {
+            return Ok(detail);
+        }
 
-                course_health.push(CourseHealthInfo {
-                    course_id: course_id.clone(),
-                    course_name: course_name.clone(),
-                    notes_count: stats.notes_count,
-                    assignments_count: stats.assignments_count,
-                    days_since_last_activity: days_since_last,
-                    health_status,
-                });
-            }
+        let extensions = config.typst.source_extensions();
+        detail.notes = DirectoryScanner::scan_course_notes(
+            &detail.course_path,
+            &extensions,
+            config.paths.flat_layout,
+            config.paths.follow_symlinks,
+        )?;
+        detail.assignments = DirectoryScanner::scan_course_assignments(
+            &detail.course_path,
+            &extensions,
+            config.paths.follow_symlinks,
+        )?;
+        for files in [&mut detail.notes, &mut detail.assignments] {
+            files.sort_by(|a, b| {
+                b.modified
+                    .cmp(&a.modified)
+                    .then_with(|| a.path.cmp(&b.path))
+            });
         }
 
-        // Sort by health status and then by activity
-        course_health.sort_by(|a, b| a.days_since_last_activity.cmp(&b.days_since_last_activity));
+        detail.last_activity = [detail.notes.first(), detail.assignments.first()]
+            .into_iter()
+            .flatten()
+            .max_by_key(|file| file.modified)
+            .cloned();
+        detail.health = Some(Self::course_health_info(
+            config,
+            course_id,
+            &detail.course_name,
+            &detail.course_path,
+        )?);
+        detail.uncompiled = TypstCompiler::uncompiled_in_course(&detail.course_path, config)?;
+        detail.disk_usage = FileOperations::directory_size(&detail.course_path);
+
+        Ok(detail)
+    }
 
-        Ok(course_health)
+    /// Build and environment details: versions, config location, resolved
+    /// paths and the installed template packages
+    pub fn get_environment_info(config: &Config) -> Result<EnvironmentInfo> {
+        let template_packages = TemplateDiscovery::discover_packages(config)?
+            .into_iter()
+            .map(|package| TemplatePackageInfo {
+                name: package.config.metadata.name,
+                version: package.config.metadata.version,
+                location: package.location.label().to_string(),
+                path: package.dir,
+                shadowed: package.shadowed,
+            })
+            .collect();
+
+        Ok(EnvironmentInfo {
+            version: crate::VERSION.to_string(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            typst_version: TypstCompiler::check_typst_availability().ok(),
+            config_path: Config::config_file_path()?,
+            paths: config.paths.clone(),
+            template_packages,
+        })
     }
 
     /// Get current semester information
     pub fn get_semester_info(config: &Config) -> SemesterInfo {
-        let now = chrono::Local::now();
-        let year = now.year();
-        let month = now.month();
-        let is_spring = month <= 6;
+        let (year, is_spring) = config
+            .semester_boundaries
+            .season_for(chrono::Local::now().date_naive());
 
         SemesterInfo {
             current_semester: config.format_semester(year, is_spring),
@@ -227,26 +463,29 @@ impl StatusManager {
         }
     }
 
-    fn determine_health_status(stats: &CourseStats, days_since_last: u64) -> HealthStatus {
+    fn determine_health_status(
+        stats: &CourseStats,
+        days_since_last: u64,
+        thresholds: &HealthThresholds,
+    ) -> HealthStatus {
         let total_files = stats.notes_count + stats.assignments_count;
+        let days_since_last = stats.last_activity.as_ref().map(|_| days_since_last);
 
-        match (total_files, days_since_last) {
+        // Courses with only a handful of files need more recent activity to stay healthy
+        match (
+            total_files,
+            HealthStatus::from_days_since_activity(days_since_last, thresholds),
+        ) {
             (0, _) => HealthStatus::Critical,
-            (_, 0..=3) => HealthStatus::Excellent,
-            (_, 4..=7) if total_files > 3 => HealthStatus::Good,
-            (_, 8..=14) if total_files > 1 => HealthStatus::Warning,
-            _ => HealthStatus::Critical,
+            (0..=3, HealthStatus::Good) => HealthStatus::Critical,
+            (0..=1, HealthStatus::Warning) => HealthStatus::Critical,
+            (_, status) => status,
         }
     }
 
     /// Get current semester string
     pub fn get_current_semester(config: &Config) -> String {
-        let now = chrono::Local::now();
-        let year = now.year();
-        let month = now.month();
-        let is_spring = month <= 6;
-
-        config.format_semester(year, is_spring)
+        config.semester_for_date(chrono::Local::now().date_naive())
     }
 
     /// Get course name from config with fallback to common courses
@@ -256,8 +495,11 @@ impl StatusManager {
             return name.clone();
         }
 
-        // Fallback to common DTU courses
-        crate::data::get_course_name(course_id)
+        // Fall back to the course catalog
+        CourseCatalog::cached(config)
+            .name(course_id)
+            .unwrap_or_default()
+            .to_string()
     }
 }
 
@@ -269,3 +511,173 @@ pub struct SemesterInfo {
     pub is_spring: bool,
     pub format: crate::config::SemesterFormat,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::{Duration, SystemTime};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_activity_summary_most_recent_across_courses() {
+        let temp_dir = TempDir::new().unwrap();
+        let older = temp_dir.path().join("02101").join("lectures");
+        let newer = temp_dir.path().join("01005").join("assignments");
+        fs::create_dir_all(&older).unwrap();
+        fs::create_dir_all(&newer).unwrap();
+
+        let old_file = fs::File::create(older.join("old.typ")).unwrap();
+        old_file
+            .set_modified(SystemTime::now() - Duration::from_secs(3600))
+            .unwrap();
+        fs::write(newer.join("new.typ"), "= New").unwrap();
+
+        let mut config = Config::default();
+        config.paths.notes_dir = temp_dir.path().to_string_lossy().to_string();
+
+        let summary = StatusManager::get_activity_summary(&config, 0).unwrap();
+        let recent = summary.most_recent_activity.unwrap();
+
+        assert_eq!(recent.course_id, "01005");
+        assert_eq!(recent.file_path, newer.join("new.typ"));
+    }
+
+    #[test]
+    fn test_most_active_course_uses_activity_window() {
+        let temp_dir = TempDir::new().unwrap();
+        let crammed = temp_dir.path().join("02101").join("lectures");
+        let current = temp_dir.path().join("02102").join("lectures");
+        fs::create_dir_all(&crammed).unwrap();
+        fs::create_dir_all(&current).unwrap();
+
+        let last_year = SystemTime::now() - Duration::from_secs(365 * 24 * 60 * 60);
+        for i in 0..3 {
+            let file = fs::File::create(crammed.join(format!("lecture-{}.typ", i))).unwrap();
+            file.set_modified(last_year).unwrap();
+        }
+        fs::write(current.join("lecture-1.typ"), "= Recent").unwrap();
+
+        let mut config = Config::default();
+        config.paths.notes_dir = temp_dir.path().to_string_lossy().to_string();
+
+        let recent = StatusManager::get_activity_summary(&config, 30).unwrap();
+        assert_eq!(recent.most_active_course, Some(("02102".to_string(), 1)));
+        // Totals stay all-time
+        assert_eq!(recent.total_notes, 4);
+
+        let all_time = StatusManager::get_activity_summary(&config, 0).unwrap();
+        assert_eq!(all_time.most_active_course, Some(("02101".to_string(), 3)));
+
+        // Windows too large to subtract from now also mean all time
+        for window_days in [u64::MAX, u64::MAX / (24 * 60 * 60)] {
+            let huge = StatusManager::get_activity_summary(&config, window_days).unwrap();
+            assert_eq!(huge.most_active_course, all_time.most_active_course);
+        }
+    }
+
+    #[test]
+    fn test_environment_info_serializes_inventory() {
+        let temp_dir = TempDir::new().unwrap();
+        let package_dir = temp_dir.path().join("dtu-template").join("0.2.0");
+        fs::create_dir_all(&package_dir).unwrap();
+        fs::write(
+            package_dir.join(".noter.config.toml"),
+            "[metadata]\nname = \"dtu-template\"\nversion = \"0.2.0\"\n\n[[templates]]\nname = \"lecture\"\ndisplay_name = \"Lecture\"\ndescription = \"\"\nfile = \"lecture.typ\"\nfunction = \"dtu-note\"\nsupports_variants = false\ndefault_sections = []\n",
+        )
+        .unwrap();
+
+        let mut config = Config::default();
+        config.paths.typst_packages_dir = temp_dir.path().to_string_lossy().to_string();
+        config.paths.templates_dir = temp_dir.path().join("none").to_string_lossy().to_string();
+
+        let info = StatusManager::get_environment_info(&config).unwrap();
+        assert_eq!(info.version, crate::VERSION);
+
+        let json: serde_json::Value = serde_json::to_value(&info).unwrap();
+        assert_eq!(json["template_packages"][0]["name"], "dtu-template");
+        assert_eq!(json["template_packages"][0]["version"], "0.2.0");
+        assert_eq!(json["template_packages"][0]["location"], "packages");
+        assert_eq!(
+            json["paths"]["typst_packages_dir"],
+            config.paths.typst_packages_dir.as_str()
+        );
+    }
+
+    #[test]
+    fn test_health_status_uses_configured_thresholds() {
+        let weekly = HealthThresholds {
+            excellent_days: 7,
+            good_days: 14,
+            warning_days: 21,
+        };
+
+        let status = |days| HealthStatus::from_days_since_activity(days, &weekly);
+        assert_eq!(status(Some(7)), HealthStatus::Excellent);
+        assert_eq!(status(Some(8)), HealthStatus::Good);
+        assert_eq!(status(Some(21)), HealthStatus::Warning);
+        assert_eq!(status(Some(22)), HealthStatus::Critical);
+        assert_eq!(status(None), HealthStatus::Critical);
+
+        let defaults = HealthThresholds::default();
+        assert_eq!(
+            HealthStatus::from_days_since_activity(Some(8), &defaults),
+            HealthStatus::Warning
+        );
+
+        let unordered = HealthThresholds {
+            good_days: 2,
+            ..defaults.clone()
+        };
+        assert!(defaults.validate().is_ok());
+        assert!(unordered.validate().is_err());
+    }
+
+    #[test]
+    fn test_course_detail_for_one_course() {
+        let temp_dir = TempDir::new().unwrap();
+        let lectures = temp_dir.path().join("02101").join("lectures");
+        let assignments = temp_dir.path().join("02101").join("assignments");
+        fs::create_dir_all(&lectures).unwrap();
+        fs::create_dir_all(&assignments).unwrap();
+
+        let old_note = fs::File::create(lectures.join("loops.typ")).unwrap();
+        old_note
+            .set_modified(SystemTime::now() - Duration::from_secs(3600))
+            .unwrap();
+        fs::write(lectures.join("recursion.typ"), "= Recursion").unwrap();
+        fs::write(lectures.join("recursion.pdf"), "%PDF").unwrap();
+        fs::write(assignments.join("lab-1.typ"), "= Lab 1").unwrap();
+
+        let mut config = Config::default();
+        config.paths.notes_dir = temp_dir.path().to_string_lossy().to_string();
+        config
+            .courses
+            .insert("02101".to_string(), "Programming".to_string());
+
+        let detail = StatusManager::get_course_detail(&config, "02101").unwrap();
+        assert!(detail.has_directory());
+        assert_eq!(detail.course_name, "Programming");
+        let notes: Vec<&Path> = detail
+            .notes
+            .iter()
+            .map(|file| file.path.as_path())
+            .collect();
+        assert_eq!(
+            notes,
+            vec![lectures.join("recursion.typ"), lectures.join("loops.typ")]
+        );
+        assert_eq!(detail.assignments.len(), 1);
+        assert_eq!(
+            detail.uncompiled,
+            vec![assignments.join("lab-1.typ"), lectures.join("loops.typ")]
+        );
+        assert_eq!(detail.health.unwrap().notes_count, 2);
+        assert_eq!(detail.disk_usage, 11 + 4 + 7);
+
+        // A configured course without a directory yet
+        let empty = StatusManager::get_course_detail(&config, "02102").unwrap();
+        assert!(!empty.has_directory());
+        assert!(empty.notes.is_empty() && empty.last_activity.is_none());
+    }
+}
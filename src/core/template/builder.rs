@@ -6,13 +6,20 @@
 //! TemplateEngine.
 
 use anyhow::Result;
+use chrono::NaiveDate;
+use std::time::Duration;
 
+use super::cache::RenderCache;
 use super::config::{TemplateConfig, TemplateDefinition, TemplateVariant};
-use super::context::{TemplateContext, TemplateContextBuilder, TemplateMetadata};
+use super::context::{
+    CustomField, RubricCriterion, TemplateContext, TemplateContextBuilder, TemplateMetadata,
+};
 use super::discovery::TemplateDiscovery;
 use super::engine::{TemplateEngine, TemplateReference};
 use super::validation::{TemplateValidator, ValidationIssue, ValidationSeverity};
 use crate::config::Config;
+use crate::core::validation::Validator;
+use crate::error::NoterError;
 
 /// Template builder for fluent template construction
 ///
@@ -22,19 +29,27 @@ use crate::config::Config;
 ///
 /// ## Usage Examples
 ///
-/// ```rust
+/// ```no_run
+/// # use noter::config::Config;
+/// # use noter::core::template::builder::TemplateBuilder;
+/// # use noter::core::template::engine::TemplateReference;
+/// # let config = Config::default();
 /// let content = TemplateBuilder::new("02101", &config)?
 ///     .with_title("Advanced Data Structures")
 ///     .with_reference(TemplateReference::assignment())
 ///     .with_sections(vec!["Problem 1".to_string(), "Analysis".to_string()])
 ///     .with_custom_field("difficulty", "advanced")
 ///     .build()?;
+/// # Ok::<(), anyhow::Error>(())
 /// ```
 pub struct TemplateBuilder {
     context_builder: TemplateContextBuilder,
     template_reference: TemplateReference,
     variant_override: Option<String>,
     processing_options: ProcessingOptions,
+    /// Why the title given to [`TemplateBuilder::with_title`] was rejected,
+    /// reported when building
+    invalid_title: Option<String>,
 }
 
 /// Processing options for template generation
@@ -43,6 +58,10 @@ pub struct ProcessingOptions {
     pub validate_before_build: bool,
     pub apply_transformations: bool,
     pub include_debug_info: bool,
+    /// Print the JSON-serialized context to stderr before rendering
+    pub dump_context: bool,
+    /// Serve identical renders from the [`RenderCache`] when caching is enabled
+    pub use_render_cache: bool,
     pub validation_level: ValidationLevel,
     pub fail_on_validation_errors: bool,
 }
@@ -74,18 +93,41 @@ impl TemplateBuilder {
             template_reference: TemplateReference::lecture(), // Default
             variant_override: None,
             processing_options: ProcessingOptions::default(),
+            invalid_title: None,
         })
     }
 
+    /// Render against a specific template package configuration instead of
+    /// the newest installed one
+    pub fn with_template_config(mut self, template_config: TemplateConfig) -> Self {
+        self.context_builder = self.context_builder.with_template_config(template_config);
+        self
+    }
+
     /// Set the template reference
     pub fn with_reference(mut self, template_ref: TemplateReference) -> Self {
         self.template_reference = template_ref;
         self
     }
 
-    /// Set the template title
+    /// Set the template title, trimmed. A title that fails
+    /// [`Validator::validate_title`] makes building fail.
     pub fn with_title(mut self, title: &str) -> Self {
-        self.context_builder = self.context_builder.with_title(title);
+        match Validator::validate_title(title) {
+            Ok(title) => {
+                self.context_builder = self.context_builder.with_title(title);
+                self.invalid_title = None;
+            }
+            Err(e) => self.invalid_title = Some(e.to_string()),
+        }
+        self
+    }
+
+    /// Set the note's authors, as [`Validator::parse_authors`] returns them, in
+    /// place of the configured author. With more than one, the header lists
+    /// them as a Typst array.
+    pub fn with_authors(mut self, authors: Vec<String>) -> Self {
+        self.context_builder = self.context_builder.with_authors(authors);
         self
     }
 
@@ -95,14 +137,49 @@ impl TemplateBuilder {
         self
     }
 
+    /// Set the assignment due date. Assignments built without one are due
+    /// `note_preferences.default_due_days` from today.
+    pub fn with_due_date(mut self, due_date: NaiveDate) -> Self {
+        self.context_builder = self.context_builder.with_due_date(due_date);
+        self
+    }
+
+    /// Date the note is for, e.g. an upcoming lecture. Defaults to today.
+    pub fn with_date(mut self, date: NaiveDate) -> Self {
+        self.context_builder = self.context_builder.with_date(date);
+        self
+    }
+
+    /// Names of the templates in the loaded template configuration
+    pub fn template_names(&self) -> Vec<String> {
+        self.context_builder
+            .get_template_config()
+            .map(|config| config.templates.iter().map(|t| t.name.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Use `body`, already Typst markup, in place of the generated sections
+    pub fn with_body(mut self, body: String) -> Self {
+        self.context_builder = self.context_builder.with_body(body);
+        self
+    }
+
+    /// Grade the assignment with `rubric`, added as a `Rubric` section with a
+    /// table of the criteria and their points
+    pub fn with_rubric(mut self, rubric: Vec<RubricCriterion>) -> Self {
+        self.context_builder = self.context_builder.with_rubric(rubric);
+        self
+    }
+
     /// Add a template variable
     pub fn with_variable(mut self, key: &str, value: &str) -> Self {
         self.context_builder = self.context_builder.with_variable(key, value);
         self
     }
 
-    /// Add a custom field
-    pub fn with_custom_field(mut self, key: &str, value: &str) -> Self {
+    /// Add a custom header field, passed to the template function as
+    /// `key: value`. Strings and lists of strings are supported.
+    pub fn with_custom_field(mut self, key: &str, value: impl Into<CustomField>) -> Self {
         self.context_builder = self.context_builder.with_custom_field(key, value);
         self
     }
@@ -149,10 +226,24 @@ impl TemplateBuilder {
         self
     }
 
+    /// Enable/disable dumping the context to stderr before rendering
+    pub fn with_context_dump(mut self, enabled: bool) -> Self {
+        self.processing_options.dump_context = enabled;
+        self
+    }
+
+    /// Enable/disable the render cache. It is only used when both
+    /// `templates.enable_caching` and the template's `rendering.enable_caching`
+    /// are on.
+    pub fn with_render_cache(mut self, enabled: bool) -> Self {
+        self.processing_options.use_render_cache = enabled;
+        self
+    }
+
     /// Build the template content - returns only the generated Typst code
     pub fn build(&self) -> Result<String> {
         // Build the context
-        let mut context = self.context_builder.clone().build()?;
+        let mut context = self.build_context()?;
 
         // Apply validation if enabled
         if self.processing_options.validate_before_build {
@@ -176,13 +267,38 @@ impl TemplateBuilder {
             self.template_reference.clone()
         };
 
+        if self.processing_options.dump_context {
+            eprintln!("{}", context.to_json()?);
+        }
+
+        if let Some((cache, ttl)) = self.render_cache(&context) {
+            return cache.render(&context, &template_ref, ttl);
+        }
+
         // Delegate to TemplateEngine for actual generation
-        TemplateEngine::render_template(&context, &template_ref)
+        Ok(TemplateEngine::render_template(&context, &template_ref)?)
+    }
+
+    /// The render cache and its entry lifetime, when caching applies
+    fn render_cache(&self, context: &TemplateContext) -> Option<(RenderCache, Duration)> {
+        let rendering = &context.engine_config.rendering;
+        let enabled = self.processing_options.use_render_cache
+            && rendering.enable_caching
+            && self
+                .context_builder
+                .get_config()
+                .is_some_and(|config| config.templates.enable_caching);
+        if !enabled {
+            return None;
+        }
+
+        let ttl = Duration::from_secs(u64::from(rendering.cache_duration_minutes) * 60);
+        Some((RenderCache::open().ok()?, ttl))
     }
 
     /// Build with validation report (for debugging and analysis)
     pub fn build_with_validation(&self) -> Result<TemplateOutputWithValidation> {
-        let context = self.context_builder.clone().build()?;
+        let context = self.build_context()?;
 
         // Always perform validation for this method
         let validation_result = self.validate_template(&context)?;
@@ -208,7 +324,7 @@ impl TemplateBuilder {
 
     /// Build with metadata information (for advanced use cases)
     pub fn build_with_metadata(&self) -> Result<TemplateOutput> {
-        let context = self.context_builder.clone().build()?;
+        let context = self.build_context()?;
         let content = self.build()?;
         let context_summary = ContextSummary::from_context(&context);
 
@@ -221,10 +337,80 @@ impl TemplateBuilder {
 
     /// Validate the template without building it
     pub fn validate(&self) -> Result<ValidationResult> {
-        let context = self.context_builder.clone().build()?;
+        let context = self.build_context()?;
         self.validate_template(&context)
     }
 
+    /// Collect non-blocking context warnings (e.g. empty author or unknown course name)
+    /// to show the user before a file is written.
+    ///
+    /// Uses the template definition's richer validation when the referenced template
+    /// exists in the loaded configuration, and falls back to the basic context checks
+    /// otherwise.
+    pub fn context_warnings(&self) -> Result<Vec<String>> {
+        let context = self.build_context()?;
+
+        // The fallback definition used when nothing was discovered doesn't count
+        let template_def = context
+            .template_config
+            .as_ref()
+            .filter(|template_config| !template_config.templates.is_empty())
+            .and_then(|template_config| {
+                self.find_template_definition(template_config, &self.template_reference)
+                    .ok()
+                    .map(|def| (template_config, def))
+            });
+
+        let Some((template_config, template_def)) = template_def else {
+            return context.validate();
+        };
+
+        let variant = self
+            .find_template_variant(template_config, &template_def)
+            .ok()
+            .flatten();
+        let issues = TemplateValidator::validate_template_context(
+            &context,
+            &template_def,
+            variant.as_ref(),
+        )?;
+
+        Ok(issues
+            .into_iter()
+            .filter(|issue| issue.severity != ValidationSeverity::Info)
+            .map(|issue| match issue.suggestion {
+                Some(suggestion) => format!("{} ({})", issue.message, suggestion),
+                None => issue.message,
+            })
+            .collect())
+    }
+
+    /// Build the context, filling in the section preset for the template type
+    /// when no sections were given explicitly
+    fn build_context(&self) -> Result<TemplateContext> {
+        if let Some(message) = &self.invalid_title {
+            return Err(NoterError::Validation(message.clone()).into());
+        }
+
+        let mut context = self.context_builder.clone().build()?;
+
+        if let Some(config) = self.context_builder.get_config() {
+            if context.sections.is_empty() {
+                context.sections = config
+                    .note_preferences
+                    .sections_for(&self.template_reference.name);
+            }
+
+            if context.due_date.is_none()
+                && self.template_reference.name == TemplateReference::assignment().name
+            {
+                context.due_date = Some(TemplateContext::default_due_date(config));
+            }
+        }
+
+        Ok(context)
+    }
+
     /// Internal validation method
     fn validate_template(&self, context: &TemplateContext) -> Result<ValidationResult> {
         let mut all_issues = Vec::new();
@@ -350,16 +536,7 @@ impl TemplateBuilder {
         config: &TemplateConfig,
         reference: &TemplateReference,
     ) -> Result<TemplateDefinition> {
-        // config.templates is a Vec<TemplateDefinition>, not Option<Vec<TemplateDefinition>>
-        for template in &config.templates {
-            if template.name == reference.name {
-                return Ok(template.clone());
-            }
-        }
-        Err(anyhow::anyhow!(
-            "Template '{}' not found in configuration",
-            reference.name
-        ))
+        Ok(TemplateEngine::resolve_definition(config, &reference.name)?)
     }
 
     /// Find template variant if specified
@@ -376,11 +553,11 @@ impl TemplateBuilder {
                     }
                 }
             }
-            return Err(anyhow::anyhow!(
-                "Variant '{}' not found for template '{}'",
-                variant_name,
-                template_def.name
-            ));
+            return Err(NoterError::VariantNotFound {
+                template: template_def.name.clone(),
+                variant: variant_name.clone(),
+            }
+            .into());
         }
         Ok(None)
     }
@@ -477,6 +654,8 @@ impl Default for ProcessingOptions {
             validate_before_build: true,
             apply_transformations: true,
             include_debug_info: false,
+            dump_context: false,
+            use_render_cache: false,
             validation_level: ValidationLevel::Standard,
             fail_on_validation_errors: false, // Allow warnings to pass through
         }
@@ -520,6 +699,11 @@ impl ProcessingOptionsBuilder {
         self
     }
 
+    pub fn with_context_dump(mut self, enabled: bool) -> Self {
+        self.options.dump_context = enabled;
+        self
+    }
+
     pub fn build(self) -> ProcessingOptions {
         self.options
     }
@@ -563,21 +747,22 @@ mod tests {
 
     #[test]
     fn test_validation_result_methods() {
-        let mut issues = Vec::new();
-        issues.push(ValidationIssue {
-            severity: ValidationSeverity::Error,
-            category: "test".to_string(),
-            message: "Test error".to_string(),
-            suggestion: None,
-            location: None,
-        });
-        issues.push(ValidationIssue {
-            severity: ValidationSeverity::Warning,
-            category: "test".to_string(),
-            message: "Test warning".to_string(),
-            suggestion: None,
-            location: None,
-        });
+        let issues = vec![
+            ValidationIssue {
+                severity: ValidationSeverity::Error,
+                category: "test".to_string(),
+                message: "Test error".to_string(),
+                suggestion: None,
+                location: None,
+            },
+            ValidationIssue {
+                severity: ValidationSeverity::Warning,
+                category: "test".to_string(),
+                message: "Test warning".to_string(),
+                suggestion: None,
+                location: None,
+            },
+        ];
 
         let result = ValidationResult { issues };
 
@@ -587,4 +772,53 @@ mod tests {
         assert_eq!(result.warning_count(), 1);
         assert!(!result.is_clean());
     }
+
+    #[test]
+    fn test_context_warnings_without_template_definition() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config {
+            author: String::new(),
+            ..Default::default()
+        };
+        config.paths.typst_packages_dir = temp_dir.path().to_string_lossy().to_string();
+
+        let warnings = TemplateBuilder::new("99999", &config)
+            .unwrap()
+            .context_warnings()
+            .unwrap();
+
+        assert!(warnings.iter().any(|w| w == "Author name is empty"));
+        assert!(
+            warnings
+                .iter()
+                .any(|w| w.contains("Course name not found for 99999"))
+        );
+    }
+
+    #[test]
+    fn test_with_title_rejects_titles_without_filename_characters() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.paths.typst_packages_dir = temp_dir.path().to_string_lossy().to_string();
+
+        for title in ["   ", "###"] {
+            let error = TemplateBuilder::new("02101", &config)
+                .unwrap()
+                .with_title(title)
+                .build()
+                .unwrap_err();
+            assert!(matches!(
+                error.downcast_ref::<NoterError>(),
+                Some(NoterError::Validation(_))
+            ));
+        }
+
+        // Surrounding whitespace is trimmed before use
+        let context = TemplateBuilder::new("02101", &config)
+            .unwrap()
+            .with_title("  Graphs  ")
+            .build_context()
+            .unwrap();
+        assert_eq!(context.title, "Graphs");
+    }
 }
@@ -0,0 +1,224 @@
+//! Render cache for generated notes
+//!
+//! Rendered documents are stored under `<config dir>/render-cache/`, keyed by
+//! a SHA-256 hash of the template context, the template reference and the
+//! template package version, so regenerating an identical note skips
+//! rendering. The package version is part of the key, so installing another
+//! template version never serves output rendered for the old one. Entries
+//! expire after the template's `rendering.cache_duration_minutes`.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use super::context::TemplateContext;
+use super::engine::{TemplateEngine, TemplateReference};
+use crate::config::Config;
+
+/// Cache directory name, inside the config directory
+pub const CACHE_DIR: &str = "render-cache";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    package: String,
+    version: String,
+    created: SystemTime,
+    content: String,
+}
+
+/// Rendered documents on disk, one JSON file per cache key
+#[derive(Debug, Clone)]
+pub struct RenderCache {
+    dir: PathBuf,
+}
+
+#[allow(dead_code)]
+impl RenderCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The cache in the config directory
+    pub fn open() -> Result<Self> {
+        Ok(Self::new(Config::config_dir()?.join(CACHE_DIR)))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Cache key for rendering `context` with `template_ref`. The context's
+    /// creation timestamp changes on every build without affecting the output,
+    /// so it is left out.
+    pub fn key(context: &TemplateContext, template_ref: &TemplateReference) -> Result<String> {
+        // Maps serialize with sorted keys, so the same context always hashes the same
+        let mut value = serde_json::to_value(context)?;
+        if let Some(metadata) = value
+            .get_mut("metadata")
+            .and_then(serde_json::Value::as_object_mut)
+        {
+            metadata.remove("creation_date");
+        }
+
+        // Entries outlive the binary, so the hash has to be stable across builds
+        let (package, version) = Self::package_version(context);
+        let key = serde_json::to_string(&(
+            value,
+            &template_ref.name,
+            &template_ref.variant,
+            package,
+            version,
+        ))?;
+        let digest = Sha256::digest(key.as_bytes());
+
+        Ok(digest.iter().map(|byte| format!("{:02x}", byte)).collect())
+    }
+
+    /// Cached output for `key` that is younger than `ttl`. Expired and
+    /// unreadable entries are removed.
+    pub fn get(&self, key: &str, ttl: Duration) -> Option<String> {
+        let path = self.entry_path(key);
+        let content = fs::read_to_string(&path).ok()?;
+
+        let fresh = serde_json::from_str::<CacheEntry>(&content)
+            .ok()
+            .filter(|entry| entry.created.elapsed().is_ok_and(|age| age <= ttl));
+        if fresh.is_none() {
+            let _ = fs::remove_file(&path);
+        }
+        fresh.map(|entry| entry.content)
+    }
+
+    /// Store rendered output for `key`
+    pub fn put(&self, key: &str, context: &TemplateContext, content: &str) -> Result<()> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create render cache {}", self.dir.display()))?;
+
+        let (package, version) = Self::package_version(context);
+        let entry = CacheEntry {
+            package: package.to_string(),
+            version: version.to_string(),
+            created: SystemTime::now(),
+            content: content.to_string(),
+        };
+        let path = self.entry_path(key);
+        fs::write(&path, serde_json::to_string(&entry)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Render through the cache: cached output when a fresh entry exists,
+    /// otherwise render and store the result
+    pub fn render(
+        &self,
+        context: &TemplateContext,
+        template_ref: &TemplateReference,
+        ttl: Duration,
+    ) -> Result<String> {
+        let key = Self::key(context, template_ref)?;
+        if let Some(content) = self.get(&key, ttl) {
+            return Ok(content);
+        }
+
+        let content = TemplateEngine::render_template(context, template_ref)?;
+        // A cache that can't be written only costs the next render
+        let _ = self.put(&key, context, &content);
+        Ok(content)
+    }
+
+    /// Remove every cached entry, returning how many were removed
+    pub fn clear(&self) -> Result<usize> {
+        if !self.dir.exists() {
+            return Ok(0);
+        }
+
+        let mut removed = 0;
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                fs::remove_file(&path)?;
+                removed += 1;
+            }
+        }
+
+        Ok(removed)
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+
+    fn package_version(context: &TemplateContext) -> (&str, &str) {
+        context
+            .template_config
+            .as_ref()
+            .map(|config| {
+                (
+                    config.metadata.name.as_str(),
+                    config.metadata.version.as_str(),
+                )
+            })
+            .unwrap_or(("", ""))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::template::config::TemplateConfig;
+    use tempfile::TempDir;
+
+    fn context(version: &str) -> Result<TemplateContext> {
+        let mut template_config = TemplateConfig::default();
+        template_config.metadata.version = version.to_string();
+        TemplateContext::build_lecture_context(
+            "02101",
+            &Config::default(),
+            &template_config,
+            Some("Graphs"),
+        )
+    }
+
+    #[test]
+    fn test_render_cache_keys_and_expiry() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let cache = RenderCache::new(temp_dir.path());
+        let lecture = TemplateReference::lecture();
+
+        // Contexts built at different times share a key
+        let key = RenderCache::key(&context("0.2.0")?, &lecture)?;
+        assert_eq!(key, RenderCache::key(&context("0.2.0")?, &lecture)?);
+        // A SHA-256 digest in hex
+        assert_eq!(key.len(), 64);
+        assert!(key.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(key, RenderCache::key(&context("0.3.0")?, &lecture)?);
+        assert_ne!(
+            key,
+            RenderCache::key(&context("0.2.0")?, &TemplateReference::assignment())?
+        );
+
+        let hour = Duration::from_secs(60 * 60);
+        assert!(cache.get(&key, hour).is_none());
+        cache.put(&key, &context("0.2.0")?, "= Cached")?;
+        assert_eq!(cache.get(&key, hour).as_deref(), Some("= Cached"));
+
+        // Expired entries are dropped
+        let expired = CacheEntry {
+            package: String::new(),
+            version: "0.2.0".to_string(),
+            created: SystemTime::now() - hour * 2,
+            content: "= Old".to_string(),
+        };
+        fs::write(cache.entry_path(&key), serde_json::to_string(&expired)?)?;
+        assert!(cache.get(&key, hour).is_none());
+        assert!(!cache.entry_path(&key).exists());
+
+        cache.put(&key, &context("0.2.0")?, "= Cached")?;
+        assert_eq!(cache.clear()?, 1);
+        assert!(cache.get(&key, hour).is_none());
+
+        Ok(())
+    }
+}
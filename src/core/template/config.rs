@@ -35,6 +35,10 @@ pub struct TemplateDefinition {
     pub supports_variants: bool,
     pub course_types: Option<Vec<String>>,
     pub default_sections: Vec<String>,
+    /// Typst placeholder emitted under each section heading, keyed by section
+    /// name. `{{section}}` is replaced with the section name.
+    #[serde(default)]
+    pub section_scaffolding: HashMap<String, String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
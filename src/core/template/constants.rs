@@ -1 +1,8 @@
 pub const TOML_FILE_NAME: &str = ".noter.config.toml";
+
+/// Package imported when no template package configuration is discovered
+pub const FALLBACK_PACKAGE_NAME: &str = "dtu-template";
+
+/// Template functions the fallback package's notes and assignments call
+pub const FALLBACK_NOTE_FUNCTION: &str = "dtu-note";
+pub const FALLBACK_ASSIGNMENT_FUNCTION: &str = "dtu-assignment";
@@ -6,11 +6,12 @@
 //! integrates with the new template configuration system.
 
 use anyhow::Result;
-use chrono::Local;
+use chrono::{Duration, Local, NaiveDate};
+use serde::Serialize;
 use std::collections::HashMap;
 
 use super::config::{EngineConfig, TemplateConfig};
-use crate::config::Config;
+use crate::config::{Config, ImportStyle};
 use crate::core::status_manager::StatusManager;
 
 /// Rich context structure containing all metadata needed for template generation.
@@ -26,18 +27,40 @@ use crate::core::status_manager::StatusManager;
 /// - `template_dir`: Path to the template directory for resolving includes
 /// - `variables`: Dynamic variables for template substitution
 /// - `metadata`: Additional metadata for template processing
-#[derive(Debug, Clone)]
+///
+/// The context serializes to JSON (see [`TemplateContext::to_json`]) so it can
+/// be dumped with `--dump-context` when a template renders unexpectedly.
+#[derive(Debug, Clone, Serialize)]
 pub struct TemplateContext {
     // Core template data
     pub course_id: String,
     pub course_name: String,
     pub title: String,
     pub author: String,
+    /// Every author of a group submission, in order. With more than one the
+    /// header's `author` becomes a Typst array; `author` holds the first.
+    pub authors: Vec<String>,
     pub date: String,
     pub semester: String,
     pub template_version: String,
     pub sections: Vec<String>,
-    pub custom_fields: HashMap<String, String>,
+    /// Assignment due date, emitted as `due-date` in the header when set
+    pub due_date: Option<NaiveDate>,
+    /// Date the note is for, emitted as the header `date` in place of today when set
+    pub note_date: Option<NaiveDate>,
+    /// Section scaffolding from the user's config, see [`NotePreferences::section_scaffolding`]
+    ///
+    /// [`NotePreferences::section_scaffolding`]: crate::config::NotePreferences::section_scaffolding
+    pub section_scaffolding: HashMap<String, String>,
+    /// Extra header parameters passed to the template function, e.g.
+    /// `collaborators: ("Alice", "Bob")`
+    pub custom_fields: HashMap<String, CustomField>,
+    /// Form of the template package import, see [`ImportStyle`]
+    pub import_style: ImportStyle,
+    /// Imported Typst body that replaces the generated sections
+    pub body: Option<String>,
+    /// Grading rubric, rendered as a table in a `Rubric` section at the end
+    pub rubric: Vec<RubricCriterion>,
 
     // Enhanced template system fields
     pub template_config: Option<TemplateConfig>,
@@ -47,8 +70,74 @@ pub struct TemplateContext {
     pub metadata: TemplateMetadata,
 }
 
+/// Value of a custom header field
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum CustomField {
+    /// Rendered as a Typst string
+    Text(String),
+    /// Rendered as a Typst array of strings
+    List(Vec<String>),
+}
+
+impl CustomField {
+    /// Typst literal for the value, with quotes and backslashes escaped
+    pub fn to_typst(&self) -> String {
+        fn string(value: &str) -> String {
+            format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+        }
+
+        match self {
+            CustomField::Text(value) => string(value),
+            // A one-element array needs a trailing comma in Typst
+            CustomField::List(values) if values.len() == 1 => format!("({},)", string(&values[0])),
+            CustomField::List(values) => format!(
+                "({})",
+                values
+                    .iter()
+                    .map(|value| string(value))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+impl From<&str> for CustomField {
+    fn from(value: &str) -> Self {
+        CustomField::Text(value.to_string())
+    }
+}
+
+impl From<String> for CustomField {
+    fn from(value: String) -> Self {
+        CustomField::Text(value)
+    }
+}
+
+impl From<Vec<String>> for CustomField {
+    fn from(values: Vec<String>) -> Self {
+        CustomField::List(values)
+    }
+}
+
+/// A graded criterion of an assignment rubric, e.g. `Correctness` for 60 points
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RubricCriterion {
+    pub name: String,
+    pub points: u32,
+}
+
+impl RubricCriterion {
+    /// Points of every criterion together. `Validator::parse_rubric` rejects
+    /// rubrics whose total doesn't fit in a `u32`.
+    pub fn total(criteria: &[RubricCriterion]) -> u32 {
+        criteria.iter().map(|criterion| criterion.points).sum()
+    }
+}
+
 /// Additional metadata for template processing
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct TemplateMetadata {
     pub course_type: String,
     pub assignment_type: Option<String>,
@@ -94,11 +183,18 @@ impl TemplateContext {
             course_name,
             title,
             author: config.author.clone(),
+            authors: Vec::new(),
             date: Local::now().format("%Y-%m-%d").to_string(),
             semester,
             template_version: config.template_version.clone(),
-            sections: config.note_preferences.lecture_sections.clone(),
+            sections: config.note_preferences.sections_for("lecture"),
+            due_date: None,
+            note_date: None,
+            section_scaffolding: config.note_preferences.section_scaffolding.clone(),
             custom_fields: HashMap::new(),
+            import_style: config.templates.import_style,
+            body: None,
+            rubric: Vec::new(),
             template_config: Some(template_config.clone()),
             engine_config,
             template_dir: config.paths.templates_dir.clone(),
@@ -114,12 +210,14 @@ impl TemplateContext {
         })
     }
 
-    /// Build assignment context with template configuration support
+    /// Build assignment context with template configuration support. Without
+    /// an explicit `due_date` the assignment is due `default_due_days` from today.
     pub fn build_assignment_context(
         course_id: &str,
         assignment_title: &str,
         config: &Config,
         template_config: &TemplateConfig,
+        due_date: Option<NaiveDate>,
     ) -> Result<Self> {
         use super::discovery::TemplateDiscovery;
 
@@ -141,11 +239,18 @@ impl TemplateContext {
             course_name,
             title: assignment_title.to_string(),
             author: config.author.clone(),
+            authors: Vec::new(),
             date: Local::now().format("%Y-%m-%d").to_string(),
             semester,
             template_version: config.template_version.clone(),
-            sections: config.note_preferences.assignment_sections.clone(),
+            sections: config.note_preferences.sections_for("assignment"),
+            due_date: Some(due_date.unwrap_or_else(|| Self::default_due_date(config))),
+            note_date: None,
+            section_scaffolding: config.note_preferences.section_scaffolding.clone(),
             custom_fields: HashMap::new(),
+            import_style: config.templates.import_style,
+            body: None,
+            rubric: Vec::new(),
             template_config: Some(template_config.clone()),
             engine_config,
             template_dir: config.paths.templates_dir.clone(),
@@ -185,11 +290,18 @@ impl TemplateContext {
             course_name,
             title: String::new(),
             author: config.author.clone(),
+            authors: Vec::new(),
             date: Local::now().format("%Y-%m-%d").to_string(),
             semester,
             template_version: config.template_version.clone(),
             sections: Vec::new(),
+            due_date: None,
+            note_date: None,
+            section_scaffolding: config.note_preferences.section_scaffolding.clone(),
             custom_fields: HashMap::new(),
+            import_style: config.templates.import_style,
+            body: None,
+            rubric: Vec::new(),
             template_config: Some(template_config.clone()),
             engine_config,
             template_dir: config.paths.templates_dir.clone(),
@@ -215,6 +327,11 @@ impl TemplateContext {
         self.variables.get(key)
     }
 
+    /// Pretty-printed JSON of the full context, for debugging template output
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
     /// Apply variable transformations based on engine config
     pub fn apply_transformations(&mut self) -> Result<()> {
         // Apply transformations defined in engine config
@@ -251,8 +368,14 @@ impl TemplateContext {
     }
 
     // Helper methods
+    /// Due date used when none is given: `note_preferences.default_due_days` from today
+    pub fn default_due_date(config: &Config) -> NaiveDate {
+        Local::now().date_naive()
+            + Duration::days(i64::from(config.note_preferences.default_due_days))
+    }
+
     fn resolve_course_name(course_id: &str, config: &Config) -> String {
-        config.get_course_name(course_id)
+        StatusManager::resolve_course_name(course_id, config)
     }
 
     fn determine_course_type(course_id: &str) -> String {
@@ -325,8 +448,13 @@ pub struct TemplateContextBuilder {
     config: Option<Config>,
     template_config: Option<TemplateConfig>,
     title: Option<String>,
-    custom_fields: HashMap<String, String>,
+    authors: Option<Vec<String>>,
+    custom_fields: HashMap<String, CustomField>,
     sections: Option<Vec<String>>,
+    due_date: Option<NaiveDate>,
+    date: Option<NaiveDate>,
+    body: Option<String>,
+    rubric: Vec<RubricCriterion>,
     variables: HashMap<String, String>,
 }
 
@@ -337,8 +465,13 @@ impl TemplateContextBuilder {
             config: None,
             template_config: None,
             title: None,
+            authors: None,
             custom_fields: HashMap::new(),
             sections: None,
+            due_date: None,
+            date: None,
+            body: None,
+            rubric: Vec::new(),
             variables: HashMap::new(),
         }
     }
@@ -347,6 +480,10 @@ impl TemplateContextBuilder {
         self.config.as_ref()
     }
 
+    pub fn get_template_config(&self) -> Option<&TemplateConfig> {
+        self.template_config.as_ref()
+    }
+
     pub fn with_course_id(mut self, course_id: &str) -> Self {
         self.course_id = Some(course_id.to_string());
         self
@@ -367,19 +504,43 @@ impl TemplateContextBuilder {
         self
     }
 
+    pub fn with_authors(mut self, authors: Vec<String>) -> Self {
+        self.authors = Some(authors);
+        self
+    }
+
     pub fn with_sections(mut self, sections: Vec<String>) -> Self {
         self.sections = Some(sections);
         self
     }
 
+    pub fn with_due_date(mut self, due_date: NaiveDate) -> Self {
+        self.due_date = Some(due_date);
+        self
+    }
+
+    pub fn with_date(mut self, date: NaiveDate) -> Self {
+        self.date = Some(date);
+        self
+    }
+
+    pub fn with_body(mut self, body: String) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    pub fn with_rubric(mut self, rubric: Vec<RubricCriterion>) -> Self {
+        self.rubric = rubric;
+        self
+    }
+
     pub fn with_variable(mut self, key: &str, value: &str) -> Self {
         self.variables.insert(key.to_string(), value.to_string());
         self
     }
 
-    pub fn with_custom_field(mut self, key: &str, value: &str) -> Self {
-        self.custom_fields
-            .insert(key.to_string(), value.to_string());
+    pub fn with_custom_field(mut self, key: &str, value: impl Into<CustomField>) -> Self {
+        self.custom_fields.insert(key.to_string(), value.into());
         self
     }
 
@@ -399,10 +560,33 @@ impl TemplateContextBuilder {
             context.title = title;
         }
 
+        if let Some(authors) = self.authors.filter(|authors| !authors.is_empty()) {
+            context.author = authors[0].clone();
+            context.set_variable("author", &authors.join(", "));
+            context.authors = authors;
+        }
+
         if let Some(sections) = self.sections {
             context.sections = sections;
         }
 
+        if self.due_date.is_some() {
+            context.due_date = self.due_date;
+        }
+
+        if let Some(date) = self.date {
+            context.date = date.format("%Y-%m-%d").to_string();
+            context.note_date = Some(date);
+        }
+
+        if self.body.is_some() {
+            context.body = self.body;
+        }
+
+        if !self.rubric.is_empty() {
+            context.rubric = self.rubric;
+        }
+
         // Merge custom fields and variables
         context.custom_fields.extend(self.custom_fields);
         context.variables.extend(self.variables);
@@ -435,3 +619,35 @@ impl Default for TemplateConfig {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_serializes_to_json() -> Result<()> {
+        let config = Config {
+            author: "Ada".to_string(),
+            ..Config::default()
+        };
+
+        let context = TemplateContext::builder()
+            .with_course_id("02101")
+            .with_config(config)
+            .with_title("Recursion")
+            .with_due_date(NaiveDate::from_ymd_opt(2025, 6, 1).unwrap())
+            .with_custom_field("difficulty", "hard")
+            .build()?;
+
+        let json: serde_json::Value = serde_json::from_str(&context.to_json()?)?;
+        assert_eq!(json["course_id"], "02101");
+        assert_eq!(json["title"], "Recursion");
+        assert_eq!(json["author"], "Ada");
+        assert_eq!(json["due_date"], "2025-06-01");
+        assert_eq!(json["custom_fields"]["difficulty"], "hard");
+        assert!(json["metadata"]["creation_date"].is_string());
+        assert!(json["engine_config"].is_object());
+
+        Ok(())
+    }
+}
@@ -5,7 +5,7 @@
 
 use super::config::{TemplateConfig, TemplateDefinition, TemplateVariant};
 use super::constants::TOML_FILE_NAME;
-use crate::config::Config;
+use crate::config::{Config, TemplateLocation, UserTemplateConfig};
 use anyhow::Result;
 use semver::Version;
 use std::path::{Path, PathBuf};
@@ -63,50 +63,179 @@ pub struct TemplatePackageInfo {
     pub last_updated: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+/// A template package found in one of the resolution locations
+#[derive(Debug, Clone)]
+pub struct DiscoveredPackage {
+    pub config: TemplateConfig,
+    pub dir: PathBuf,
+    pub location: TemplateLocation,
+    /// Whether a location earlier in `templates.resolution_order` already
+    /// provides a package with this name
+    pub shadowed: bool,
+}
+
+/// Why a course resolved to its course type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CourseTypeMatch {
+    /// The course id is listed in a package's course mapping
+    Exact { package: String },
+    /// The course id matched a mapping pattern such as "01xxx"
+    Pattern { package: String, pattern: String },
+    /// No mapping applied, so the fallback type was used
+    Fallback,
+}
+
+/// The template and variant a course resolves to
+#[derive(Debug, Clone)]
+pub struct TemplateResolution {
+    pub course_type: String,
+    pub course_type_match: CourseTypeMatch,
+    pub package: String,
+    pub version: String,
+    pub template: TemplateDefinition,
+    /// Variant chosen for the course type, `None` for the base template
+    pub variant: Option<TemplateVariant>,
+}
+
 pub struct TemplateDiscovery;
 
 impl TemplateDiscovery {
-    /// Load all template configurations from template packages
+    /// Load the template configurations that win resolution, skipping
+    /// packages shadowed by an earlier location. When several directories
+    /// declare the same name and version, only the most recently modified one
+    /// is loaded, the one [`Self::find_package_directory_for_config`] picks.
     pub fn load_template_configs(user_config: &Config) -> Result<Vec<TemplateConfig>> {
-        let typst_packages_dir = Path::new(&user_config.paths.typst_packages_dir);
+        let mut winners: Vec<DiscoveredPackage> = Vec::new();
+
+        for package in Self::discover_packages(user_config)?
+            .into_iter()
+            .filter(|package| !package.shadowed)
+        {
+            match winners.iter_mut().find(|winner| {
+                winner.config.metadata.name == package.config.metadata.name
+                    && winner.config.metadata.version == package.config.metadata.version
+            }) {
+                Some(winner) => {
+                    if Self::newest_first(&package, winner).is_lt() {
+                        *winner = package;
+                    }
+                }
+                None => winners.push(package),
+            }
+        }
 
-        let template_package_dirs = Self::find_all_template_packages(typst_packages_dir)?;
-        let mut configs = Vec::new();
+        Ok(winners.into_iter().map(|package| package.config).collect())
+    }
 
-        for package_dir in template_package_dirs {
-            let config_path = package_dir.join(TOML_FILE_NAME);
+    /// Every template package in the locations of `templates.resolution_order`,
+    /// in search order. Once a location provides a package name, the same
+    /// name in later locations is marked as shadowed.
+    pub fn discover_packages(user_config: &Config) -> Result<Vec<DiscoveredPackage>> {
+        let mut packages: Vec<DiscoveredPackage> = Vec::new();
+        let mut searched: Vec<&Path> = Vec::new();
+
+        for location in user_config.templates.template_locations() {
+            let dir = location.directory(user_config);
+            // Both locations may point at the same directory
+            if searched.contains(&dir) {
+                continue;
+            }
+            searched.push(dir);
 
-            if config_path.exists() {
-                let content = std::fs::read_to_string(&config_path)?;
-                let package_config: TemplateConfig = toml::from_str(&content)?;
+            let mut found = Vec::new();
+            for package_dir in Self::find_all_template_packages(dir)? {
+                let config_path = package_dir.join(TOML_FILE_NAME);
+                if !config_path.exists() {
+                    continue;
+                }
 
-                configs.push(package_config);
+                let content = std::fs::read_to_string(&config_path)?;
+                let config: TemplateConfig = toml::from_str(&content)?;
+                let shadowed = packages
+                    .iter()
+                    .any(|package| package.config.metadata.name == config.metadata.name);
+
+                found.push(DiscoveredPackage {
+                    config,
+                    dir: package_dir,
+                    location,
+                    shadowed,
+                });
             }
+            packages.extend(found);
         }
 
-        Ok(configs)
+        Ok(packages)
     }
     // TODO: This loads ALL templates, but realistically we should only load the one the user wants as primary.
 
-    /// Backwards compatibility - returns newest config or default
+    /// Configuration new notes use: the newest installed package, at its
+    /// pinned version when that is installed. Default when nothing is installed.
     pub fn load_template_config(user_config: &Config) -> Result<TemplateConfig> {
-        let mut configs = Self::load_template_configs(user_config)?;
+        let configs = Self::load_template_configs(user_config)?;
+        Ok(Self::select_template_config(&configs, &user_config.templates).unwrap_or_default())
+    }
+
+    /// The newest of `configs`, or its package's pinned version in `templates`
+    /// when one of `configs` has it
+    pub fn select_template_config(
+        configs: &[TemplateConfig],
+        templates: &UserTemplateConfig,
+    ) -> Option<TemplateConfig> {
+        let newest = configs.iter().max_by(|a, b| {
+            Self::compare_template_versions(&a.metadata.version, &b.metadata.version)
+        })?;
+
+        let pinned = templates
+            .pinned_version(&newest.metadata.name)
+            .and_then(|version| {
+                configs.iter().find(|config| {
+                    config.metadata.name == newest.metadata.name
+                        && config.metadata.version == version
+                })
+            });
+        Some(pinned.unwrap_or(newest).clone())
+    }
+
+    /// Version of each template package notes should import, keyed by package
+    /// name: the pinned version when it's installed, else the newest installed
+    pub fn installed_package_versions(
+        user_config: &Config,
+    ) -> Result<std::collections::HashMap<String, String>> {
+        let mut versions = std::collections::HashMap::new();
+        let configs = Self::load_template_configs(user_config)?;
+
+        for config in &configs {
+            let metadata = &config.metadata;
+            let is_newer = versions.get(&metadata.name).is_none_or(|current: &String| {
+                Self::compare_template_versions(&metadata.version, current).is_gt()
+            });
+            if is_newer {
+                versions.insert(metadata.name.clone(), metadata.version.clone());
+            }
+        }
 
-        if configs.is_empty() {
-            return Ok(TemplateConfig::default());
+        for (package, version) in versions.iter_mut() {
+            if let Some(pinned) = user_config.templates.pinned_version(package) {
+                if Self::is_installed(&configs, package, pinned) {
+                    *version = pinned.to_string();
+                }
+            }
         }
 
-        // Sort by semantic version to get the newest one
-        configs.sort_by(|a, b| {
-            Self::compare_template_versions(&a.metadata.version, &b.metadata.version)
-        });
+        Ok(versions)
+    }
 
-        // Take the last one (highest version)
-        Ok(configs.into_iter().last().unwrap())
+    /// Whether `configs` include `version` of `package`
+    pub fn is_installed(configs: &[TemplateConfig], package: &str, version: &str) -> bool {
+        configs.iter().any(|config| {
+            UserTemplateConfig::same_package(&config.metadata.name, package)
+                && config.metadata.version == version
+        })
     }
 
     /// Compare semantic versions using the semver crate
-    fn compare_template_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    pub fn compare_template_versions(a: &str, b: &str) -> std::cmp::Ordering {
         let version_a = Version::parse(a).unwrap_or_else(|_| {
             // Fallback for invalid versions - treat as 0.0.0
             Version::new(0, 0, 0)
@@ -224,7 +353,7 @@ impl TemplateDiscovery {
         Ok(available_templates)
     }
 
-    /// Find all template packages in the typst packages directory
+    /// Find all template packages in a packages or templates directory
     fn find_all_template_packages(typst_packages_dir: &Path) -> Result<Vec<PathBuf>> {
         let mut template_packages = Vec::new();
 
@@ -261,36 +390,78 @@ impl TemplateDiscovery {
         Ok(template_packages)
     }
 
-    /// Find the package directory for a specific config (for file resolution)
+    /// Find the package directory for a specific config (for file resolution).
+    /// When several directories declare the same name and version, the most
+    /// recently modified one wins.
     fn find_package_directory_for_config(
         user_config: &Config,
         target_config: &TemplateConfig,
     ) -> Result<PathBuf> {
-        let typst_packages_dir = Path::new(&user_config.paths.typst_packages_dir);
-        let package_dirs = Self::find_all_template_packages(typst_packages_dir)?;
+        let packages: Vec<DiscoveredPackage> = Self::discover_packages(user_config)?
+            .into_iter()
+            .filter(|package| !package.shadowed)
+            .collect();
 
         // Find the directory that contains this specific config
-        for package_dir in package_dirs.clone() {
-            let config_path = package_dir.join(TOML_FILE_NAME);
-            if config_path.exists() {
-                let content = std::fs::read_to_string(&config_path)?;
-                if let Ok(config) = toml::from_str::<TemplateConfig>(&content) {
-                    if config.metadata.name == target_config.metadata.name
-                        && config.metadata.version == target_config.metadata.version
-                    {
-                        return Ok(package_dir);
-                    }
-                }
-            }
+        let mut matches: Vec<&DiscoveredPackage> = packages
+            .iter()
+            .filter(|package| {
+                package.config.metadata.name == target_config.metadata.name
+                    && package.config.metadata.version == target_config.metadata.version
+            })
+            .collect();
+        matches.sort_by(|a, b| Self::newest_first(a, b));
+        if let Some(package) = matches.first() {
+            return Ok(package.dir.clone());
         }
 
         // Fallback: return first package directory if exact match not found
-        package_dirs
+        packages
             .into_iter()
             .next()
+            .map(|package| package.dir)
             .ok_or_else(|| anyhow::anyhow!("No template packages found"))
     }
 
+    /// Groups of unshadowed packages declaring the same name and version,
+    /// newest directory first. Only name/version pairs found in more than
+    /// one directory are returned.
+    pub fn ambiguous_packages(user_config: &Config) -> Result<Vec<Vec<DiscoveredPackage>>> {
+        let mut groups: Vec<Vec<DiscoveredPackage>> = Vec::new();
+
+        for package in Self::discover_packages(user_config)?
+            .into_iter()
+            .filter(|package| !package.shadowed)
+        {
+            match groups.iter_mut().find(|group| {
+                group[0].config.metadata.name == package.config.metadata.name
+                    && group[0].config.metadata.version == package.config.metadata.version
+            }) {
+                Some(group) => group.push(package),
+                None => groups.push(vec![package]),
+            }
+        }
+
+        groups.retain(|group| group.len() > 1);
+        for group in &mut groups {
+            group.sort_by(Self::newest_first);
+        }
+        Ok(groups)
+    }
+
+    /// Most recently modified directory first, then by path so equal
+    /// timestamps still resolve the same way every time
+    fn newest_first(a: &DiscoveredPackage, b: &DiscoveredPackage) -> std::cmp::Ordering {
+        let modified = |package: &DiscoveredPackage| {
+            std::fs::metadata(&package.dir)
+                .and_then(|m| m.modified())
+                .ok()
+        };
+        modified(b)
+            .cmp(&modified(a))
+            .then_with(|| a.dir.cmp(&b.dir))
+    }
+
     /// Extract package information from config and directory
     fn extract_package_info(config: &TemplateConfig, package_dir: &PathBuf) -> TemplatePackageInfo {
         TemplatePackageInfo {
@@ -321,25 +492,69 @@ impl TemplateDiscovery {
         course_id: &str,
         fallback: &str,
     ) -> String {
+        Self::resolve_course_type_with_match(configs, course_id, fallback).0
+    }
+
+    /// Course type for a course along with the mapping entry that produced it
+    pub fn resolve_course_type_with_match(
+        configs: &[TemplateConfig],
+        course_id: &str,
+        fallback: &str,
+    ) -> (String, CourseTypeMatch) {
         // Try each config's course mapping
         for config in configs.iter() {
             if let Some(course_mapping) = &config.course_mapping {
+                let package = config.metadata.name.clone();
+
                 // Check for exact course ID match first
                 if let Some(mapped_type) = course_mapping.get(course_id) {
-                    return mapped_type.clone();
+                    return (mapped_type.clone(), CourseTypeMatch::Exact { package });
                 }
 
                 // Then check for pattern matches (like "01xxx")
                 for (pattern, course_type) in course_mapping {
                     if Self::matches_course_pattern(course_id, pattern) {
-                        return course_type.clone();
+                        return (
+                            course_type.clone(),
+                            CourseTypeMatch::Pattern {
+                                package,
+                                pattern: pattern.clone(),
+                            },
+                        );
                     }
                 }
             }
         }
 
         // Fallback to provided default
-        fallback.to_string()
+        (fallback.to_string(), CourseTypeMatch::Fallback)
+    }
+
+    /// Resolve the template and variant `template_name` uses for a course, the
+    /// same way note creation picks them. Returns `None` when no config
+    /// provides the template.
+    pub fn resolve_template(
+        configs: &[TemplateConfig],
+        course_id: &str,
+        template_name: &str,
+    ) -> Option<TemplateResolution> {
+        let (template, config) = Self::find_template(configs, template_name)?;
+        let (course_type, course_type_match) = Self::resolve_course_type_with_match(
+            std::slice::from_ref(config),
+            course_id,
+            "general",
+        );
+        let variant =
+            Self::find_best_variant(std::slice::from_ref(config), template_name, &course_type);
+
+        Some(TemplateResolution {
+            course_type,
+            course_type_match,
+            package: config.metadata.name.clone(),
+            version: config.metadata.version.clone(),
+            template: template.clone(),
+            variant,
+        })
     }
 
     /// Simple pattern matching for course IDs (like "01xxx" matches "01005")
@@ -536,6 +751,67 @@ mod tests {
         println!("✅ Course type resolution works correctly");
     }
 
+    #[test]
+    fn test_resolve_template_reports_match() {
+        let config: TemplateConfig = toml::from_str(
+            r#"
+            [metadata]
+            name = "dtu-template"
+            version = "0.2.0"
+
+            [[templates]]
+            name = "note"
+            display_name = "Lecture Notes"
+            description = "Notes"
+            file = "lib.typ"
+            function = "dtu-note"
+            supports_variants = true
+            default_sections = []
+
+            [[variants]]
+            template = "note"
+            name = "math-note"
+            display_name = "Math Notes"
+            course_types = ["math"]
+            file = "lib.typ"
+
+            [course_mapping]
+            "01005" = "math"
+            "02xxx" = "programming"
+            "#,
+        )
+        .unwrap();
+        let configs = std::slice::from_ref(&config);
+
+        let exact = TemplateDiscovery::resolve_template(configs, "01005", "note").unwrap();
+        assert_eq!(exact.course_type, "math");
+        assert_eq!(
+            exact.course_type_match,
+            CourseTypeMatch::Exact {
+                package: "dtu-template".to_string()
+            }
+        );
+        assert_eq!(exact.variant.unwrap().name, "math-note");
+
+        let pattern = TemplateDiscovery::resolve_template(configs, "02101", "note").unwrap();
+        assert_eq!(
+            pattern.course_type_match,
+            CourseTypeMatch::Pattern {
+                package: "dtu-template".to_string(),
+                pattern: "02xxx".to_string()
+            }
+        );
+        assert!(pattern.variant.is_none());
+
+        let fallback = TemplateDiscovery::resolve_template(configs, "99999", "note").unwrap();
+        assert_eq!(
+            (fallback.course_type.as_str(), fallback.course_type_match),
+            ("general", CourseTypeMatch::Fallback)
+        );
+
+        assert!(TemplateDiscovery::resolve_template(configs, "01005", "thesis").is_none());
+    }
+
     #[test]
     fn test_pattern_matching() {
         assert!(TemplateDiscovery::matches_course_pattern("01005", "01xxx"));
@@ -545,4 +821,131 @@ mod tests {
 
         println!("✅ Pattern matching works correctly");
     }
+
+    #[test]
+    fn test_resolution_order_shadows_later_locations() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let write_package = |dir: &Path, version: &str| -> Result<()> {
+            std::fs::create_dir_all(dir)?;
+            std::fs::write(
+                dir.join(TOML_FILE_NAME),
+                format!(
+                    "templates = []\n\n[metadata]\nname = \"dtu-template\"\nversion = \"{}\"\n",
+                    version
+                ),
+            )?;
+            Ok(())
+        };
+
+        let packages_dir = temp_dir.path().join("packages");
+        let project_dir = temp_dir.path().join("templates");
+        write_package(&packages_dir.join("dtu-template").join("0.2.0"), "0.2.0")?;
+        write_package(&packages_dir.join("dtu-template").join("0.1.0"), "0.1.0")?;
+        write_package(&project_dir.join("dtu-template"), "0.1.5")?;
+
+        let mut config = Config::default();
+        config.paths.typst_packages_dir = packages_dir.to_string_lossy().to_string();
+        config.paths.templates_dir = project_dir.to_string_lossy().to_string();
+
+        // Packages first by default; every installed version of the package is kept
+        let mut versions: Vec<String> = TemplateDiscovery::load_template_configs(&config)?
+            .into_iter()
+            .map(|config| config.metadata.version)
+            .collect();
+        versions.sort();
+        assert_eq!(versions, vec!["0.1.0", "0.2.0"]);
+
+        // Project-local first shadows all installed versions
+        config.templates.resolution_order =
+            vec![TemplateLocation::Project, TemplateLocation::Packages];
+        let packages = TemplateDiscovery::discover_packages(&config)?;
+        assert_eq!(packages.len(), 3);
+        assert_eq!(packages[0].location, TemplateLocation::Project);
+        assert!(!packages[0].shadowed);
+        assert!(packages[1..].iter().all(|package| package.shadowed));
+        assert_eq!(
+            TemplateDiscovery::load_template_config(&config)?
+                .metadata
+                .version,
+            "0.1.5"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_duplicate_package_resolves_to_newest_directory() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let packages_dir = temp_dir.path().join("packages");
+        let current = packages_dir.join("dtu-template").join("0.2.0");
+        let stale = packages_dir.join("dtu-template-copy").join("0.2.0");
+        let toml = "templates = []\n\n[metadata]\nname = \"dtu-template\"\nversion = \"0.2.0\"\n";
+        for dir in [&current, &stale] {
+            std::fs::create_dir_all(dir)?;
+            std::fs::write(dir.join(TOML_FILE_NAME), toml)?;
+        }
+        let now = std::time::SystemTime::now();
+        std::fs::File::open(&stale)?.set_modified(now - std::time::Duration::from_secs(3600))?;
+        std::fs::File::open(&current)?.set_modified(now)?;
+
+        let mut config = Config::default();
+        config.paths.typst_packages_dir = packages_dir.to_string_lossy().to_string();
+        config.paths.templates_dir = temp_dir
+            .path()
+            .join("templates")
+            .to_string_lossy()
+            .to_string();
+
+        let target = TemplateDiscovery::load_template_config(&config)?;
+        assert_eq!(
+            TemplateDiscovery::find_package_directory_for_config(&config, &target)?,
+            current
+        );
+
+        let groups = TemplateDiscovery::ambiguous_packages(&config)?;
+        assert_eq!(groups.len(), 1);
+        let dirs: Vec<&PathBuf> = groups[0].iter().map(|package| &package.dir).collect();
+        assert_eq!(dirs, vec![&current, &stale]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_duplicate_package_config_comes_from_newest_directory() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let packages_dir = temp_dir.path().join("packages");
+        let current = packages_dir.join("dtu-template").join("0.2.0");
+        let stale = packages_dir.join("dtu-template-copy").join("0.2.0");
+        let toml = |template: &str| {
+            format!(
+                "[metadata]\nname = \"dtu-template\"\nversion = \"0.2.0\"\n\n[[templates]]\nname = \"{template}\"\ndisplay_name = \"{template}\"\ndescription = \"\"\nfile = \"{template}.typ\"\nfunction = \"dtu-{template}\"\nsupports_variants = false\ndefault_sections = []\n"
+            )
+        };
+        let now = std::time::SystemTime::now();
+        for (dir, template, age) in [(&current, "note", 0), (&stale, "old-note", 3600)] {
+            std::fs::create_dir_all(dir)?;
+            std::fs::write(dir.join(TOML_FILE_NAME), toml(template))?;
+            std::fs::File::open(dir)?.set_modified(now - std::time::Duration::from_secs(age))?;
+        }
+
+        let mut config = Config::default();
+        config.paths.typst_packages_dir = packages_dir.to_string_lossy().to_string();
+        config.paths.templates_dir = temp_dir
+            .path()
+            .join("templates")
+            .to_string_lossy()
+            .to_string();
+
+        // The templates come from the directory files are resolved against
+        let selected = TemplateDiscovery::load_template_config(&config)?;
+        let names: Vec<&str> = selected.templates.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["note"]);
+        assert_eq!(
+            TemplateDiscovery::find_package_directory_for_config(&config, &selected)?,
+            current
+        );
+        assert_eq!(TemplateDiscovery::load_template_configs(&config)?.len(), 1);
+
+        Ok(())
+    }
 }
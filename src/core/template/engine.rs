@@ -4,10 +4,74 @@
 //! discovery, and rendering.
 
 use super::config::{TemplateConfig, TemplateDefinition, TemplateVariant};
-use super::context::TemplateContext;
+use super::constants::{
+    FALLBACK_ASSIGNMENT_FUNCTION, FALLBACK_NOTE_FUNCTION, FALLBACK_PACKAGE_NAME,
+};
+use super::context::{CustomField, RubricCriterion, TemplateContext};
 use super::discovery::TemplateDiscovery;
-use crate::config::Config;
+use crate::config::{Config, ImportStyle};
+use crate::error::{NoterError, NoterResult};
 use anyhow::{Result, anyhow};
+use chrono::{Datelike, NaiveDate};
+use semver::Version;
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+
+/// Typst keywords and built-in functions, which scaffolding may call without
+/// the template package exporting them
+const TYPST_BUILTINS: &[&str] = &[
+    "let",
+    "set",
+    "show",
+    "import",
+    "include",
+    "if",
+    "else",
+    "for",
+    "while",
+    "break",
+    "continue",
+    "return",
+    "context",
+    "align",
+    "block",
+    "box",
+    "circle",
+    "colbreak",
+    "columns",
+    "datetime",
+    "emph",
+    "enum",
+    "figure",
+    "footnote",
+    "grid",
+    "h",
+    "heading",
+    "highlight",
+    "image",
+    "line",
+    "link",
+    "list",
+    "lorem",
+    "outline",
+    "pad",
+    "pagebreak",
+    "place",
+    "quote",
+    "raw",
+    "rect",
+    "ref",
+    "stack",
+    "strong",
+    "table",
+    "terms",
+    "text",
+    "underline",
+    "v",
+];
 
 pub struct TemplateEngine;
 
@@ -27,14 +91,61 @@ impl TemplateEngine {
         )?;
 
         let template_ref = TemplateReference::lecture();
-        Self::render_template(&context, &template_ref)
+        Ok(Self::render_template(&context, &template_ref)?)
+    }
+
+    /// Installed versions of a template package, newest first. Every version
+    /// subdirectory of `<typst_packages_dir>/<package_name>/` holding a
+    /// `typst.toml` counts, under the version the manifest declares or the
+    /// directory name when it declares none. Versions that aren't valid semver
+    /// sort last.
+    pub fn available_versions(package_name: &str, config: &Config) -> Vec<String> {
+        let package_dir = Path::new(&config.paths.typst_packages_dir).join(package_name);
+        let Ok(entries) = fs::read_dir(&package_dir) else {
+            return Vec::new();
+        };
+
+        let mut versions: Vec<String> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .filter_map(|path| {
+                let manifest = fs::read_to_string(path.join("typst.toml")).ok()?;
+                let dir_name = path.file_name()?.to_string_lossy().into_owned();
+                Some(Self::manifest_version(&manifest).unwrap_or(dir_name))
+            })
+            .collect();
+
+        versions.sort_by(|a, b| Self::compare_versions_descending(a, b));
+        versions.dedup();
+        versions
+    }
+
+    /// `package.version` from a `typst.toml` manifest
+    fn manifest_version(manifest: &str) -> Option<String> {
+        let manifest: toml::Table = toml::from_str(manifest).ok()?;
+        manifest
+            .get("package")?
+            .get("version")?
+            .as_str()
+            .map(str::to_string)
+    }
+
+    /// Newest semver first, then non-semver versions in name order
+    fn compare_versions_descending(a: &str, b: &str) -> Ordering {
+        match (Version::parse(a), Version::parse(b)) {
+            (Ok(a), Ok(b)) => b.cmp(&a),
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+            (Err(_), Err(_)) => a.cmp(b),
+        }
     }
 
     /// Main template rendering function
     pub fn render_template(
         context: &TemplateContext,
         template_ref: &TemplateReference,
-    ) -> Result<String> {
+    ) -> NoterResult<String> {
         // Get the template definition based on reference
         let template_def = Self::get_template_definition(context, template_ref)?;
 
@@ -42,7 +153,11 @@ impl TemplateEngine {
         let variant = Self::select_variant_for_template(context, &template_def, template_ref)?;
 
         // Generate the complete Typst document
-        Self::generate_typst_document(context, &template_def, variant.as_ref())
+        Ok(Self::generate_typst_document(
+            context,
+            &template_def,
+            variant.as_ref(),
+        )?)
     }
 
     /// Generate the complete Typst document
@@ -53,49 +168,192 @@ impl TemplateEngine {
     ) -> Result<String> {
         let mut document = String::new();
 
+        // An imported body replaces the sections, generate them otherwise
+        let (body, sections) = if let Some(body) = &context.body {
+            (body.clone(), Vec::new())
+        } else if !context.sections.is_empty() {
+            (
+                Self::generate_sections_from_context(context, template_def)?,
+                context.sections.clone(),
+            )
+        } else {
+            (
+                Self::generate_sections_from_template(context, template_def, variant)?,
+                Self::get_template_sections(template_def, variant),
+            )
+        };
+
         // Generate import statement
-        document.push_str(&Self::generate_import_statement(context)?);
+        document.push_str(&Self::generate_import_statement(
+            context,
+            Self::template_function(template_def, variant),
+            &Self::scaffolding_identifiers(&sections, context, template_def),
+        )?);
         document.push_str("\n\n");
 
         // Generate show rule with template function call
         document.push_str(&Self::generate_show_rule(context, template_def, variant)?);
         document.push_str("\n\n");
 
-        // Generate sections from template configuration
-        if !context.sections.is_empty() {
-            document.push_str(&Self::generate_sections_from_context(context)?);
-        } else {
-            document.push_str(&Self::generate_sections_from_template(
-                template_def,
-                variant,
-            )?);
+        document.push_str(&body);
+
+        if !context.rubric.is_empty() {
+            document.truncate(document.trim_end().len());
+            document.push_str("\n\n");
+            document.push_str(&Self::generate_rubric_section(&context.rubric));
         }
 
         Ok(document)
     }
 
+    /// `Rubric` section with a table of the criteria, their points and an
+    /// empty score column, closed by a total row
+    fn generate_rubric_section(rubric: &[RubricCriterion]) -> String {
+        let mut section = String::from(
+            "= Rubric\n\n#table(\n  columns: (1fr, auto, auto),\n  table.header([*Criterion*], [*Points*], [*Score*]),\n",
+        );
+        for criterion in rubric {
+            section.push_str(&format!(
+                "  {}, [{}], [],\n",
+                CustomField::from(criterion.name.as_str()).to_typst(),
+                criterion.points
+            ));
+        }
+        section.push_str(&format!(
+            "  [*Total*], [*{}*], [],\n)\n",
+            RubricCriterion::total(rubric)
+        ));
+        section
+    }
+
     /// Generate sections from context (custom sections)
-    fn generate_sections_from_context(context: &TemplateContext) -> Result<String> {
+    fn generate_sections_from_context(
+        context: &TemplateContext,
+        template_def: &TemplateDefinition,
+    ) -> Result<String> {
         let mut sections = String::new();
 
         for section in &context.sections {
-            sections.push_str(&format!("= {}\n\n", section));
+            sections.push_str(&Self::render_section(section, context, template_def));
         }
 
         Ok(sections)
     }
 
-    /// Generate the Typst import statement
-    fn generate_import_statement(context: &TemplateContext) -> Result<String> {
+    /// Section heading followed by its scaffolding, if any. The user's config
+    /// takes precedence over the template package; sections without
+    /// scaffolding get an empty body.
+    fn render_section(
+        section: &str,
+        context: &TemplateContext,
+        template_def: &TemplateDefinition,
+    ) -> String {
+        let scaffolding = Self::find_scaffolding(&context.section_scaffolding, section)
+            .or_else(|| Self::find_scaffolding(&template_def.section_scaffolding, section));
+
+        match scaffolding {
+            Some(body) => format!(
+                "= {}\n\n{}\n\n",
+                section,
+                body.replace("{{section}}", section).trim_end()
+            ),
+            None => format!("= {}\n\n", section),
+        }
+    }
+
+    /// Functions the scaffolding of `sections` calls, such as `problem` for
+    /// `#problem[]`, leaving out Typst keywords and built-ins
+    fn scaffolding_identifiers(
+        sections: &[String],
+        context: &TemplateContext,
+        template_def: &TemplateDefinition,
+    ) -> BTreeSet<String> {
+        let call = regex::Regex::new(r"#([A-Za-z_][A-Za-z0-9_-]*)\s*[\[(.]").unwrap();
+
+        sections
+            .iter()
+            .filter_map(|section| {
+                Self::find_scaffolding(&context.section_scaffolding, section)
+                    .or_else(|| Self::find_scaffolding(&template_def.section_scaffolding, section))
+            })
+            .flat_map(|body| call.captures_iter(body).map(|caps| caps[1].to_string()))
+            .filter(|name| !TYPST_BUILTINS.contains(&name.as_str()))
+            .collect()
+    }
+
+    /// Look up scaffolding by exact section name, then case-insensitively
+    fn find_scaffolding<'a>(
+        scaffolding: &'a HashMap<String, String>,
+        section: &str,
+    ) -> Option<&'a String> {
+        scaffolding.get(section).or_else(|| {
+            scaffolding
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(section))
+                .map(|(_, body)| body)
+        })
+    }
+
+    /// Generate the Typst import statement, importing everything or only
+    /// `function_name` depending on the context's import style
+    fn generate_import_statement(
+        context: &TemplateContext,
+        function_name: &str,
+        scaffolding_identifiers: &BTreeSet<String>,
+    ) -> Result<String> {
         let template_config = context
             .template_config
             .as_ref()
             .ok_or_else(|| anyhow!("No template configuration available"))?;
 
-        let package_name = &template_config.metadata.name;
-        let version = &template_config.metadata.version;
+        // Without a discovered package, import the fallback at the configured version
+        let (package_name, version) = if template_config.metadata.name.is_empty() {
+            (FALLBACK_PACKAGE_NAME, context.template_version.as_str())
+        } else {
+            (
+                template_config.metadata.name.as_str(),
+                template_config.metadata.version.as_str(),
+            )
+        };
+
+        Ok(match context.import_style {
+            ImportStyle::Glob => format!("#import \"@local/{}:{}\":*", package_name, version),
+            ImportStyle::Explicit => {
+                let mut names = vec![function_name];
+                names.extend(
+                    scaffolding_identifiers
+                        .iter()
+                        .map(String::as_str)
+                        .filter(|name| *name != function_name),
+                );
+                format!(
+                    "#import \"@local/{}:{}\": {}",
+                    package_name,
+                    version,
+                    names.join(", ")
+                )
+            }
+        })
+    }
 
-        Ok(format!("#import \"@local/{}:{}\":*", package_name, version))
+    /// Typst `datetime(...)` for a date
+    fn typst_date(date: NaiveDate) -> String {
+        format!(
+            "datetime(year: {}, month: {}, day: {})",
+            date.year(),
+            date.month(),
+            date.day()
+        )
+    }
+
+    /// Template function a document calls: the variant's, when it has one
+    fn template_function<'a>(
+        template_def: &'a TemplateDefinition,
+        variant: Option<&'a TemplateVariant>,
+    ) -> &'a str {
+        variant
+            .and_then(|variant| variant.function.as_deref())
+            .unwrap_or(&template_def.function)
     }
 
     /// Generate the show rule with standard template parameters
@@ -104,22 +362,42 @@ impl TemplateEngine {
         template_def: &TemplateDefinition,
         variant: Option<&TemplateVariant>,
     ) -> Result<String> {
-        // Determine which function to call
-        let function_name = if let Some(variant) = variant {
-            variant.function.as_ref().unwrap_or(&template_def.function)
-        } else {
-            &template_def.function
-        };
+        let function_name = Self::template_function(template_def, variant);
 
         // Build the standard parameters that all templates expect
-        let params = [
+        let mut params = vec![
             format!("course: \"{}\"", context.course_id),
             format!("course-name: \"{}\"", context.course_name),
             format!("title: \"{}\"", context.title),
-            "date: datetime.today()".to_string(),
-            format!("author: \"{}\"", context.author),
-            format!("semester: \"{}\"", context.semester),
+            match context.note_date {
+                Some(date) => format!("date: {}", Self::typst_date(date)),
+                None => "date: datetime.today()".to_string(),
+            },
         ];
+        if let Some(due_date) = context.due_date {
+            params.push(format!("due-date: {}", Self::typst_date(due_date)));
+        }
+        params.extend([
+            // Several authors become an array, a single author stays a string
+            if context.authors.len() > 1 {
+                format!(
+                    "author: {}",
+                    CustomField::List(context.authors.clone()).to_typst()
+                )
+            } else {
+                format!("author: \"{}\"", context.author)
+            },
+            format!("semester: \"{}\"", context.semester),
+        ]);
+
+        // Custom fields follow the standard parameters, sorted for stable output
+        let mut custom_fields: Vec<_> = context.custom_fields.iter().collect();
+        custom_fields.sort_by(|a, b| a.0.cmp(b.0));
+        params.extend(
+            custom_fields
+                .into_iter()
+                .map(|(key, value)| format!("{}: {}", key, value.to_typst())),
+        );
 
         let params_str = params.join(",\n  ");
 
@@ -131,6 +409,7 @@ impl TemplateEngine {
 
     /// Generate sections based on template configuration
     fn generate_sections_from_template(
+        context: &TemplateContext,
         template_def: &TemplateDefinition,
         variant: Option<&TemplateVariant>,
     ) -> Result<String> {
@@ -144,8 +423,8 @@ impl TemplateEngine {
                 content.push_str("\n\n");
             }
 
-            // Generate section header with empty content for user to fill
-            content.push_str(&format!("= {}\n\n", section));
+            // Generate section header with scaffolding or empty content for user to fill
+            content.push_str(&Self::render_section(section, context, template_def));
         }
 
         Ok(content)
@@ -178,25 +457,60 @@ impl TemplateEngine {
     fn get_template_definition(
         context: &TemplateContext,
         template_ref: &TemplateReference,
-    ) -> Result<TemplateDefinition> {
+    ) -> NoterResult<TemplateDefinition> {
         let template_config = context
             .template_config
             .as_ref()
-            .ok_or_else(|| anyhow!("No template configuration available"))?;
+            .ok_or_else(|| NoterError::Config("No template configuration available".to_string()))?;
+
+        Self::resolve_definition(template_config, &template_ref.name)
+    }
+
+    /// Definition of the template `name` in `template_config`. The header
+    /// calls the function it names, so any package's naming works. Only when
+    /// no configuration was discovered at all does this fall back to the DTU
+    /// template's functions.
+    pub fn resolve_definition(
+        template_config: &TemplateConfig,
+        name: &str,
+    ) -> NoterResult<TemplateDefinition> {
+        if template_config.templates.is_empty() {
+            return Ok(Self::fallback_definition(name));
+        }
 
         template_config
             .templates
             .iter()
-            .find(|t| t.name == template_ref.name)
+            .find(|t| t.name == name)
             .cloned()
-            .ok_or_else(|| anyhow!("Template '{}' not found", template_ref.name))
+            .ok_or_else(|| NoterError::TemplateNotFound(name.to_string()))
+    }
+
+    fn fallback_definition(name: &str) -> TemplateDefinition {
+        let function = if name == TemplateReference::assignment().name {
+            FALLBACK_ASSIGNMENT_FUNCTION
+        } else {
+            FALLBACK_NOTE_FUNCTION
+        };
+
+        TemplateDefinition {
+            name: name.to_string(),
+            display_name: name.to_string(),
+            description: String::new(),
+            file: format!("{}.typ", name),
+            function: function.to_string(),
+            supports_variants: false,
+            course_types: None,
+            default_sections: Vec::new(),
+            section_scaffolding: HashMap::new(),
+        }
     }
 
     fn select_variant_for_template(
         context: &TemplateContext,
         template_def: &TemplateDefinition,
         template_ref: &TemplateReference,
-    ) -> Result<Option<TemplateVariant>> {
+    ) -> NoterResult<Option<TemplateVariant>> {
         // If a specific variant is requested, try to find it
         if let Some(variant_name) = &template_ref.variant {
             return Self::find_specific_variant(context, template_def, variant_name);
@@ -232,7 +546,7 @@ impl TemplateEngine {
         context: &TemplateContext,
         template_def: &TemplateDefinition,
         variant_name: &str,
-    ) -> Result<Option<TemplateVariant>> {
+    ) -> NoterResult<Option<TemplateVariant>> {
         if let Some(template_config) = &context.template_config {
             if let Some(variants) = &template_config.variants {
                 for variant in variants {
@@ -244,11 +558,10 @@ impl TemplateEngine {
             }
         }
 
-        anyhow::bail!(
-            "Variant '{}' not found for template '{}'",
-            variant_name,
-            template_def.name
-        );
+        Err(NoterError::VariantNotFound {
+            template: template_def.name.clone(),
+            variant: variant_name.to_string(),
+        })
     }
 
     /// Resolve course type using template's course mapping or context
@@ -296,7 +609,20 @@ pub enum TemplateType {
     Custom(String),
 }
 
-#[derive(Clone, Debug)]
+/// Serializes as the plain type name, e.g. `"lecture"` or the custom name
+#[allow(deprecated)]
+impl Serialize for TemplateType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let name = match self {
+            TemplateType::Lecture => "lecture",
+            TemplateType::Assignment => "assignment",
+            TemplateType::Custom(name) => name,
+        };
+        serializer.serialize_str(name)
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
 pub struct TemplateReference {
     pub name: String,
     pub variant: Option<String>,
@@ -315,6 +641,18 @@ impl TemplateReference {
         self
     }
 
+    /// Reference for a template type name or alias, e.g. `lecture` or `l`
+    /// for the `note` template
+    pub fn for_type(template_type: &str) -> Self {
+        match template_type.to_lowercase().as_str() {
+            "lecture" | "l" | "note" => Self::lecture(),
+            "assignment" | "a" => Self::assignment(),
+            "lab" | "lab-report" => Self::lab_report(),
+            "thesis" | "project" => Self::thesis(),
+            custom => Self::new(custom),
+        }
+    }
+
     // Convenience constructors for common types
     pub fn lecture() -> Self {
         Self::new("note")
@@ -331,4 +669,306 @@ impl TemplateReference {
     pub fn thesis() -> Self {
         Self::new("thesis")
     }
+
+    /// Group project notes, with a `collaborators` header field
+    pub fn group() -> Self {
+        Self::new("group")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::validation::Validator;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_available_versions_sorted_newest_first() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let mut config = Config::default();
+        config.paths.typst_packages_dir = temp_dir.path().to_string_lossy().to_string();
+
+        let package_dir = temp_dir.path().join("dtu-template");
+        for (dir, manifest) in [
+            (
+                "0.2.0",
+                "[package]\nname = \"dtu-template\"\nversion = \"0.2.0\"\n",
+            ),
+            (
+                "0.10.0",
+                "[package]\nname = \"dtu-template\"\nversion = \"0.10.0\"\n",
+            ),
+            (
+                "0.1.0",
+                "[package]\nname = \"dtu-template\"\nversion = \"0.1.0\"\n",
+            ),
+            // No version in the manifest, so the directory name is used
+            ("dev", "[package]\nname = \"dtu-template\"\n"),
+            ("latest", "not toml"),
+        ] {
+            fs::create_dir_all(package_dir.join(dir))?;
+            fs::write(package_dir.join(dir).join("typst.toml"), manifest)?;
+        }
+        // Not an installed version without a manifest
+        fs::create_dir_all(package_dir.join("0.3.0"))?;
+
+        assert_eq!(
+            TemplateEngine::available_versions("dtu-template", &config),
+            vec!["0.10.0", "0.2.0", "0.1.0", "dev", "latest"]
+        );
+        assert!(TemplateEngine::available_versions("missing", &config).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_section_scaffolding_precedence() {
+        let mut config = Config::default();
+        config
+            .note_preferences
+            .section_scaffolding
+            .insert("Solution".to_string(), "#solution[]".to_string());
+
+        let context = TemplateContext::builder()
+            .with_course_id("02101")
+            .with_config(config)
+            .build()
+            .unwrap();
+
+        let template_def = TemplateDefinition {
+            name: "assignment".to_string(),
+            display_name: "Assignment".to_string(),
+            description: String::new(),
+            file: "assignment.typ".to_string(),
+            function: "assignment".to_string(),
+            supports_variants: false,
+            course_types: None,
+            default_sections: vec![],
+            section_scaffolding: HashMap::from([
+                (
+                    "problem 1".to_string(),
+                    "#problem[{{section}}]\n".to_string(),
+                ),
+                ("Solution".to_string(), "#answer[]".to_string()),
+            ]),
+        };
+
+        let render = |section| TemplateEngine::render_section(section, &context, &template_def);
+
+        assert_eq!(
+            render("Problem 1"),
+            "= Problem 1\n\n#problem[Problem 1]\n\n"
+        );
+        assert_eq!(render("Solution"), "= Solution\n\n#solution[]\n\n");
+        assert_eq!(render("Notes"), "= Notes\n\n");
+    }
+
+    #[test]
+    fn test_show_rule_renders_custom_fields() {
+        let members = vec!["Alice".to_string(), "Bob \"B\" Jensen".to_string()];
+        let context = TemplateContext::builder()
+            .with_course_id("02223")
+            .with_config(Config::default())
+            .with_custom_field("collaborators", members)
+            .with_custom_field("group", "7")
+            .build()
+            .unwrap();
+
+        let template_def = TemplateDefinition {
+            name: "group".to_string(),
+            display_name: "Group Project".to_string(),
+            description: String::new(),
+            file: "group.typ".to_string(),
+            function: "group-project".to_string(),
+            supports_variants: false,
+            course_types: None,
+            default_sections: vec![],
+            section_scaffolding: HashMap::new(),
+        };
+
+        let show_rule = TemplateEngine::generate_show_rule(&context, &template_def, None).unwrap();
+        assert!(show_rule.ends_with(
+            "  collaborators: (\"Alice\", \"Bob \\\"B\\\" Jensen\"),\n  group: \"7\"\n)"
+        ));
+
+        // One-element arrays keep the trailing comma Typst needs
+        assert_eq!(
+            CustomField::List(vec!["Alice".to_string()]).to_typst(),
+            "(\"Alice\",)"
+        );
+
+        // Group submissions list every author, a single author stays a string
+        let with_authors = |authors: &[&str]| {
+            let context = TemplateContext::builder()
+                .with_course_id("02223")
+                .with_config(Config::default())
+                .with_authors(authors.iter().map(|author| author.to_string()).collect())
+                .build()
+                .unwrap();
+            TemplateEngine::generate_show_rule(&context, &template_def, None).unwrap()
+        };
+        assert!(with_authors(&["Alice", "Bob"]).contains("  author: (\"Alice\", \"Bob\"),\n"));
+        assert!(with_authors(&["Alice"]).contains("  author: \"Alice\",\n"));
+    }
+
+    #[test]
+    fn test_assignment_header_includes_due_date() {
+        let context = TemplateContext::builder()
+            .with_course_id("02101")
+            .with_config(Config::default())
+            .with_template_config(TemplateConfig::default())
+            .with_due_date(NaiveDate::from_ymd_opt(2025, 6, 1).unwrap())
+            .build()
+            .unwrap();
+
+        let content =
+            TemplateEngine::render_template(&context, &TemplateReference::assignment()).unwrap();
+        assert!(content.contains("  due-date: datetime(year: 2025, month: 6, day: 1),\n"));
+    }
+
+    #[test]
+    fn test_rubric_section_renders_table() {
+        let rubric = Validator::parse_rubric("Correctness:60, \"Style\": 30").unwrap();
+        assert_eq!(RubricCriterion::total(&rubric), 90);
+        assert_eq!(
+            TemplateEngine::generate_rubric_section(&rubric),
+            "= Rubric\n\n\
+             #table(\n\
+             \x20 columns: (1fr, auto, auto),\n\
+             \x20 table.header([*Criterion*], [*Points*], [*Score*]),\n\
+             \x20 \"Correctness\", [60], [],\n\
+             \x20 \"\\\"Style\\\"\", [30], [],\n\
+             \x20 [*Total*], [*90*], [],\n\
+             )\n"
+        );
+    }
+
+    #[test]
+    fn test_header_calls_the_discovered_template_function() {
+        let config = Config {
+            template_version: "0.3.0".to_string(),
+            ..Default::default()
+        };
+        let render = |template_config: TemplateConfig, reference: TemplateReference| {
+            let context = TemplateContext::builder()
+                .with_course_id("02101")
+                .with_config(config.clone())
+                .with_template_config(template_config)
+                .build()
+                .unwrap();
+            TemplateEngine::render_template(&context, &reference).unwrap()
+        };
+
+        // A third-party package's own function name is used
+        let mut third_party = TemplateConfig::default();
+        third_party.metadata.name = "uni-notes".to_string();
+        third_party.metadata.version = "1.0.0".to_string();
+        third_party.templates.push(TemplateDefinition {
+            name: "assignment".to_string(),
+            display_name: "Assignment".to_string(),
+            description: String::new(),
+            file: "assignment.typ".to_string(),
+            function: "homework".to_string(),
+            supports_variants: false,
+            course_types: None,
+            default_sections: vec![],
+            section_scaffolding: HashMap::new(),
+        });
+        let document = render(third_party.clone(), TemplateReference::assignment());
+        assert!(
+            document.starts_with("#import \"@local/uni-notes:1.0.0\":*\n\n#show: homework.with(")
+        );
+        assert!(matches!(
+            TemplateEngine::resolve_definition(&third_party, "lecture"),
+            Err(NoterError::TemplateNotFound(_))
+        ));
+
+        // Nothing discovered: the DTU template's names at the configured version
+        let document = render(TemplateConfig::default(), TemplateReference::assignment());
+        assert!(
+            document.starts_with(
+                "#import \"@local/dtu-template:0.3.0\":*\n\n#show: dtu-assignment.with("
+            )
+        );
+        let document = render(TemplateConfig::default(), TemplateReference::lecture());
+        assert!(document.contains("#show: dtu-note.with("));
+    }
+
+    #[test]
+    fn test_import_statement_styles() {
+        let mut config = Config::default();
+        let mut template_config = TemplateConfig::default();
+        template_config.metadata.name = "dtu-template".to_string();
+        template_config.metadata.version = "0.2.0".to_string();
+
+        let template_def = TemplateDefinition {
+            name: "lecture".to_string(),
+            display_name: "Lecture".to_string(),
+            description: String::new(),
+            file: "lecture.typ".to_string(),
+            function: "lecture-template".to_string(),
+            supports_variants: true,
+            course_types: None,
+            default_sections: vec![],
+            section_scaffolding: HashMap::new(),
+        };
+        let variant = TemplateVariant {
+            template: "lecture".to_string(),
+            name: "math".to_string(),
+            display_name: "Math".to_string(),
+            course_types: vec!["math".to_string()],
+            file: "math.typ".to_string(),
+            function: Some("math-lecture".to_string()),
+            additional_sections: None,
+            override_sections: None,
+        };
+
+        let import = |config: &Config, variant: Option<&TemplateVariant>| {
+            let context =
+                TemplateContext::build_lecture_context("02101", config, &template_config, None)
+                    .unwrap();
+            TemplateEngine::generate_import_statement(
+                &context,
+                TemplateEngine::template_function(&template_def, variant),
+                &BTreeSet::new(),
+            )
+            .unwrap()
+        };
+
+        assert_eq!(
+            import(&config, None),
+            "#import \"@local/dtu-template:0.2.0\":*"
+        );
+
+        config.templates.import_style = ImportStyle::Explicit;
+        assert_eq!(
+            import(&config, None),
+            "#import \"@local/dtu-template:0.2.0\": lecture-template"
+        );
+        assert_eq!(
+            import(&config, Some(&variant)),
+            "#import \"@local/dtu-template:0.2.0\": math-lecture"
+        );
+
+        // Functions the scaffolding calls are imported too, built-ins are not
+        let template_def = TemplateDefinition {
+            section_scaffolding: HashMap::from([
+                (
+                    "Problem 1".to_string(),
+                    "#problem[]\n#solution(hidden: true)[]".to_string(),
+                ),
+                ("Notes".to_string(), "#table(columns: 2)[]".to_string()),
+            ]),
+            ..template_def
+        };
+        let mut context =
+            TemplateContext::build_lecture_context("02101", &config, &template_config, None)
+                .unwrap();
+        context.sections = vec!["Problem 1".to_string(), "Notes".to_string()];
+        let document =
+            TemplateEngine::generate_typst_document(&context, &template_def, None).unwrap();
+        assert!(document.starts_with(
+            "#import \"@local/dtu-template:0.2.0\": lecture-template, problem, solution\n"
+        ));
+    }
 }
@@ -0,0 +1,360 @@
+//! Repair of template imports in existing notes
+//!
+//! Notes pin the template package version in their import line, e.g.
+//! `#import "@local/dtu-template:0.1.0":*`. After a template update those
+//! versions no longer exist locally, so this module rewrites them to the
+//! installed version. Only `#import` lines are touched.
+//!
+//! Notes that are regenerated or recompiled are reconciled against the version
+//! they already import, so editing a note doesn't silently move it to another
+//! template version.
+
+use anyhow::Result;
+use regex::Regex;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use super::config::TemplateConfig;
+use super::discovery::TemplateDiscovery;
+use crate::config::{Config, UserTemplateConfig};
+use crate::core::directory_scanner::DirectoryScanner;
+use crate::core::file_operations::FileOperations;
+
+/// Matches `@local/<package>:<version>` inside an import line
+static LOCAL_IMPORT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"@local/([A-Za-z0-9_-]+):([0-9]+\.[0-9]+\.[0-9]+[A-Za-z0-9.+-]*)").unwrap()
+});
+
+/// A package version rewritten in a file
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VersionChange {
+    pub package: String,
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportFix {
+    pub path: PathBuf,
+    pub changes: Vec<VersionChange>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportFixReport {
+    pub files_scanned: usize,
+    pub fixed: Vec<ImportFix>,
+}
+
+impl ImportFixReport {
+    /// Distinct version changes with the number of files each was applied to
+    pub fn version_changes(&self) -> Vec<(VersionChange, usize)> {
+        let mut counts: HashMap<&VersionChange, usize> = HashMap::new();
+        for fix in &self.fixed {
+            for change in &fix.changes {
+                *counts.entry(change).or_default() += 1;
+            }
+        }
+
+        let mut changes: Vec<(VersionChange, usize)> = counts
+            .into_iter()
+            .map(|(change, count)| (change.clone(), count))
+            .collect();
+        changes.sort_by(|a, b| (&a.0.package, &a.0.from).cmp(&(&b.0.package, &b.0.from)));
+        changes
+    }
+}
+
+/// Template configuration an existing note should be rendered against
+#[derive(Debug, Clone)]
+pub struct TemplateReconciliation {
+    pub config: TemplateConfig,
+    /// Template package versions the note imports that aren't installed
+    pub missing: Vec<VersionChange>,
+}
+
+impl TemplateReconciliation {
+    /// Warning for each imported version that isn't installed
+    pub fn warnings(&self) -> Vec<String> {
+        self.missing
+            .iter()
+            .map(|change| {
+                format!(
+                    "Note imports {} {}, which isn't installed (installed: {}). Run 'noter template fix-imports' to update it",
+                    change.package, change.from, change.to
+                )
+            })
+            .collect()
+    }
+}
+
+pub struct ImportFixer;
+
+#[allow(dead_code)]
+impl ImportFixer {
+    /// Rewrite outdated `@local` imports in the notes directory, or one course,
+    /// to the installed template versions. Each changed file is backed up first.
+    pub fn fix_imports(config: &Config, course_id: Option<&str>) -> Result<ImportFixReport> {
+        let installed = TemplateDiscovery::installed_package_versions(config)?;
+        let mut report = ImportFixReport::default();
+
+        let notes_dir = Path::new(&config.paths.notes_dir);
+        let scan_dir = match course_id {
+            Some(course_id) => notes_dir.join(course_id),
+            None => notes_dir.to_path_buf(),
+        };
+        if installed.is_empty() || !scan_dir.exists() {
+            return Ok(report);
+        }
+
+        let mut files = DirectoryScanner::scan_directory_for_files(
+            &scan_dir,
+            &config.typst.source_extensions(),
+            config.paths.follow_symlinks,
+        )?;
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        for file in files {
+            report.files_scanned += 1;
+
+            let content = fs::read_to_string(&file.path)?;
+            if let Some((updated, changes)) = Self::rewrite_imports(&content, &installed) {
+                FileOperations::create_backup(&file.path)?;
+                FileOperations::safe_write(&file.path, &updated)?;
+                report.fixed.push(ImportFix {
+                    path: file.path,
+                    changes,
+                });
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// `@local` packages and versions imported by `#import` lines, in order
+    pub fn imported_versions(content: &str) -> Vec<(String, String)> {
+        let mut imports = Vec::new();
+        for line in content.lines() {
+            if !line.trim_start().starts_with("#import") {
+                continue;
+            }
+
+            for caps in LOCAL_IMPORT.captures_iter(line) {
+                let import = (caps[1].to_string(), caps[2].to_string());
+                if !imports.contains(&import) {
+                    imports.push(import);
+                }
+            }
+        }
+
+        imports
+    }
+
+    /// Pick the installed template configuration for an existing note. The
+    /// version the note already imports wins when it's installed; otherwise,
+    /// and for fresh notes, the newest installed version is used. Imported
+    /// versions of known template packages that aren't installed are reported
+    /// in [`TemplateReconciliation::missing`].
+    pub fn reconcile_template_config(
+        user_config: &Config,
+        content: &str,
+    ) -> Result<TemplateReconciliation> {
+        let configs = TemplateDiscovery::load_template_configs(user_config)?;
+        Ok(Self::reconcile(
+            &configs,
+            &Self::imported_versions(content),
+            &user_config.templates,
+        ))
+    }
+
+    /// Reconcile imported versions against the installed configurations.
+    /// Notes without an installed import get the version new notes would.
+    fn reconcile(
+        configs: &[TemplateConfig],
+        imported: &[(String, String)],
+        templates: &UserTemplateConfig,
+    ) -> TemplateReconciliation {
+        let fallback = TemplateDiscovery::select_template_config(configs, templates);
+        let mut missing = Vec::new();
+
+        for (package, version) in imported {
+            let installed: Vec<&TemplateConfig> = configs
+                .iter()
+                .filter(|config| &config.metadata.name == package)
+                .collect();
+
+            if let Some(config) = installed
+                .iter()
+                .find(|config| &config.metadata.version == version)
+            {
+                return TemplateReconciliation {
+                    config: (*config).clone(),
+                    missing,
+                };
+            }
+
+            // Packages without a template configuration aren't ours to judge
+            if let Some(config) = Self::newest(installed.into_iter()) {
+                let to = templates
+                    .pinned_version(package)
+                    .filter(|pinned| TemplateDiscovery::is_installed(configs, package, pinned))
+                    .unwrap_or(&config.metadata.version);
+                missing.push(VersionChange {
+                    package: package.clone(),
+                    from: version.clone(),
+                    to: to.to_string(),
+                });
+            }
+        }
+
+        TemplateReconciliation {
+            config: fallback.unwrap_or_default(),
+            missing,
+        }
+    }
+
+    fn newest<'a>(configs: impl Iterator<Item = &'a TemplateConfig>) -> Option<&'a TemplateConfig> {
+        configs.max_by(|a, b| {
+            TemplateDiscovery::compare_template_versions(&a.metadata.version, &b.metadata.version)
+        })
+    }
+
+    /// Rewrite import lines pointing at a version other than the installed one.
+    /// Returns `None` when nothing needs to change.
+    pub fn rewrite_imports(
+        content: &str,
+        installed: &HashMap<String, String>,
+    ) -> Option<(String, Vec<VersionChange>)> {
+        let mut changes = Vec::new();
+
+        let lines: Vec<String> = content
+            .split_inclusive('\n')
+            .map(|line| {
+                if !line.trim_start().starts_with("#import") {
+                    return line.to_string();
+                }
+
+                LOCAL_IMPORT
+                    .replace_all(line, |caps: &regex::Captures| {
+                        let (package, version) = (&caps[1], &caps[2]);
+                        match installed.get(package) {
+                            Some(installed_version) if installed_version != version => {
+                                let change = VersionChange {
+                                    package: package.to_string(),
+                                    from: version.to_string(),
+                                    to: installed_version.clone(),
+                                };
+                                if !changes.contains(&change) {
+                                    changes.push(change);
+                                }
+                                format!("@local/{}:{}", package, installed_version)
+                            }
+                            _ => caps[0].to_string(),
+                        }
+                    })
+                    .into_owned()
+            })
+            .collect();
+
+        (!changes.is_empty()).then(|| (lines.concat(), changes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rewrite_imports_only_touches_import_lines() {
+        let installed = HashMap::from([("dtu-template".to_string(), "0.2.0".to_string())]);
+        let content = "#import \"@local/dtu-template:0.1.0\":*\n\
+                       = Notes\n\
+                       See @local/dtu-template:0.1.0 for details\n\
+                       #import \"@local/other:1.0.0\": helper\n";
+
+        let (updated, changes) = ImportFixer::rewrite_imports(content, &installed).unwrap();
+
+        assert_eq!(
+            updated,
+            "#import \"@local/dtu-template:0.2.0\":*\n\
+             = Notes\n\
+             See @local/dtu-template:0.1.0 for details\n\
+             #import \"@local/other:1.0.0\": helper\n"
+        );
+        assert_eq!(
+            changes,
+            vec![VersionChange {
+                package: "dtu-template".to_string(),
+                from: "0.1.0".to_string(),
+                to: "0.2.0".to_string(),
+            }]
+        );
+
+        assert!(ImportFixer::rewrite_imports(&updated, &installed).is_none());
+    }
+
+    fn template_config(name: &str, version: &str) -> TemplateConfig {
+        let mut config = TemplateConfig::default();
+        config.metadata.name = name.to_string();
+        config.metadata.version = version.to_string();
+        config
+    }
+
+    #[test]
+    fn test_reconcile_prefers_imported_installed_version() {
+        let configs = vec![
+            template_config("dtu-template", "0.1.0"),
+            template_config("dtu-template", "0.3.0"),
+            template_config("dtu-template", "0.2.0"),
+        ];
+        let imported = |content| ImportFixer::imported_versions(content);
+        let mut templates = UserTemplateConfig::default();
+
+        // The version a note imports wins over the newest one
+        let note = "#import \"@local/dtu-template:0.2.0\":*\n#import \"@local/helpers:1.0.0\": x\n";
+        assert_eq!(
+            imported(note),
+            vec![
+                ("dtu-template".to_string(), "0.2.0".to_string()),
+                ("helpers".to_string(), "1.0.0".to_string())
+            ]
+        );
+        let reconciled = ImportFixer::reconcile(&configs, &imported(note), &templates);
+        assert_eq!(reconciled.config.metadata.version, "0.2.0");
+        // Unknown packages are not reported
+        assert!(reconciled.missing.is_empty());
+
+        // Fresh notes get the newest version
+        let reconciled = ImportFixer::reconcile(&configs, &imported("= Notes\n"), &templates);
+        assert_eq!(reconciled.config.metadata.version, "0.3.0");
+
+        // A version that isn't installed falls back to the newest, with a warning
+        let note = "#import \"@local/dtu-template:0.0.9\":*\n";
+        let reconciled = ImportFixer::reconcile(&configs, &imported(note), &templates);
+        assert_eq!(reconciled.config.metadata.version, "0.3.0");
+        assert_eq!(
+            reconciled.missing,
+            vec![VersionChange {
+                package: "dtu-template".to_string(),
+                from: "0.0.9".to_string(),
+                to: "0.3.0".to_string(),
+            }]
+        );
+        assert_eq!(reconciled.warnings().len(), 1);
+
+        // A pinned version replaces the newest, unless it isn't installed
+        templates
+            .pinned_versions
+            .insert("dtu_template".to_string(), "0.1.0".to_string());
+        let reconciled = ImportFixer::reconcile(&configs, &imported(note), &templates);
+        assert_eq!(reconciled.config.metadata.version, "0.1.0");
+        assert_eq!(reconciled.missing[0].to, "0.1.0");
+
+        templates
+            .pinned_versions
+            .insert("dtu_template".to_string(), "0.9.0".to_string());
+        let reconciled = ImportFixer::reconcile(&configs, &imported("= Notes\n"), &templates);
+        assert_eq!(reconciled.config.metadata.version, "0.3.0");
+    }
+}
@@ -4,9 +4,11 @@
 //! dynamic content, variants, and configuration-driven behaviour.
 
 pub mod builder;
+pub mod cache;
 pub mod config;
 mod constants;
 pub mod context;
 pub mod discovery;
 pub mod engine;
+pub mod imports;
 pub mod validation;
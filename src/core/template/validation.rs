@@ -12,11 +12,13 @@ use crate::core::template::context::TemplateContext;
 use crate::core::template::discovery::{AvailableTemplate, TemplateDiscovery};
 use anyhow::Result;
 use regex::Regex;
+use serde::Serialize;
 use std::collections::HashMap;
 use std::path::Path;
 
 /// Validation severity levels
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ValidationSeverity {
     Error,
     Warning,
@@ -24,7 +26,7 @@ pub enum ValidationSeverity {
 }
 
 /// Validation result with detailed information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ValidationIssue {
     pub severity: ValidationSeverity,
     pub category: String,
@@ -57,6 +59,7 @@ impl TemplateValidator {
 
                 // Cross-configuration validation
                 issues.extend(Self::validate_cross_configurations(&template_configs)?);
+                issues.extend(Self::validate_duplicate_packages(config)?);
             }
             Err(e) => {
                 issues.push(ValidationIssue {
@@ -489,6 +492,34 @@ impl TemplateValidator {
         Ok(issues)
     }
 
+    fn validate_duplicate_packages(config: &Config) -> Result<Vec<ValidationIssue>> {
+        let mut issues = Vec::new();
+
+        for group in TemplateDiscovery::ambiguous_packages(config)? {
+            let chosen = &group[0];
+            let others: Vec<String> = group[1..]
+                .iter()
+                .map(|package| package.dir.display().to_string())
+                .collect();
+
+            issues.push(ValidationIssue {
+                severity: ValidationSeverity::Warning,
+                category: "cross_config".to_string(),
+                message: format!(
+                    "Package '{}' v{} is installed in {} directories; using the most recently modified one: {}",
+                    chosen.config.metadata.name,
+                    chosen.config.metadata.version,
+                    group.len(),
+                    chosen.dir.display()
+                ),
+                suggestion: Some(format!("Remove the stale copies: {}", others.join(", "))),
+                location: Some(chosen.dir.display().to_string()),
+            });
+        }
+
+        Ok(issues)
+    }
+
     fn validate_file_system(config: &Config) -> Result<Vec<ValidationIssue>> {
         let mut issues = Vec::new();
 
@@ -731,6 +762,11 @@ impl TemplateValidator {
 
         report
     }
+
+    /// Format validation issues as JSON for machine consumption (e.g. CI annotations)
+    pub fn format_validation_report_json(issues: &[ValidationIssue]) -> Result<String> {
+        Ok(serde_json::to_string_pretty(issues)?)
+    }
 }
 
 #[cfg(test)]
@@ -775,4 +811,23 @@ mod tests {
         assert!(report.contains("❌"));
         assert!(report.contains("⚠️"));
     }
+
+    #[test]
+    fn test_format_validation_report_json() {
+        let issues = vec![ValidationIssue {
+            severity: ValidationSeverity::Error,
+            category: "metadata".to_string(),
+            message: "Template package name is required".to_string(),
+            suggestion: None,
+            location: Some("metadata.name".to_string()),
+        }];
+
+        let json = TemplateValidator::format_validation_report_json(&issues).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed[0]["severity"], "error");
+        assert_eq!(parsed[0]["category"], "metadata");
+        assert_eq!(parsed[0]["location"], "metadata.name");
+        assert!(parsed[0]["suggestion"].is_null());
+    }
 }
@@ -0,0 +1,164 @@
+//! Pluggable template sources
+//!
+//! Template repositories are fetched through the [`TemplateFetcher`] trait so
+//! hosts other than GitHub can be supported. The fetcher is selected from the
+//! repository host: plain `owner/repo` values and `https://github.com/owner/repo`
+//! URLs use [`GitHubTemplateFetcher`]; GitLab URLs, or any repository with a
+//! non-GitHub `host` set, use [`GitLabTemplateFetcher`].
+
+use anyhow::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::{Config, TemplateRepository};
+use crate::core::github_template_fetcher::{GitHubTemplateFetcher, TemplateDownloadResult};
+use crate::core::gitlab_template_fetcher::GitLabTemplateFetcher;
+
+/// Host assumed when a repository is given as `owner/repo`
+pub const DEFAULT_HOST: &str = "github.com";
+
+/// A source that can resolve, download and install template releases
+pub trait TemplateFetcher {
+    /// Latest released version (tag) of the repository
+    fn latest_version(&self, repo_config: &TemplateRepository) -> Result<String>;
+
+    /// Download the archive for `version` to `archive_path`
+    fn download(
+        &self,
+        repo_config: &TemplateRepository,
+        version: &str,
+        archive_path: &Path,
+    ) -> Result<()>;
+
+    /// Install a downloaded archive into the Typst packages directory
+    fn install(
+        &self,
+        archive_path: &Path,
+        repo_config: &TemplateRepository,
+        version: &str,
+        config: &Config,
+    ) -> Result<()>;
+}
+
+/// Select the fetcher for a repository based on its host
+pub fn fetcher_for(repo_config: &TemplateRepository) -> Result<Box<dyn TemplateFetcher>> {
+    match repository_host(repo_config).as_str() {
+        DEFAULT_HOST => Ok(Box::new(GitHubTemplateFetcher)),
+        // An explicit host is only needed for self-hosted instances, which are GitLab
+        host if host.contains("gitlab") || repo_config.host.is_some() => {
+            Ok(Box::new(GitLabTemplateFetcher))
+        }
+        host => anyhow::bail!(
+            "Unsupported template host '{}' for repository '{}'",
+            host,
+            repo_config.name
+        ),
+    }
+}
+
+/// Host of a repository: parsed from a URL, then the `host` field, falling back
+/// to [`DEFAULT_HOST`] for `owner/repo`
+pub fn repository_host(repo_config: &TemplateRepository) -> String {
+    match repo_config.repository.split_once("://") {
+        Some((_, rest)) => rest
+            .split('/')
+            .next()
+            .unwrap_or(DEFAULT_HOST)
+            .to_lowercase(),
+        None => repo_config
+            .host
+            .as_deref()
+            .unwrap_or(DEFAULT_HOST)
+            .to_lowercase(),
+    }
+}
+
+/// Repository path without scheme and host (e.g. `owner/repo`)
+pub fn repository_path(repo_config: &TemplateRepository) -> String {
+    let path = match repo_config.repository.split_once("://") {
+        Some((_, rest)) => rest.split_once('/').map(|(_, path)| path).unwrap_or(""),
+        None => repo_config.repository.as_str(),
+    };
+
+    path.trim_matches('/').trim_end_matches(".git").to_string()
+}
+
+/// Resolve, download (unless cached) and install the latest release of a repository
+pub fn install_from_repository(
+    fetcher: &dyn TemplateFetcher,
+    config: &Config,
+    repo_config: &TemplateRepository,
+    force_update: bool,
+) -> Result<TemplateDownloadResult> {
+    let version = fetcher.latest_version(repo_config)?;
+    let installed_path = PathBuf::from(&config.paths.templates_dir).join(&repo_config.name);
+
+    // Skip the download when this version is already installed
+    let template_installed_marker = installed_path.join(".template_version");
+    let is_already_installed = fs::read_to_string(&template_installed_marker)
+        .is_ok_and(|installed_version| installed_version.trim() == version);
+
+    if is_already_installed && !force_update {
+        return Ok(TemplateDownloadResult {
+            version,
+            installed_path,
+            is_cached: true,
+        });
+    }
+
+    // Download if not cached or force update
+    let archive_path = GitHubTemplateFetcher::get_cache_path(&repo_config.name, &version)?;
+    if !archive_path.exists() || force_update {
+        fetcher.download(repo_config, &version, &archive_path)?;
+    }
+
+    fetcher.install(&archive_path, repo_config, &version, config)?;
+
+    Ok(TemplateDownloadResult {
+        version,
+        installed_path,
+        is_cached: archive_path.exists(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo(repository: &str) -> TemplateRepository {
+        TemplateRepository {
+            name: "test".to_string(),
+            repository: repository.to_string(),
+            host: None,
+            version: None,
+            branch: None,
+            template_path: None,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn test_repository_host_and_path() {
+        let plain = repo("HollowNumber/dtu-note-template");
+        assert_eq!(repository_host(&plain), "github.com");
+        assert_eq!(repository_path(&plain), "HollowNumber/dtu-note-template");
+
+        let url = repo("https://GitHub.com/owner/templates.git");
+        assert_eq!(repository_host(&url), "github.com");
+        assert_eq!(repository_path(&url), "owner/templates");
+
+        let gitlab = repo("https://gitlab.example.com/group/templates");
+        assert_eq!(repository_host(&gitlab), "gitlab.example.com");
+        assert_eq!(repository_path(&gitlab), "group/templates");
+        assert!(fetcher_for(&gitlab).is_ok());
+
+        let self_hosted = TemplateRepository {
+            host: Some("git.example.dk".to_string()),
+            ..repo("group/templates")
+        };
+        assert_eq!(repository_host(&self_hosted), "git.example.dk");
+        assert!(fetcher_for(&self_hosted).is_ok());
+
+        assert!(fetcher_for(&repo("https://bitbucket.org/owner/repo")).is_err());
+    }
+}
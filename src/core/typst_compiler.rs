@@ -3,18 +3,30 @@
 //! Handles compiling Typst files to PDF, watching for changes, and cleaning compiled files.
 
 use crate::config::Config;
+use crate::core::directory_scanner::DirectoryScanner;
+use crate::core::file_operations::FileOperations;
+use crate::core::process::ProcessRunner;
+use crate::error::{NoterError, NoterResult};
 use anyhow::Result;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
+use std::process::{Child, Command, ExitStatus};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// How often watch mode checks on the typst process and, with `--open`,
+/// whether the first compile has finished
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(250);
 
 pub struct TypstCompiler;
 
 #[allow(dead_code)]
 impl TypstCompiler {
     /// Compile a Typst file to PDF
-    pub fn compile_file(filepath: &str, config: &Config) -> Result<String> {
-        let input_path = Self::resolve_input_path(filepath)?;
+    pub fn compile_file(filepath: &str, config: &Config) -> NoterResult<String> {
+        let input_path = Self::resolve_input_path(filepath, config)?;
         let output_path = Self::determine_output_path(&input_path, config)?;
 
         // Clean before compiling if configured
@@ -31,31 +43,74 @@ impl TypstCompiler {
         // Build command arguments - modern Typst syntax: typst compile input.typ output.pdf
         let mut args = vec!["compile", &input_str, &output_str];
 
-        // Add custom compile arguments
-        for arg in &config.typst.compile_args {
+        // Note metadata first, so custom `--input` arguments can override it
+        let metadata_args = Self::metadata_input_args(&input_path);
+        // Add custom compile arguments (global first, then course-specific)
+        let compile_args = Self::compile_args_for(&input_path, config);
+        let font_args = Self::font_path_args(config);
+        for arg in metadata_args.iter().chain(&compile_args).chain(&font_args) {
             args.push(arg);
         }
 
         // Execute compilation
-        let output = Command::new("typst")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()?;
+        let output = ProcessRunner::output(Command::new("typst").args(&args))?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            anyhow::bail!("Typst compilation failed: {}", stderr);
+            return Err(NoterError::Compilation(stderr.into_owned()));
         }
 
         Ok(output_str)
     }
 
-    /// Watch a Typst file for changes and auto-compile
-    pub fn watch_file(filepath: &str, config: &Config) -> Result<()> {
-        let input_path = Self::resolve_input_path(filepath)?;
+    /// Compile in-memory Typst content to a PDF at `output_path`
+    ///
+    /// The content is piped to `typst compile -`, so no temporary source file is
+    /// written and diagnostics point at `<stdin>` with line numbers relative to the
+    /// piped content. Imports are resolved relative to the current directory.
+    pub fn compile_content(
+        content: &str,
+        output_path: &Path,
+        config: &Config,
+    ) -> NoterResult<String> {
+        if let Some(parent) = output_path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+
+        let output_str = output_path.to_string_lossy().into_owned();
+
+        let mut args = vec!["compile", "-", &output_str];
+        let font_args = Self::font_path_args(config);
+        for arg in config.typst.compile_args.iter().chain(&font_args) {
+            args.push(arg);
+        }
+
+        let output = ProcessRunner::output_with_input(
+            Command::new("typst").args(&args),
+            content.as_bytes(),
+        )?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(NoterError::Compilation(stderr.into_owned()));
+        }
+
+        Ok(output_str)
+    }
+
+    /// Watch a Typst file, recompiling on changes. With `open_pdf`, the PDF is
+    /// opened once after the first successful compile; the viewer is expected
+    /// to pick up later recompiles itself.
+    pub fn watch_file(filepath: &str, config: &Config, open_pdf: bool) -> Result<()> {
+        let input_path = Self::resolve_input_path(filepath, config)?;
         let output_path = Self::determine_output_path(&input_path, config)?;
 
+        if open_pdf {
+            Self::open_when_compiled(output_path.clone());
+        }
+
         // Convert paths to strings once to avoid temporary value issues
         let input_str = input_path.to_string_lossy().into_owned();
         let output_str = output_path.to_string_lossy().into_owned();
@@ -64,43 +119,265 @@ impl TypstCompiler {
         let mut args = vec!["watch", &input_str, &output_str];
 
         // Add custom watch arguments
-        for arg in &config.typst.watch_args {
+        let font_args = Self::font_path_args(config);
+        for arg in config.typst.watch_args.iter().chain(&font_args) {
             args.push(arg);
         }
 
+        // Ctrl+C reaches typst too, but noter stops it itself so the process
+        // is always reaped rather than left running if typst ignores the signal
+        let interrupted = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&interrupted);
+        ctrlc::set_handler(move || flag.store(true, Ordering::SeqCst))?;
+
         // Execute watch command (this blocks until interrupted)
         let mut child = Command::new("typst").args(&args).spawn()?;
 
-        // Wait for the child process to complete
-        let status = child.wait()?;
+        match Self::supervise(&mut child, &interrupted)? {
+            // Stopped with Ctrl+C
+            None => Ok(()),
+            Some(status) if status.success() => Ok(()),
+            Some(status) => {
+                anyhow::bail!("Typst watch failed with exit code: {:?}", status.code())
+            }
+        }
+    }
+
+    /// Wait for `child` to exit, or kill and reap it once `interrupted` is set.
+    /// Returns `None` when it was interrupted.
+    fn supervise(child: &mut Child, interrupted: &AtomicBool) -> Result<Option<ExitStatus>> {
+        loop {
+            if interrupted.load(Ordering::SeqCst) {
+                // Already gone if typst handled the Ctrl+C itself
+                let _ = child.kill();
+                child.wait()?;
+                return Ok(None);
+            }
+            if let Some(status) = child.try_wait()? {
+                return Ok(Some(status));
+            }
+            thread::sleep(WATCH_POLL_INTERVAL);
+        }
+    }
 
-        if !status.success() {
-            anyhow::bail!("Typst watch failed with exit code: {:?}", status.code());
+    /// Clean compiled PDFs of every course in the notes directory, returning
+    /// the number of files removed per course
+    ///
+    /// Removes PDFs inside each course directory, plus the course's outputs in
+    /// `typst.output_dir` when that is an absolute path outside the course.
+    pub fn clean_all_courses(config: &Config) -> Result<Vec<(String, usize)>> {
+        let notes_dir = Path::new(&config.paths.notes_dir);
+        if !notes_dir.exists() {
+            return Ok(Vec::new());
         }
 
-        Ok(())
+        Self::course_ids(notes_dir, config)?
+            .into_iter()
+            .map(|course_id| {
+                let cleaned = Self::clean_course(&notes_dir.join(&course_id), config)?;
+                Ok((course_id, cleaned))
+            })
+            .collect()
     }
 
-    /// Clean compiled PDF files in the notes directory
-    pub fn clean_files(config: &Config) -> Result<usize> {
-        let mut cleaned_count = 0;
+    /// Compiled PDFs of every course in the notes directory, the files
+    /// [`TypstCompiler::clean_all_courses`] would remove
+    pub fn all_compiled_outputs(config: &Config) -> Result<Vec<(String, Vec<PathBuf>)>> {
+        let notes_dir = Path::new(&config.paths.notes_dir);
+        if !notes_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        Self::course_ids(notes_dir, config)?
+            .into_iter()
+            .map(|course_id| {
+                let outputs = Self::compiled_outputs(&notes_dir.join(&course_id), config)?;
+                Ok((course_id, outputs))
+            })
+            .collect()
+    }
 
-        // Clean main notes directory
-        if Path::new(&config.paths.notes_dir).exists() {
-            cleaned_count += Self::clean_directory_recursive(&config.paths.notes_dir)?;
+    /// Notes and assignments that have never been compiled, i.e. whose PDF
+    /// (in `typst.output_dir` when set) doesn't exist, grouped by course.
+    /// Courses without any are left out.
+    pub fn uncompiled_sources(config: &Config) -> Result<Vec<(String, Vec<PathBuf>)>> {
+        let notes_dir = Path::new(&config.paths.notes_dir);
+        if !notes_dir.exists() {
+            return Ok(Vec::new());
         }
 
-        // Clean obsidian directory if it exists
-        if Path::new(&config.paths.obsidian_dir).exists() {
-            cleaned_count += Self::clean_directory_recursive(&config.paths.obsidian_dir)?;
+        let mut uncompiled = Vec::new();
+        for course_id in Self::course_ids(notes_dir, config)? {
+            let sources = Self::uncompiled_in_course(&notes_dir.join(&course_id), config)?;
+            if !sources.is_empty() {
+                uncompiled.push((course_id, sources));
+            }
         }
 
-        Ok(cleaned_count)
+        Ok(uncompiled)
+    }
+
+    /// Notes and assignments of one course directory without a PDF, sorted
+    pub fn uncompiled_in_course(course_path: &Path, config: &Config) -> Result<Vec<PathBuf>> {
+        let extensions = config.typst.source_extensions();
+        let notes = DirectoryScanner::scan_course_notes(
+            course_path,
+            &extensions,
+            config.paths.flat_layout,
+            config.paths.follow_symlinks,
+        )?;
+        let assignments = DirectoryScanner::scan_course_assignments(
+            course_path,
+            &extensions,
+            config.paths.follow_symlinks,
+        )?;
+
+        let mut sources: Vec<PathBuf> = notes
+            .into_iter()
+            .chain(assignments)
+            .map(|file| file.path)
+            .filter(|path| !Self::output_path_for(path, config).exists())
+            .collect();
+        sources.sort();
+        sources.dedup();
+
+        Ok(sources)
+    }
+
+    /// Course directories in the notes directory, plus configured courses that
+    /// have one, sorted
+    pub fn course_ids(notes_dir: &Path, config: &Config) -> Result<Vec<String>> {
+        let mut course_ids: Vec<String> = DirectoryScanner::scan_notes_directory(
+            notes_dir,
+            &config.typst.source_extensions(),
+            config.paths.flat_layout,
+            config.paths.follow_symlinks,
+        )?
+        .into_iter()
+        .map(|(course_id, _)| course_id)
+        .chain(config.courses.keys().cloned())
+        .filter(|course_id| notes_dir.join(course_id).is_dir())
+        .collect();
+        course_ids.sort();
+        course_ids.dedup();
+
+        Ok(course_ids)
+    }
+
+    /// Clean compiled PDFs of a single course directory
+    pub fn clean_course(course_path: &Path, config: &Config) -> Result<usize> {
+        let outputs = Self::compiled_outputs(course_path, config)?;
+        for output in &outputs {
+            fs::remove_file(output)?;
+        }
+
+        Ok(outputs.len())
+    }
+
+    /// Open `output_path` from a background thread as soon as it's written.
+    /// Typst only writes the PDF when a compile succeeds, so failed compiles
+    /// keep it waiting, and a PDF left from an earlier compile doesn't count.
+    fn open_when_compiled(output_path: PathBuf) {
+        let before = Self::modified_time(&output_path);
+        thread::spawn(move || {
+            while !Self::output_updated(&output_path, before) {
+                thread::sleep(WATCH_POLL_INTERVAL);
+            }
+            if let Err(e) = opener::open(&output_path) {
+                eprintln!("Could not open {}: {}", output_path.display(), e);
+            }
+        });
+    }
+
+    /// Whether `output_path` exists with a modification time other than `before`
+    fn output_updated(output_path: &Path, before: Option<SystemTime>) -> bool {
+        match Self::modified_time(output_path) {
+            Some(modified) => before != Some(modified),
+            None => false,
+        }
+    }
+
+    fn modified_time(path: &Path) -> Option<SystemTime> {
+        fs::metadata(path).and_then(|m| m.modified()).ok()
+    }
+
+    /// Compiled PDFs belonging to a course: the existing outputs of its
+    /// sources, in `typst.output_dir` when set. PDFs no source compiles to,
+    /// such as downloaded slides, are not included.
+    pub fn compiled_outputs(course_path: &Path, config: &Config) -> Result<Vec<PathBuf>> {
+        if !course_path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let sources = DirectoryScanner::scan_directory_for_files(
+            course_path,
+            &config.typst.source_extensions(),
+            config.paths.follow_symlinks,
+        )?;
+        Ok(Self::existing_outputs(
+            sources.into_iter().map(|source| source.path),
+            config,
+        ))
+    }
+
+    /// Compiled PDFs of the Typst sources in the Obsidian vault, the files
+    /// [`TypstCompiler::clean_obsidian_vault`] would remove
+    pub fn obsidian_outputs(config: &Config) -> Result<Vec<PathBuf>> {
+        Self::compiled_outputs(Path::new(&config.paths.obsidian_dir), config)
+    }
+
+    /// Clean compiled PDFs in the Obsidian vault
+    pub fn clean_obsidian_vault(config: &Config) -> Result<usize> {
+        Self::clean_course(Path::new(&config.paths.obsidian_dir), config)
+    }
+
+    /// Clean compiled PDFs of the sources in a single directory, without
+    /// descending into subdirectories
+    pub fn clean_current_directory(dir: &Path, config: &Config) -> Result<usize> {
+        let outputs = Self::directory_outputs(dir, config)?;
+        for output in &outputs {
+            fs::remove_file(output)?;
+        }
+
+        Ok(outputs.len())
+    }
+
+    /// Compiled PDFs of the sources directly in `dir`, the files
+    /// [`TypstCompiler::clean_current_directory`] would remove
+    pub fn directory_outputs(dir: &Path, config: &Config) -> Result<Vec<PathBuf>> {
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut sources = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_file() && config.typst.is_source_file(&path) {
+                sources.push(path);
+            }
+        }
+
+        Ok(Self::existing_outputs(sources, config))
+    }
+
+    /// Output paths of `sources` that exist, sorted and without duplicates
+    fn existing_outputs(
+        sources: impl IntoIterator<Item = PathBuf>,
+        config: &Config,
+    ) -> Vec<PathBuf> {
+        let mut outputs: Vec<PathBuf> = sources
+            .into_iter()
+            .map(|source| Self::output_path_for(&source, config))
+            .filter(|output| output.is_file())
+            .collect();
+        outputs.sort();
+        outputs.dedup();
+        outputs
     }
 
     /// Get compilation status for a file
     pub fn get_compilation_status(filepath: &str, config: &Config) -> Result<CompilationStatus> {
-        let input_path = Self::resolve_input_path(filepath)?;
+        let input_path = Self::resolve_input_path(filepath, config)?;
         let output_path = Self::determine_output_path(&input_path, config)?;
 
         if !input_path.exists() {
@@ -111,19 +388,123 @@ impl TypstCompiler {
             return Ok(CompilationStatus::NotCompiled);
         }
 
-        let source_modified = fs::metadata(&input_path)?.modified()?;
-        let output_modified = fs::metadata(&output_path)?.modified()?;
-
-        if source_modified > output_modified {
+        if CompilationStatus::needs_recompile(&input_path, &output_path) {
             Ok(CompilationStatus::OutOfDate)
         } else {
             Ok(CompilationStatus::UpToDate)
         }
     }
 
+    /// Count sources in a directory tree whose PDF is missing or older than the source
+    pub fn count_stale_outputs<P: AsRef<Path>>(dir: P, config: &Config) -> Result<usize> {
+        let dir = dir.as_ref();
+        if !dir.exists() {
+            return Ok(0);
+        }
+
+        let sources = DirectoryScanner::scan_directory_for_files(
+            dir,
+            &config.typst.source_extensions(),
+            config.paths.follow_symlinks,
+        )?;
+        let stale = sources
+            .iter()
+            .filter(|file| {
+                let output_path = Self::output_path_for(&file.path, config);
+                CompilationStatus::needs_recompile(&file.path, &output_path)
+            })
+            .count();
+
+        Ok(stale)
+    }
+
+    /// `--input` arguments carrying the title and author from a note's header,
+    /// available to templates as `sys.inputs.title` and `sys.inputs.author` for
+    /// the PDF's document metadata. Missing header values are left out.
+    pub fn metadata_input_args(input_path: &Path) -> Vec<String> {
+        ["title", "author"]
+            .into_iter()
+            .filter_map(|name| {
+                let value = FileOperations::read_header_argument(input_path, name)?;
+                Some(["--input".to_string(), format!("{}={}", name, value)])
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// Compile arguments for a source file: the global `typst.compile_args` followed
+    /// by any `typst.course_compile_args` for the course the file belongs to
+    pub fn compile_args_for(input_path: &Path, config: &Config) -> Vec<String> {
+        let mut args = config.typst.compile_args.clone();
+
+        if let Some(course_args) = Self::detect_course_id(input_path, config)
+            .and_then(|course_id| config.typst.course_compile_args.get(&course_id))
+        {
+            args.extend(course_args.iter().cloned());
+        }
+
+        args
+    }
+
+    /// Detect which course a source file belongs to from its path.
+    ///
+    /// Files under the notes directory use the first directory below it
+    /// (`{notes_dir}/{course_id}/...`). Files elsewhere fall back to the nearest
+    /// parent directory named after a configured course.
+    pub fn detect_course_id(input_path: &Path, config: &Config) -> Option<String> {
+        let notes_dir = Path::new(&config.paths.notes_dir);
+        let canonical_input = input_path.canonicalize().ok();
+        let canonical_notes = notes_dir.canonicalize().ok();
+
+        let relative = match (&canonical_input, &canonical_notes) {
+            (Some(input), Some(notes)) => input.strip_prefix(notes).ok(),
+            _ => input_path.strip_prefix(notes_dir).ok(),
+        };
+
+        if let Some(relative) = relative {
+            let mut components = relative.components();
+            // Require at least one more component so the course directory isn't the file itself
+            if let (Some(course), Some(_)) = (components.next(), components.next()) {
+                return Some(course.as_os_str().to_string_lossy().to_string());
+            }
+        }
+
+        let path = canonical_input.as_deref().unwrap_or(input_path);
+        path.parent()?
+            .ancestors()
+            .filter_map(|dir| dir.file_name())
+            .map(|name| name.to_string_lossy().to_string())
+            .find(|name| {
+                config.courses.contains_key(name)
+                    || config.typst.course_compile_args.contains_key(name)
+            })
+    }
+
+    /// `--font-path` arguments for every configured font directory that exists
+    pub fn font_path_args(config: &Config) -> Vec<String> {
+        config
+            .typst
+            .font_paths
+            .iter()
+            .filter(|dir| Path::new(dir).is_dir())
+            .flat_map(|dir| ["--font-path".to_string(), dir.clone()])
+            .collect()
+    }
+
+    /// Configured font directories that don't exist and will be skipped
+    pub fn missing_font_paths(config: &Config) -> Vec<String> {
+        config
+            .typst
+            .font_paths
+            .iter()
+            .filter(|dir| !Path::new(dir).is_dir())
+            .cloned()
+            .collect()
+    }
+
     /// Check if Typst is available on the system
     pub fn check_typst_availability() -> Result<String> {
-        let output = Command::new("typst").arg("--version").output()?;
+        let output = ProcessRunner::output(Command::new("typst").arg("--version"))?;
 
         if output.status.success() {
             let version = String::from_utf8_lossy(&output.stdout);
@@ -137,12 +518,32 @@ impl TypstCompiler {
 
     // Private helper methods
 
-    /// Resolve input path (add .typ extension if missing)
-    fn resolve_input_path(filepath: &str) -> Result<PathBuf> {
+    /// Number of pages in a compiled PDF, counted from its `/Type /Page`
+    /// objects. `None` when the file can't be read or the page objects are
+    /// hidden in compressed object streams.
+    pub fn pdf_page_count(pdf_path: &Path) -> Option<usize> {
+        let bytes = fs::read(pdf_path).ok()?;
+        if !bytes.starts_with(b"%PDF-") {
+            return None;
+        }
+
+        // `\b` keeps the `/Type /Pages` tree nodes out of the count
+        let page_object = regex::bytes::Regex::new(r"/Type\s*/Page\b").ok()?;
+        let pages = page_object.find_iter(&bytes).count();
+        (pages > 0).then_some(pages)
+    }
+
+    /// Resolve input path, trying each configured source extension when it's missing
+    pub fn resolve_input_path(filepath: &str, config: &Config) -> Result<PathBuf> {
         let mut path = PathBuf::from(filepath);
 
         if path.extension().is_none() {
-            path.set_extension("typ");
+            let extensions = config.typst.source_extensions();
+            path = extensions
+                .iter()
+                .map(|ext| path.with_extension(ext))
+                .find(|candidate| candidate.exists())
+                .unwrap_or_else(|| path.with_extension(extensions[0]));
         }
 
         if !path.exists() {
@@ -152,8 +553,21 @@ impl TypstCompiler {
         Ok(path)
     }
 
-    /// Determine output path based on configuration
+    /// Determine output path based on configuration, creating the output directory
     fn determine_output_path(input_path: &Path, config: &Config) -> Result<PathBuf> {
+        let output_path = Self::output_path_for(input_path, config);
+
+        if config.typst.output_dir.is_some() {
+            if let Some(parent) = output_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+
+        Ok(output_path)
+    }
+
+    /// Compute the output path for a source file without touching the file system
+    fn output_path_for(input_path: &Path, config: &Config) -> PathBuf {
         let mut output_path = input_path.with_extension("pdf");
 
         // Use custom output directory if configured
@@ -168,59 +582,31 @@ impl TypstCompiler {
                         .join(output_dir)
                 };
 
-                fs::create_dir_all(&custom_dir)?;
                 output_path = custom_dir.join(filename);
             }
         }
 
-        Ok(output_path)
+        output_path
     }
 
-    /// Clean PDF files in a single directory
+    /// Clean PDF files in a single directory, used by `typst.clean_before_compile`
+    /// on the output directory
     fn clean_directory(dir: &Path) -> Result<usize> {
-        let mut cleaned = 0;
-
-        if !dir.exists() {
-            return Ok(0);
-        }
-
-        for entry in fs::read_dir(dir)? {
-            let entry = entry?;
-            let path = entry.path();
-
-            if path.is_file() && path.extension().is_some_and(|ext| ext == "pdf") {
-                fs::remove_file(&path)?;
-                cleaned += 1;
+        let mut pdfs = Vec::new();
+        if dir.exists() {
+            for entry in fs::read_dir(dir)? {
+                let path = entry?.path();
+                if path.is_file() && path.extension().is_some_and(|ext| ext == "pdf") {
+                    pdfs.push(path);
+                }
             }
         }
 
-        Ok(cleaned)
-    }
-
-    /// Clean PDF files recursively
-    fn clean_directory_recursive(dir: &str) -> Result<usize> {
-        let mut cleaned = 0;
-        let path = Path::new(dir);
-
-        if !path.exists() {
-            return Ok(0);
+        for pdf in &pdfs {
+            fs::remove_file(pdf)?;
         }
 
-        for entry in fs::read_dir(path)? {
-            let entry = entry?;
-            let entry_path = entry.path();
-
-            if entry_path.is_dir() {
-                // Convert to owned string to avoid borrowing issues
-                let subdir_str = entry_path.to_string_lossy().into_owned();
-                cleaned += Self::clean_directory_recursive(&subdir_str)?;
-            } else if entry_path.extension().is_some_and(|ext| ext == "pdf") {
-                fs::remove_file(&entry_path)?;
-                cleaned += 1;
-            }
-        }
-
-        Ok(cleaned)
+        Ok(pdfs.len())
     }
 }
 
@@ -232,12 +618,89 @@ pub enum CompilationStatus {
     SourceNotFound,
 }
 
+impl CompilationStatus {
+    /// Check whether a source needs recompiling by comparing modification times.
+    ///
+    /// Returns `true` when the output is missing or older than the source.
+    pub fn needs_recompile(source: &Path, output: &Path) -> bool {
+        let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok();
+
+        match (modified(source), modified(output)) {
+            (Some(source_modified), Some(output_modified)) => source_modified > output_modified,
+            (Some(_), None) => true,
+            _ => false,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::fs::File;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_metadata_input_args_from_header() {
+        let temp_dir = TempDir::new().unwrap();
+        let note = temp_dir.path().join("lecture.typ");
+        fs::write(
+            &note,
+            "#show: dtu-note.with(\n  course: \"02101\",\n  course-name: \"Programming\",\n  \
+             title: \"Loops\",\n  author: \"Ada Lovelace\"\n)\n",
+        )
+        .unwrap();
+        assert_eq!(
+            TypstCompiler::metadata_input_args(&note),
+            vec!["--input", "title=Loops", "--input", "author=Ada Lovelace"]
+        );
+
+        let plain = temp_dir.path().join("scratch.typ");
+        fs::write(&plain, "= Scratch\n").unwrap();
+        assert!(TypstCompiler::metadata_input_args(&plain).is_empty());
+    }
+
+    #[test]
+    fn test_clean_all_courses_reports_per_course() {
+        let temp_dir = TempDir::new().unwrap();
+        let lectures = temp_dir.path().join("02101").join("lectures");
+        let assignments = temp_dir.path().join("01005").join("assignments");
+        fs::create_dir_all(&lectures).unwrap();
+        fs::create_dir_all(&assignments).unwrap();
+
+        File::create(lectures.join("lecture-1.pdf")).unwrap();
+        File::create(lectures.join("lecture-2.pdf")).unwrap();
+        File::create(lectures.join("lecture-1.typ")).unwrap();
+        File::create(lectures.join("lecture-2.typ")).unwrap();
+        File::create(assignments.join("assignment-1.pdf")).unwrap();
+        File::create(assignments.join("assignment-1.typ")).unwrap();
+        // Downloaded slides without a source are kept
+        File::create(lectures.join("slides.pdf")).unwrap();
+        // Not a course directory, left untouched
+        File::create(temp_dir.path().join("overview.pdf")).unwrap();
+
+        let mut config = Config::default();
+        config.paths.notes_dir = temp_dir.path().to_string_lossy().to_string();
+
+        // A dry run lists the same files without removing them
+        let outputs = TypstCompiler::all_compiled_outputs(&config).unwrap();
+        let listed: Vec<(String, usize)> = outputs
+            .iter()
+            .map(|(course_id, files)| (course_id.clone(), files.len()))
+            .collect();
+        assert!(lectures.join("lecture-1.pdf").exists());
+
+        let cleaned = TypstCompiler::clean_all_courses(&config).unwrap();
+        assert_eq!(cleaned, listed);
+
+        assert_eq!(
+            cleaned,
+            vec![("01005".to_string(), 1), ("02101".to_string(), 2)]
+        );
+        assert!(lectures.join("lecture-1.typ").exists());
+        assert!(lectures.join("slides.pdf").exists());
+        assert!(temp_dir.path().join("overview.pdf").exists());
+    }
+
     #[test]
     fn test_resolve_input_path_adds_extension() {
         let temp_dir = TempDir::new().unwrap();
@@ -245,11 +708,29 @@ mod tests {
         File::create(&file_path).unwrap();
 
         let test_path = temp_dir.path().join("test").to_string_lossy().into_owned();
-        let resolved = TypstCompiler::resolve_input_path(&test_path).unwrap();
+        let resolved = TypstCompiler::resolve_input_path(&test_path, &Config::default()).unwrap();
 
         assert_eq!(resolved, file_path);
     }
 
+    #[test]
+    fn test_resolve_input_path_accepts_typst_extension() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("notes.typst");
+        File::create(&file_path).unwrap();
+
+        let config = Config::default();
+        let test_path = temp_dir.path().join("notes").to_string_lossy().into_owned();
+        let resolved = TypstCompiler::resolve_input_path(&test_path, &config).unwrap();
+
+        assert_eq!(resolved, file_path);
+        assert_eq!(
+            TypstCompiler::output_path_for(&resolved, &config),
+            temp_dir.path().join("notes.pdf")
+        );
+        assert!(config.typst.is_source_file(&file_path));
+    }
+
     #[test]
     fn test_resolve_input_path_keeps_extension() {
         let temp_dir = TempDir::new().unwrap();
@@ -257,7 +738,8 @@ mod tests {
         File::create(&file_path).unwrap();
 
         let file_path_str = file_path.to_string_lossy().into_owned();
-        let resolved = TypstCompiler::resolve_input_path(&file_path_str).unwrap();
+        let resolved =
+            TypstCompiler::resolve_input_path(&file_path_str, &Config::default()).unwrap();
 
         assert_eq!(resolved, file_path);
     }
@@ -287,4 +769,224 @@ mod tests {
         assert!(!temp_dir.path().join("test2.pdf").exists());
         assert!(temp_dir.path().join("keep.txt").exists());
     }
+
+    #[test]
+    fn test_clean_current_directory_keeps_unrelated_pdfs() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.typst.output_dir = Some("out".to_string());
+
+        let out = temp_dir.path().join("out");
+        fs::create_dir_all(&out).unwrap();
+        File::create(temp_dir.path().join("lecture.typ")).unwrap();
+        File::create(temp_dir.path().join("summary.typst")).unwrap();
+        File::create(out.join("lecture.pdf")).unwrap();
+        File::create(out.join("summary.pdf")).unwrap();
+        // No source compiles to these, so they aren't ours to remove
+        File::create(temp_dir.path().join("paper.pdf")).unwrap();
+        File::create(out.join("slides.pdf")).unwrap();
+
+        assert_eq!(
+            TypstCompiler::directory_outputs(temp_dir.path(), &config).unwrap(),
+            vec![out.join("lecture.pdf"), out.join("summary.pdf")]
+        );
+        assert_eq!(
+            TypstCompiler::clean_current_directory(temp_dir.path(), &config).unwrap(),
+            2
+        );
+        assert!(!out.join("lecture.pdf").exists());
+        assert!(temp_dir.path().join("paper.pdf").exists());
+        assert!(out.join("slides.pdf").exists());
+    }
+
+    #[test]
+    fn test_pdf_page_count() {
+        let temp_dir = TempDir::new().unwrap();
+        let pdf = temp_dir.path().join("note.pdf");
+        fs::write(
+            &pdf,
+            "%PDF-1.7\n1 0 obj\n<< /Type /Pages /Kids [2 0 R 3 0 R] /Count 2 >>\nendobj\n\
+             2 0 obj\n<</Type/Page/Parent 1 0 R>>\nendobj\n\
+             3 0 obj\n<< /Type /Page /Parent 1 0 R >>\nendobj\n\
+             4 0 obj\n<< /Type /Outlines /Count 5 >>\nendobj\n%%EOF\n",
+        )
+        .unwrap();
+        assert_eq!(TypstCompiler::pdf_page_count(&pdf), Some(2));
+
+        // Not a PDF, or no readable page objects
+        fs::write(&pdf, "= Notes").unwrap();
+        assert_eq!(TypstCompiler::pdf_page_count(&pdf), None);
+        fs::write(&pdf, "%PDF-1.7\n<< /Type /ObjStm >>").unwrap();
+        assert_eq!(TypstCompiler::pdf_page_count(&pdf), None);
+        assert_eq!(
+            TypstCompiler::pdf_page_count(&temp_dir.path().join("missing.pdf")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_output_updated_waits_for_a_new_pdf() {
+        let temp_dir = TempDir::new().unwrap();
+        let pdf = temp_dir.path().join("note.pdf");
+        assert!(!TypstCompiler::output_updated(&pdf, None));
+
+        // First compile writes the PDF
+        File::create(&pdf).unwrap();
+        assert!(TypstCompiler::output_updated(&pdf, None));
+
+        // A PDF from before the watch only counts once it's rewritten
+        let before = TypstCompiler::modified_time(&pdf);
+        assert!(!TypstCompiler::output_updated(&pdf, before));
+        File::options()
+            .write(true)
+            .open(&pdf)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(5))
+            .unwrap();
+        assert!(TypstCompiler::output_updated(&pdf, before));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_supervise_reaps_interrupted_child() {
+        let interrupted = AtomicBool::new(false);
+        let mut child = Command::new("sh").args(["-c", "exit 2"]).spawn().unwrap();
+        let status = TypstCompiler::supervise(&mut child, &interrupted).unwrap();
+        assert_eq!(status.and_then(|status| status.code()), Some(2));
+
+        let mut child = Command::new("sleep").arg("30").spawn().unwrap();
+        interrupted.store(true, Ordering::SeqCst);
+        let started = std::time::Instant::now();
+        assert!(
+            TypstCompiler::supervise(&mut child, &interrupted)
+                .unwrap()
+                .is_none()
+        );
+        assert!(started.elapsed() < Duration::from_secs(5));
+        // Reaped: the exit status is already collected
+        assert!(child.try_wait().unwrap().is_some());
+    }
+
+    #[test]
+    fn test_needs_recompile() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("note.typ");
+        let output = temp_dir.path().join("note.pdf");
+
+        let source_file = File::create(&source).unwrap();
+        assert!(CompilationStatus::needs_recompile(&source, &output));
+
+        let output_file = File::create(&output).unwrap();
+        let now = std::time::SystemTime::now();
+        source_file
+            .set_modified(now - std::time::Duration::from_secs(60))
+            .unwrap();
+        output_file.set_modified(now).unwrap();
+        assert!(!CompilationStatus::needs_recompile(&source, &output));
+
+        source_file
+            .set_modified(now + std::time::Duration::from_secs(60))
+            .unwrap();
+        assert!(CompilationStatus::needs_recompile(&source, &output));
+    }
+
+    #[test]
+    fn test_count_stale_outputs() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = Config::default();
+        let lectures = temp_dir.path().join("lectures");
+        fs::create_dir_all(&lectures).unwrap();
+
+        File::create(lectures.join("a.typ")).unwrap();
+        File::create(lectures.join("b.typ")).unwrap();
+        let compiled = File::create(lectures.join("b.pdf")).unwrap();
+        compiled
+            .set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(60))
+            .unwrap();
+
+        let stale = TypstCompiler::count_stale_outputs(temp_dir.path(), &config).unwrap();
+        assert_eq!(stale, 1);
+    }
+
+    #[test]
+    fn test_uncompiled_sources_respect_output_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.paths.notes_dir = temp_dir.path().to_string_lossy().to_string();
+        config.typst.output_dir = Some("pdfs".to_string());
+
+        let lectures = temp_dir.path().join("02101").join("lectures");
+        let assignments = temp_dir.path().join("02102").join("assignments");
+        fs::create_dir_all(lectures.join("pdfs")).unwrap();
+        fs::create_dir_all(&assignments).unwrap();
+        File::create(lectures.join("a.typ")).unwrap();
+        File::create(lectures.join("b.typ")).unwrap();
+        File::create(assignments.join("set-1.typ")).unwrap();
+
+        // Compiled into the output directory, and a stray PDF next to the source
+        File::create(lectures.join("pdfs").join("b.pdf")).unwrap();
+        File::create(lectures.join("a.pdf")).unwrap();
+
+        let uncompiled = TypstCompiler::uncompiled_sources(&config).unwrap();
+        assert_eq!(
+            uncompiled,
+            vec![
+                ("02101".to_string(), vec![lectures.join("a.typ")]),
+                ("02102".to_string(), vec![assignments.join("set-1.typ")]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compile_args_for_appends_course_args() {
+        let temp_dir = TempDir::new().unwrap();
+        let lectures = temp_dir.path().join("02101").join("lectures");
+        fs::create_dir_all(&lectures).unwrap();
+        let source = lectures.join("note.typ");
+        File::create(&source).unwrap();
+
+        let mut config = Config::default();
+        config.paths.notes_dir = temp_dir.path().to_string_lossy().into_owned();
+        config.typst.compile_args = vec!["--root".to_string(), ".".to_string()];
+        config.typst.course_compile_args.insert(
+            "02101".to_string(),
+            vec!["--input".to_string(), "lang=en".to_string()],
+        );
+
+        assert_eq!(
+            TypstCompiler::detect_course_id(&source, &config).as_deref(),
+            Some("02101")
+        );
+        assert_eq!(
+            TypstCompiler::compile_args_for(&source, &config),
+            vec!["--root", ".", "--input", "lang=en"]
+        );
+
+        let outside = temp_dir.path().join("loose.typ");
+        File::create(&outside).unwrap();
+        assert_eq!(
+            TypstCompiler::compile_args_for(&outside, &config),
+            vec!["--root", "."]
+        );
+    }
+
+    #[test]
+    fn test_font_path_args_skip_missing_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        let fonts = temp_dir.path().to_string_lossy().into_owned();
+        let missing = temp_dir
+            .path()
+            .join("missing")
+            .to_string_lossy()
+            .into_owned();
+
+        let mut config = Config::default();
+        config.typst.font_paths = vec![fonts.clone(), missing.clone()];
+
+        assert_eq!(
+            TypstCompiler::font_path_args(&config),
+            vec!["--font-path".to_string(), fonts]
+        );
+        assert_eq!(TypstCompiler::missing_font_paths(&config), vec![missing]);
+    }
 }
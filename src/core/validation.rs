@@ -2,24 +2,72 @@
 //!
 //! Centralized validation logic for various input types.
 
-use anyhow::Result;
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+use crate::core::calendar_export::CalendarExporter;
+use crate::core::template::context::RubricCriterion;
+use crate::error::{NoterError, NoterResult};
+
+/// Example inputs shown when a due date cannot be parsed
+const DUE_DATE_EXAMPLES: &str = "2025-06-01, 01/06/2025, +7d, +2w, tomorrow, friday, next friday";
 
 pub struct Validator;
 
 #[allow(dead_code)]
 impl Validator {
-    pub fn validate_course_id(course_id: &str) -> Result<()> {
+    pub fn validate_course_id(course_id: &str) -> NoterResult<()> {
         if course_id.len() != 5 {
-            anyhow::bail!("Course ID must be exactly 5 characters long (e.g., 02101)");
+            return Err(NoterError::Validation(
+                "Course ID must be exactly 5 characters long (e.g., 02101)".to_string(),
+            ));
         }
 
         if !course_id.chars().all(|c| c.is_ascii_digit()) {
-            anyhow::bail!("Course ID must contain only digits (e.g., 02101)");
+            return Err(NoterError::Validation(
+                "Course ID must contain only digits (e.g., 02101)".to_string(),
+            ));
         }
 
         Ok(())
     }
 
+    /// Trim a note or assignment title, rejecting titles that leave nothing to
+    /// build a filename from (empty, whitespace or punctuation only)
+    pub fn validate_title(title: &str) -> NoterResult<&str> {
+        let trimmed = title.trim();
+
+        if trimmed.is_empty() {
+            return Err(NoterError::Validation(
+                "Title cannot be empty. Use a non-empty title (e.g., \"Problem Set 1\")"
+                    .to_string(),
+            ));
+        }
+
+        if Self::sanitize_filename(trimmed).is_empty() {
+            return Err(NoterError::Validation(format!(
+                "Title '{}' has no letters or digits to build a filename from. Use a title with some (e.g., \"Problem Set 1\")",
+                trimmed
+            )));
+        }
+
+        Ok(trimmed)
+    }
+
+    /// Check that a template type is one of the `available` template names.
+    /// With no templates available no package is installed, and every type is
+    /// built from the fallback template, so any type is accepted.
+    pub fn validate_template_type(template_type: &str, available: &[String]) -> NoterResult<()> {
+        if available.is_empty() || available.iter().any(|name| name == template_type) {
+            return Ok(());
+        }
+
+        Err(NoterError::Validation(format!(
+            "Unknown template type '{}'. Available types: {}",
+            template_type,
+            available.join(", ")
+        )))
+    }
+
     pub fn sanitize_filename(input: &str) -> String {
         input
             .chars()
@@ -43,12 +91,352 @@ impl Validator {
             .to_string()
     }
 
-    pub fn validate_file_path(path: &str) -> Result<()> {
+    /// Split a comma-separated member list (`"Alice, Bob"`) into names, see
+    /// [`Validator::parse_names`]
+    pub fn parse_members(input: &str) -> NoterResult<Vec<String>> {
+        Self::parse_names(input, "member")
+    }
+
+    /// Split a comma-separated author list (`"Alice, Bob"`) into names, see
+    /// [`Validator::parse_names`]
+    pub fn parse_authors(input: &str) -> NoterResult<Vec<String>> {
+        Self::parse_names(input, "author")
+    }
+
+    /// Split a comma-separated list of names into trimmed names. Every name
+    /// needs a letter or digit, and names may not repeat; `kind` names what
+    /// they are in errors.
+    fn parse_names(input: &str, kind: &str) -> NoterResult<Vec<String>> {
+        let mut names: Vec<String> = Vec::new();
+
+        for name in input.split(',').map(str::trim) {
+            if !name.chars().any(char::is_alphanumeric) {
+                return Err(NoterError::Validation(format!(
+                    "Invalid {} name '{}' in '{}'. Separate names with commas (e.g., \"Alice,Bob\")",
+                    kind, name, input
+                )));
+            }
+
+            if names.iter().any(|listed| listed.eq_ignore_ascii_case(name)) {
+                return Err(NoterError::Validation(format!(
+                    "The {} '{}' is listed more than once",
+                    kind, name
+                )));
+            }
+
+            names.push(name.to_string());
+        }
+
+        Ok(names)
+    }
+
+    /// Parse a comma-separated rubric (`"Correctness:60, Style:40"`) into
+    /// criteria. Every criterion needs a name and a positive whole number of
+    /// points, and names may not repeat.
+    pub fn parse_rubric(input: &str) -> NoterResult<Vec<RubricCriterion>> {
+        let mut criteria: Vec<RubricCriterion> = Vec::new();
+        let mut total: u32 = 0;
+
+        for entry in input.split(',').map(str::trim) {
+            let invalid = || {
+                NoterError::Validation(format!(
+                    "Invalid rubric criterion '{}'. Give each as name:points (e.g., \"Correctness:60,Style:40\")",
+                    entry
+                ))
+            };
+
+            let (name, points) = entry.rsplit_once(':').ok_or_else(invalid)?;
+            let name = name.trim();
+            let points: u32 = points.trim().parse().map_err(|_| invalid())?;
+            if points == 0 || !name.chars().any(char::is_alphanumeric) {
+                return Err(invalid());
+            }
+
+            if criteria
+                .iter()
+                .any(|criterion| criterion.name.eq_ignore_ascii_case(name))
+            {
+                return Err(NoterError::Validation(format!(
+                    "Rubric criterion '{}' is listed more than once",
+                    name
+                )));
+            }
+
+            total = total.checked_add(points).ok_or_else(|| {
+                NoterError::Validation(format!("Rubric points add up to more than {}", u32::MAX))
+            })?;
+            criteria.push(RubricCriterion {
+                name: name.to_string(),
+                points,
+            });
+        }
+
+        Ok(criteria)
+    }
+
+    /// Shorten a sanitized filename part to at most `max_len` characters. The
+    /// tail is replaced with a short hash of the whole name, so distinct long
+    /// names stay distinct. Names within the limit, and `max_len` 0, are left
+    /// unchanged.
+    pub fn truncate_filename(name: &str, max_len: usize) -> String {
+        if max_len == 0 || name.chars().count() <= max_len {
+            return name.to_string();
+        }
+
+        // FNV-1a, so the suffix is stable across runs and toolchains
+        let hash = name.bytes().fold(0x811c9dc5u32, |hash, byte| {
+            (hash ^ byte as u32).wrapping_mul(0x01000193)
+        });
+        let suffix = format!("{:08x}", hash);
+
+        let keep = max_len.saturating_sub(suffix.len() + 1);
+        let head: String = name.chars().take(keep).collect();
+        let head = head.trim_end_matches(['-', '_']);
+
+        if head.is_empty() {
+            suffix
+        } else {
+            format!("{}-{}", head, suffix)
+        }
+    }
+
+    /// Parse a due date given on the command line: an absolute date (`2025-06-01`),
+    /// an offset from `today` (`+7d`, `+2w`), `today`/`tomorrow`, or a weekday
+    /// (`friday`, `next friday`), which means its next occurrence after today
+    pub fn parse_due_date(input: &str, today: NaiveDate) -> NoterResult<NaiveDate> {
+        let normalized = input.trim().to_lowercase();
+        let invalid = || {
+            NoterError::Validation(format!(
+                "Invalid due date '{}'. Examples: {}",
+                input.trim(),
+                DUE_DATE_EXAMPLES
+            ))
+        };
+
+        if let Some(offset) = normalized.strip_prefix('+') {
+            // The unit is the last character, which need not be a single byte
+            let unit_start = offset.char_indices().last().map_or(0, |(index, _)| index);
+            let (count, unit) = offset.split_at(unit_start);
+            let count: i64 = count.trim().parse().map_err(|_| invalid())?;
+            if count < 0 {
+                return Err(invalid());
+            }
+            let days = match unit {
+                "d" => Some(count),
+                "w" => count.checked_mul(7),
+                _ => None,
+            };
+            return days
+                .and_then(Duration::try_days)
+                .and_then(|offset| today.checked_add_signed(offset))
+                .ok_or_else(invalid);
+        }
+
+        match normalized.as_str() {
+            "today" => return Ok(today),
+            "tomorrow" => return Ok(today + Duration::days(1)),
+            _ => {}
+        }
+
+        let weekday_name = normalized.strip_prefix("next ").unwrap_or(&normalized);
+        if let Ok(weekday) = weekday_name.trim().parse::<Weekday>() {
+            let days_ahead =
+                (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+            let days_ahead = if days_ahead == 0 { 7 } else { days_ahead };
+            return Ok(today + Duration::days(i64::from(days_ahead)));
+        }
+
+        CalendarExporter::parse_date(&normalized).ok_or_else(invalid)
+    }
+
+    /// Parse a time window such as `14d`, `2w` or `12h` (hours, days or weeks)
+    pub fn parse_duration(input: &str) -> NoterResult<Duration> {
+        let normalized = input.trim().to_lowercase();
+        let invalid = || {
+            NoterError::Validation(format!(
+                "Invalid duration '{}'. Examples: 12h, 14d, 2w",
+                input.trim()
+            ))
+        };
+
+        // The unit is the last character, which need not be a single byte
+        let unit_start = normalized
+            .char_indices()
+            .last()
+            .map_or(0, |(index, _)| index);
+        let (count, unit) = normalized.split_at(unit_start);
+        let count: i64 = count.trim().parse().map_err(|_| invalid())?;
+        if count < 0 {
+            return Err(invalid());
+        }
+
+        match unit {
+            "h" => Duration::try_hours(count),
+            "d" => Duration::try_days(count),
+            "w" => Duration::try_weeks(count),
+            _ => None,
+        }
+        .ok_or_else(invalid)
+    }
+
+    pub fn validate_file_path(path: &str) -> NoterResult<()> {
         if path.is_empty() {
-            anyhow::bail!("File path cannot be empty");
+            return Err(NoterError::Validation(
+                "File path cannot be empty".to_string(),
+            ));
         }
 
         // Add more path validation as needed
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(
+            Validator::parse_duration("14d").unwrap(),
+            Duration::days(14)
+        );
+        assert_eq!(
+            Validator::parse_duration(" 2W ").unwrap(),
+            Duration::weeks(2)
+        );
+        assert_eq!(
+            Validator::parse_duration("12h").unwrap(),
+            Duration::hours(12)
+        );
+
+        for invalid in ["", "d", "14", "-3d", "3m", "14é", "é", "two weeks"] {
+            assert!(Validator::parse_duration(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_truncate_filename_stays_within_limit_and_unique() {
+        let long_title = "a".repeat(299);
+        let first = Validator::truncate_filename(&format!("{}x", long_title), 60);
+        let second = Validator::truncate_filename(&format!("{}y", long_title), 60);
+
+        assert_eq!(first.len(), 60);
+        assert_eq!(second.len(), 60);
+        assert_ne!(first, second);
+        // Truncation is deterministic
+        assert_eq!(
+            first,
+            Validator::truncate_filename(&format!("{}x", long_title), 60)
+        );
+
+        assert_eq!(
+            Validator::truncate_filename("short-title", 60),
+            "short-title"
+        );
+        assert_eq!(Validator::truncate_filename(&long_title, 0), long_title);
+    }
+
+    #[test]
+    fn test_validate_template_type() {
+        let available = vec!["lecture".to_string(), "reading".to_string()];
+        assert!(Validator::validate_template_type("reading", &available).is_ok());
+
+        let error = Validator::validate_template_type("summary", &available).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Available types: lecture, reading")
+        );
+        assert!(Validator::validate_template_type("lecture", &[]).is_ok());
+    }
+
+    #[test]
+    fn test_parse_members() {
+        assert_eq!(
+            Validator::parse_members(" Alice,Bob , Søren Ø. ").unwrap(),
+            vec!["Alice", "Bob", "Søren Ø."]
+        );
+
+        for invalid in ["", "Alice,,Bob", "Alice, ---", "Alice,alice"] {
+            assert!(Validator::parse_members(invalid).is_err(), "{:?}", invalid);
+            // Authors follow the same rules
+            let error = Validator::parse_authors(invalid).unwrap_err();
+            assert!(error.to_string().contains("author"), "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_parse_rubric() {
+        let rubric = Validator::parse_rubric("Correctness:60, \"Style\": 30").unwrap();
+        assert_eq!(rubric.len(), 2);
+        assert_eq!(
+            (rubric[1].name.as_str(), rubric[1].points),
+            ("\"Style\"", 30)
+        );
+
+        for invalid in [
+            "Correctness",
+            "Correctness:0",
+            "Style:-5",
+            ":40",
+            "A:50,a:50",
+            "A:4294967295,B:1",
+        ] {
+            let error = Validator::parse_rubric(invalid).unwrap_err();
+            assert!(matches!(error, NoterError::Validation(_)), "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_validate_title() {
+        assert_eq!(
+            Validator::validate_title("  Problem Set 1 ").unwrap(),
+            "Problem Set 1"
+        );
+        assert_eq!(Validator::validate_title("Øvelse").unwrap(), "Øvelse");
+
+        for invalid in ["", "   ", "\t\n", "###", " .,; "] {
+            let error = Validator::validate_title(invalid).unwrap_err();
+            assert!(matches!(error, NoterError::Validation(_)), "{:?}", invalid);
+            assert!(error.to_string().contains("Problem Set 1"), "{:?}", invalid);
+            // These would all have produced an empty filename
+            assert!(Validator::sanitize_filename(invalid).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_parse_due_date() {
+        // Wednesday
+        let today = NaiveDate::from_ymd_opt(2025, 3, 12).unwrap();
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let parse = |input| Validator::parse_due_date(input, today).unwrap();
+
+        assert_eq!(parse("2025-06-01"), date(2025, 6, 1));
+        assert_eq!(parse("01/06/2025"), date(2025, 6, 1));
+        assert_eq!(parse("+7d"), date(2025, 3, 19));
+        assert_eq!(parse("+2W"), date(2025, 3, 26));
+        assert_eq!(parse("tomorrow"), date(2025, 3, 13));
+        assert_eq!(parse("friday"), date(2025, 3, 14));
+        assert_eq!(parse("Next Fri"), date(2025, 3, 14));
+        // The same weekday means a week from today
+        assert_eq!(parse("next wednesday"), date(2025, 3, 19));
+
+        for invalid in [
+            "",
+            "+7",
+            "+xd",
+            "+3m",
+            "+7é",
+            "+é",
+            "+-3d",
+            "+99999999999d",
+            "+999999999999999w",
+            "someday",
+            "2025-13-01",
+        ] {
+            let error = Validator::parse_due_date(invalid, today).unwrap_err();
+            assert!(error.to_string().contains("Examples:"), "{}", invalid);
+        }
+    }
+}
@@ -0,0 +1,339 @@
+//! Obsidian course indices
+//!
+//! Each course gets an index note at `<obsidian_dir>/courses/<id>-<name>.md`.
+//! The vault hub, `courses/_index.md`, links to every course index grouped by
+//! semester. The links live between two marker comments, so refreshing the hub
+//! replaces them and leaves anything written around them alone.
+
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::core::status_manager::StatusManager;
+
+/// Directory in the Obsidian vault that holds the course indices
+pub const COURSES_DIR: &str = "courses";
+
+/// File name of the vault hub inside [`COURSES_DIR`]
+pub const HUB_FILE: &str = "_index.md";
+
+const HUB_START: &str = "<!-- noter:courses:start -->";
+const HUB_END: &str = "<!-- noter:courses:end -->";
+
+/// Line of a course index that records its semester
+const SEMESTER_PREFIX: &str = "- **Semester**:";
+
+/// A course linked from the vault hub
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HubEntry {
+    pub course_id: String,
+    pub course_name: String,
+    pub semester: String,
+}
+
+/// Result of refreshing every course index and the hub
+#[derive(Debug, Clone)]
+pub struct VaultRefresh {
+    /// Course indices that didn't exist yet and were created
+    pub created: Vec<PathBuf>,
+    pub hub_path: PathBuf,
+    pub courses: usize,
+}
+
+pub struct VaultIndex;
+
+#[allow(dead_code)]
+impl VaultIndex {
+    pub fn courses_dir(config: &Config) -> PathBuf {
+        config.get_obsidian_dir_path().join(COURSES_DIR)
+    }
+
+    /// Index note for a course, `<id>-<name>.md`
+    pub fn course_index_path(config: &Config, course_id: &str, course_name: &str) -> PathBuf {
+        Self::courses_dir(config).join(Self::index_file_name(course_id, course_name))
+    }
+
+    /// Create every configured course's missing index and rewrite the hub's links
+    pub fn refresh_all(config: &Config) -> Result<VaultRefresh> {
+        let courses_dir = Self::courses_dir(config);
+        fs::create_dir_all(&courses_dir)?;
+
+        let current_semester = StatusManager::get_current_semester(config);
+        let mut created = Vec::new();
+        let mut entries = Vec::new();
+
+        for (course_id, course_name) in config.list_courses() {
+            let index_path = Self::course_index_path(config, &course_id, &course_name);
+            let semester = if index_path.exists() {
+                Self::read_semester(&index_path).unwrap_or_else(|| current_semester.clone())
+            } else {
+                fs::write(
+                    &index_path,
+                    Self::course_index_content(&course_id, &course_name, &current_semester),
+                )?;
+                created.push(index_path);
+                current_semester.clone()
+            };
+
+            entries.push(HubEntry {
+                course_id,
+                course_name,
+                semester,
+            });
+        }
+
+        let hub_path = courses_dir.join(HUB_FILE);
+        let existing = fs::read_to_string(&hub_path).ok();
+        let links = Self::hub_links(&entries, &current_semester, config);
+        fs::write(&hub_path, Self::hub_content(existing.as_deref(), &links))?;
+
+        Ok(VaultRefresh {
+            created,
+            hub_path,
+            courses: entries.len(),
+        })
+    }
+
+    /// Starting content of a course index note
+    pub fn course_index_content(course_id: &str, course_name: &str, semester: &str) -> String {
+        format!(
+            r#"# {} - {}
+
+## Course Information
+- **Course Code**: {}
+- **Semester**: {}
+- **University**: Technical University of Denmark (DTU)
+- **Professor**:
+- **Credits**:
+
+## Recent Lectures
+
+## Key Topics
+
+## Assignments
+
+## Connections to Other Courses
+
+## Questions & Review Points
+
+## Resources
+- Textbook:
+- Course website:
+- Office hours:
+
+"#,
+            course_id, course_name, course_id, semester
+        )
+    }
+
+    /// Semester recorded in a course index, if it still has the line
+    pub fn read_semester(index_path: &Path) -> Option<String> {
+        fs::read_to_string(index_path)
+            .ok()?
+            .lines()
+            .find_map(|line| line.trim().strip_prefix(SEMESTER_PREFIX))
+            .map(str::trim)
+            .filter(|semester| !semester.is_empty())
+            .map(str::to_string)
+    }
+
+    /// Links to the course indices under a heading per semester. The current
+    /// semester comes first, then the others newest first by the configured
+    /// semester format, and any that don't parse last; courses are ordered by
+    /// ID.
+    pub fn hub_links(entries: &[HubEntry], current_semester: &str, config: &Config) -> String {
+        let mut by_semester: BTreeMap<&str, Vec<&HubEntry>> = BTreeMap::new();
+        for entry in entries {
+            by_semester.entry(&entry.semester).or_default().push(entry);
+        }
+
+        let mut semesters: Vec<&str> = by_semester.keys().copied().collect();
+        semesters.sort_by_key(|semester| {
+            let parsed = config
+                .parse_semester(semester)
+                .map(|(year, is_spring)| (year, !is_spring));
+            (
+                *semester != current_semester,
+                parsed.is_none(),
+                std::cmp::Reverse(parsed),
+                std::cmp::Reverse(*semester),
+            )
+        });
+
+        let mut links = String::new();
+        for semester in semesters {
+            let mut courses = by_semester[semester].clone();
+            courses.sort_by(|a, b| a.course_id.cmp(&b.course_id));
+
+            links.push_str(&format!("## {}\n\n", semester));
+            for course in courses {
+                links.push_str(&format!(
+                    "- {}\n",
+                    Self::link(course, &config.obsidian_integration.link_format)
+                ));
+            }
+            links.push('\n');
+        }
+
+        links
+    }
+
+    /// The hub with `links` between its markers. A hub without markers, or no
+    /// hub yet, gets a fresh file with a title.
+    pub fn hub_content(existing: Option<&str>, links: &str) -> String {
+        let region = format!("{}\n{}{}", HUB_START, links, HUB_END);
+
+        if let Some(existing) = existing {
+            if let (Some(start), Some(end)) = (existing.find(HUB_START), existing.find(HUB_END)) {
+                if start < end {
+                    return format!(
+                        "{}{}{}",
+                        &existing[..start],
+                        region,
+                        &existing[end + HUB_END.len()..]
+                    );
+                }
+            }
+        }
+
+        format!("# Courses\n\n{}\n", region)
+    }
+
+    fn index_file_name(course_id: &str, course_name: &str) -> String {
+        format!("{}-{}.md", course_id, course_name)
+    }
+
+    /// Wiki link by default, a Markdown link when `link_format` is "markdown"
+    fn link(entry: &HubEntry, link_format: &str) -> String {
+        let file_name = Self::index_file_name(&entry.course_id, &entry.course_name);
+        let label = format!("{} - {}", entry.course_id, entry.course_name);
+
+        if link_format.eq_ignore_ascii_case("markdown") {
+            format!("[{}]({})", label, file_name.replace(' ', "%20"))
+        } else {
+            format!("[[{}|{}]]", file_name.trim_end_matches(".md"), label)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SemesterFormat;
+
+    fn entry(course_id: &str, course_name: &str, semester: &str) -> HubEntry {
+        HubEntry {
+            course_id: course_id.to_string(),
+            course_name: course_name.to_string(),
+            semester: semester.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_hub_groups_by_semester_and_refreshes_in_place() {
+        let entries = vec![
+            entry("02105", "Algorithms", "2025 Fall"),
+            entry("02102", "Programming", "2026 Fall"),
+            entry("02101", "Intro to Programming", "2026 Fall"),
+        ];
+        let mut config = Config::default();
+        let links = VaultIndex::hub_links(&entries, "2026 Fall", &config);
+        assert_eq!(
+            links,
+            "## 2026 Fall\n\n\
+             - [[02101-Intro to Programming|02101 - Intro to Programming]]\n\
+             - [[02102-Programming|02102 - Programming]]\n\n\
+             ## 2025 Fall\n\n\
+             - [[02105-Algorithms|02105 - Algorithms]]\n\n"
+        );
+        config.obsidian_integration.link_format = "markdown".to_string();
+        assert!(
+            VaultIndex::hub_links(&entries[..1], "2026 Fall", &config)
+                .contains("[02105 - Algorithms](02105-Algorithms.md)")
+        );
+
+        // Refreshing replaces the links and keeps what's around them
+        let hub = VaultIndex::hub_content(None, &links);
+        let edited = format!("{}\nMy own notes\n", hub.replace("# Courses", "# DTU"));
+        let refreshed = VaultIndex::hub_content(Some(&edited), "## 2026 Fall\n\n");
+        assert_eq!(
+            refreshed,
+            format!(
+                "# DTU\n\n{}\n## 2026 Fall\n\n{}\n\nMy own notes\n",
+                HUB_START, HUB_END
+            )
+        );
+        assert_eq!(refreshed.matches(HUB_START).count(), 1);
+    }
+
+    /// Semester headings of the hub for one course per semester
+    fn hub_semesters(config: &Config, semesters: &[&str], current: &str) -> Vec<String> {
+        let entries: Vec<HubEntry> = semesters
+            .iter()
+            .enumerate()
+            .map(|(i, semester)| entry(&format!("0210{}", i), "Course", semester))
+            .collect();
+        VaultIndex::hub_links(&entries, current, config)
+            .lines()
+            .filter_map(|line| line.strip_prefix("## "))
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_hub_orders_year_season_semesters_newest_first() {
+        let config = Config::default();
+        assert_eq!(
+            hub_semesters(
+                &config,
+                &[
+                    "2024 Fall",
+                    "2025 Spring",
+                    "Old notes",
+                    "2024 Spring",
+                    "2025 Fall"
+                ],
+                "2025 Spring"
+            ),
+            vec![
+                "2025 Spring",
+                "2025 Fall",
+                "2024 Fall",
+                "2024 Spring",
+                "Old notes"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hub_orders_season_year_semesters_newest_first() {
+        let config = Config {
+            semester_format: SemesterFormat::SeasonYear,
+            ..Config::default()
+        };
+        // Alphabetically "Spring 2024" would come before "Fall 2025"
+        assert_eq!(
+            hub_semesters(
+                &config,
+                &["Spring 2024", "Fall 2025", "Fall 2024", "Spring 2026"],
+                "Spring 2026"
+            ),
+            vec!["Spring 2026", "Fall 2025", "Fall 2024", "Spring 2024"]
+        );
+    }
+
+    #[test]
+    fn test_hub_orders_short_form_semesters_newest_first() {
+        let config = Config {
+            semester_format: SemesterFormat::ShortForm,
+            ..Config::default()
+        };
+        // Alphabetically every "S" semester would come before every "F" one
+        assert_eq!(
+            hub_semesters(&config, &["F24", "S25", "S24", "F25"], "F25"),
+            vec!["F25", "S25", "F24", "S24"]
+        );
+    }
+}
@@ -0,0 +1,194 @@
+//! Weekly activity summary
+//!
+//! Groups the notes and assignments modified during the current week by day
+//! and course. The week starts on the configured `week_start` day (Monday by
+//! default).
+
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Weekday};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::config::Config;
+use crate::core::directory_scanner::DirectoryScanner;
+
+/// Notes and assignments changed in one course on one day
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CourseDayCounts {
+    pub notes: usize,
+    pub assignments: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct DaySummary {
+    pub date: NaiveDate,
+    /// Counts keyed by course ID
+    pub courses: BTreeMap<String, CourseDayCounts>,
+}
+
+#[derive(Debug, Clone)]
+pub struct WeekSummary {
+    /// First day of the week
+    pub start: NaiveDate,
+    /// Last day of the week (inclusive)
+    pub end: NaiveDate,
+    /// One entry per day of the week, in order
+    pub days: Vec<DaySummary>,
+}
+
+impl WeekSummary {
+    pub fn total_notes(&self) -> usize {
+        self.counts().map(|counts| counts.notes).sum()
+    }
+
+    pub fn total_assignments(&self) -> usize {
+        self.counts().map(|counts| counts.assignments).sum()
+    }
+
+    /// Number of distinct courses with activity this week
+    pub fn active_courses(&self) -> usize {
+        let mut courses: Vec<&String> = self
+            .days
+            .iter()
+            .flat_map(|day| day.courses.keys())
+            .collect();
+        courses.sort();
+        courses.dedup();
+        courses.len()
+    }
+
+    fn counts(&self) -> impl Iterator<Item = &CourseDayCounts> {
+        self.days.iter().flat_map(|day| day.courses.values())
+    }
+}
+
+pub struct WeeklySummary;
+
+#[allow(dead_code)]
+impl WeeklySummary {
+    /// First and last day of the week containing `today`
+    pub fn week_bounds(today: NaiveDate, week_start: Weekday) -> (NaiveDate, NaiveDate) {
+        let start = today.week(week_start).first_day();
+        (start, start + Duration::days(6))
+    }
+
+    /// Summarize activity in every course during the week containing `today`
+    pub fn collect(config: &Config, today: NaiveDate) -> Result<WeekSummary> {
+        let (start, end) = Self::week_bounds(today, config.week_start);
+        let mut days: Vec<DaySummary> = (0..7)
+            .map(|offset| DaySummary {
+                date: start + Duration::days(offset),
+                courses: BTreeMap::new(),
+            })
+            .collect();
+
+        let notes_dir = Path::new(&config.paths.notes_dir);
+        if !notes_dir.exists() {
+            return Ok(WeekSummary { start, end, days });
+        }
+
+        let extensions = config.typst.source_extensions();
+        let flat_layout = config.paths.flat_layout;
+        let follow_symlinks = config.paths.follow_symlinks;
+        for (course_id, _) in DirectoryScanner::scan_notes_directory(
+            notes_dir,
+            &extensions,
+            flat_layout,
+            follow_symlinks,
+        )? {
+            let course_dir = notes_dir.join(&course_id);
+            let notes = DirectoryScanner::scan_course_notes(
+                &course_dir,
+                &extensions,
+                flat_layout,
+                follow_symlinks,
+            )?;
+            let assignments = DirectoryScanner::scan_course_assignments(
+                &course_dir,
+                &extensions,
+                follow_symlinks,
+            )?;
+
+            let files = notes
+                .into_iter()
+                .map(|file| (file, false))
+                .chain(assignments.into_iter().map(|file| (file, true)));
+            for (file, is_assignment) in files {
+                let date = DateTime::<Local>::from(file.modified).date_naive();
+                if date < start || date > end {
+                    continue;
+                }
+
+                let day = &mut days[(date - start).num_days() as usize];
+                let counts = day.courses.entry(course_id.clone()).or_default();
+                if is_assignment {
+                    counts.assignments += 1;
+                } else {
+                    counts.notes += 1;
+                }
+            }
+        }
+
+        Ok(WeekSummary { start, end, days })
+    }
+
+    /// Short day label such as "Mon 13.10"
+    pub fn day_label(date: NaiveDate) -> String {
+        format!("{} {:02}.{:02}", date.weekday(), date.day(), date.month())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_week_bounds() {
+        // Wednesday 2025-03-12
+        let today = NaiveDate::from_ymd_opt(2025, 3, 12).unwrap();
+
+        let (start, end) = WeeklySummary::week_bounds(today, Weekday::Mon);
+        assert_eq!(start, NaiveDate::from_ymd_opt(2025, 3, 10).unwrap());
+        assert_eq!(end, NaiveDate::from_ymd_opt(2025, 3, 16).unwrap());
+
+        let (start, end) = WeeklySummary::week_bounds(today, Weekday::Sun);
+        assert_eq!(start, NaiveDate::from_ymd_opt(2025, 3, 9).unwrap());
+        assert_eq!(end, NaiveDate::from_ymd_opt(2025, 3, 15).unwrap());
+
+        // The start day itself belongs to its own week
+        let (start, _) = WeeklySummary::week_bounds(start, Weekday::Sun);
+        assert_eq!(start, NaiveDate::from_ymd_opt(2025, 3, 9).unwrap());
+    }
+
+    #[test]
+    fn test_collect_groups_by_day_and_course() {
+        let temp_dir = TempDir::new().unwrap();
+        let lectures = temp_dir.path().join("02101").join("lectures");
+        let assignments = temp_dir.path().join("02101").join("assignments");
+        fs::create_dir_all(&lectures).unwrap();
+        fs::create_dir_all(&assignments).unwrap();
+        fs::write(lectures.join("lecture-1.typ"), "= Lecture 1").unwrap();
+        fs::write(lectures.join("lecture-2.typ"), "= Lecture 2").unwrap();
+        fs::write(assignments.join("assignment-1.typ"), "= Assignment 1").unwrap();
+
+        let mut config = Config::default();
+        config.paths.notes_dir = temp_dir.path().to_string_lossy().to_string();
+
+        let today = Local::now().date_naive();
+        let summary = WeeklySummary::collect(&config, today).unwrap();
+
+        let day = &summary.days[(today - summary.start).num_days() as usize];
+        assert_eq!(
+            day.courses["02101"],
+            CourseDayCounts {
+                notes: 2,
+                assignments: 1
+            }
+        );
+        assert_eq!(summary.total_notes(), 2);
+        assert_eq!(summary.total_assignments(), 1);
+        assert_eq!(summary.active_courses(), 1);
+    }
+}
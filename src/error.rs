@@ -0,0 +1,67 @@
+//! Library error type
+//!
+//! Core modules return [`NoterError`] so library consumers can match on the
+//! failure category (template not found, network, validation, ...). The CLI keeps
+//! using `anyhow` at the top level; `anyhow::Error: From<NoterError>` means `?`
+//! works unchanged, and the original variant can be recovered with
+//! `anyhow::Error::downcast_ref::<NoterError>()`.
+
+use thiserror::Error;
+
+/// Result type returned by core modules
+pub type NoterResult<T> = std::result::Result<T, NoterError>;
+
+#[derive(Debug, Error)]
+pub enum NoterError {
+    /// File system failure
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    /// The configuration is missing or invalid
+    #[error("Configuration error: {0}")]
+    Config(String),
+
+    /// A template was not found in the loaded template configuration
+    #[error("Template '{0}' not found")]
+    TemplateNotFound(String),
+
+    /// A template variant was not found for a template
+    #[error("Variant '{variant}' not found for template '{template}'")]
+    VariantNotFound { template: String, variant: String },
+
+    /// A remote request (e.g. to GitHub) failed
+    #[error("Network error: {0}")]
+    Network(String),
+
+    /// User input failed validation
+    #[error("{0}")]
+    Validation(String),
+
+    /// Typst reported a compilation failure
+    #[error("Typst compilation failed: {0}")]
+    Compilation(String),
+
+    /// Serialization or deserialization failed
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    /// Any other failure from code that still uses `anyhow`
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anyhow_round_trip_preserves_variant() {
+        let error: anyhow::Error = NoterError::TemplateNotFound("lecture".to_string()).into();
+
+        assert_eq!(error.to_string(), "Template 'lecture' not found");
+        assert!(matches!(
+            error.downcast_ref::<NoterError>(),
+            Some(NoterError::TemplateNotFound(name)) if name == "lecture"
+        ));
+    }
+}
@@ -17,30 +17,23 @@
 //!
 //! ### Basic Template Generation
 //!
-//! ```rust
-//! use dtu_notes::core::template_engine::{TemplateEngine, TemplateType};
-//! use dtu_notes::config::Config;
+//! ```no_run
+//! use noter::core::template::engine::TemplateEngine;
+//! use noter::config::Config;
 //!
 //! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! let config = Config::default();
 //!
 //! // Generate a lecture template
 //! let lecture = TemplateEngine::generate_lecture_template("02101", &config, None)?;
-//!
-//! // Generate an assignment template
-//! let assignment = TemplateEngine::generate_assignment_template(
-//!     "02101",
-//!     "Problem Set 1",
-//!     &config
-//! )?;
 //! # Ok(())
 //! # }
 //! ```
 //!
 //! ### Configuration Management
 //!
-//! ```rust
-//! use dtu_notes::config::get_config;
+//! ```no_run
+//! use noter::config::get_config;
 //!
 //! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! // Load configuration
@@ -55,18 +48,19 @@
 //!
 //! ### Template Builder Pattern
 //!
-//! ```rust
-//! use dtu_notes::core::template_engine::{TemplateBuilder, TemplateType};
-//! use dtu_notes::config::Config;
+//! ```no_run
+//! use noter::core::template::builder::TemplateBuilder;
+//! use noter::core::template::engine::TemplateReference;
+//! use noter::config::Config;
 //!
 //! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! let config = Config::default();
 //!
-//! let (content, filename) = TemplateBuilder::new("02101", &config)?
+//! let content = TemplateBuilder::new("02101", &config)?
 //!     .with_title("Advanced Topics")
-//!     .with_type(TemplateType::Custom("research".to_string()))
+//!     .with_reference(TemplateReference::assignment())
 //!     .with_sections(vec!["Methodology".to_string(), "Results".to_string()])
-//!     .build_with_filename()?;
+//!     .build()?;
 //! # Ok(())
 //! # }
 //! ```
@@ -75,19 +69,25 @@
 //!
 //! - [`config`] - Configuration management and serialization
 //! - [`core`] - Core business logic modules
-//!   - [`core::template_engine`] - Template generation and management
+//!   - [`core::template`] - Template generation and management
 //!   - [`core::status_manager`] - System status and health monitoring
+//!   - [`core::recommendations`] - Ranked suggestions for the health and status views
 //!   - [`core::typst_compiler`] - Typst compilation and file watching
 //!   - [`core::file_operations`] - Safe file operations
 //!   - [`core::github_template_fetcher`] - Template repository management
+//!   - [`core::template_fetcher`] - Pluggable template sources
+//! - [`commands`] - Command routing, including [`commands::registry`] for custom commands
 //! - [`ui`] - User interface components
 //! - [`data`] - Static data and course information
+//! - [`error`] - The [`NoterError`] type returned by core modules
 //!
 //! ## Error Handling
 //!
-//! All functions return `Result<T, anyhow::Error>` for comprehensive error handling
-//! with contextual information. Errors can be easily converted to other error types
-//! or displayed to users with helpful context.
+//! Core APIs such as validation, template rendering, Typst compilation and
+//! release lookups return [`NoterResult<T>`], so failures can be matched by
+//! category. Everything else returns `Result<T, anyhow::Error>`; since
+//! `anyhow::Error: From<NoterError>`, both mix freely with `?`, and a wrapped
+//! `NoterError` can be recovered with `downcast_ref`.
 //!
 //! ## Thread Safety
 //!
@@ -99,10 +99,13 @@ pub mod commands;
 pub mod config;
 pub mod core;
 pub mod data;
+pub mod error;
 pub mod ui;
 
 use clap::Subcommand;
 
+use crate::config::ConflictPolicy;
+
 #[derive(Subcommand)]
 pub enum Commands {
     /// Create a new lecture note
@@ -115,6 +118,11 @@ pub enum Commands {
         #[arg(short, long)]
         title: Option<String>,
 
+        /// Template type (lecture, reading, summary, ...; default: the course's
+        /// `note_preferences.course_note_types` entry, else lecture)
+        #[arg(long = "type", value_name = "TYPE")]
+        template_type: Option<String>,
+
         /// Template variant to use (e.g., math, programming)
         #[arg(short, long)]
         variant: Option<String>,
@@ -126,6 +134,26 @@ pub enum Commands {
         /// Skip auto opening for file
         #[arg(long)]
         no_open: bool,
+
+        /// Print the template context as JSON to stderr before rendering
+        #[arg(long)]
+        dump_context: bool,
+
+        /// Use the clipboard text as the note body; top-level bullets become headings
+        #[arg(long, conflicts_with_all = ["from_file", "sections"])]
+        from_clipboard: bool,
+
+        /// Use the text of a file as the note body; top-level bullets become headings
+        #[arg(long, value_name = "PATH", conflicts_with = "sections")]
+        from_file: Option<String>,
+
+        /// Create N placeholder notes for upcoming lectures, numbered or a week apart
+        #[arg(
+            long,
+            value_name = "N",
+            conflicts_with_all = ["title", "dump_context", "from_clipboard", "from_file"]
+        )]
+        batch: Option<u32>,
     },
     /// Create a new assignment
     #[command(alias = "a")]
@@ -134,21 +162,63 @@ pub enum Commands {
         course_id: String,
         /// Assignment title
         title: String,
+        /// Due date: 2025-06-01, +7d, +2w, tomorrow or next friday. Defaults to
+        /// `note_preferences.default_due_days` from today
+        #[arg(long)]
+        due: Option<String>,
+        /// Print the template context as JSON to stderr before rendering
+        #[arg(long)]
+        dump_context: bool,
+        /// What to do when the assignment file already exists. Defaults to
+        /// `note_preferences.on_conflict`
+        #[arg(long, value_enum)]
+        on_conflict: Option<ConflictPolicy>,
+        /// Comma-separated authors of a group submission (e.g., "Alice,Bob").
+        /// Defaults to the configured author
+        #[arg(long)]
+        authors: Option<String>,
+        /// Grading rubric as comma-separated criterion:points pairs (e.g.,
+        /// "Correctness:60,Style:40"), added as a table in a Rubric section
+        #[arg(long)]
+        rubric: Option<String>,
+    },
+    /// Create group project notes listing the collaborators
+    Group {
+        /// Course code (e.g., 02223)
+        course_id: String,
+        /// Project title
+        title: String,
+        /// Comma-separated group members (e.g., "Alice,Bob")
+        #[arg(short, long)]
+        members: String,
+        /// Skip auto opening for file
+        #[arg(long)]
+        no_open: bool,
     },
     /// Compile a Typst file to PDF
     #[command(alias = "c")]
     Compile {
         /// Path to the .typ file (with or without extension)
-        filepath: String,
+        #[arg(required_unless_present = "stdin")]
+        filepath: Option<String>,
         /// Check compilation status before compiling
-        #[arg(long)]
+        #[arg(long, conflicts_with = "stdin")]
         check_status: bool,
+        /// Read Typst content from stdin instead of a file (for editor integrations)
+        #[arg(long, conflicts_with = "filepath", requires = "output")]
+        stdin: bool,
+        /// Output PDF path (used with --stdin)
+        #[arg(short, long, conflicts_with = "filepath")]
+        output: Option<String>,
     },
     /// Watch and auto-compile a Typst file
     #[command(alias = "w")]
     Watch {
         /// Path to the .typ file (with or without extension)
         filepath: String,
+        /// Open the PDF once the first compile succeeds
+        #[arg(long)]
+        open: bool,
     },
     /// Check compilation status of files
     Check {
@@ -157,12 +227,32 @@ pub enum Commands {
         /// Show detailed status information
         #[arg(long)]
         detailed: bool,
+        /// List notes and assignments that have never been compiled, by course
+        #[arg(long, conflicts_with = "filepath")]
+        uncompiled: bool,
+        /// Find notes whose header course name differs from the configured
+        /// one and offer to update them
+        #[arg(long, conflicts_with_all = ["filepath", "uncompiled"])]
+        course_names: bool,
+    },
+    /// Validate template configurations and installation
+    Validate {
+        /// Output validation issues as JSON
+        #[arg(long)]
+        json: bool,
     },
     /// List recent notes for a course
     #[command(alias = "r")]
     Recent {
         /// Course code
         course_id: String,
+        /// Only show notes modified within this window (e.g. 14d, 2w, 12h)
+        #[arg(long, value_name = "DURATION")]
+        since: Option<String>,
+        /// Ask for a listed note to open; opens the most recent when not
+        /// run in a terminal
+        #[arg(long)]
+        open: bool,
     },
     /// Initialize repository structure
     Setup {
@@ -173,13 +263,63 @@ pub enum Commands {
     #[command(alias = "i")]
     Index {
         /// Course code
-        course_id: String,
+        #[arg(required_unless_present = "all")]
+        course_id: Option<String>,
+
+        /// Create every course's missing index and link them all from courses/_index.md
+        #[arg(long, conflicts_with = "course_id")]
+        all: bool,
     },
     /// Search through notes
     #[command(alias = "s")]
     Search {
         /// Search query
-        query: String,
+        #[arg(required_unless_present = "reindex")]
+        query: Option<String>,
+
+        /// Replace each match with this text, prompting for every line
+        #[arg(long, requires = "query")]
+        replace: Option<String>,
+
+        /// Apply all replacements without prompting (requires --replace)
+        #[arg(long, requires = "replace")]
+        all: bool,
+
+        /// Build or incrementally update the on-disk search index first
+        #[arg(long)]
+        reindex: bool,
+
+        /// Match case exactly, overriding `search.case_sensitive`
+        #[arg(long, conflicts_with = "ignore_case")]
+        case_sensitive: bool,
+
+        /// Ignore case, overriding `search.case_sensitive`
+        #[arg(long, short = 'i')]
+        ignore_case: bool,
+
+        /// Match lines containing any word of the query
+        #[arg(long, conflicts_with_all = ["all_words", "replace"])]
+        any: bool,
+
+        /// Match lines containing every word of the query, in any order
+        #[arg(long, conflicts_with = "replace")]
+        all_words: bool,
+
+        /// Treat the query as a regular expression
+        #[arg(long, conflicts_with_all = ["any", "all_words", "replace"])]
+        regex: bool,
+
+        /// With --regex, highlight only this capture group instead of the whole match
+        #[arg(long, value_name = "N", requires = "regex")]
+        highlight_group: Option<usize>,
+
+        /// Print only the number of matches
+        #[arg(long, requires = "query", conflicts_with = "replace")]
+        count: bool,
+
+        /// With --count, also print `<file>:<count>` for every matching file
+        #[arg(long, requires = "count")]
+        per_file: bool,
     },
     /// Rebuild search index
     #[command(name = "rebuild-index", alias = "ri")]
@@ -203,17 +343,63 @@ pub enum Commands {
     /// Open most recent note for a course
     #[command(alias = "o")]
     Open {
-        /// Course code
-        course_id: String,
+        /// Course code (optional - opens the most recent note across all courses if omitted)
+        course_id: Option<String>,
+        /// Open the nth most recent note of the course instead (1 = most recent)
+        #[arg(requires = "course_id")]
+        index: Option<usize>,
+    },
+
+    /// Open the notes directory (or a course directory) in the file manager
+    OpenDir {
+        /// Course code (optional - opens the notes directory if omitted)
+        course_id: Option<String>,
+    },
+
+    /// Show version, environment and template package details for bug reports
+    Info {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// Show comprehensive status dashboard
-    Status,
+    Status {
+        /// Days counted for the most active course, overriding
+        /// `activity_window_days` (0 = all time)
+        #[arg(long, value_name = "DAYS", conflicts_with = "course")]
+        window: Option<u64>,
+        /// Show one course in detail instead: its notes, assignments, health,
+        /// uncompiled files and disk usage
+        #[arg(long, value_name = "COURSE_ID")]
+        course: Option<String>,
+    },
 
-    /// Clean up compiled PDFs
-    Clean,
-    /// Show current semester info
-    Semester,
+    /// Clean up compiled PDFs of the Typst files in the current directory.
+    /// PDFs without a matching source are left alone.
+    Clean {
+        /// Clean every course directory in the notes directory and the Obsidian vault instead
+        #[arg(long)]
+        all: bool,
+        /// Also remove backup files (`*.bak`, `*.bak.<timestamp>`) in course
+        /// directories: the current course, every course from the notes
+        /// directory itself, or with `--all` every course
+        #[arg(long)]
+        backups: bool,
+        /// List the files that would be removed and the space reclaimed,
+        /// without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Sync the notes directory with its git remote (pull --rebase, then push)
+    Sync,
+    /// Show current semester info, or wrap up the semester
+    Semester {
+        #[command(subcommand)]
+        action: Option<SemesterAction>,
+    },
+    /// Show notes and assignments worked on this week, grouped by day
+    Week,
     /// Configuration management
     Config {
         #[command(subcommand)]
@@ -241,6 +427,16 @@ pub enum SetupAction {
     Clean,
 }
 
+#[derive(Subcommand)]
+pub enum SemesterAction {
+    /// Archive every active course, then clean compiled PDFs and prune backups
+    End {
+        /// Skip confirmation prompt
+        #[arg(long, short)]
+        yes: bool,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum AssignmentAction {
     /// List recent assignments for a course
@@ -250,6 +446,9 @@ pub enum AssignmentAction {
         /// Number of recent assignments to show
         #[arg(short, long, default_value = "5")]
         limit: usize,
+        /// Only show assignments modified within this window (e.g. 14d, 2w, 12h)
+        #[arg(long, value_name = "DURATION")]
+        since: Option<String>,
     },
     /// Show assignment statistics for a course
     Stats {
@@ -262,6 +461,18 @@ pub enum AssignmentAction {
     Health {
         /// Course code (optional - shows all courses if omitted)
         course_id: Option<String>,
+        /// Output the health analysis as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Export assignment due dates to an iCalendar (.ics) file
+    #[command(name = "export-ics")]
+    ExportIcs {
+        /// Course code (optional - exports all courses if omitted)
+        course_id: Option<String>,
+        /// Output file path (defaults to assignments.ics in the notes directory)
+        #[arg(short, long)]
+        output: Option<String>,
     },
 }
 
@@ -284,6 +495,29 @@ pub enum CourseAction {
     /// Show common DTU course codes
     #[command(alias = "common")]
     Browse,
+    /// Search the DTU course catalog by code or name
+    Search {
+        /// Course code or part of a course name
+        query: String,
+        /// Maximum number of results to show
+        #[arg(short, long, default_value = "10")]
+        limit: usize,
+        /// Add a matching course to your configuration
+        #[arg(long)]
+        add: bool,
+    },
+    /// Add a shorthand alias for a course (e.g., introprog -> 02101)
+    Alias {
+        /// Alias to use in place of the course code
+        alias: String,
+        /// Course code the alias refers to
+        course_id: String,
+    },
+    /// Remove a course alias
+    Unalias {
+        /// Alias to remove
+        alias: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -302,6 +536,11 @@ pub enum ConfigAction {
         /// Value to set (will be parsed based on the field type)
         value: String,
     },
+    /// Clear an optional setting (editor, output-dir, vault-structure) back to its default
+    Unset {
+        /// Field name (e.g., "editor") or dot-notation key (e.g., "typst.output_dir")
+        field: String,
+    },
     /// Open configuration file in editor
     Edit,
     /// List all available configuration keys
@@ -322,7 +561,7 @@ pub enum ConfigAction {
     AddTemplateRepo {
         /// Repository name
         name: String,
-        /// GitHub repository (owner/repo)
+        /// GitHub repository (owner/repo) or full GitLab repository URL
         repository: String,
         /// Specific version (optional)
         #[arg(long)]
@@ -361,8 +600,16 @@ pub enum ConfigAction {
     },
     /// Show config file path
     Path,
+    /// Show every configured directory resolved to an absolute path, and whether it exists
+    Paths,
     /// Validate current configuration
-    Check,
+    #[command(alias = "validate")]
+    Check {
+        /// Repair fixable warnings: set the placeholder author, reset a
+        /// search.max_results of 0 and create a missing templates directory
+        #[arg(long)]
+        fix: bool,
+    },
     /// Migrate configuration to latest format (usually happens automatically)
     Migrate,
 }
@@ -372,7 +619,11 @@ pub enum TemplateAction {
     /// Check template status and version
     Status,
     /// Update to the latest template version
-    Update,
+    Update {
+        /// Show what would be updated without downloading or installing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
     /// Force reinstall templates
     Reinstall,
     /// Create a custom template file
@@ -387,6 +638,36 @@ pub enum TemplateAction {
         /// Custom sections (comma-separated)
         #[arg(short, long)]
         sections: Option<String>,
+        /// Directory to write the template to (default: `templates.custom_template_dir`)
+        #[arg(short, long, value_name = "DIR")]
+        output: Option<String>,
+    },
+    /// Show which template and variant a course resolves to, and why
+    Resolve {
+        /// Course code
+        course_id: String,
+        /// Template type (lecture, assignment, or custom)
+        #[arg(short = 't', long = "type", default_value = "lecture")]
+        template_type: String,
+    },
+    /// Rewrite outdated template imports in notes to the installed version
+    FixImports {
+        /// Course code (optional - fixes all courses if omitted)
+        course_id: Option<String>,
+    },
+    /// Remove cached rendered notes
+    ClearCache,
+    /// Use a fixed version of a template package instead of the newest installed
+    Pin {
+        /// Template package name (e.g., dtu-template)
+        package: String,
+        /// Version to use (e.g., 0.2.0)
+        version: String,
+    },
+    /// Go back to the newest installed version of a template package
+    Unpin {
+        /// Template package name (e.g., dtu-template)
+        package: String,
     },
 }
 
@@ -417,6 +698,7 @@ pub use config::{Config, get_config};
 pub use core::dev_data_generator::{CleanupStats, Course, DevDataGenerator, GenerationStats};
 pub use core::status_manager::{HealthStatus, StatusManager};
 pub use core::typst_compiler::{CompilationStatus, TypstCompiler};
+pub use error::{NoterError, NoterResult};
 
 /// Current version of the DTU Notes library
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
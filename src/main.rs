@@ -30,36 +30,33 @@
 //! noter status
 //! ```
 
-mod commands;
-mod config;
-mod core;
-mod data;
-mod ui;
-
 use anyhow::Result;
-use clap::Parser;
-use noter::{AssignmentAction, Commands, ConfigAction, CourseAction, SetupAction, TemplateAction};
-
-#[cfg(feature = "dev-tools")]
-use noter::DevAction;
+use clap::{CommandFactory, FromArgMatches, Parser};
+use noter::commands::registry::CommandRegistry;
 
 /// Command-line interface structure using clap derive macros.
 ///
-/// This structure defines the main CLI application with global configuration
-/// and routing to subcommands.
+/// This structure defines the main CLI application with global configuration.
+/// Subcommands come from the [`CommandRegistry`].
 #[derive(Parser)]
 #[command(name = "noter")]
 #[command(about = "DTU note-taking CLI with official branding")]
 #[command(version = env!("CARGO_PKG_VERSION"))]
 struct Cli {
-    #[command(subcommand)]
-    command: Commands,
+    /// Open files with this editor for this run, instead of the configured one
+    #[arg(long, global = true, value_name = "CMD")]
+    editor: Option<String>,
+
+    /// Print without colors, icons or decoration: one record per line with
+    /// tab-separated fields, and status messages on stderr
+    #[arg(long, global = true)]
+    plain: bool,
 }
 
 /// Main application entry point.
 ///
 /// Parses command-line arguments using clap and routes execution to the
-/// appropriate command handler. All errors are propagated using the `?` operator
+/// command's handler in the [`CommandRegistry`]. All errors are propagated using the `?` operator
 /// and handled by anyhow's automatic error formatting.
 ///
 /// # Returns
@@ -67,7 +64,10 @@ struct Cli {
 /// Returns `Ok(())` on successful execution, or an error with context
 /// if any command fails.
 fn main() -> Result<()> {
-    let cli = Cli::parse();
-    commands::execute_command(&cli.command)?;
+    let registry = CommandRegistry::new();
+    let matches = registry.command(Cli::command()).get_matches();
+    let cli = Cli::from_arg_matches(&matches)?;
+    noter::ui::output::OutputManager::set_plain(cli.plain);
+    registry.execute(&matches)?;
     Ok(())
 }
@@ -3,6 +3,7 @@
 //! Consistent formatting for different types of output.
 
 use crate::core::search_engine::SearchMatch;
+use crate::ui::output::{OutputManager, Status};
 use colored::*;
 
 pub struct Formatters;
@@ -10,6 +11,13 @@ pub struct Formatters;
 #[allow(dead_code)]
 impl Formatters {
     pub fn format_course_list(courses: &[(String, String)]) -> String {
+        if OutputManager::is_plain() {
+            return Self::plain_records(
+                courses
+                    .iter()
+                    .map(|(course_id, course_name)| vec![course_id.as_str(), course_name.as_str()]),
+            );
+        }
         if courses.is_empty() {
             return format!("{} No courses configured.", "ℹ️".blue());
         }
@@ -30,7 +38,33 @@ impl Formatters {
         output
     }
 
+    pub fn format_course_aliases(aliases: &[(String, String)]) -> String {
+        if OutputManager::is_plain() {
+            return Self::plain_records(
+                aliases
+                    .iter()
+                    .map(|(alias, course_id)| vec![alias.as_str(), course_id.as_str()]),
+            );
+        }
+        let mut output = format!("{} Course Aliases:\n\n", "🔖".blue());
+
+        for (alias, course_id) in aliases {
+            output.push_str(&format!("  {} -> {}\n", alias.green(), course_id.yellow()));
+        }
+
+        output
+    }
+
     pub fn format_search_results(results: &[SearchMatch], query: &str) -> String {
+        if OutputManager::is_plain() {
+            return Self::plain_records(results.iter().map(|result| {
+                vec![
+                    result.file_path.display().to_string(),
+                    result.line_number.to_string(),
+                    result.line_content.clone(),
+                ]
+            }));
+        }
         if results.is_empty() {
             return "No results found".to_string();
         }
@@ -42,11 +76,15 @@ impl Formatters {
         );
 
         for result in results {
-            let highlighted = Self::highlight_precise_match(
-                &result.line_content,
-                result.match_start,
-                result.match_end,
-            );
+            let highlighted = if result.spans.len() > 1 {
+                Self::highlight_spans(&result.line_content, &result.spans)
+            } else {
+                Self::highlight_precise_match(
+                    &result.line_content,
+                    result.match_start,
+                    result.match_end,
+                )
+            };
             output.push_str(&format!(
                 "{}:{}: {}\n",
                 result.file_path.display().to_string().bright_blue(),
@@ -64,23 +102,47 @@ impl Formatters {
     }
 
     pub fn format_status_section(title: &str, icon: &str, content: &str) -> String {
+        if OutputManager::is_plain() {
+            return content.to_string();
+        }
         format!("{} {}:\n{}\n", icon.blue(), title, content)
     }
 
     pub fn format_success(message: &str) -> String {
-        format!("{} {}", "✅".green(), message)
+        Self::format_message(Status::Success, "✅".green(), message)
     }
 
     pub fn format_warning(message: &str) -> String {
-        format!("{} {}", "⚠️".yellow(), message)
+        Self::format_message(Status::Warning, "⚠️".yellow(), message)
     }
 
     pub fn format_error(message: &str) -> String {
-        format!("{} {}", "❌".red(), message)
+        Self::format_message(Status::Error, "❌".red(), message)
     }
 
     pub fn format_info(message: &str) -> String {
-        format!("{} {}", "ℹ️".blue(), message)
+        Self::format_message(Status::Info, "ℹ️".blue(), message)
+    }
+
+    fn format_message(status: Status, icon: ColoredString, message: &str) -> String {
+        if OutputManager::is_plain() {
+            format!("{}: {}", status.label(), message)
+        } else {
+            format!("{} {}", icon, message)
+        }
+    }
+
+    /// One plain record per line, without a trailing newline
+    fn plain_records<I, S>(records: I) -> String
+    where
+        I: IntoIterator<Item = Vec<S>>,
+        S: AsRef<str>,
+    {
+        records
+            .into_iter()
+            .map(|fields| OutputManager::plain_fields(&fields))
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 
     fn highlight_match(line: &str, query: &str) -> String {
@@ -97,6 +159,31 @@ impl Formatters {
         }
     }
 
+    /// Highlight several byte spans, skipping any that are out of range or
+    /// not on character boundaries
+    fn highlight_spans(line: &str, spans: &[(usize, usize)]) -> String {
+        let mut output = String::with_capacity(line.len());
+        let mut last_end = 0;
+
+        for &(start, end) in spans {
+            if start < last_end
+                || start >= end
+                || end > line.len()
+                || !line.is_char_boundary(start)
+                || !line.is_char_boundary(end)
+            {
+                continue;
+            }
+
+            output.push_str(&line[last_end..start]);
+            output.push_str(&line[start..end].bright_yellow().bold().to_string());
+            last_end = end;
+        }
+
+        output.push_str(&line[last_end..]);
+        output
+    }
+
     // Fixed: Handle Unicode characters properly by converting byte positions to character positions
     fn highlight_precise_match(line: &str, match_start: usize, match_end: usize) -> String {
         // Convert byte positions to character positions
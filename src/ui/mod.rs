@@ -5,3 +5,4 @@
 pub mod formatters;
 pub mod output;
 pub mod prompts;
+pub mod width;
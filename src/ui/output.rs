@@ -2,14 +2,24 @@
 //!
 //! Handles different types of output including tables, lists, progress indicators,
 //! and structured displays.
+//!
+//! With `--plain` the same calls print for scripts instead: no colors or icons,
+//! one record per line with tab-separated fields, no headers or decoration,
+//! and status messages on stderr so stdout only carries data.
 
 use colored::*;
 use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use super::width::TextWidth;
+
+static PLAIN: AtomicBool = AtomicBool::new(false);
 
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub struct TableColumn {
     pub header: String,
+    /// Minimum width; the column grows to fit its header and cells
     pub width: usize,
     pub align: Alignment,
 }
@@ -26,37 +36,136 @@ pub struct OutputManager;
 
 #[allow(dead_code)]
 impl OutputManager {
-    /// Print a formatted table with headers and data
-    pub fn print_table(columns: &[TableColumn], rows: &[Vec<String>]) {
+    /// Widest a course name is shown in tables before it's truncated
+    pub const MAX_NAME_WIDTH: usize = 36;
+
+    /// Switch to plain output for the rest of this invocation
+    pub fn set_plain(plain: bool) {
+        PLAIN.store(plain, Ordering::Relaxed);
+        if plain {
+            colored::control::set_override(false);
+        }
+    }
+
+    pub fn is_plain() -> bool {
+        PLAIN.load(Ordering::Relaxed)
+    }
+
+    /// `icon`, or `text` in plain mode
+    pub fn symbol<'a>(icon: &'a str, text: &'a str) -> &'a str {
+        if Self::is_plain() { text } else { icon }
+    }
+
+    /// Print `display`, or in plain mode `fields` separated by tabs
+    pub fn print_record<S: AsRef<str>>(display: &str, fields: &[S]) {
+        if Self::is_plain() {
+            println!("{}", Self::plain_fields(fields));
+        } else {
+            println!("{}", display);
+        }
+    }
+
+    /// Print a hint for people, left out of plain output
+    pub fn print_hint(text: &str) {
+        if !Self::is_plain() {
+            println!("{}", text);
+        }
+    }
+
+    /// `fields` as one plain record: trimmed and joined by tabs, with tabs and
+    /// newlines inside a field replaced by spaces
+    pub fn plain_fields<S: AsRef<str>>(fields: &[S]) -> String {
+        fields
+            .iter()
+            .map(|field| field.as_ref().trim().replace(['\t', '\n'], " "))
+            .collect::<Vec<_>>()
+            .join("\t")
+    }
+
+    /// Print a left-aligned table with one column per header, each as wide
+    /// as its widest cell. Color codes don't count towards the width.
+    pub fn print_table(headers: &[&str], rows: &[Vec<String>]) {
+        let columns: Vec<TableColumn> = headers
+            .iter()
+            .map(|header| TableColumn {
+                header: header.to_string(),
+                width: 0,
+                align: Alignment::Left,
+            })
+            .collect();
+        Self::print_table_with_columns(&columns, rows);
+    }
+
+    /// Print a formatted table with per-column alignment and minimum widths
+    pub fn print_table_with_columns(columns: &[TableColumn], rows: &[Vec<String>]) {
         if columns.is_empty() || rows.is_empty() {
             return;
         }
+        if Self::is_plain() {
+            for row in rows {
+                println!("{}", Self::plain_fields(row));
+            }
+            return;
+        }
+
+        let columns: Vec<TableColumn> = columns
+            .iter()
+            .zip(Self::column_widths(columns, rows))
+            .map(|(column, width)| TableColumn {
+                width,
+                ..column.clone()
+            })
+            .collect();
 
-        Self::print_table_header(columns);
-        Self::print_table_separator(columns);
+        Self::print_table_header(&columns);
+        Self::print_table_separator(&columns);
 
         for row in rows {
-            Self::print_table_row(columns, row);
+            Self::print_table_row(&columns, row);
         }
     }
 
+    /// Width of each column: the largest of its minimum width, its header and
+    /// its cells, measured with [`TextWidth::of`]
+    pub fn column_widths(columns: &[TableColumn], rows: &[Vec<String>]) -> Vec<usize> {
+        columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                rows.iter()
+                    .filter_map(|row| row.get(i))
+                    .map(|cell| TextWidth::of(cell))
+                    .chain([column.width, TextWidth::of(&column.header)])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect()
+    }
+
     /// Print a simple list with bullet points
     pub fn print_list(items: &[String], bullet: Option<&str>) {
         let bullet = bullet.unwrap_or("•");
         for item in items {
-            println!("  {} {}", bullet.dimmed(), item);
+            Self::print_record(&format!("  {} {}", bullet.dimmed(), item), &[item]);
         }
     }
 
     /// Print a numbered list
     pub fn print_numbered_list(items: &[String]) {
         for (i, item) in items.iter().enumerate() {
-            println!("  {}. {}", (i + 1).to_string().dimmed(), item);
+            let number = (i + 1).to_string();
+            Self::print_record(
+                &format!("  {}. {}", number.dimmed(), item),
+                &[number.as_str(), item],
+            );
         }
     }
 
     /// Print a section header with optional icon
     pub fn print_section(title: &str, icon: Option<&str>) {
+        if Self::is_plain() {
+            return;
+        }
         let icon_str = icon.unwrap_or("📄");
         println!();
         println!("{} {}:", icon_str.blue(), title.bright_white());
@@ -65,12 +174,19 @@ impl OutputManager {
 
     /// Print a separator line
     pub fn print_separator(width: Option<usize>) {
+        if Self::is_plain() {
+            return;
+        }
         let width = width.unwrap_or(50);
         println!("{}", "─".repeat(width).dimmed());
     }
 
     /// Print status with icon and color coding
     pub fn print_status(status: Status, message: &str) {
+        if Self::is_plain() {
+            eprintln!("{}: {}", status.label(), message);
+            return;
+        }
         match status {
             Status::Success => println!("{} {}", "✅".green(), message),
             Status::Warning => println!("{} {}", "⚠️".yellow(), message),
@@ -83,6 +199,9 @@ impl OutputManager {
 
     /// Print a progress bar (simple text-based)
     pub fn print_progress(current: usize, total: usize, description: Option<&str>) {
+        if Self::is_plain() {
+            return;
+        }
         let percentage = if total > 0 {
             (current as f32 / total as f32 * 100.0) as usize
         } else {
@@ -130,27 +249,42 @@ impl OutputManager {
     /// Print a key-value pair list
     pub fn print_key_value_pairs(pairs: &[(String, String)], indent: Option<usize>) {
         let indent_str = " ".repeat(indent.unwrap_or(2));
-        let max_key_width = pairs.iter().map(|(k, _)| k.len()).max().unwrap_or(0);
+        let max_key_width = pairs
+            .iter()
+            .map(|(k, _)| TextWidth::of(k))
+            .max()
+            .unwrap_or(0);
 
         for (key, value) in pairs {
-            println!(
-                "{}{}: {}",
-                indent_str,
-                format!("{:<width$}", key, width = max_key_width).bright_blue(),
-                value
+            Self::print_record(
+                &format!(
+                    "{}{}: {}",
+                    indent_str,
+                    Self::pad_text(key, max_key_width, &Alignment::Left).bright_blue(),
+                    value
+                ),
+                &[key, value],
             );
         }
     }
 
     /// Print a box around text
     pub fn print_box(content: &str, title: Option<&str>) {
+        if Self::is_plain() {
+            println!("{}", content);
+            return;
+        }
         let lines: Vec<&str> = content.lines().collect();
-        let max_width = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+        let max_width = lines
+            .iter()
+            .map(|line| TextWidth::of(line))
+            .max()
+            .unwrap_or(0);
         let box_width = max_width + 4; // 2 spaces padding on each side
 
         // Top border
         if let Some(title) = title {
-            let title_len = title.len();
+            let title_len = TextWidth::of(title);
             let padding = if box_width > title_len + 4 {
                 (box_width - title_len - 4) / 2
             } else {
@@ -169,7 +303,7 @@ impl OutputManager {
 
         // Content
         for line in lines {
-            println!("│ {:<width$} │", line, width = max_width);
+            println!("│ {} │", Self::pad_text(line, max_width, &Alignment::Left));
         }
 
         // Bottom border
@@ -178,6 +312,9 @@ impl OutputManager {
 
     /// Print command examples with syntax highlighting
     pub fn print_command_examples(examples: &[(&str, &str)]) {
+        if Self::is_plain() {
+            return;
+        }
         println!("{}", "Command Examples:".bright_green());
         for (command, description) in examples {
             println!(
@@ -190,6 +327,9 @@ impl OutputManager {
 
     /// Clear the current line (for progress updates)
     pub fn clear_line() {
+        if Self::is_plain() {
+            return;
+        }
         print!("\r{}\r", " ".repeat(80));
         io::stdout().flush().unwrap();
     }
@@ -225,16 +365,15 @@ impl OutputManager {
         println!();
     }
 
+    /// Pad `text` to `width` display columns. Padding is added by hand since
+    /// `format!` widths would count color codes.
     fn pad_text(text: &str, width: usize, align: &Alignment) -> String {
-        if text.len() >= width {
-            return text[..width].to_string();
-        }
+        let padding = width.saturating_sub(TextWidth::of(text));
 
         match align {
-            Alignment::Left => format!("{:<width$}", text, width = width),
-            Alignment::Right => format!("{:>width$}", text, width = width),
+            Alignment::Left => format!("{}{}", text, " ".repeat(padding)),
+            Alignment::Right => format!("{}{}", " ".repeat(padding), text),
             Alignment::Center => {
-                let padding = width - text.len();
                 let left_pad = padding / 2;
                 let right_pad = padding - left_pad;
                 format!("{}{}{}", " ".repeat(left_pad), text, " ".repeat(right_pad))
@@ -254,6 +393,20 @@ pub enum Status {
     Complete,
 }
 
+impl Status {
+    /// Prefix of the message in plain output
+    pub fn label(&self) -> &'static str {
+        match self {
+            Status::Success => "success",
+            Status::Warning => "warning",
+            Status::Error => "error",
+            Status::Info => "info",
+            Status::Loading => "info",
+            Status::Complete => "success",
+        }
+    }
+}
+
 /// Helper trait for easy status printing
 pub trait StatusPrint {
     fn print_success(&self);
@@ -297,3 +450,39 @@ impl StatusPrint for String {
         self.as_str().print_info();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_widths_ignore_ansi_codes() {
+        let colored = "\u{1b}[33m02101\u{1b}[0m";
+        assert_eq!(TextWidth::of(colored), 5);
+        assert_eq!(TextWidth::of("✅ ok"), 5);
+        assert_eq!(TextWidth::of("⚠️"), 2);
+
+        let column = |header: &str, width| TableColumn {
+            header: header.to_string(),
+            width,
+            align: Alignment::Left,
+        };
+        let rows = vec![
+            vec![colored.to_string(), "Algorithms".to_string()],
+            vec!["1".to_string(), "\u{1b}[1;2mOS\u{1b}[0m".to_string()],
+        ];
+
+        assert_eq!(
+            OutputManager::column_widths(&[column("Course", 0), column("Name", 12)], &rows),
+            vec![6, 12]
+        );
+        assert_eq!(
+            OutputManager::pad_text(colored, 7, &Alignment::Right),
+            format!("  {}", colored)
+        );
+        assert_eq!(
+            OutputManager::plain_fields(&["02101", "Intro\tto Programming ", "3"]),
+            "02101\tIntro to Programming\t3"
+        );
+    }
+}
@@ -310,11 +310,20 @@ impl NoterPrompts {
 
         let templates_dir = PromptManager::input("Templates directory", Some("templates"))?;
 
+        let flat_layout = PromptManager::confirm(
+            "Keep notes directly in course folders (no lectures/ subdirectory)",
+            Some(false),
+        )?;
+
         Ok(PathConfig {
             notes_dir,
             obsidian_dir,
             templates_dir,
             typst_packages_dir: crate::config::PathConfig::default().typst_packages_dir,
+            flat_layout,
+            follow_symlinks: true,
+            course_catalog: None,
+            create_missing_dirs: true,
         })
     }
 
@@ -0,0 +1,132 @@
+//! Visible width of terminal text
+//!
+//! Colored output carries ANSI escape sequences that take no space on screen,
+//! so byte or character counts misjudge how wide a string is. These helpers
+//! measure and truncate by visible width instead, and give the same layout
+//! whether or not colors are enabled.
+
+pub struct TextWidth;
+
+/// A piece of terminal text: an escape sequence or one visible character
+enum Segment<'a> {
+    Escape(&'a str),
+    Char(char),
+}
+
+#[allow(dead_code)]
+impl TextWidth {
+    /// Columns `text` takes up in a terminal. ANSI escape sequences, variation
+    /// selectors and joiners take none; emoji and other pictographs take two.
+    pub fn of(text: &str) -> usize {
+        Self::segments(text)
+            .map(|segment| match segment {
+                Segment::Escape(_) => 0,
+                Segment::Char(c) => Self::char_width(c),
+            })
+            .sum()
+    }
+
+    /// `text` without ANSI escape sequences
+    pub fn strip_ansi(text: &str) -> String {
+        Self::segments(text)
+            .filter_map(|segment| match segment {
+                Segment::Escape(_) => None,
+                Segment::Char(c) => Some(c),
+            })
+            .collect()
+    }
+
+    /// Cut `text` to at most `width` visible columns, ending in `…` when
+    /// anything was cut. Escape sequences are kept, and a reset is appended
+    /// after a cut so a color doesn't leak past the end.
+    pub fn truncate(text: &str, width: usize) -> String {
+        if Self::of(text) <= width {
+            return text.to_string();
+        }
+        if width == 0 {
+            return String::new();
+        }
+
+        let mut output = String::new();
+        let mut used = 0;
+        let mut styled = false;
+        for segment in Self::segments(text) {
+            match segment {
+                Segment::Escape(escape) => {
+                    output.push_str(escape);
+                    styled = true;
+                }
+                Segment::Char(c) => {
+                    let char_width = Self::char_width(c);
+                    // Leave a column for the ellipsis
+                    if used + char_width > width - 1 {
+                        break;
+                    }
+                    output.push(c);
+                    used += char_width;
+                }
+            }
+        }
+
+        output.push('…');
+        if styled {
+            output.push_str("\u{1b}[0m");
+        }
+        output
+    }
+
+    fn char_width(c: char) -> usize {
+        match c {
+            '\u{200d}' | '\u{fe00}'..='\u{fe0f}' => 0,
+            '\u{2600}'..='\u{27bf}' | '\u{1f000}'..='\u{1faff}' => 2,
+            _ => 1,
+        }
+    }
+
+    /// Split `text` into `ESC [ ... <letter>` sequences and visible characters
+    fn segments(text: &str) -> impl Iterator<Item = Segment<'_>> {
+        let mut rest = text;
+        std::iter::from_fn(move || {
+            let c = rest.chars().next()?;
+            if let Some(after) = rest.strip_prefix("\u{1b}[") {
+                let end = after
+                    .find(|c: char| c.is_ascii_alphabetic())
+                    .map_or(after.len(), |i| i + 1);
+                let (escape, remaining) = rest.split_at(2 + end);
+                rest = remaining;
+                return Some(Segment::Escape(escape));
+            }
+
+            rest = &rest[c.len_utf8()..];
+            Some(Segment::Char(c))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use colored::Colorize;
+
+    #[test]
+    fn test_width_and_truncation_ignore_colors() {
+        // Whether or not colors are enabled, the visible width is the same
+        let course = "02101".yellow().to_string();
+        assert_eq!(TextWidth::of(&course), 5);
+        assert_eq!(TextWidth::strip_ansi(&course), "02101");
+
+        let colored = "\u{1b}[1;33mIntroduction to Programming\u{1b}[0m";
+        assert_eq!(TextWidth::of(colored), 27);
+        assert_eq!(TextWidth::of("✅ ok"), 5);
+        assert_eq!(TextWidth::of("⚠️"), 2);
+
+        let truncated = TextWidth::truncate(colored, 10);
+        assert_eq!(TextWidth::of(&truncated), 10);
+        assert_eq!(truncated, "\u{1b}[1;33mIntroduct…\u{1b}[0m");
+        assert_eq!(TextWidth::truncate(&course, 5), course);
+
+        let name = "Algorithms and Data Structures".dimmed().to_string();
+        assert_eq!(TextWidth::of(&TextWidth::truncate(&name, 12)), 12);
+        assert_eq!(TextWidth::truncate("🟢🟢🟢", 4), "🟢…");
+    }
+}